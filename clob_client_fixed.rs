@@ -1,33 +1,183 @@
-use anyhow::{anyhow, Result};
+use anyhow::anyhow;
 use ethers::prelude::*;
 use ethers::types::{Address, U256};
 use log::{info, warn};
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::clob::api::{CancelResponse, OpenOrder, PostOrderResponse, PostedOrder};
+use crate::clob::{auth, ApiCredentials, ClobApi};
+use crate::config::{ChainConfig, COLLATERAL_SYMBOL};
+use crate::domain::order::Side;
+use crate::error::{ClobError, Result};
+use crate::execution::checks::{self, PreTradeCheck, PreTradeOrder};
+use crate::execution::intents::{IntentStore, OrderIntent};
+use crate::execution::algo::TICK;
+use crate::execution::paper::PaperExchange;
+use crate::execution::orderbook::{
+    fetch_book, fetch_books, fetch_market_rules, fetch_midpoint, fetch_midpoints, fetch_prices,
+    OrderBook, SweepQuote,
+};
+use crate::execution::tca::{Arrival, Tca, TcaReport};
+use crate::execution::throttle::RateLimiter;
+use crate::history::{Candle, PriceHistory};
+use crate::markets::{read_payouts, verify_payouts, ExpiryGuard, GammaMarket, ResolutionCTF};
+use crate::net::{rpc, RpcProvider, RpcStatus, SendRetry};
+use crate::orders::{OrderEvent, OrderState, OrderTracker, TrackedOrder};
+use crate::risk::{GasBudget, OrderRequest, RiskDecision, RiskManager, TxPriority};
+use crate::wallet::errors::SigningError;
+use crate::wallet::order_builder::{MarketRules, OrderBuilder, SaltGenerator, MIN_GTD_SECS};
+use crate::wallet::safe;
+use crate::wallet::signer::{verify_order_signature, OrderType, SignatureType, WalletSigner};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 
 // ==================================================
-// CONSTANTS (Polygon / Polymarket)
+// CONSTANTS
 // ==================================================
 
-const POLYMARKET_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
-const CTF_CONTRACT: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
-const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+// Contract addresses come from the chain profile (config::ChainConfig)
 const MIN_ALLOWANCE: u128 = 1_000_000; // $1 (6 decimals)
+const MAX_BATCH_ORDERS: usize = 15; // CLOB limit per POST /orders
+// How long an accepted key answers retries before it's forgotten
+const SUBMISSION_TTL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+const BALANCE_BATCH: usize = 200; // token ids per balanceOfBatch call
+const USDC_SWAP_FEE_TIER: u32 = 100; // 0.01% Uniswap V3 stable pool
+const USDC_SWAP_SLIPPAGE_BPS: u64 = 50;
+const CLOB_API_URL: &str = "https://clob.polymarket.com";
 
 // ==================================================
-// CLIENT (DELEGATES TO PYTHON EXECUTOR)
+// CLIENT (NATIVE CLOB API, PYTHON EXECUTOR FALLBACK)
 // ==================================================
 
 #[derive(Clone)]
 pub struct ClobClient {
     pub http: Client,
-    provider: Arc<SignerMiddleware<Provider<Http>, LocalWallet>>,
+    provider: Arc<SignerMiddleware<RpcProvider, LocalWallet>>,
     proxy_wallet: Address,
+    // Signs replacement orders (same key as `provider`)
+    order_signer: WalletSigner,
+    // Shared by every builder, so identical orders built in the same
+    // millisecond still get distinct salts (and idempotency keys)
+    salts: SaltGenerator,
+    chain: ChainConfig,
     read_only: bool,
-    // Python executor URL (no more manual API credentials!)
+    clob_url: String,
+    // Native CLOB API (L2 HMAC auth) when credentials are configured
+    api: Option<ClobApi>,
+    // Python executor URL (used when no API credentials are set)
     python_executor_url: String,
+    // Idempotency key → submission state
+    submissions: Arc<Mutex<HashMap<String, Submission>>>,
+    // Resting orders we expect to find on the book (persisted)
+    intents: Arc<Mutex<IntentStore>>,
+    // Lifecycle state of every order submitted by this process
+    orders: Arc<Mutex<OrderTracker>>,
+    // What to do with post-only orders that would cross
+    post_only: PostOnlyMode,
+    // Refuse orders whose expected average fill is further than this
+    // fraction from the touch (MAX_PRICE_IMPACT, unset = no limit)
+    max_impact: Option<f64>,
+    // Market VWAP and fill benchmarks
+    tca: Arc<Mutex<Tca>>,
+    // Tick size / minimum size per token
+    rules: Arc<Mutex<HashMap<String, MarketRules>>>,
+    // Blocks entries near market end (EXPIRY_GUARD_ENABLED)
+    expiry: Option<Arc<ExpiryGuard>>,
+    // Pre-trade exposure limits (RISK_ENABLED)
+    risk: Option<Arc<RiskManager>>,
+    // Run in order on every signed order before it is sent
+    checks: Vec<Arc<dyn PreTradeCheck>>,
+    // Submission and cancel budgets (ORDER_RATE_*, CANCEL_RATE_*)
+    order_rate: Arc<RateLimiter>,
+    cancel_rate: Arc<RateLimiter>,
+    // Daily POL budget for on-chain actions (GAS_DAILY_BUDGET)
+    gas: Option<Arc<GasBudget>>,
+    // Orders, cancels and balances go here instead (PAPER_TRADING)
+    paper: Option<Arc<PaperExchange>>,
+}
+
+/// Handling of post-only orders priced through the opposite touch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostOnlyMode {
+    /// Refuse the order
+    Reject,
+    /// Move it one tick behind the opposite touch
+    Reprice,
+}
+
+impl PostOnlyMode {
+    /// `POST_ONLY_MODE` = reject (default) | reprice
+    pub fn from_env() -> Self {
+        match std::env::var("POST_ONLY_MODE")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "reprice" => PostOnlyMode::Reprice,
+            _ => PostOnlyMode::Reject,
+        }
+    }
+}
+
+/// Outcome of `replace_order`
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceReport {
+    pub old_order_id: String,
+    /// Replacement order, if one was posted
+    pub new_order_id: Option<String>,
+    /// Matched on the old order before we touched it
+    pub filled_before: Decimal,
+    /// Matched on the old order between lookup and cancel
+    pub filled_during_swap: Decimal,
+    /// Size of the replacement (requested size net of swap fills)
+    pub new_size: Decimal,
+}
+
+/// Wallet outcome tokens are transferred from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenHolder {
+    /// The proxy / Safe that trades
+    Funder,
+    /// The signing EOA
+    Signer,
+}
+
+/// A checked, unsent outcome-token transfer
+#[derive(Debug, Clone)]
+pub struct TransferPreview {
+    pub token_id: String,
+    pub from: Address,
+    pub to: Address,
+    pub amount: Decimal,
+    /// Sender's balance before the transfer
+    pub balance: Decimal,
+    /// Destination has code, so must accept ERC-1155 (`onERC1155Received`)
+    pub to_contract: bool,
+}
+
+impl TransferPreview {
+    pub fn log(&self) {
+        info!("📝 Transfer preview");
+        info!("   Token:  {}", self.token_id);
+        info!("   From:   {:?} (balance {})", self.from, self.balance);
+        info!(
+            "   To:     {:?}{}",
+            self.to,
+            if self.to_contract { " (contract)" } else { "" }
+        );
+        info!("   Amount: {} (leaves {})", self.amount, self.balance - self.amount);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Submission {
+    InFlight,
+    Accepted(String, std::time::Instant),
 }
 
 impl ClobClient {
@@ -35,17 +185,83 @@ impl ClobClient {
         rpc_url: &str,
         private_key: &str,
         proxy_wallet: &str,
-        _api_key: String,  // Kept for compatibility but unused
-        _api_secret: String,
-        _api_passphrase: String,
+        api_key: String,
+        api_secret: String,
+        api_passphrase: String,
     ) -> Result<Self> {
-        let wallet: LocalWallet = private_key.parse()?;
-        let provider = Provider::<Http>::try_from(rpc_url)?;
+        let wallet: LocalWallet = private_key.parse().map_err(|e| anyhow!("Invalid private key: {}", e))?;
+        let provider = rpc::provider(rpc_url)?;
         let chain_id = provider.get_chainid().await?.as_u64();
         let wallet = wallet.with_chain_id(chain_id);
+        let chain = ChainConfig::from_env(chain_id)?;
+
+        let eoa = wallet.address();
+        let signer = Arc::new(SignerMiddleware::new(provider, wallet));
+        let proxy_wallet =
+            Address::from_str(proxy_wallet).map_err(|e| anyhow!("Invalid proxy wallet {}: {}", proxy_wallet, e))?;
+
+        let clob_url = std::env::var("CLOB_API_URL")
+            .unwrap_or_else(|_| CLOB_API_URL.to_string());
+
+        let mut creds = ApiCredentials::new(api_key, api_secret, api_passphrase);
+        if !creds.is_complete() {
+            // Bootstrap credentials from the wallet (L1 auth)
+            let nonce = std::env::var("POLY_API_NONCE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+
+            match auth::create_or_derive_api_key(&clob_url, signer.signer(), chain_id, nonce).await {
+                Ok(derived) => creds = derived,
+                Err(e) => warn!("⚠️  API key bootstrap failed: {}", e),
+            }
+        }
+
+        let api = if creds.is_complete() {
+            Some(ClobApi::new(&clob_url, eoa, creds))
+        } else {
+            None
+        };
+
+        let client = Self::from_parts(signer, private_key, chain, proxy_wallet, clob_url, api)?;
+        info!("✅ ClobClient initialized");
+        info!("   Chain: {} ({})", client.chain.name, client.chain.chain_id);
+        info!("   RPC: {}", client.provider.inner().as_ref().labels().join(", "));
+        match &client.api {
+            Some(_) => info!("   CLOB API: {} (native)", client.clob_url),
+            None => info!("   Python executor: {}", client.python_executor_url),
+        }
+        Ok(client)
+    }
 
+    /// Client that never reaches the chain or the CLOB: read-only, no
+    /// API credentials, Polygon contracts, the signer as funder. For
+    /// backtests and other offline runs; seed the market rules it would
+    /// otherwise fetch with `set_market_rules`.
+    pub fn offline(private_key: &str) -> Result<Self> {
+        let chain = ChainConfig::polygon();
+        let wallet = private_key
+            .parse::<LocalWallet>()
+            .map_err(|e| anyhow!("Invalid private key: {}", e))?
+            .with_chain_id(chain.chain_id);
+        let proxy_wallet = wallet.address();
+        // Never queried
+        let provider = rpc::provider("http://127.0.0.1:8545")?;
         let signer = Arc::new(SignerMiddleware::new(provider, wallet));
+        let clob_url = std::env::var("CLOB_API_URL").unwrap_or_else(|_| CLOB_API_URL.to_string());
+        let mut client = Self::from_parts(signer, private_key, chain, proxy_wallet, clob_url, None)?;
+        client.read_only = true;
+        Ok(client)
+    }
 
+    fn from_parts(
+        signer: Arc<SignerMiddleware<RpcProvider, LocalWallet>>,
+        private_key: &str,
+        chain: ChainConfig,
+        proxy_wallet: Address,
+        clob_url: String,
+        api: Option<ClobApi>,
+    ) -> Result<Self> {
         // Check for read-only mode from env
         let read_only = std::env::var("READ_ONLY")
             .unwrap_or_else(|_| "false".to_string())
@@ -60,18 +276,151 @@ impl ClobClient {
         let python_executor_url = std::env::var("PYTHON_EXECUTOR_URL")
             .unwrap_or_else(|_| "http://localhost:8765".to_string());
 
-        info!("✅ ClobClient initialized");
-        info!("   Python executor: {}", python_executor_url);
+        let expiry = if std::env::var("EXPIRY_GUARD_ENABLED").as_deref() == Ok("true") {
+            Some(Arc::new(ExpiryGuard::from_env()?))
+        } else {
+            None
+        };
 
         Ok(Self {
             http: Client::new(),
             provider: signer,
-            proxy_wallet: Address::from_str(proxy_wallet)?,
+            proxy_wallet,
+            order_signer: WalletSigner::new(private_key, chain.chain_id)?
+                .with_exchange(chain.exchange)
+                .with_neg_risk_exchange(chain.neg_risk_exchange),
+            salts: SaltGenerator::new(),
+            chain,
             read_only,
+            clob_url,
+            api,
             python_executor_url,
+            submissions: Arc::new(Mutex::new(HashMap::new())),
+            intents: Arc::new(Mutex::new(IntentStore::from_env()?)),
+            orders: Arc::new(Mutex::new(OrderTracker::new())),
+            post_only: PostOnlyMode::from_env(),
+            max_impact: std::env::var("MAX_PRICE_IMPACT")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            tca: Arc::new(Mutex::new(Tca::from_env())),
+            rules: Arc::new(Mutex::new(HashMap::new())),
+            expiry,
+            risk: None,
+            checks: checks::default_checks(),
+            order_rate: Arc::new(RateLimiter::orders_from_env()),
+            cancel_rate: Arc::new(RateLimiter::cancels_from_env()),
+            gas: GasBudget::from_env().map(Arc::new),
+            paper: None,
         })
     }
 
+    /// Pass every order through `risk` before it is signed or sent
+    pub fn risk(mut self, risk: Arc<RiskManager>) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    /// Match orders on a paper exchange instead of posting them; its
+    /// virtual balances replace the on-chain ones
+    pub fn paper(mut self, paper: Arc<PaperExchange>) -> Self {
+        self.paper = Some(paper);
+        self
+    }
+
+    /// Trade through the native API at `clob_url` with `creds`, e.g. an
+    /// offline client against a staging host or a fixture server. Market
+    /// data is read from `clob_url` too.
+    pub fn api(mut self, clob_url: &str, creds: ApiCredentials) -> Self {
+        self.api = Some(ClobApi::new(clob_url, self.order_signer.address(), creds));
+        self.clob_url = clob_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Override `READ_ONLY`, e.g. to let an offline client trade
+    /// against a fixture server
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Append a check to the pre-trade pipeline; it runs after the
+    /// built-in ones
+    pub fn pre_trade_check(mut self, check: Arc<dyn PreTradeCheck>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Contract addresses for the connected chain
+    pub fn chain(&self) -> &ChainConfig {
+        &self.chain
+    }
+
+    /// Wallet that holds funds and positions (proxy / Safe)
+    pub fn funder(&self) -> Address {
+        self.proxy_wallet
+    }
+
+    /// EOA that signs orders and transactions
+    pub fn signer_address(&self) -> Address {
+        self.provider.address()
+    }
+
+    /// Salt generator for orders built outside the client, so they
+    /// can't collide with its own
+    pub fn salts(&self) -> SaltGenerator {
+        self.salts.clone()
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn is_paper(&self) -> bool {
+        self.paper.is_some()
+    }
+
+    pub fn clob_url(&self) -> &str {
+        &self.clob_url
+    }
+
+    /// Health of each RPC URL the client fails over between
+    pub fn rpc_status(&self) -> Vec<RpcStatus> {
+        self.provider.inner().as_ref().status()
+    }
+
+    /// End-of-market guard, for strategies to widen or pull quotes
+    pub fn expiry_guard(&self) -> Option<Arc<ExpiryGuard>> {
+        self.expiry.clone()
+    }
+
+    pub fn risk_manager(&self) -> Option<Arc<RiskManager>> {
+        self.risk.clone()
+    }
+
+    /// `MAX_PRICE_IMPACT`, if set
+    pub fn max_impact(&self) -> Option<f64> {
+        self.max_impact
+    }
+
+    pub fn gas_budget(&self) -> Option<Arc<GasBudget>> {
+        self.gas.clone()
+    }
+
+    /// Submission budget, spent by the throttle check
+    pub fn order_rate(&self) -> &RateLimiter {
+        &self.order_rate
+    }
+
+    /// Names of the pre-trade checks, in the order they run
+    pub fn pre_trade_checks(&self) -> Vec<String> {
+        self.checks.iter().map(|c| c.name().to_string()).collect()
+    }
+
+    /// Credentials in use for the native API (configured or derived)
+    pub fn api_credentials(&self) -> Option<ApiCredentials> {
+        self.api.as_ref().map(|a| a.credentials().clone())
+    }
+
     // ==================================================
     // TRADING READINESS CHECK
     // ==================================================
@@ -79,11 +428,15 @@ impl ClobClient {
     pub async fn ensure_trading_ready(&self, required_usdc: u128) -> Result<()> {
         self.ensure_balance(required_usdc).await?;
 
-        if self.proxy_is_contract().await? {
-            self.ensure_safe_checks().await?;
-        } else {
-            self.ensure_usdc_allowance().await?;
-            self.ensure_erc1155_approval().await?;
+        let is_safe = self.proxy_is_contract().await?;
+
+        for (name, spender) in self.spenders() {
+            if is_safe {
+                self.ensure_safe_checks(name, spender).await?;
+            } else {
+                self.ensure_usdc_allowance(name, spender).await?;
+                self.ensure_erc1155_approval(name, spender).await?;
+            }
         }
 
         Ok(())
@@ -99,13 +452,25 @@ impl ClobClient {
     }
 
     async fn ensure_balance(&self, required: u128) -> Result<()> {
-        let bal = self.usdc().balance_of(self.proxy_wallet).call().await?;
+        let (bal, native) = self.collateral_balances().await?;
         if bal < U256::from(required) {
-            return Err(anyhow!(
-                "❌ Insufficient USDC balance. Need: {}, Have: {}",
-                required as f64 / 1_000_000.0,
-                bal.as_u128() as f64 / 1_000_000.0
-            ));
+            // Native USDC can't back orders: the exchange settles in USDC.e
+            if !native.is_zero() {
+                return Err(ClobError::Refused {
+                    check: "collateral".to_string(),
+                    reason: format!(
+                        "❌ Funder holds {} native USDC but {} USDC.e, need {}: convert to USDC.e first (convert_native_to_bridged)",
+                        token_amount(native),
+                        token_amount(bal),
+                        token_amount(U256::from(required))
+                    ),
+                });
+            }
+            return Err(ClobError::InsufficientBalance {
+                asset: COLLATERAL_SYMBOL.to_string(),
+                needed: Some(token_amount(U256::from(required))),
+                available: Some(token_amount(bal)),
+            });
         }
         info!(
             "✅ USDC balance OK: ${:.2}",
@@ -114,105 +479,481 @@ impl ClobClient {
         Ok(())
     }
 
-    async fn ensure_safe_checks(&self) -> Result<()> {
+    async fn ensure_safe_checks(&self, name: &str, spender: Address) -> Result<()> {
         let allowance = self
             .usdc()
-            .allowance(self.proxy_wallet, self.exchange())
+            .allowance(self.proxy_wallet, spender)
             .call()
             .await?;
 
         if allowance < U256::from(MIN_ALLOWANCE) {
-            return Err(anyhow!(
-                "❌ USDC allowance missing on Gnosis Safe. Please approve in Polymarket UI."
-            ));
+            if self.read_only {
+                return Err(ClobError::ApprovalMissing {
+                    what: "USDC".to_string(),
+                    spender: format!("{} on Gnosis Safe", name),
+                });
+            }
+
+            self.gas_allow("USDC approve", TxPriority::Routine)?;
+            warn!("⚠️  Approving USDC spending to {} from Gnosis Safe...", name);
+            let data = self
+                .usdc()
+                .approve(spender, U256::MAX)
+                .calldata()
+                .ok_or_else(|| anyhow!("Failed to encode USDC approve"))?;
+            let receipt = self.exec_via_safe(self.usdc().address(), data).await?;
+            info!("✅ USDC approved via Safe. Tx: {:?}", receipt.transaction_hash);
         }
 
         let approved = self
             .ctf()
-            .is_approved_for_all(self.proxy_wallet, self.exchange())
+            .is_approved_for_all(self.proxy_wallet, spender)
             .call()
             .await?;
 
         if !approved {
-            return Err(anyhow!(
-                "❌ ERC-1155 approval missing on Gnosis Safe. Please approve in Polymarket UI."
-            ));
+            if self.read_only {
+                return Err(ClobError::ApprovalMissing {
+                    what: "ERC-1155".to_string(),
+                    spender: format!("{} on Gnosis Safe", name),
+                });
+            }
+
+            self.gas_allow("setApprovalForAll", TxPriority::Routine)?;
+            warn!("⚠️  Approving ERC-1155 (CTF) to {} from Gnosis Safe...", name);
+            let data = self
+                .ctf()
+                .set_approval_for_all(spender, true)
+                .calldata()
+                .ok_or_else(|| anyhow!("Failed to encode setApprovalForAll"))?;
+            let receipt = self.exec_via_safe(self.ctf().address(), data).await?;
+            info!("✅ ERC-1155 approved via Safe. Tx: {:?}", receipt.transaction_hash);
         }
 
-        info!("✅ Gnosis Safe approvals OK");
+        info!("✅ Gnosis Safe approvals OK for {}", name);
         Ok(())
     }
 
-    async fn ensure_usdc_allowance(&self) -> Result<()> {
+    /// Run a call from the proxy Safe, signed by our EOA owner key
+    async fn exec_via_safe(&self, to: Address, data: Bytes) -> Result<TransactionReceipt> {
+        let receipt = safe::exec_transaction(self.provider.clone(), self.proxy_wallet, to, data).await?;
+        self.record_gas(&receipt);
+        Ok(receipt)
+    }
+
+    /// Refuse a routine transaction once the day's gas budget is spent
+    fn gas_allow(&self, what: &str, priority: TxPriority) -> Result<()> {
+        match &self.gas {
+            Some(gas) => Ok(gas.allow(what, priority)?),
+            None => Ok(()),
+        }
+    }
+
+    fn record_gas(&self, receipt: &TransactionReceipt) {
+        if let Some(gas) = &self.gas {
+            gas.record(receipt);
+        }
+    }
+
+    async fn ensure_usdc_allowance(&self, name: &str, spender: Address) -> Result<()> {
         let allowance = self
             .usdc()
-            .allowance(self.proxy_wallet, self.exchange())
+            .allowance(self.proxy_wallet, spender)
             .call()
             .await?;
 
         if allowance >= U256::from(MIN_ALLOWANCE) {
-            info!("✅ USDC allowance OK");
+            info!("✅ USDC allowance OK for {}", name);
             return Ok(());
         }
 
-        warn!("⚠️  Approving USDC spending to Polymarket exchange...");
+        self.gas_allow("USDC approve", TxPriority::Routine)?;
+        warn!("⚠️  Approving USDC spending to {}...", name);
         let tx = self
             .usdc()
-            .approve(self.exchange(), U256::MAX)
+            .approve(spender, U256::MAX)
             .send()
             .await?
             .await?;
+        if let Some(receipt) = &tx {
+            self.record_gas(receipt);
+        }
 
         info!("✅ USDC approved. Tx: {:?}", tx);
         Ok(())
     }
 
-    async fn ensure_erc1155_approval(&self) -> Result<()> {
+    async fn ensure_erc1155_approval(&self, name: &str, spender: Address) -> Result<()> {
         let approved = self
             .ctf()
-            .is_approved_for_all(self.proxy_wallet, self.exchange())
+            .is_approved_for_all(self.proxy_wallet, spender)
             .call()
             .await?;
 
         if approved {
-            info!("✅ ERC-1155 approval OK");
+            info!("✅ ERC-1155 approval OK for {}", name);
             return Ok(());
         }
 
-        warn!("⚠️  Approving ERC-1155 (CTF) to Polymarket exchange...");
+        self.gas_allow("setApprovalForAll", TxPriority::Routine)?;
+        warn!("⚠️  Approving ERC-1155 (CTF) to {}...", name);
         let tx = self
             .ctf()
-            .set_approval_for_all(self.exchange(), true)
+            .set_approval_for_all(spender, true)
             .send()
             .await?
             .await?;
+        if let Some(receipt) = &tx {
+            self.record_gas(receipt);
+        }
 
         info!("✅ ERC-1155 approved. Tx: {:?}", tx);
         Ok(())
     }
 
     // ==================================================
-    // ORDER SUBMISSION - VIA PYTHON EXECUTOR
+    // ORDER SUBMISSION
     // ==================================================
 
+    /// Submit a signed order and return the exchange order ID.
+    ///
+    /// Each order carries an idempotency key (its struct hash). Retrying
+    /// the same signed order after a timeout returns the original result
+    /// instead of creating a second live order. In read-only mode the key
+    /// stands in for the order ID.
+    ///
+    /// The order runs through the pre-trade checks first (see
+    /// `execution::checks`); a signed order can't be repriced or
+    /// downsized, so any failure refuses it.
     pub async fn submit_order(
         &self,
         order: crate::wallet::signer::ClobOrder,
-        _sig: Signature,  // Not needed - Python will sign
+        sig: Signature,
         _proxy: &str,
-    ) -> Result<()> {
-        if self.read_only {
-            info!("📝 [READ-ONLY] Would submit order:");
-            info!("   Token: 0x{}", hex::encode(order.token_id.as_bytes()));
-            info!("   Side: {}", if order.side == 0 { "BUY" } else { "SELL" });
-            info!(
-                "   Maker Amount: {:.6}",
-                order.maker_amount.as_u128() as f64 / 1_000_000.0
+    ) -> Result<String> {
+        self.run_checks(&order, &[]).await?;
+        self.submit_checked(order, sig).await
+    }
+
+    /// `submit_order` for an order that already passed the checks
+    async fn submit_checked(&self, order: crate::wallet::signer::ClobOrder, sig: Signature) -> Result<String> {
+        let key = order.idempotency_key();
+
+        if self.read_only && self.paper.is_none() {
+            log_read_only(&order);
+            return Ok(key);
+        }
+
+        if let Some(order_id) = self.start_submission(&key, &order).await? {
+            return Ok(order_id);
+        }
+
+        let submit = async {
+            match (&self.paper, &self.api) {
+                (Some(paper), _) => Ok(paper.submit(&order).await?),
+                (None, Some(api)) => self.submit_native(api, &order, &sig).await,
+                (None, None) => self.submit_via_executor(&order, &key).await,
+            }
+        };
+        // Arrival mid for TCA, fetched alongside so it adds no latency
+        let (posted, mid) = tokio::join!(submit, self.arrival_mid(&order));
+
+        self.complete_submission(&key, &order, posted, mid).await
+    }
+
+    /// Sign and submit several orders, returning one result per order
+    /// in input order.
+    ///
+    /// With native API access the orders go out in `POST /orders`
+    /// batches; through the Python executor they are pipelined as
+    /// concurrent single-order requests.
+    pub async fn submit_orders(
+        &self,
+        orders: Vec<crate::wallet::signer::ClobOrder>,
+    ) -> Vec<Result<String>> {
+        let mut signed = Vec::with_capacity(orders.len());
+        for order in orders {
+            let sig = self.order_signer.sign_order(&order).await;
+            signed.push((order, sig));
+        }
+
+        let Some(api) = self.api.as_ref().filter(|_| !self.read_only && self.paper.is_none()) else {
+            // Checked in turn, so earlier orders count against the limits,
+            // then posted together
+            let mut batch: Vec<OrderIntent> = Vec::new();
+            let mut checked = Vec::with_capacity(signed.len());
+            for (order, sig) in signed {
+                let passed = match sig {
+                    Ok(sig) => self.run_checks(&order, &batch).await.map(|()| (order, sig)),
+                    Err(e) => Err(e.into()),
+                };
+                if let Ok((order, _)) = &passed {
+                    batch.push(OrderIntent::from_order(&order.idempotency_key(), order));
+                }
+                checked.push(passed);
+            }
+            let futs = checked.into_iter().map(|passed| async move {
+                let (order, sig) = passed?;
+                self.submit_checked(order, sig).await
+            });
+            return futures_util::future::join_all(futs).await;
+        };
+
+        let mut results: Vec<Option<Result<String>>> = Vec::with_capacity(signed.len());
+        let mut pending = Vec::new();
+        // Earlier orders of the batch count against the limits too
+        let mut batch: Vec<OrderIntent> = Vec::new();
+
+        for (i, (order, sig)) in signed.iter().enumerate() {
+            let key = order.idempotency_key();
+            let started = match sig {
+                Ok(_) => match self.run_checks(order, &batch).await {
+                    Ok(()) => {
+                        batch.push(OrderIntent::from_order(&key, order));
+                        self.start_submission(&key, order).await
+                    }
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(match e.downcast_ref::<SigningError>() {
+                    Some(e) => ClobError::Signing(e.clone()),
+                    None => anyhow!("Signing failed: {}", e).into(),
+                }),
+            };
+            match started {
+                Ok(None) => {
+                    results.push(None);
+                    pending.push(i);
+                }
+                Ok(Some(order_id)) => results.push(Some(Ok(order_id))),
+                Err(e) => results.push(Some(Err(e))),
+            }
+        }
+
+        for chunk in pending.chunks(MAX_BATCH_ORDERS) {
+            let payloads: Vec<_> = chunk
+                .iter()
+                .map(|&i| {
+                    let (order, sig) = &signed[i];
+                    let sig = sig.as_ref().expect("signed above");
+                    (order.to_payload(sig), order.order_type)
+                })
+                .collect();
+
+            info!("📤 Submitting batch of {} orders to CLOB API...", payloads.len());
+            let mids = futures_util::future::join_all(
+                chunk.iter().map(|&i| self.arrival_mid(&signed[i].0)),
             );
-            info!("   Taker Amount: {:.6}", order.taker_amount.as_u128() as f64 / 1_000_000.0);
-            return Ok(());
+            let (posted, mids) = tokio::join!(api.post_orders(&payloads), mids);
+
+            for (n, &i) in chunk.iter().enumerate() {
+                let (order, _) = &signed[i];
+                let outcome = match &posted {
+                    Ok(resps) => match resps.get(n) {
+                        Some(r) if r.success => Ok(PostedOrder {
+                            order_id: r.order_id.clone(),
+                            status: r.status.clone(),
+                            matched: r.matched(order.side),
+                        }),
+                        Some(r) => self.recover_duplicate(api, order, ClobError::rejected(&r.error_msg)).await,
+                        None => Err(ClobError::rejected(&format!("No response for order {} in batch", n))),
+                    },
+                    Err(e) => Err(e.share()),
+                };
+                let key = order.idempotency_key();
+                results[i] = Some(self.complete_submission(&key, order, outcome, mids[n]).await);
+            }
         }
 
+        results.into_iter().map(|r| r.expect("every order resolved")).collect()
+    }
+
+    /// Run the pre-trade pipeline; the first failing check refuses the
+    /// order. `batch` holds earlier orders of the same submission.
+    async fn run_checks(
+        &self,
+        order: &crate::wallet::signer::ClobOrder,
+        batch: &[OrderIntent],
+    ) -> Result<()> {
+        let pending = PreTradeOrder::new(order, batch);
+        for check in &self.checks {
+            check.check(self, &pending).await.map_err(|e| match ClobError::from(e) {
+                ClobError::Other(e) => ClobError::Refused {
+                    check: check.name().to_string(),
+                    reason: e.to_string(),
+                },
+                e => e,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// The kill switch was tripped: pull everything resting
+    pub(crate) async fn halt(&self, reason: String) -> ClobError {
+        if let Err(e) = self.cancel_all().await {
+            warn!("⚠️  Kill switch cancel-all failed: {}", e);
+        }
+        ClobError::Risk { reason, halted: true }
+    }
+
+    /// Size the risk manager lets through for an order about to be
+    /// built: `size`, less under `RISK_ON_BREACH=downsize`, or an error
+    async fn risk_size(&self, token_id: &str, side: &Side, price: Decimal, size: Decimal) -> Result<Decimal> {
+        let Some(risk) = &self.risk else {
+            return Ok(size);
+        };
+        let request = OrderRequest {
+            token_id: token_id.to_string(),
+            side: side.clone(),
+            price,
+            size,
+        };
+        match risk.evaluate(&request, &self.order_intents().await).await {
+            RiskDecision::Allow => Ok(size),
+            RiskDecision::Resize(room) => Ok(room),
+            RiskDecision::Reject(reason) => Err(ClobError::Risk { reason, halted: false }),
+            RiskDecision::Halt(reason) => Err(self.halt(reason).await),
+        }
+    }
+
+    /// Dedupe check plus state-machine bookkeeping before an order goes
+    /// out. Returns the existing order ID if this key was already accepted.
+    async fn start_submission(
+        &self,
+        key: &str,
+        order: &crate::wallet::signer::ClobOrder,
+    ) -> Result<Option<String>> {
+        if let Some(order_id) = self.begin_submission(key).await? {
+            info!("↩️  Order {} already submitted as {}, not resending", key, order_id);
+            return Ok(Some(order_id));
+        }
+
+        let mut orders = self.orders.lock().await;
+        orders.create(key, order_size(order));
+        if let Err(e) = orders.apply_all(key, vec![OrderEvent::Signed, OrderEvent::Submitted]) {
+            warn!("⚠️  {}", e);
+        }
+        Ok(None)
+    }
+
+    /// Record the exchange's answer: state machine, dedupe map,
+    /// persisted intents for resting orders and TCA benchmarks.
+    async fn complete_submission(
+        &self,
+        key: &str,
+        order: &crate::wallet::signer::ClobOrder,
+        posted: Result<PostedOrder>,
+        arrival_mid: Option<f64>,
+    ) -> Result<String> {
+        let posted = posted.map_err(|e| self.order_error(order, e));
+        let events = match &posted {
+            Ok(p) => OrderEvent::from_post_status(&p.status, &p.order_id, order_size(order), p.matched),
+            Err(e) => vec![OrderEvent::Rejected { reason: e.to_string() }],
+        };
+        if let Err(e) = self.orders.lock().await.apply_all(key, events) {
+            warn!("⚠️  {}", e);
+        }
+
+        if let Ok(p) = &posted {
+            self.record_arrival(p, order, arrival_mid).await;
+        }
+
+        let result = posted.map(|p| p.order_id);
+        self.finish_submission(key, &result).await;
+
+        if let Ok(order_id) = &result {
+            if order.order_type.is_resting() {
+                let intent = OrderIntent::from_order(order_id, order);
+                if let Err(e) = self.intents.lock().await.insert(intent) {
+                    warn!("⚠️  Failed to persist order intent {}: {}", order_id, e);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The exchange's "not enough balance / allowance" as a shortfall of
+    /// what the order spends. It doesn't say which of the two, or how much.
+    fn order_error(&self, order: &crate::wallet::signer::ClobOrder, e: ClobError) -> ClobError {
+        match e {
+            ClobError::ExchangeRejected { code, .. } if code == "INVALID_ORDER_NOT_ENOUGH_BALANCE" => {
+                ClobError::InsufficientBalance {
+                    asset: if order.side == 0 { COLLATERAL_SYMBOL } else { "tokens" }.to_string(),
+                    needed: None,
+                    available: None,
+                }
+            }
+            e => e,
+        }
+    }
+
+    /// Book midpoint for TCA; failures only cost the benchmark
+    async fn arrival_mid(&self, order: &crate::wallet::signer::ClobOrder) -> Option<f64> {
+        fetch_midpoint(&self.clob_url, &order.token_id.to_string())
+            .await
+            .ok()
+    }
+
+    /// Snapshot an accepted order for TCA, benchmarking it right away
+    /// if it matched on arrival
+    async fn record_arrival(&self, posted: &PostedOrder, order: &crate::wallet::signer::ClobOrder, mid: Option<f64>) {
+        let order_id = &posted.order_id;
+        let (price, size) = order.price_and_size();
+        let mut tca = self.tca.lock().await;
+        tca.on_submitted(
+            order_id,
+            Arrival {
+                token_id: order.token_id.to_string(),
+                side: if order.side == 0 { Side::Buy } else { Side::Sell },
+                price,
+                mid,
+                at_ms: chrono::Utc::now().timestamp_millis() as u64,
+            },
+        );
+        if posted.status.eq_ignore_ascii_case("matched") {
+            tca.on_fill(order_id, posted.matched.and_then(|m| m.to_f64()).filter(|m| *m > 0.0).unwrap_or(size));
+        }
+        if !order.order_type.is_resting() {
+            tca.forget(order_id);
+        }
+    }
+
+    /// Mark `key` in flight; returns the order ID if it went through
+    /// within `SUBMISSION_TTL`.
+    async fn begin_submission(&self, key: &str) -> Result<Option<String>> {
+        let mut submissions = self.submissions.lock().await;
+        submissions.retain(|_, s| !matches!(s, Submission::Accepted(_, at) if at.elapsed() > SUBMISSION_TTL));
+        match submissions.get(key) {
+            Some(Submission::Accepted(order_id, _)) => Ok(Some(order_id.clone())),
+            Some(Submission::InFlight) => Err(ClobError::InFlight(key.to_string())),
+            None => {
+                submissions.insert(key.to_string(), Submission::InFlight);
+                Ok(None)
+            }
+        }
+    }
+
+    async fn finish_submission(&self, key: &str, result: &Result<String>) {
+        let mut submissions = self.submissions.lock().await;
+        match result {
+            Ok(order_id) => {
+                submissions.insert(
+                    key.to_string(),
+                    Submission::Accepted(order_id.clone(), std::time::Instant::now()),
+                );
+            }
+            // Safe to retry: the exchange dedupes identical signed orders
+            Err(_) => {
+                submissions.remove(key);
+            }
+        }
+    }
+
+    async fn submit_via_executor(
+        &self,
+        order: &crate::wallet::signer::ClobOrder,
+        key: &str,
+    ) -> Result<PostedOrder> {
         // Convert order to format Python executor expects
         #[derive(Serialize, Debug)]
         struct PythonOrderRequest {
@@ -221,38 +962,29 @@ impl ClobClient {
             price: String,
             size: String,
             order_type: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            expiration: Option<String>,
+            idempotency_key: String,
         }
 
-        // Calculate price and size from maker/taker amounts
-        let (price, size) = if order.side == 0 {
-            // BUY: we're the maker (providing USDC), they're the taker (providing tokens)
-            // price = maker_amount / taker_amount
-            // size = taker_amount (in token units)
-            let price = (order.maker_amount.as_u128() as f64 / 1_000_000.0) 
-                       / (order.taker_amount.as_u128() as f64 / 1_000_000.0);
-            let size = order.taker_amount.as_u128() as f64 / 1_000_000.0;
-            (price, size)
-        } else {
-            // SELL: we're the maker (providing tokens), they're the taker (providing USDC)
-            // price = taker_amount / maker_amount
-            // size = maker_amount (in token units)
-            let price = (order.taker_amount.as_u128() as f64 / 1_000_000.0)
-                       / (order.maker_amount.as_u128() as f64 / 1_000_000.0);
-            let size = order.maker_amount.as_u128() as f64 / 1_000_000.0;
-            (price, size)
-        };
+        let (price, size) = order.price_and_size();
 
         let python_order = PythonOrderRequest {
-            token_id: format!("{:#x}", order.token_id),
-            side: if order.side == 0 { "BUY" } else { "SELL" }.to_string(),
+            token_id: order.token_id.to_string(),
+            side: order.side_str().to_string(),
             price: format!("{:.6}", price),
             size: format!("{:.6}", size),
-            order_type: "FOK".to_string(),  // Fill-or-Kill
+            order_type: order.order_type.as_str().to_string(),
+            expiration: (!order.expiration.is_zero()).then(|| order.expiration.to_string()),
+            idempotency_key: key.to_string(),
         };
 
         info!("📤 Sending order to Python executor...");
         info!("   Token: {}", &python_order.token_id[..16]);
-        info!("   {} price={} size={}", python_order.side, python_order.price, python_order.size);
+        info!(
+            "   {} {} price={} size={}",
+            python_order.order_type, python_order.side, python_order.price, python_order.size
+        );
 
         let url = format!("{}/order", self.python_executor_url);
         
@@ -261,17 +993,22 @@ impl ClobClient {
             .post(&url)
             .json(&python_order)
             .timeout(std::time::Duration::from_secs(10))
-            .send()
+            // May have reached the executor: only retried if never sent
+            .send_retry_unsent()
             .await?;
 
         let status = resp.status();
         
+        // An earlier attempt with this key is still being posted
+        if status == StatusCode::CONFLICT {
+            return Err(ClobError::InFlight(key.to_string()));
+        }
         if !status.is_success() {
             let error_body = resp.text().await?;
             warn!("❌ Python executor rejected order");
             warn!("   Status: {}", status);
             warn!("   Error: {}", error_body);
-            return Err(anyhow!("Python executor error: {} - {}", status, error_body));
+            return Err(ClobError::from_response(status, &error_body));
         }
 
         // Parse response
@@ -280,76 +1017,1366 @@ impl ClobClient {
             success: bool,
             order_id: Option<String>,
             error: Option<String>,
+            // Raw CLOB response
+            response: Option<serde_json::Value>,
         }
 
         let response: PythonOrderResponse = resp.json().await?;
         
-        if response.success {
-            if let Some(order_id) = response.order_id {
-                info!("✅ Order placed! ID: {}", order_id);
-            } else {
-                info!("✅ Order placed successfully!");
-            }
-        } else {
+        if !response.success {
             let error_msg = response.error.unwrap_or_else(|| "Unknown error".to_string());
-            return Err(anyhow!("Order failed: {}", error_msg));
+            return Err(ClobError::rejected(&error_msg));
         }
 
-        Ok(())
+        let order_id = response.order_id.unwrap_or_else(|| key.to_string());
+        let clob: Option<PostOrderResponse> = response.response.and_then(|r| serde_json::from_value(r).ok());
+        let status = clob.as_ref().map(|r| r.status.clone()).unwrap_or_default();
+        info!("✅ Order placed! ID: {} ({})", order_id, status);
+        Ok(PostedOrder {
+            order_id,
+            status,
+            matched: clob.and_then(|r| r.matched(order.side)),
+        })
     }
 
-    // ==================================================
-    // STUBS FOR FUTURE
-    // ==================================================
+    /// Post the locally signed order straight to the CLOB
+    async fn submit_native(
+        &self,
+        api: &ClobApi,
+        order: &crate::wallet::signer::ClobOrder,
+        sig: &Signature,
+    ) -> Result<PostedOrder> {
+        let order_hash = verify_order_signature(order, sig, self.order_signer.domain_separator(order))?;
+
+        info!("📤 Submitting order to CLOB API...");
+        info!("   Hash: {:?}", order_hash);
+        info!("   Token: {}", order.token_id);
+        info!(
+            "   {} {} maker={} taker={}",
+            order.order_type.as_str(),
+            order.side_str(),
+            order.maker_amount,
+            order.taker_amount
+        );
 
-    pub async fn get_orderbook(&self, _token_id: &str) -> Result<()> {
-        Err(anyhow!("Use execution::orderbook::fetch_orderbook instead"))
+        let resp = match api.post_order(&order.to_payload(sig), order.order_type).await {
+            Ok(resp) => resp,
+            Err(e) => return self.recover_duplicate(api, order, e).await,
+        };
+        info!("✅ Order placed! ID: {} ({})", resp.order_id, resp.status);
+        Ok(PostedOrder {
+            matched: resp.matched(order.side),
+            order_id: resp.order_id,
+            status: resp.status,
+        })
     }
 
-    pub fn best_price(&self, _book: &(), _side: u8) -> Result<()> {
-        Err(anyhow!("Use execution::orderbook methods instead"))
+    /// The exchange refuses a signed order it already holds as a
+    /// duplicate: an earlier attempt got through (e.g. before timing
+    /// out). Its ID is the order hash, so look it up rather than calling
+    /// a live order rejected.
+    async fn recover_duplicate(
+        &self,
+        api: &ClobApi,
+        order: &crate::wallet::signer::ClobOrder,
+        e: ClobError,
+    ) -> Result<PostedOrder> {
+        if !matches!(&e, ClobError::ExchangeRejected { code, .. } if code == "INVALID_ORDER_DUPLICATED") {
+            return Err(e);
+        }
+        let order_hash = self.order_signer.order_hash(order);
+        match api.get_order(&format!("{:?}", order_hash)).await {
+            Ok(existing) => {
+                info!("↩️  Order {:?} was already posted: {} ({})", order_hash, existing.id, existing.status);
+                Ok(PostedOrder {
+                    matched: Some(existing.filled()),
+                    order_id: existing.id,
+                    status: existing.status,
+                })
+            }
+            Err(lookup) => {
+                warn!("⚠️  Duplicate order {:?} not found: {}", order_hash, lookup);
+                Err(e)
+            }
+        }
     }
 
     // ==================================================
-    // CONTRACT HELPERS
+    // ORDER CANCELLATION
     // ==================================================
 
-    fn exchange(&self) -> Address {
-        Address::from_str(POLYMARKET_EXCHANGE).unwrap()
+    pub async fn cancel_order(&self, order_id: &str) -> Result<CancelResponse> {
+        self.throttle_cancels(&[order_id.to_string()]).await?;
+        if let Some(paper) = &self.paper {
+            let resp = paper.cancel(&[order_id.to_string()]).await;
+            self.log_cancel(&resp).await;
+            return Ok(resp);
+        }
+        if self.read_only {
+            info!("📝 [READ-ONLY] Would cancel order {}", order_id);
+            return Ok(CancelResponse::default());
+        }
+
+        let resp = match &self.api {
+            Some(api) => api.cancel_order(order_id).await?,
+            None => {
+                let path = format!("/cancel/{}", order_id);
+                self.cancel_via_executor(&path, serde_json::json!({})).await?
+            }
+        };
+        self.log_cancel(&resp).await;
+        Ok(resp)
     }
 
-    fn usdc(&self) -> USDCContract<SignerMiddleware<Provider<Http>, LocalWallet>> {
-        USDCContract::new(
-            Address::from_str(USDC_ADDRESS).unwrap(),
-            self.provider.clone(),
-        )
+    pub async fn cancel_orders(&self, order_ids: &[String]) -> Result<CancelResponse> {
+        if order_ids.is_empty() {
+            return Ok(CancelResponse::default());
+        }
+        self.throttle_cancels(order_ids).await?;
+
+        if let Some(paper) = &self.paper {
+            let resp = paper.cancel(order_ids).await;
+            self.log_cancel(&resp).await;
+            return Ok(resp);
+        }
+        if self.read_only {
+            info!("📝 [READ-ONLY] Would cancel {} orders:", order_ids.len());
+            for id in order_ids {
+                info!("   {}", id);
+            }
+            return Ok(CancelResponse::default());
+        }
+
+        let resp = match &self.api {
+            Some(api) => api.cancel_orders(order_ids).await?,
+            None => {
+                self.cancel_via_executor("/cancel-orders", serde_json::json!({ "order_ids": order_ids }))
+                    .await?
+            }
+        };
+        self.log_cancel(&resp).await;
+        Ok(resp)
     }
 
-    fn ctf(&self) -> CTFContract<SignerMiddleware<Provider<Http>, LocalWallet>> {
-        CTFContract::new(
-            Address::from_str(CTF_CONTRACT).unwrap(),
-            self.provider.clone(),
-        )
+    /// Round trip to the exchange (`GET /time`); an error means it
+    /// isn't reachable
+    pub async fn ping(&self) -> Result<()> {
+        self.http
+            .get(format!("{}/time", self.clob_url))
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
     }
-}
 
-// ==================================================
-// ABI GENERATION
-// ==================================================
+    /// Cancel every open order for this API key
+    pub async fn cancel_all(&self) -> Result<CancelResponse> {
+        if let Some(paper) = &self.paper {
+            let resp = paper.cancel_all(None).await;
+            self.log_cancel(&resp).await;
+            return Ok(resp);
+        }
+        if self.read_only {
+            info!("📝 [READ-ONLY] Would cancel ALL open orders");
+            return Ok(CancelResponse::default());
+        }
 
-abigen!(
-    USDCContract,
-    r#"[
-        function balanceOf(address) view returns (uint256)
-        function allowance(address,address) view returns (uint256)
-        function approve(address,uint256) returns (bool)
-    ]"#
-);
+        let resp = match &self.api {
+            Some(api) => api.cancel_all().await?,
+            None => self.cancel_via_executor("/cancel-all", serde_json::json!({})).await?,
+        };
+        self.log_cancel(&resp).await;
+        Ok(resp)
+    }
 
-abigen!(
-    CTFContract,
-    r#"[
-        function isApprovedForAll(address,address) view returns (bool)
-        function setApprovalForAll(address,bool)
+    /// Cancel every open order on one outcome token
+    pub async fn cancel_market(&self, token_id: &str) -> Result<CancelResponse> {
+        self.cancel_rate.acquire(&[(token_id, 1)])?;
+        if let Some(paper) = &self.paper {
+            let resp = paper.cancel_all(Some(token_id)).await;
+            self.log_cancel(&resp).await;
+            return Ok(resp);
+        }
+        if self.read_only {
+            info!("📝 [READ-ONLY] Would cancel all orders on token {}", token_id);
+            return Ok(CancelResponse::default());
+        }
+
+        let resp = match &self.api {
+            Some(api) => api.cancel_market(None, Some(token_id)).await?,
+            None => {
+                self.cancel_via_executor("/cancel-market", serde_json::json!({ "asset_id": token_id }))
+                    .await?
+            }
+        };
+        self.log_cancel(&resp).await;
+        Ok(resp)
+    }
+
+    /// Spend the cancel budget for `order_ids`, each charged to its
+    /// token when we know it (the id itself otherwise)
+    async fn throttle_cancels(&self, order_ids: &[String]) -> Result<()> {
+        if self.cancel_rate.is_unlimited() {
+            return Ok(());
+        }
+        let mut per_market: HashMap<String, u32> = HashMap::new();
+        {
+            let intents = self.intents.lock().await;
+            for id in order_ids {
+                let market = intents.get(id).map(|i| i.token_id.clone()).unwrap_or_else(|| id.clone());
+                *per_market.entry(market).or_default() += 1;
+            }
+        }
+        let markets: Vec<(&str, u32)> = per_market.iter().map(|(m, n)| (m.as_str(), *n)).collect();
+        self.cancel_rate.acquire(&markets)
+    }
+
+    /// Log the outcome and drop cancelled orders from the intent store
+    async fn log_cancel(&self, resp: &CancelResponse) {
+        info!("🗑️  Cancelled {} order(s)", resp.canceled.len());
+        for (id, reason) in &resp.not_canceled {
+            warn!("   Not cancelled {}: {}", id, reason);
+        }
+        if let Err(e) = self.intents.lock().await.remove(&resp.canceled) {
+            warn!("⚠️  Failed to update order intents: {}", e);
+        }
+
+        let mut orders = self.orders.lock().await;
+        for id in &resp.canceled {
+            orders.apply_by_id(id, OrderEvent::Cancelled);
+        }
+    }
+
+    async fn cancel_via_executor(&self, path: &str, body: serde_json::Value) -> Result<CancelResponse> {
+        #[derive(Deserialize)]
+        struct PythonCancelResponse {
+            success: bool,
+            error: Option<String>,
+            #[serde(flatten)]
+            result: CancelResponse,
+        }
+
+        let url = format!("{}{}", self.python_executor_url, path);
+        let resp = self
+            .http
+            .post(&url)
+            .json(&body)
+            .timeout(std::time::Duration::from_secs(10))
+            .send_retry()
+            .await?;
+
+        let status = resp.status();
+        let text = resp.text().await?;
+        let parsed: PythonCancelResponse = serde_json::from_str(&text)
+            .map_err(|_| anyhow!("Python executor error: {} - {}", status, text))?;
+
+        if !parsed.success {
+            return Err(ClobError::rejected(&parsed.error.unwrap_or_else(|| "Unknown error".to_string())));
+        }
+        Ok(parsed.result)
+    }
+
+    // ==================================================
+    // OPEN ORDERS
+    // ==================================================
+
+    /// Our open orders on the exchange, optionally for one token
+    pub async fn open_orders(&self, token_id: Option<&str>) -> Result<Vec<OpenOrder>> {
+        if let Some(paper) = &self.paper {
+            return Ok(paper.open_orders(token_id).await);
+        }
+        match &self.api {
+            Some(api) => api.get_open_orders(None, token_id).await,
+            None => {
+                #[derive(Deserialize)]
+                struct PythonOrdersResponse {
+                    success: bool,
+                    #[serde(default)]
+                    orders: Vec<OpenOrder>,
+                    error: Option<String>,
+                }
+
+                let mut url = format!("{}/orders", self.python_executor_url);
+                if let Some(t) = token_id {
+                    url.push_str(&format!("?asset_id={}", t));
+                }
+
+                let resp: PythonOrdersResponse = self
+                    .http
+                    .get(&url)
+                    .timeout(std::time::Duration::from_secs(10))
+                    .send_retry()
+                    .await?
+                    .json()
+                    .await?;
+
+                if !resp.success {
+                    return Err(ClobError::rejected(
+                        &resp.error.unwrap_or_else(|| "Unknown error".to_string()),
+                    ));
+                }
+                Ok(resp.orders)
+            }
+        }
+    }
+
+    /// Lifecycle state of an order we submitted, by exchange id
+    pub async fn order_state(&self, order_id: &str) -> Option<TrackedOrder> {
+        self.orders.lock().await.get_by_id(order_id).cloned()
+    }
+
+    /// Exchange ids of the orders we track as still able to match
+    pub async fn open_order_ids(&self) -> Vec<String> {
+        let orders = self.orders.lock().await;
+        let mut ids: Vec<String> = orders.open().into_iter().filter_map(|o| o.order_id.clone()).collect();
+        ids.sort();
+        ids
+    }
+
+    /// Stream of order state changes (fills, cancels, rejections)
+    pub async fn subscribe_orders(&self) -> tokio::sync::broadcast::Receiver<TrackedOrder> {
+        self.orders.lock().await.subscribe()
+    }
+
+    /// Feed an exchange-side update (e.g. from the user channel) into
+    /// the order state machine. Fills and terminal states also update
+    /// the persisted intents.
+    pub async fn on_order_event(&self, order_id: &str, event: OrderEvent) -> Option<OrderState> {
+        let fill = match &event {
+            OrderEvent::Fill { size } => size.to_f64(),
+            _ => None,
+        };
+        let state = self.orders.lock().await.apply_by_id(order_id, event)?;
+
+        let mut tca = self.tca.lock().await;
+        if let Some(size) = fill {
+            tca.on_fill(order_id, size);
+        }
+        if state.is_terminal() {
+            tca.forget(order_id);
+        }
+        drop(tca);
+
+        if state.is_terminal() {
+            if let Err(e) = self.forget_orders(&[order_id.to_string()]).await {
+                warn!("⚠️  Failed to update order intents: {}", e);
+            }
+        }
+        Some(state)
+    }
+
+    /// Shared TCA state, e.g. for `tca::run_trade_feed`
+    pub fn tca(&self) -> Arc<Mutex<Tca>> {
+        self.tca.clone()
+    }
+
+    /// Fills so far benchmarked against arrival mid and interval VWAP
+    pub async fn tca_report(&self) -> TcaReport {
+        self.tca.lock().await.report()
+    }
+
+    /// Locally persisted resting-order intents
+    pub async fn order_intents(&self) -> Vec<OrderIntent> {
+        self.intents.lock().await.all()
+    }
+
+    /// Track an order found on the exchange as if we had placed it
+    pub async fn adopt_order(&self, order: &OpenOrder) -> Result<()> {
+        let intent = OrderIntent {
+            order_id: order.id.clone(),
+            token_id: order.asset_id.clone(),
+            side: order.side.clone(),
+            price: order.price.parse().unwrap_or_default(),
+            size: order.original_size.parse().unwrap_or_default(),
+            order_type: match order.order_type.as_str() {
+                "GTD" => OrderType::Gtd,
+                _ => OrderType::Gtc,
+            },
+            created_at: order.created_at,
+        };
+        self.intents.lock().await.insert(intent)?;
+        self.track_order(order).await;
+        Ok(())
+    }
+
+    /// Follow fills and cancels of an order an earlier run placed, as
+    /// for our own: it's tracked as live with its remaining size
+    pub async fn track_order(&self, order: &OpenOrder) {
+        self.orders.lock().await.adopt(&order.id, order.remaining());
+    }
+
+    /// Drop intents for orders that are no longer on the book
+    pub async fn forget_orders(&self, order_ids: &[String]) -> Result<()> {
+        Ok(self.intents.lock().await.remove(order_ids)?)
+    }
+
+    // ==================================================
+    // CANCEL / REPLACE
+    // ==================================================
+
+    /// Look up one of our orders on the exchange
+    pub async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        if let Some(paper) = &self.paper {
+            return Ok(paper.get_order(order_id).await?);
+        }
+        match &self.api {
+            Some(api) => api.get_order(order_id).await,
+            None => {
+                #[derive(Deserialize)]
+                struct PythonOrderLookup {
+                    success: bool,
+                    order: Option<OpenOrder>,
+                    error: Option<String>,
+                }
+
+                let url = format!("{}/order/{}", self.python_executor_url, order_id);
+                let resp: PythonOrderLookup = self
+                    .http
+                    .get(&url)
+                    .timeout(std::time::Duration::from_secs(10))
+                    .send_retry()
+                    .await?
+                    .json()
+                    .await?;
+
+                match resp.order {
+                    Some(order) if resp.success => Ok(order),
+                    _ => Err(ClobError::rejected(&resp.error.unwrap_or_else(|| "not found".to_string()))),
+                }
+            }
+        }
+    }
+
+    /// Amend a resting order: cancel it, then post the new price/size.
+    ///
+    /// Cancelling first means we never have both orders live. Any size
+    /// that matched while the cancel was in flight is subtracted from the
+    /// replacement, so the net position ends up where the caller asked.
+    pub async fn replace_order(
+        &self,
+        order_id: &str,
+        new_price: Decimal,
+        new_size: Decimal,
+    ) -> Result<ReplaceReport> {
+        self.replace(order_id, new_price, new_size, false).await
+    }
+
+    /// `replace_order` for resting quotes: the replacement is post-only,
+    /// so a move through the touch is refused (or repriced under
+    /// `POST_ONLY_MODE=reprice`) instead of taking
+    pub async fn replace_post_only(
+        &self,
+        order_id: &str,
+        new_price: Decimal,
+        new_size: Decimal,
+    ) -> Result<ReplaceReport> {
+        self.replace(order_id, new_price, new_size, true).await
+    }
+
+    async fn replace(
+        &self,
+        order_id: &str,
+        new_price: Decimal,
+        new_size: Decimal,
+        post_only: bool,
+    ) -> Result<ReplaceReport> {
+        let before = self.get_order(order_id).await?;
+        let side = match before.side.to_uppercase().as_str() {
+            "BUY" => Side::Buy,
+            _ => Side::Sell,
+        };
+        // Checked before cancelling, so a refusal leaves the old order
+        let new_price = if post_only {
+            let book = fetch_book(&self.clob_url, &before.asset_id).await?;
+            post_only_price(&book, &side, new_price, self.post_only)?
+        } else {
+            new_price
+        };
+        // A GTD replacement keeps the old expiry, which the exchange
+        // refuses once it's inside its 60s threshold
+        let expires_in = if before.order_type == "GTD" {
+            let expires_at: u64 = before.expiration.parse().unwrap_or_default();
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_err(anyhow::Error::from)?
+                .as_secs();
+            let left = expires_at.saturating_sub(now);
+            if left < MIN_GTD_SECS {
+                return Err(ClobError::Refused {
+                    check: "gtd expiry".to_string(),
+                    reason: format!(
+                        "❌ Order {} expires in {}s, a GTD replacement needs at least {}s",
+                        order_id, left, MIN_GTD_SECS
+                    ),
+                });
+            }
+            Some(left)
+        } else {
+            None
+        };
+
+        let mut report = ReplaceReport {
+            old_order_id: order_id.to_string(),
+            filled_before: before.filled(),
+            ..Default::default()
+        };
+
+        let cancel = self.cancel_order(order_id).await?;
+        let live = !self.read_only || self.paper.is_some();
+        if live && !cancel.canceled.iter().any(|id| id == order_id) {
+            // Most likely fully matched before the cancel landed
+            let after = self.get_order(order_id).await?;
+            report.filled_during_swap = after.filled() - report.filled_before;
+            warn!(
+                "⚠️  Order {} not cancelled ({}), not replacing",
+                order_id,
+                cancel.not_canceled.get(order_id).map(String::as_str).unwrap_or(&after.status)
+            );
+            return Ok(report);
+        }
+
+        if live {
+            let after = self.get_order(order_id).await?;
+            report.filled_during_swap = after.filled() - report.filled_before;
+        }
+
+        report.new_size = (new_size - report.filled_during_swap).max(Decimal::ZERO);
+        if report.filled_during_swap > Decimal::ZERO {
+            info!(
+                "   {} filled during swap, replacement size {} → {}",
+                report.filled_during_swap, new_size, report.new_size
+            );
+        }
+        if report.new_size.is_zero() {
+            info!("✅ Order {} filled during swap, nothing left to replace", order_id);
+            return Ok(report);
+        }
+
+        let mut builder = OrderBuilder::new(self.proxy_wallet, self.order_signer.address())
+            .salts(self.salts())
+            .signature_type(SignatureType::from_env())
+            .market_rules(self.market_rules(&before.asset_id).await?);
+        if post_only {
+            builder = builder.post_only();
+        }
+        builder = match expires_in {
+            Some(secs) => builder.expires_in(secs),
+            None => builder.order_type(OrderType::Gtc),
+        };
+
+        let order = builder.build(&before.asset_id, side, new_price, report.new_size)?;
+        let sig = self.order_signer.sign_order(&order).await?;
+        let new_id = self.submit_order(order, sig, "").await?;
+
+        info!(
+            "🔁 Replaced {} → {} ({} @ {})",
+            order_id, new_id, report.new_size, new_price
+        );
+        report.new_order_id = Some(new_id);
+        Ok(report)
+    }
+
+    // ==================================================
+    // MARKET (TAKER) ORDERS
+    // ==================================================
+
+    /// Spend `usdc_amount` on `token_id` right now.
+    ///
+    /// Walks the live book and posts a FOK at the worst level needed,
+    /// refusing if that level is more than `max_slippage` (0.02 = 2%)
+    /// above the best ask.
+    pub async fn market_buy(
+        &self,
+        token_id: &str,
+        usdc_amount: Decimal,
+        max_slippage: f64,
+    ) -> Result<String> {
+        let book = fetch_book(&self.clob_url, token_id).await?;
+        let quote = book.quote_buy(usdc_amount.to_f64().unwrap_or_default(), max_slippage)?;
+        log_quote("BUY", &quote);
+
+        let price = to_decimal(quote.limit_price)?;
+        self.place_order(token_id, Side::Buy, price, usdc_amount / price, OrderType::Fok)
+            .await
+    }
+
+    /// Sell `size` tokens of `token_id` right now, refusing if the book
+    /// would take us more than `max_slippage` below the best bid.
+    pub async fn market_sell(
+        &self,
+        token_id: &str,
+        size: Decimal,
+        max_slippage: f64,
+    ) -> Result<String> {
+        let book = fetch_book(&self.clob_url, token_id).await?;
+        let quote = book.quote_sell(size.to_f64().unwrap_or_default(), max_slippage)?;
+        log_quote("SELL", &quote);
+
+        let price = to_decimal(quote.limit_price)?;
+        self.place_order(token_id, Side::Sell, price, size, OrderType::Fok)
+            .await
+    }
+
+    /// Build, sign and submit a limit order from the funder wallet
+    pub async fn place_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+    ) -> Result<String> {
+        let size = self.risk_size(token_id, &side, price, size).await?;
+        let order = OrderBuilder::new(self.proxy_wallet, self.order_signer.address())
+            .salts(self.salts())
+            .signature_type(SignatureType::from_env())
+            .order_type(order_type)
+            .market_rules(self.market_rules(token_id).await?)
+            .build(token_id, side, price, size)?;
+        let sig = self.order_signer.sign_order(&order).await?;
+        self.submit_order(order, sig, "").await
+    }
+
+    /// Build, sign and submit an order that must rest on the book.
+    ///
+    /// If `price` would cross the live book the order is refused, or
+    /// with `POST_ONLY_MODE=reprice` moved one tick behind the opposite
+    /// touch, so quotes never pay taker fees or trade with themselves.
+    pub async fn place_post_only(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+    ) -> Result<String> {
+        let book = fetch_book(&self.clob_url, token_id).await?;
+        let price = post_only_price(&book, &side, price, self.post_only)?;
+        let size = self.risk_size(token_id, &side, price, size).await?;
+
+        let order = OrderBuilder::new(self.proxy_wallet, self.order_signer.address())
+            .salts(self.salts())
+            .signature_type(SignatureType::from_env())
+            .order_type(order_type)
+            .post_only()
+            .market_rules(self.market_rules(token_id).await?)
+            .build(token_id, side, price, size)?;
+        let sig = self.order_signer.sign_order(&order).await?;
+        self.submit_order(order, sig, "").await
+    }
+
+    /// Tick size and minimum order size for `token_id`, cached after the
+    /// first lookup (both are fixed for the life of a market, except for
+    /// tick changes near 0/1, which the exchange announces)
+    pub async fn market_rules(&self, token_id: &str) -> Result<MarketRules> {
+        if let Some(rules) = self.rules.lock().await.get(token_id) {
+            return Ok(*rules);
+        }
+        let rules = fetch_market_rules(&self.clob_url, token_id).await?;
+        self.rules.lock().await.insert(token_id.to_string(), rules);
+        Ok(rules)
+    }
+
+    /// Use `rules` for `token_id` instead of fetching them
+    pub async fn set_market_rules(&self, token_id: &str, rules: MarketRules) {
+        self.rules.lock().await.insert(token_id.to_string(), rules);
+    }
+
+    // ==================================================
+    // BATCH MARKET DATA
+    // ==================================================
+
+    /// Books for many tokens in as few requests as possible
+    pub async fn books(&self, token_ids: &[String]) -> Result<HashMap<String, OrderBook>> {
+        Ok(fetch_books(&self.clob_url, token_ids).await?)
+    }
+
+    /// Midpoints for many tokens in as few requests as possible
+    pub async fn midpoints(&self, token_ids: &[String]) -> Result<HashMap<String, f64>> {
+        Ok(fetch_midpoints(&self.clob_url, token_ids).await?)
+    }
+
+    /// Best `side` price for many tokens in as few requests as possible
+    pub async fn prices(&self, token_ids: &[String], side: &Side) -> Result<HashMap<String, f64>> {
+        Ok(fetch_prices(&self.clob_url, token_ids, side).await?)
+    }
+
+    /// OHLC candles of `interval` for `token_id` over the last `lookback`
+    pub async fn candles(
+        &self,
+        token_id: &str,
+        lookback: std::time::Duration,
+        interval: std::time::Duration,
+    ) -> Result<Vec<Candle>> {
+        Ok(PriceHistory::new(self.clob_url.clone())
+            .recent_candles(token_id, lookback, interval)
+            .await?)
+    }
+
+    // ==================================================
+    // ORDERBOOK
+    // ==================================================
+
+    /// Full depth for `token_id`, best level first on both sides
+    pub async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
+        Ok(fetch_book(&self.clob_url, token_id).await?)
+    }
+
+    /// Price a marketable `side` order trades at first: the best ask
+    /// for BUY, the best bid for SELL
+    pub fn best_price(book: &OrderBook, side: &Side) -> Result<f64> {
+        match side {
+            Side::Buy => book.best_ask(),
+            Side::Sell => book.best_bid(),
+        }
+        .map(|l| l.price)
+        .ok_or_else(|| anyhow!("Empty orderbook").into())
+    }
+
+    // ==================================================
+    // BALANCE QUERY
+    // ==================================================
+
+    /// Get USDC balance from blockchain (the virtual one when paper trading)
+    pub async fn get_usdc_balance(&self) -> Result<rust_decimal::Decimal> {
+        if let Some(paper) = &self.paper {
+            return Ok(paper.cash().await);
+        }
+        let balance = self.usdc().balance_of(self.proxy_wallet).call().await?;
+        Ok(rust_decimal::Decimal::from(balance.as_u128()) / rust_decimal::Decimal::from(1_000_000))
+    }
+
+    /// (USDC.e, native USDC) balances of the funder wallet, in base units
+    pub async fn collateral_balances(&self) -> Result<(U256, U256)> {
+        let bridged = self
+            .token(self.chain.usdc)
+            .balance_of(self.proxy_wallet)
+            .call()
+            .await?;
+        let native = match self.chain.native_usdc {
+            Some(addr) => self.token(addr).balance_of(self.proxy_wallet).call().await?,
+            None => U256::zero(),
+        };
+        Ok((bridged, native))
+    }
+
+    // ==================================================
+    // COLLATERAL CONVERSION
+    // ==================================================
+
+    /// Swap `amount` (base units) of native USDC into USDC.e for the
+    /// funder wallet via the chain's Uniswap V3 router.
+    pub async fn convert_native_to_bridged(&self, amount: u128) -> Result<TransactionReceipt> {
+        let native = self
+            .chain
+            .native_usdc
+            .ok_or_else(|| anyhow!("No native USDC on chain {}", self.chain.name))?;
+        let router = self
+            .chain
+            .swap_router
+            .ok_or_else(|| anyhow!("No swap router configured for {}", self.chain.name))?;
+
+        if self.read_only {
+            return Err(ClobError::ReadOnly("USDC → USDC.e conversion".to_string()));
+        }
+
+        let amount_in = U256::from(amount);
+        let min_out = amount_in * U256::from(10_000 - USDC_SWAP_SLIPPAGE_BPS) / U256::from(10_000);
+        let deadline = U256::from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_err(anyhow::Error::from)?
+                .as_secs()
+                + 300,
+        );
+
+        warn!(
+            "🔄 Converting {:.2} USDC → USDC.e (min out {:.2})",
+            amount as f64 / 1_000_000.0,
+            min_out.as_u128() as f64 / 1_000_000.0
+        );
+
+        self.gas_allow("USDC → USDC.e swap", TxPriority::Routine)?;
+        let token = self.token(native);
+        let swap = SwapRouter::new(router, self.provider.clone());
+        let params = ExactInputSingleParams {
+            token_in: native,
+            token_out: self.chain.usdc,
+            fee: USDC_SWAP_FEE_TIER,
+            recipient: self.proxy_wallet,
+            deadline,
+            amount_in,
+            amount_out_minimum: min_out,
+            sqrt_price_limit_x96: U256::zero(),
+        };
+
+        let receipt = if self.proxy_is_contract().await? {
+            let approve = token
+                .approve(router, amount_in)
+                .calldata()
+                .ok_or_else(|| anyhow!("Failed to encode USDC approve"))?;
+            self.exec_via_safe(native, approve).await?;
+
+            let data = swap
+                .exact_input_single(params)
+                .calldata()
+                .ok_or_else(|| anyhow!("Failed to encode swap"))?;
+            self.exec_via_safe(router, data).await?
+        } else {
+            if let Some(receipt) = token.approve(router, amount_in).send().await?.await? {
+                self.record_gas(&receipt);
+            }
+            let receipt = swap
+                .exact_input_single(params)
+                .send()
+                .await?
+                .await?
+                .ok_or_else(|| anyhow!("Swap tx dropped from mempool"))?;
+            self.record_gas(&receipt);
+            receipt
+        };
+
+        info!("✅ Converted to USDC.e. Tx: {:?}", receipt.transaction_hash);
+        Ok(receipt)
+    }
+
+    // ==================================================
+    // CTF SPLIT / MERGE
+    // ==================================================
+
+    /// Mint a full set: lock `usdc` collateral and receive that many of
+    /// both outcome tokens, ready to quote on both sides without buying
+    /// inventory from the book. Neg-risk conditions go through the
+    /// neg-risk adapter.
+    pub async fn mint_full_set(
+        &self,
+        condition_id: H256,
+        usdc: Decimal,
+        neg_risk: bool,
+    ) -> Result<TransactionReceipt> {
+        let amount = usdc_units(usdc)?;
+        let balance = self.token(self.chain.usdc).balance_of(self.proxy_wallet).call().await?;
+        if balance < amount {
+            return Err(ClobError::InsufficientBalance {
+                asset: "USDC.e".to_string(),
+                needed: Some(usdc),
+                available: Some(token_amount(balance)),
+            });
+        }
+
+        info!(
+            "🪙 Splitting ${} into YES+NO on {:?}{}",
+            usdc,
+            condition_id,
+            if neg_risk { " (neg-risk)" } else { "" }
+        );
+        if neg_risk {
+            self.split_neg_risk(condition_id, amount).await
+        } else {
+            self.split_position(condition_id, amount).await
+        }
+    }
+
+    /// Lock `amount` USDC.e (base units) in the CTF and receive `amount`
+    /// of both outcome tokens of a binary condition.
+    pub async fn split_position(&self, condition_id: H256, amount: U256) -> Result<TransactionReceipt> {
+        self.ensure_split_allowance(self.chain.ctf, amount).await?;
+        let call = self.ctf().split_position(
+            self.chain.usdc,
+            [0u8; 32],
+            condition_id.0,
+            vec![U256::from(1), U256::from(2)],
+            amount,
+        );
+        self.send_from_funder(self.ctf().address(), call, "splitPosition", TxPriority::Routine).await
+    }
+
+    /// Burn `amount` of both outcome tokens and get `amount` USDC.e back
+    pub async fn merge_positions(&self, condition_id: H256, amount: U256) -> Result<TransactionReceipt> {
+        let call = self.ctf().merge_positions(
+            self.chain.usdc,
+            [0u8; 32],
+            condition_id.0,
+            vec![U256::from(1), U256::from(2)],
+            amount,
+        );
+        self.send_from_funder(self.ctf().address(), call, "mergePositions", TxPriority::Critical).await
+    }
+
+    /// Split of a neg-risk condition: the adapter wraps the USDC.e and
+    /// splits against its wrapped collateral
+    pub async fn split_neg_risk(&self, condition_id: H256, amount: U256) -> Result<TransactionReceipt> {
+        let adapter = self.neg_risk_adapter()?;
+        self.ensure_split_allowance(adapter.address(), amount).await?;
+        let call = adapter.split_position(condition_id.0, amount);
+        self.send_from_funder(adapter.address(), call, "splitPosition (neg-risk)", TxPriority::Routine)
+            .await
+    }
+
+    /// Burn `tokens` full sets (equal YES and NO) back into USDC.e, e.g.
+    /// to realize an arbitrage or unwind two-sided inventory. Refused
+    /// before sending if either of `token_ids` (YES, NO) is short.
+    pub async fn merge_full_sets(
+        &self,
+        condition_id: H256,
+        token_ids: [&str; 2],
+        tokens: Decimal,
+        neg_risk: bool,
+    ) -> Result<TransactionReceipt> {
+        let balances = self.token_balances(&token_ids.map(str::to_string)).await?;
+        for token_id in token_ids {
+            let held = balances.get(token_id).copied().unwrap_or_default();
+            if held < tokens {
+                return Err(ClobError::InsufficientBalance {
+                    asset: token_id.to_string(),
+                    needed: Some(tokens),
+                    available: Some(held),
+                });
+            }
+        }
+        let amount = usdc_units(tokens)?;
+        info!(
+            "🔥 Merging {} YES+NO on {:?} back to USDC.e{}",
+            tokens,
+            condition_id,
+            if neg_risk { " (neg-risk)" } else { "" }
+        );
+        if neg_risk {
+            self.merge_neg_risk(condition_id, amount).await
+        } else {
+            self.merge_positions(condition_id, amount).await
+        }
+    }
+
+    /// Merge of a neg-risk condition; the adapter unwraps the collateral
+    /// and pays out USDC.e
+    pub async fn merge_neg_risk(&self, condition_id: H256, amount: U256) -> Result<TransactionReceipt> {
+        let adapter = self.neg_risk_adapter()?;
+        let call = adapter.merge_positions(condition_id.0, amount);
+        self.send_from_funder(adapter.address(), call, "mergePositions (neg-risk)", TxPriority::Critical)
+            .await
+    }
+
+    /// Convert NO positions across a neg-risk event: `amount` NO on each
+    /// question in `index_set` (bit i = question i of `market_id`) is
+    /// burnt for `(k - 1) * amount` USDC.e plus `amount` YES on every
+    /// other question. Plan it with `NegRiskGroup::conversion`.
+    pub async fn convert_neg_risk(
+        &self,
+        market_id: H256,
+        index_set: U256,
+        amount: Decimal,
+    ) -> Result<TransactionReceipt> {
+        if index_set.is_zero() {
+            return Err(anyhow!("Empty index set, nothing to convert").into());
+        }
+        info!(
+            "🔁 Converting {} NO on {} question(s) of neg-risk market {:?}",
+            amount,
+            (0..256).filter(|i| index_set.bit(*i)).count(),
+            market_id
+        );
+        let adapter = self.neg_risk_adapter()?;
+        let call = adapter.convert_positions(market_id.0, index_set, usdc_units(amount)?);
+        self.send_from_funder(adapter.address(), call, "convertPositions", TxPriority::Routine).await
+    }
+
+    /// Splitting pulls collateral through `spender` (the CTF itself, or
+    /// the neg-risk adapter)
+    async fn ensure_split_allowance(&self, spender: Address, amount: U256) -> Result<()> {
+        let usdc = self.token(self.chain.usdc);
+        if usdc.allowance(self.proxy_wallet, spender).call().await? >= amount {
+            return Ok(());
+        }
+        warn!("⚠️  Approving USDC.e spending to {:?} for splits...", spender);
+        let call = usdc.approve(spender, U256::MAX);
+        self.send_from_funder(usdc.address(), call, "approve", TxPriority::Routine).await?;
+        Ok(())
+    }
+
+    /// Send a contract call from the funder: through the Safe when the
+    /// funder is a contract, directly from the EOA otherwise. Routine
+    /// calls are refused once the day's gas budget is spent.
+    async fn send_from_funder<D: ethers::abi::Detokenize>(
+        &self,
+        to: Address,
+        call: ContractCall<SignerMiddleware<RpcProvider, LocalWallet>, D>,
+        what: &str,
+        priority: TxPriority,
+    ) -> Result<TransactionReceipt> {
+        if self.read_only {
+            return Err(ClobError::ReadOnly(what.to_string()));
+        }
+        self.gas_allow(what, priority)?;
+
+        let receipt = if self.proxy_is_contract().await? {
+            let data = call
+                .calldata()
+                .ok_or_else(|| anyhow!("Failed to encode {}", what))?;
+            self.exec_via_safe(to, data).await?
+        } else {
+            let receipt = call
+                .send()
+                .await?
+                .await?
+                .ok_or_else(|| anyhow!("{} tx dropped from mempool", what))?;
+            self.record_gas(&receipt);
+            receipt
+        };
+
+        info!("✅ {} confirmed. Tx: {:?}", what, receipt.transaction_hash);
+        Ok(receipt)
+    }
+
+    // ==================================================
+    // REDEMPTION
+    // ==================================================
+
+    /// Redeem our outcome tokens of a resolved market for USDC.e.
+    ///
+    /// The payout numerators are read from the CTF first and must match
+    /// the resolution Gamma reports; if either side is missing or they
+    /// disagree nothing is sent.
+    pub async fn redeem(&self, market: &GammaMarket) -> Result<TransactionReceipt> {
+        let tokens = market.token_ids();
+        let ctf = ResolutionCTF::new(self.chain.ctf, self.provider.clone());
+        let onchain = read_payouts(&ctf, &market.condition_id, tokens.len().max(2)).await?;
+        verify_payouts(onchain.as_deref(), &market.outcome_prices()).map_err(|e| {
+            warn!("🚫 Not redeeming {}: {}", market.question, e);
+            ClobError::Chain(format!("Refusing to redeem {}: {}", market.condition_id, e))
+        })?;
+
+        let condition = H256::from_str(&market.condition_id)
+            .map_err(|e| anyhow!("Bad condition id {}: {}", market.condition_id, e))?;
+        info!("💰 Redeeming {} (payout {:?})", market.question, onchain.unwrap_or_default());
+
+        if market.neg_risk {
+            // The adapter redeems explicit amounts of each outcome (zero
+            // where none is held)
+            let balances = self.token_balances(&tokens).await?;
+            let amounts = tokens
+                .iter()
+                .map(|t| usdc_units(balances.get(t).copied().unwrap_or_default()).unwrap_or_default())
+                .collect();
+            let adapter = self.neg_risk_adapter()?;
+            let call = adapter.redeem_positions(condition.0, amounts);
+            self.send_from_funder(adapter.address(), call, "redeemPositions (neg-risk)", TxPriority::Critical)
+                .await
+        } else {
+            let index_sets = (0..tokens.len().max(2)).map(|i| U256::one() << i).collect();
+            let call = self
+                .ctf()
+                .redeem_positions(self.chain.usdc, [0u8; 32], condition.0, index_sets);
+            self.send_from_funder(self.ctf().address(), call, "redeemPositions", TxPriority::Critical)
+                .await
+        }
+    }
+
+    // ==================================================
+    // OUTCOME TOKEN BALANCES (ERC-1155)
+    // ==================================================
+
+    /// On-chain balance of one outcome token held by the funder (virtual
+    /// when paper trading)
+    pub async fn token_balance(&self, token_id: &str) -> Result<Decimal> {
+        if let Some(paper) = &self.paper {
+            return Ok(paper.token_balance(token_id).await);
+        }
+        let id = U256::from_dec_str(token_id).map_err(|e| anyhow!("Bad token id {}: {}", token_id, e))?;
+        let units = self.ctf().balance_of(self.proxy_wallet, id).call().await?;
+        Ok(token_amount(units))
+    }
+
+    /// On-chain balances of many outcome tokens via `balanceOfBatch`,
+    /// keyed by token id
+    pub async fn token_balances(&self, token_ids: &[String]) -> Result<HashMap<String, Decimal>> {
+        let mut balances = HashMap::new();
+        if let Some(paper) = &self.paper {
+            for token in token_ids {
+                balances.insert(token.clone(), paper.token_balance(token).await);
+            }
+            return Ok(balances);
+        }
+        for chunk in token_ids.chunks(BALANCE_BATCH) {
+            let ids = chunk
+                .iter()
+                .map(|t| U256::from_dec_str(t).map_err(|e| anyhow!("Bad token id {}: {}", t, e)))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let owners = vec![self.proxy_wallet; ids.len()];
+            let units = self.ctf().balance_of_batch(owners, ids).call().await?;
+            for (token, units) in chunk.iter().zip(units) {
+                balances.insert(token.clone(), token_amount(units));
+            }
+        }
+        Ok(balances)
+    }
+
+    // ==================================================
+    // OUTCOME TOKEN TRANSFERS (ERC-1155)
+    // ==================================================
+
+    /// Check a transfer of `amount` tokens of `token_id` without sending
+    /// it: the destination must be a different, non-zero address and the
+    /// sender must hold enough
+    pub async fn preview_transfer(
+        &self,
+        token_id: &str,
+        from: TokenHolder,
+        to: Address,
+        amount: Decimal,
+    ) -> Result<TransferPreview> {
+        usdc_units(amount)?;
+        let from = match from {
+            TokenHolder::Funder => self.proxy_wallet,
+            TokenHolder::Signer => self.signer_address(),
+        };
+        if to.is_zero() {
+            return Err(anyhow!("❌ Refusing to transfer to the zero address").into());
+        }
+        if to == from {
+            return Err(anyhow!("❌ Transfer source and destination are both {:?}", to).into());
+        }
+
+        let id = U256::from_dec_str(token_id).map_err(|e| anyhow!("Bad token id {}: {}", token_id, e))?;
+        let balance = token_amount(self.ctf().balance_of(from, id).call().await?);
+        if balance < amount {
+            return Err(ClobError::InsufficientBalance {
+                asset: format!("token {} of {:?}", token_id, from),
+                needed: Some(amount),
+                available: Some(balance),
+            });
+        }
+        let to_contract = !self.provider.get_code(to, None).await?.is_empty();
+
+        Ok(TransferPreview {
+            token_id: token_id.to_string(),
+            from,
+            to,
+            amount,
+            balance,
+            to_contract,
+        })
+    }
+
+    /// Move outcome tokens with `safeTransferFrom`, from the funder (via
+    /// the Safe when it is a contract) or from the signer EOA. The
+    /// transfer is previewed first and refused in read-only mode.
+    pub async fn transfer_tokens(
+        &self,
+        token_id: &str,
+        from: TokenHolder,
+        to: Address,
+        amount: Decimal,
+    ) -> Result<TransactionReceipt> {
+        let preview = self.preview_transfer(token_id, from, to, amount).await?;
+        if self.read_only {
+            preview.log();
+            return Err(ClobError::ReadOnly("safeTransferFrom".to_string()));
+        }
+
+        let call = self.ctf().safe_transfer_from(
+            preview.from,
+            to,
+            U256::from_dec_str(token_id).map_err(|e| anyhow!("Bad token id {}: {}", token_id, e))?,
+            usdc_units(amount)?,
+            Bytes::new(),
+        );
+        info!("📦 Transferring {} of {} {:?} → {:?}", amount, token_id, preview.from, to);
+        match from {
+            TokenHolder::Funder => {
+                self.send_from_funder(self.ctf().address(), call, "safeTransferFrom", TxPriority::Routine)
+                    .await
+            }
+            TokenHolder::Signer => {
+                self.gas_allow("safeTransferFrom", TxPriority::Routine)?;
+                let receipt = call
+                    .send()
+                    .await?
+                    .await?
+                    .ok_or_else(|| anyhow!("safeTransferFrom tx dropped from mempool"))?;
+                self.record_gas(&receipt);
+                info!("✅ safeTransferFrom confirmed. Tx: {:?}", receipt.transaction_hash);
+                Ok(receipt)
+            }
+        }
+    }
+
+    // ==================================================
+    // CONTRACT HELPERS
+    // ==================================================
+
+    fn exchange(&self) -> Address {
+        self.chain.exchange
+    }
+
+    /// Contracts that move our USDC / outcome tokens when orders match
+    fn spenders(&self) -> Vec<(&'static str, Address)> {
+        let mut spenders = vec![
+            ("CTF exchange", self.exchange()),
+            ("neg-risk exchange", self.chain.neg_risk_exchange),
+        ];
+        if let Some(adapter) = self.chain.neg_risk_adapter {
+            spenders.push(("neg-risk adapter", adapter));
+        }
+        spenders
+    }
+
+    fn usdc(&self) -> USDCContract<SignerMiddleware<RpcProvider, LocalWallet>> {
+        self.token(self.chain.usdc)
+    }
+
+    fn token(&self, addr: Address) -> USDCContract<SignerMiddleware<RpcProvider, LocalWallet>> {
+        USDCContract::new(addr, self.provider.clone())
+    }
+
+    fn ctf(&self) -> CTFContract<SignerMiddleware<RpcProvider, LocalWallet>> {
+        CTFContract::new(
+            self.chain.ctf,
+            self.provider.clone(),
+        )
+    }
+
+    fn neg_risk_adapter(&self) -> Result<NegRiskAdapterContract<SignerMiddleware<RpcProvider, LocalWallet>>> {
+        let adapter = self
+            .chain
+            .neg_risk_adapter
+            .ok_or_else(|| ClobError::Chain(format!("No neg-risk adapter known on {}", self.chain.name)))?;
+        Ok(NegRiskAdapterContract::new(adapter, self.provider.clone()))
+    }
+}
+
+fn log_read_only(order: &crate::wallet::signer::ClobOrder) {
+    info!("📝 [READ-ONLY] Would submit order:");
+    info!("   Token: {}", order.token_id);
+    info!("   Side: {} ({})", order.side_str(), order.order_type.as_str());
+    info!(
+        "   Maker Amount: {:.6}",
+        order.maker_amount.as_u128() as f64 / 1_000_000.0
+    );
+    info!("   Taker Amount: {:.6}", order.taker_amount.as_u128() as f64 / 1_000_000.0);
+}
+
+fn log_quote(side: &str, quote: &SweepQuote) {
+    info!(
+        "📈 Market {}: {:.2} tokens, avg {:.4}, limit {:.4} (${:.2})",
+        side, quote.size, quote.avg_price, quote.limit_price, quote.notional
+    );
+}
+
+fn to_decimal(v: f64) -> anyhow::Result<Decimal> {
+    Decimal::from_f64_retain(v)
+        .map(|d| d.round_dp(4))
+        .ok_or_else(|| anyhow!("Invalid price {}", v))
+}
+
+/// Price at which a post-only order rests without crossing `book`
+fn post_only_price(
+    book: &OrderBook,
+    side: &Side,
+    price: Decimal,
+    mode: PostOnlyMode,
+) -> Result<Decimal> {
+    if !book.crosses(side, price.to_f64().unwrap_or_default()) {
+        return Ok(price);
+    }
+
+    // Crossing implies the opposite side has a level
+    let touch = ClobClient::best_price(book, side)?;
+
+    if mode == PostOnlyMode::Reject {
+        return Err(ClobError::Refused {
+            check: "post-only".to_string(),
+            reason: format!("Post-only {} at {} would cross the book at {:.4}", side.as_str(), price, touch),
+        });
+    }
+
+    let touch = to_decimal(touch)?;
+    let repriced = match side {
+        Side::Buy => touch - TICK,
+        Side::Sell => touch + TICK,
+    };
+    if repriced <= Decimal::ZERO || repriced >= Decimal::ONE {
+        return Err(ClobError::Refused {
+            check: "post-only".to_string(),
+            reason: format!("No resting price left for post-only {}", side.as_str()),
+        });
+    }
+
+    warn!(
+        "↪️  Post-only {} repriced {} → {} (touch {})",
+        side.as_str(),
+        price,
+        repriced,
+        touch
+    );
+    Ok(repriced)
+}
+
+/// USDC amount in 6-decimal base units (also the outcome-token amount
+/// of a split or merge)
+fn usdc_units(usdc: Decimal) -> anyhow::Result<U256> {
+    if usdc <= Decimal::ZERO {
+        return Err(anyhow!("Amount must be positive, got {}", usdc));
+    }
+    (usdc * Decimal::from(1_000_000))
+        .trunc()
+        .to_u128()
+        .map(U256::from)
+        .ok_or_else(|| anyhow!("Amount {} out of range", usdc))
+}
+
+/// Outcome tokens from 6-decimal base units
+fn token_amount(units: U256) -> Decimal {
+    Decimal::from_i128_with_scale(units.low_u128() as i128, 6)
+}
+
+/// Order size in outcome tokens
+fn order_size(order: &crate::wallet::signer::ClobOrder) -> Decimal {
+    Decimal::from_f64_retain(order.price_and_size().1).unwrap_or_default()
+}
+
+// ==================================================
+// ABI GENERATION
+// ==================================================
+
+abigen!(
+    USDCContract,
+    r#"[
+        function balanceOf(address) view returns (uint256)
+        function allowance(address,address) view returns (uint256)
+        function approve(address,uint256) returns (bool)
+    ]"#
+);
+
+abigen!(
+    CTFContract,
+    r#"[
+        function isApprovedForAll(address,address) view returns (bool)
+        function balanceOf(address owner, uint256 id) view returns (uint256)
+        function balanceOfBatch(address[] owners, uint256[] ids) view returns (uint256[])
+        function setApprovalForAll(address,bool)
+        function safeTransferFrom(address from, address to, uint256 id, uint256 amount, bytes data)
+        function splitPosition(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] partition, uint256 amount)
+        function mergePositions(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] partition, uint256 amount)
+        function redeemPositions(address collateralToken, bytes32 parentCollectionId, bytes32 conditionId, uint256[] indexSets)
+    ]"#
+);
+
+abigen!(
+    NegRiskAdapterContract,
+    r#"[
+        function splitPosition(bytes32 conditionId, uint256 amount)
+        function mergePositions(bytes32 conditionId, uint256 amount)
+        function convertPositions(bytes32 marketId, uint256 indexSet, uint256 amount)
+        function redeemPositions(bytes32 conditionId, uint256[] amounts)
+    ]"#
+);
+
+abigen!(
+    SwapRouter,
+    r#"[
+        struct ExactInputSingleParams { address tokenIn; address tokenOut; uint24 fee; address recipient; uint256 deadline; uint256 amountIn; uint256 amountOutMinimum; uint160 sqrtPriceLimitX96; }
+        function exactInputSingle(ExactInputSingleParams params) payable returns (uint256 amountOut)
     ]"#
 );
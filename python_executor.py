@@ -8,13 +8,14 @@ Receives order requests from Rust bot via HTTP
 import os
 import sys
 import logging
+import threading
 from decimal import Decimal
 from flask import Flask, request, jsonify
 from dotenv import load_dotenv
 
 # Import official py-clob-client
 from py_clob_client.client import ClobClient
-from py_clob_client.clob_types import ApiCreds, OrderArgs, OrderType
+from py_clob_client.clob_types import ApiCreds, OpenOrderParams, OrderArgs, OrderType
 from py_clob_client.order_builder.constants import BUY, SELL
 from py_clob_client.constants import POLYGON
 
@@ -46,6 +47,13 @@ class PolymarketExecutor:
         self.chain_id = int(os.getenv('CHAIN_ID', str(POLYGON)))
         self.host = os.getenv('CLOB_API_URL', 'https://clob.polymarket.com')
         
+        # Idempotency key -> result of the accepted order
+        self.completed_orders = {}
+        # Keys being signed/posted right now; a repeat must not sign a
+        # second order (fresh salt) while the first may still land
+        self.pending_orders = set()
+        self.orders_lock = threading.Lock()
+        
         # Validate credentials
         if not self.private_key:
             raise ValueError("❌ PRIVATE_KEY not found in .env file")
@@ -138,12 +146,29 @@ class PolymarketExecutor:
                 "side": "BUY" or "SELL",
                 "price": "0.50",
                 "size": "10.0",
-                "order_type": "FOK" or "GTC" or "GTD"
+                "order_type": "FOK" or "FAK" or "GTC" or "GTD",
+                "expiration": "1700000000"  (GTD only, unix seconds)
             }
         
         Returns:
-            {"success": bool, "order_id": str, "error": str}
+            {"success": bool, "order_id": str, "error": str, "in_progress": bool}
         """
+        # Retried submissions reuse the same idempotency key
+        key = order_data.get('idempotency_key')
+        if key:
+            with self.orders_lock:
+                if key in self.completed_orders:
+                    logger.info(f"↩️  Duplicate submission {key[:18]}... returning previous result")
+                    return self.completed_orders[key]
+                if key in self.pending_orders:
+                    logger.info(f"⏳ Submission {key[:18]}... still in progress")
+                    return {
+                        'success': False,
+                        'in_progress': True,
+                        'error': f"Order {key} is already being submitted"
+                    }
+                self.pending_orders.add(key)
+
         try:
             logger.info(f"📥 Placing {order_data['side']} order")
             logger.info(f"   Token: {order_data['token_id'][:16]}...")
@@ -157,7 +182,8 @@ class PolymarketExecutor:
                 price=float(order_data['price']),
                 size=float(order_data['size']),
                 side=side,
-                token_id=order_data['token_id']
+                token_id=order_data['token_id'],
+                expiration=int(order_data.get('expiration') or 0)
             )
             
             # Create and sign the order
@@ -169,6 +195,7 @@ class PolymarketExecutor:
                 'FOK': OrderType.FOK,
                 'GTC': OrderType.GTC,
                 'GTD': OrderType.GTD,
+                'FAK': OrderType.FAK,
             }
             order_type = order_type_map.get(order_data.get('order_type', 'FOK'), OrderType.FOK)
             
@@ -179,11 +206,15 @@ class PolymarketExecutor:
             logger.info(f"✅ Order placed successfully!")
             logger.info(f"   Response: {resp}")
             
-            return {
+            result = {
                 'success': True,
                 'order_id': resp.get('orderID', 'unknown'),
                 'response': resp
             }
+            if key:
+                with self.orders_lock:
+                    self.completed_orders[key] = result
+            return result
             
         except Exception as e:
             logger.error(f"❌ Order failed: {e}")
@@ -191,6 +222,10 @@ class PolymarketExecutor:
                 'success': False,
                 'error': str(e)
             }
+        finally:
+            if key:
+                with self.orders_lock:
+                    self.pending_orders.discard(key)
     
     def execute_arbitrage(self, arb_data: dict) -> dict:
         """
@@ -257,14 +292,56 @@ class PolymarketExecutor:
             logger.error(f"Failed to fetch orderbook: {e}")
             return {'success': False, 'error': str(e)}
     
+    def get_open_orders(self, asset_id: str = None) -> dict:
+        """List our open orders, optionally for a single token"""
+        try:
+            params = OpenOrderParams(asset_id=asset_id) if asset_id else None
+            orders = self.client.get_orders(params)
+            return {'success': True, 'orders': orders}
+        except Exception as e:
+            logger.error(f"Failed to fetch open orders: {e}")
+            return {'success': False, 'error': str(e)}
+
+    def get_order(self, order_id: str) -> dict:
+        """Look up a single order by id"""
+        try:
+            order = self.client.get_order(order_id)
+            return {'success': bool(order), 'order': order}
+        except Exception as e:
+            logger.error(f"Failed to fetch order {order_id}: {e}")
+            return {'success': False, 'error': str(e)}
+
     def cancel_order(self, order_id: str) -> dict:
         """Cancel a specific order"""
+        return self._cancel(lambda: self.client.cancel(order_id), f"order {order_id}")
+
+    def cancel_orders(self, order_ids: list) -> dict:
+        """Cancel several orders at once"""
+        return self._cancel(lambda: self.client.cancel_orders(order_ids), f"{len(order_ids)} orders")
+
+    def cancel_all(self) -> dict:
+        """Cancel every open order for this API key"""
+        return self._cancel(self.client.cancel_all, "all orders")
+
+    def cancel_market(self, asset_id: str) -> dict:
+        """Cancel every open order on one outcome token"""
+        return self._cancel(
+            lambda: self.client.cancel_market_orders(asset_id=asset_id),
+            f"orders on {asset_id[:16]}..."
+        )
+
+    def _cancel(self, call, what: str) -> dict:
+        """Run a cancel call and return {success, canceled, not_canceled}"""
         try:
-            self.client.cancel(order_id)
-            logger.info(f"✅ Cancelled order {order_id}")
-            return {'success': True}
+            resp = call() or {}
+            logger.info(f"✅ Cancelled {what}")
+            return {
+                'success': True,
+                'canceled': resp.get('canceled', []),
+                'not_canceled': resp.get('not_canceled', {}),
+            }
         except Exception as e:
-            logger.error(f"Failed to cancel order: {e}")
+            logger.error(f"Failed to cancel {what}: {e}")
             return {'success': False, 'error': str(e)}
 
 # ===== INITIALIZE EXECUTOR =====
@@ -296,7 +373,9 @@ def place_order():
         "side": "BUY"|"SELL",
         "price": "0.50",
         "size": "10",
-        "order_type": "FOK"|"GTC"
+        "order_type": "FOK"|"FAK"|"GTC"|"GTD",
+        "expiration": "1700000000" (GTD only),
+        "idempotency_key": "0x..." (optional)
     }
     """
     try:
@@ -308,6 +387,8 @@ def place_order():
         
         if result['success']:
             return jsonify(result), 200
+        elif result.get('in_progress'):
+            return jsonify(result), 409
         else:
             return jsonify(result), 500
             
@@ -350,6 +431,18 @@ def get_orderbook(token_id: str):
     else:
         return jsonify(result), 500
 
+@app.route('/orders', methods=['GET'])
+def get_open_orders():
+    """List open orders. Query: ?asset_id=123... (optional)"""
+    result = executor.get_open_orders(request.args.get('asset_id'))
+    return jsonify(result), 200 if result['success'] else 500
+
+@app.route('/order/<order_id>', methods=['GET'])
+def get_order(order_id: str):
+    """Look up an order"""
+    result = executor.get_order(order_id)
+    return jsonify(result), 200 if result['success'] else 404
+
 @app.route('/cancel/<order_id>', methods=['POST'])
 def cancel_order(order_id: str):
     """Cancel an order"""
@@ -359,6 +452,28 @@ def cancel_order(order_id: str):
     else:
         return jsonify(result), 500
 
+@app.route('/cancel-orders', methods=['POST'])
+def cancel_orders():
+    """Cancel several orders. Body: {"order_ids": ["0x...", ...]}"""
+    data = request.get_json() or {}
+    result = executor.cancel_orders(data.get('order_ids', []))
+    return jsonify(result), 200 if result['success'] else 500
+
+@app.route('/cancel-all', methods=['POST'])
+def cancel_all():
+    """Cancel all open orders"""
+    result = executor.cancel_all()
+    return jsonify(result), 200 if result['success'] else 500
+
+@app.route('/cancel-market', methods=['POST'])
+def cancel_market():
+    """Cancel all orders on a token. Body: {"asset_id": "123..."}"""
+    data = request.get_json() or {}
+    if not data.get('asset_id'):
+        return jsonify({'success': False, 'error': 'asset_id required'}), 400
+    result = executor.cancel_market(data['asset_id'])
+    return jsonify(result), 200 if result['success'] else 500
+
 # ===== RUN SERVER =====
 
 if __name__ == '__main__':
@@ -369,6 +484,8 @@ if __name__ == '__main__':
     logger.info(f"📡 Ready to receive orders from Rust bot")
     logger.info(f"   POST http://localhost:{port}/order - Place single order")
     logger.info(f"   POST http://localhost:{port}/arbitrage - Execute arbitrage")
+    logger.info(f"   POST http://localhost:{port}/cancel/<id> - Cancel order")
+    logger.info(f"   POST http://localhost:{port}/cancel-all - Cancel all orders")
     logger.info("=" * 60)
     logger.info("")
     
@@ -93,13 +93,18 @@ fi
 cp clob_client_fixed.rs "$BOT_DIR/src/execution/clob_client.rs"
 echo -e "${GREEN}✅ Updated clob_client.rs${NC}"
 
-# Update Cargo.toml to remove HMAC dependencies
-echo -e "${YELLOW}   Updating Cargo.toml...${NC}"
-# Remove hmac, sha2, base64 dependencies
-sed -i.backup '/^hmac = /d' "$BOT_DIR/Cargo.toml"
-sed -i '/^sha2 = /d' "$BOT_DIR/Cargo.toml"
-sed -i '/^base64 = /d' "$BOT_DIR/Cargo.toml"
-echo -e "${GREEN}✅ Removed manual HMAC dependencies${NC}"
+# Copy updated modules (native EIP-712 signer, order builder, ...)
+cp -r src/. "$BOT_DIR/src/"
+echo -e "${GREEN}✅ Updated src/ modules${NC}"
+
+# Crates the updated modules need on top of the bot's own
+for DEP in 'sha1 = "0.10"' 'rusqlite = { version = "0.31", features = ["bundled"] }' 'openssl = "0.10"' 'thiserror = "1.0"'; do
+    NAME="${DEP%% *}"
+    if ! grep -q "^$NAME = " "$BOT_DIR/Cargo.toml"; then
+        sed -i "/^\[dependencies\]/a $DEP" "$BOT_DIR/Cargo.toml"
+        echo -e "${GREEN}✅ Added dependency $NAME${NC}"
+    fi
+done
 
 # ===== STEP 5: Build Rust Bot =====
 echo ""
@@ -0,0 +1,298 @@
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use ethers::types::Address;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::Command;
+use crate::execution::clob_client::{ClobClient, TokenHolder};
+use crate::market_ws::replay::Replay;
+use crate::portfolio::{CostMethod, PerformanceStats, PnlEngine};
+use crate::risk::{KillSwitch, LossBreaker};
+use crate::storage::{export_fills_csv, Journal, PnlReport};
+use crate::strategy::walk_forward::lookup;
+use crate::strategy::{
+    Backtest, CostModel, LatencyModel, MarketMaker, MarketMakerConfig, MeanReversion, MeanReversionConfig, Perturbation,
+    ScenarioConfig, ScenarioEngine, Strategy, WalkForward, WalkForwardConfig,
+};
+use crate::wallet::order_builder::MarketRules;
+
+/// Key for the offline backtest client; it never signs anything
+const OFFLINE_KEY: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+// ==================================================
+// ONE-OFF COMMANDS
+// ==================================================
+
+/// Run a CLI subcommand against the journal (`JOURNAL_PATH`), the
+/// kill switch (`KILL_SWITCH_PATH`), recorded market data or, for
+/// transfers, the wallet
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::ExportTrades { out, since } => {
+            let since_ms = since_ms(since.as_deref())?;
+            export_fills_csv(&Journal::from_env()?, &out, since_ms, CostMethod::from_env())?;
+            Ok(())
+        }
+        Command::Report { out, since } => {
+            let journal = Journal::from_env()?;
+            let held: Vec<String> = journal
+                .positions()?
+                .into_iter()
+                .filter(|p| !p.size.is_zero())
+                .map(|p| p.token_id)
+                .collect();
+            // Public endpoint; without it open positions are left unmarked
+            let marks = if held.is_empty() {
+                HashMap::new()
+            } else {
+                ClobClient::offline(OFFLINE_KEY)?
+                    .midpoints(&held)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("⚠️  No midpoints for {} open position(s): {}", held.len(), e);
+                        HashMap::new()
+                    })
+            };
+            let report = PnlReport::from_journal(
+                "live",
+                &journal,
+                since_ms(since.as_deref())?,
+                CostMethod::from_env(),
+                &marks,
+            )?;
+            report.log();
+            report.write(&out)
+        }
+        Command::Stats { since } => {
+            let since = parse_date(since.as_deref())?;
+            // Replay everything so early entries still set the cost basis
+            let mut pnl = PnlEngine::from_env();
+            for fill in Journal::from_env()?.fills_since(0)? {
+                pnl.on_fill(&fill);
+            }
+            let daily: Vec<_> = pnl
+                .daily()
+                .into_iter()
+                .filter(|d| since.is_none_or(|s| d.date >= s))
+                .collect();
+            PerformanceStats::from_daily(&daily, PerformanceStats::capital_from_env()).log();
+            Ok(())
+        }
+        Command::Transfer {
+            token,
+            amount,
+            to,
+            from_eoa,
+            dry_run,
+        } => {
+            let clob = client().await?;
+            let from = if from_eoa { TokenHolder::Signer } else { TokenHolder::Funder };
+            let to = match to.to_lowercase().as_str() {
+                "proxy" => clob.funder(),
+                "eoa" => clob.signer_address(),
+                other => other
+                    .parse::<Address>()
+                    .map_err(|e| anyhow!("Invalid destination {}: {}", to, e))?,
+            };
+
+            let preview = clob.preview_transfer(&token, from, to, amount).await?;
+            preview.log();
+            if dry_run || clob.is_read_only() {
+                info!("📝 Not sent (dry run / read-only)");
+                return Ok(());
+            }
+            clob.transfer_tokens(&token, from, to, amount).await?;
+            Ok(())
+        }
+        Command::Kill { reason } => KillSwitch::from_env().trip(&reason),
+        Command::Resume => {
+            KillSwitch::from_env().reset()?;
+            LossBreaker::from_env().override_today()
+        }
+        Command::Backtest {
+            data,
+            strategy,
+            tick_size,
+            min_size,
+            taker_fee_bps,
+            maker_fee_bps,
+            slippage,
+            submit_latency,
+            cancel_latency,
+            book_latency,
+            report,
+        } => {
+            let replay = load_replay(&data)?;
+            let strategy = backtest_strategy(&strategy, |name| std::env::var(name).ok())?;
+            let defaults = CostModel::from_env();
+            let costs = CostModel {
+                taker_fee_bps: taker_fee_bps.unwrap_or(defaults.taker_fee_bps),
+                maker_fee_bps: maker_fee_bps.unwrap_or(defaults.maker_fee_bps),
+                slippage: slippage.unwrap_or(defaults.slippage),
+            };
+            let defaults = LatencyModel::from_env();
+            let latency = LatencyModel {
+                submit: submit_latency.unwrap_or(defaults.submit),
+                cancel: cancel_latency.unwrap_or(defaults.cancel),
+                book: book_latency.unwrap_or(defaults.book),
+                seed: defaults.seed,
+            };
+            let clob = Arc::new(ClobClient::offline(OFFLINE_KEY)?);
+            let journal = Arc::new(Journal::in_memory()?);
+            let run = Backtest::new(clob, replay)
+                .rules(MarketRules {
+                    tick_size,
+                    min_size,
+                    ..MarketRules::default()
+                })
+                .costs(costs)
+                .latency(latency)
+                .journal(journal.clone())
+                .run(strategy)
+                .await?;
+            run.log();
+            if let Some(dir) = report {
+                let source = format!("backtest {}", run.strategy);
+                PnlReport::from_journal(&source, &journal, 0, CostMethod::from_env(), &run.marks)?.write(dir)?;
+            }
+            Ok(())
+        }
+        Command::WalkForward {
+            data,
+            strategy,
+            params,
+            in_sample_mins,
+            out_of_sample_mins,
+            tick_size,
+            min_size,
+            export,
+        } => {
+            let config = WalkForwardConfig {
+                in_sample: Duration::from_secs(in_sample_mins * 60),
+                out_of_sample: Duration::from_secs(out_of_sample_mins * 60),
+                step: None,
+            };
+            let clob = Arc::new(ClobClient::offline(OFFLINE_KEY)?);
+            let report = WalkForward::new(clob, load_replay(&data)?, config)
+                .rules(MarketRules {
+                    tick_size,
+                    min_size,
+                    ..MarketRules::default()
+                })
+                .run(&params, |set| backtest_strategy(&strategy, |name| lookup(set, name)))
+                .await?;
+            report.log();
+            if let Some(path) = export {
+                report.export(&path)?;
+            }
+            Ok(())
+        }
+        Command::Scenario {
+            data,
+            strategy,
+            paths,
+            seed,
+            jump_window_mins,
+            p_win,
+            evaporation_prob,
+            evaporation_mins,
+            evaporation_keep,
+            evaporation_levels,
+            tick_size,
+            min_size,
+        } => {
+            let mut perturbations = Vec::new();
+            if jump_window_mins > 0 {
+                perturbations.push(Perturbation::ResolutionJump {
+                    window: Duration::from_secs(jump_window_mins * 60),
+                    p_win,
+                });
+            }
+            if evaporation_prob > 0.0 {
+                perturbations.push(Perturbation::LiquidityEvaporation {
+                    probability: evaporation_prob,
+                    duration: Duration::from_secs(evaporation_mins * 60),
+                    keep: evaporation_keep,
+                    levels: evaporation_levels,
+                });
+            }
+            let config = ScenarioConfig {
+                paths,
+                seed,
+                perturbations,
+            };
+            let clob = Arc::new(ClobClient::offline(OFFLINE_KEY)?);
+            let report = ScenarioEngine::new(clob, load_replay(&data)?, config)
+                .rules(MarketRules {
+                    tick_size,
+                    min_size,
+                    ..MarketRules::default()
+                })
+                .run(|| backtest_strategy(&strategy, |name| std::env::var(name).ok()))
+                .await?;
+            report.log();
+            if !report.failed().is_empty() {
+                return Err(anyhow!("{} scenario path(s) broke a risk limit", report.failed().len()));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// A recording file or a directory of them
+fn load_replay(data: &Path) -> Result<Replay> {
+    if data.is_dir() {
+        Replay::from_dir(data)
+    } else {
+        Replay::from_files(&[data])
+    }
+}
+
+/// A strategy the backtester can run, configured through `vars`
+fn backtest_strategy(name: &str, vars: impl Fn(&str) -> Option<String>) -> Result<Arc<dyn Strategy>> {
+    match name {
+        "market-maker" => {
+            let token_id = vars("MM_TOKEN_ID").ok_or_else(|| anyhow!("MM_TOKEN_ID missing in .env"))?;
+            Ok(Arc::new(MarketMaker::new(token_id, MarketMakerConfig::from_vars(vars))))
+        }
+        "mean-reversion" => Ok(Arc::new(MeanReversion::new(MeanReversionConfig::from_vars(vars)))),
+        other => Err(anyhow!("Can't backtest strategy {}", other)),
+    }
+}
+
+/// Wallet client from `RPC_URL`, `PRIVATE_KEY` and `PROXY_WALLET`
+async fn client() -> Result<ClobClient> {
+    let var = |name: &str| std::env::var(name).map_err(|_| anyhow!("{} missing in .env", name));
+    Ok(ClobClient::new(
+        &var("RPC_URL")?,
+        &var("PRIVATE_KEY")?,
+        &var("PROXY_WALLET")?,
+        std::env::var("POLY_API_KEY").unwrap_or_default(),
+        std::env::var("POLY_API_SECRET").unwrap_or_default(),
+        std::env::var("POLY_API_PASSPHRASE").unwrap_or_default(),
+    )
+    .await?)
+}
+
+fn parse_date(date: Option<&str>) -> Result<Option<NaiveDate>> {
+    date.map(|d| {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|e| anyhow!("Invalid --since date {}: {}", d, e))
+    })
+    .transpose()
+}
+
+/// Start of `--since` in unix ms; 0 when unset
+fn since_ms(date: Option<&str>) -> Result<u64> {
+    Ok(match parse_date(date)? {
+        Some(date) => date
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc()
+            .timestamp_millis() as u64,
+        None => 0,
+    })
+}
@@ -0,0 +1,212 @@
+use crate::execution::clob_client::ClobClient;
+use crate::domain::*;
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::domain::order::Side;
+use crate::net::SendRetry;
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Clone)]
+pub struct PolymarketClient {
+    client: Client,
+
+    pub gamma_url: String,
+    pub clob_url: String,
+
+    pub api_key: String,
+    api_secret: String,
+    api_passphrase: String,
+
+    pub read_only: bool,
+
+    pub clob_client: Arc<ClobClient>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SignedOrderPayload {
+    pub order: OrderRequest,
+    pub signature: String,
+    pub address: String,
+}
+
+// ==================================================
+// CONSTRUCTOR
+// ==================================================
+impl PolymarketClient {
+    pub fn new(
+        gamma_url: String,
+        clob_url: String,
+        api_key: String,
+        api_secret: String,
+        api_passphrase: String,
+        read_only: bool,
+        clob_client: Arc<ClobClient>,
+    ) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("HTTP client");
+
+        Self {
+            client,
+            clob_client,
+            gamma_url,
+            clob_url,
+            api_key,
+            api_secret,
+            api_passphrase,
+            read_only,
+        }
+    }
+
+    // ==================================================
+    // EXPOSE CLOB CLIENT
+    // ==================================================
+    pub fn clob_client(&self) -> Arc<ClobClient> {
+        self.clob_client.clone()
+    }
+
+    // ==================================================
+    // BUILD + SIGN ORDER (🔥 THIS WAS MISSING)
+    // ==================================================
+    pub fn build_signed_order(
+        &self,
+        priced: &crate::domain::order::PricedOrder,
+    ) -> Result<SignedOrderPayload> {
+        let order = OrderRequest {
+    token_id: priced.token_id.clone(),
+    side: match priced.side {
+        Side::Buy => "buy".to_string(),
+        Side::Sell => "sell".to_string(),
+    },
+    price: priced.price.to_string(),
+size: priced.size_usdc.to_string(),
+order_type: match priced.side {
+    Side::Buy => "buy".to_string(),
+    Side::Sell => "sell".to_string(),
+},
+};
+
+
+        let body = serde_json::to_string(&order)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs()
+            .to_string();
+
+        let signature = self.sign_request(
+            "POST",
+            "/orders",
+            &body,
+            &timestamp,
+        );
+
+        Ok(SignedOrderPayload {
+            order,
+            signature,
+            address: self.api_key.clone(),
+        })
+    }
+
+    // ==================================================
+    // REQUEST SIGNING (HMAC)
+    // ==================================================
+    fn sign_request(
+        &self,
+        method: &str,
+        path: &str,
+        body: &str,
+        timestamp: &str,
+    ) -> String {
+        let payload = format!("{}{}{}{}", timestamp, method, path, body);
+
+        let mut mac =
+            HmacSha256::new_from_slice(self.api_secret.as_bytes())
+                .expect("HMAC init failed");
+
+        mac.update(payload.as_bytes());
+        general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+    }
+
+    // ==================================================
+    // PLACE ORDER
+    // ==================================================
+    pub async fn place_signed_order(
+    &self,
+    order: &OrderRequest,
+) -> Result<OrderResponse> {
+    if self.read_only {
+        anyhow::bail!("READ ONLY MODE");
+    }
+
+    let payload = SignedOrderPayload {
+        order: order.clone(),
+        signature: "".to_string(), // Polymarket REST signs via headers
+        address: "".to_string(),
+    };
+
+    let path = "/orders";
+    let url = format!("{}{}", self.clob_url, path);
+    let body = serde_json::to_string(&payload)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_secs()
+        .to_string();
+
+    let signature =
+        self.sign_request("POST", path, &body, &timestamp);
+
+    let response = self
+        .client
+        .post(&url)
+        .header("POLY-API-KEY", &self.api_key)
+        .header("POLY-API-SIGNATURE", signature)
+        .header("POLY-API-TIMESTAMP", &timestamp)
+        .header("POLY-API-PASSPHRASE", &self.api_passphrase)
+        .json(&payload)
+        .send_retry()
+        .await?;
+
+    if !response.status().is_success() {
+        let err = response.text().await.unwrap_or_default();
+        anyhow::bail!("Order rejected: {}", err);
+    }
+
+    Ok(response.json().await?)
+}
+
+
+    // ==================================================
+    // 🔥 FIXED: GET USDC BALANCE FROM BLOCKCHAIN
+    // ==================================================
+    pub async fn get_usdc_balance(&self) -> Result<Decimal> {
+        // Read balance directly from blockchain instead of API
+        // (The CLOB API /balances/me endpoint returns 404)
+        let balance = self.clob_client
+            .get_usdc_balance()
+            .await
+            .context("Failed to read USDC balance from blockchain")?;
+        
+        Ok(balance)
+    }
+
+    pub async fn get_market_by_slug(&self, slug: &str) -> Result<Market> {
+        let url = format!("{}/events/slug/{}", self.gamma_url, slug);
+        let response = self.client.get(&url).send_retry().await?;
+        let json: Value = response.json().await?;
+
+        json["markets"]
+            .as_array()
+            .and_then(|m| m.first())
+            .map(|m| serde_json::from_value(m.clone()).unwrap())
+            .context("Market not found")
+    }
+}
@@ -0,0 +1,367 @@
+use anyhow::anyhow;
+use base64::{engine::general_purpose, Engine as _};
+use ethers::types::Address;
+use hmac::{Hmac, Mac};
+use log::warn;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Client, Method};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{ClobError, Result};
+use crate::net::SendRetry;
+use crate::wallet::signer::{OrderType, SignedOrderPayload};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// ==================================================
+// CREDENTIALS
+// ==================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiCredentials {
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    pub secret: String,
+    pub passphrase: String,
+}
+
+impl ApiCredentials {
+    pub fn new(api_key: String, secret: String, passphrase: String) -> Self {
+        Self {
+            api_key,
+            secret,
+            passphrase,
+        }
+    }
+
+    /// All three fields present
+    pub fn is_complete(&self) -> bool {
+        !self.api_key.is_empty() && !self.secret.is_empty() && !self.passphrase.is_empty()
+    }
+}
+
+// ==================================================
+// RESPONSE TYPES
+// ==================================================
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostOrderResponse {
+    #[serde(default)]
+    pub success: bool,
+    #[serde(rename = "errorMsg", default)]
+    pub error_msg: String,
+    #[serde(rename = "orderID", default)]
+    pub order_id: String,
+    /// matched | live | delayed | unmatched
+    #[serde(default)]
+    pub status: String,
+    #[serde(rename = "makingAmount", default)]
+    pub making_amount: String,
+    #[serde(rename = "takingAmount", default)]
+    pub taking_amount: String,
+    #[serde(rename = "transactionsHashes", default)]
+    pub transaction_hashes: Vec<String>,
+}
+
+impl PostOrderResponse {
+    /// Outcome tokens matched on arrival: what a buy takes or a sell
+    /// gives. None when the exchange left the amounts out.
+    pub fn matched(&self, side: u8) -> Option<Decimal> {
+        let tokens = if side == 0 { &self.taking_amount } else { &self.making_amount };
+        Decimal::from_str(tokens).ok()
+    }
+}
+
+/// An accepted order post, from whichever venue took it
+#[derive(Debug, Clone)]
+pub struct PostedOrder {
+    pub order_id: String,
+    /// matched | live | delayed | unmatched
+    pub status: String,
+    /// Outcome tokens matched on arrival, if known
+    pub matched: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CancelResponse {
+    #[serde(default)]
+    pub canceled: Vec<String>,
+    #[serde(default)]
+    pub not_canceled: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenOrder {
+    pub id: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub market: String,
+    pub asset_id: String,
+    pub side: String,
+    pub original_size: String,
+    #[serde(default)]
+    pub size_matched: String,
+    pub price: String,
+    #[serde(default)]
+    pub outcome: String,
+    #[serde(default)]
+    pub order_type: String,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub expiration: String,
+}
+
+impl OpenOrder {
+    pub fn price(&self) -> Decimal {
+        Decimal::from_str(&self.price).unwrap_or_default()
+    }
+
+    /// Size already matched against this order
+    pub fn filled(&self) -> Decimal {
+        Decimal::from_str(&self.size_matched).unwrap_or_default()
+    }
+
+    /// Size still resting on the book
+    pub fn remaining(&self) -> Decimal {
+        Decimal::from_str(&self.original_size).unwrap_or_default() - self.filled()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    data: Vec<T>,
+    #[serde(default)]
+    next_cursor: String,
+}
+
+/// Pagination cursors used by the CLOB (base64 "0" and "-1")
+const START_CURSOR: &str = "MA==";
+const END_CURSOR: &str = "LTE=";
+
+// ==================================================
+// CLOB REST API (L2 AUTH)
+// ==================================================
+
+#[derive(Clone)]
+pub struct ClobApi {
+    http: Client,
+    base_url: String,
+    address: Address,
+    creds: ApiCredentials,
+}
+
+impl ClobApi {
+    /// Requests time out after `CLOB_TIMEOUT_MS` (10000)
+    pub fn new(base_url: &str, address: Address, creds: ApiCredentials) -> Self {
+        let timeout = std::env::var("CLOB_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map_or(Duration::from_secs(10), Duration::from_millis);
+        let http = Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("HTTP client");
+
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            address,
+            creds,
+        }
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.creds.api_key
+    }
+
+    pub fn credentials(&self) -> &ApiCredentials {
+        &self.creds
+    }
+
+    // ==================================================
+    // REQUEST SIGNING (HMAC-SHA256)
+    // ==================================================
+
+    /// base64url(HMAC_SHA256(base64url_decode(secret), ts + method + path + body))
+    fn sign(&self, timestamp: &str, method: &str, path: &str, body: &str) -> anyhow::Result<String> {
+        let secret = general_purpose::URL_SAFE
+            .decode(&self.creds.secret)
+            .map_err(|e| anyhow!("API secret is not valid base64: {}", e))?;
+
+        let mut mac = HmacSha256::new_from_slice(&secret)
+            .map_err(|e| anyhow!("HMAC init failed: {}", e))?;
+        mac.update(format!("{}{}{}{}", timestamp, method, path, body).as_bytes());
+
+        Ok(general_purpose::URL_SAFE.encode(mac.finalize().into_bytes()))
+    }
+
+    fn l2_headers(&self, method: &str, path: &str, body: &str) -> anyhow::Result<HeaderMap> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs()
+            .to_string();
+        let signature = self.sign(&timestamp, method, path, body)?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("POLY_ADDRESS", HeaderValue::from_str(&format!("{:?}", self.address))?);
+        headers.insert("POLY_SIGNATURE", HeaderValue::from_str(&signature)?);
+        headers.insert("POLY_TIMESTAMP", HeaderValue::from_str(&timestamp)?);
+        headers.insert("POLY_API_KEY", HeaderValue::from_str(&self.creds.api_key)?);
+        headers.insert("POLY_PASSPHRASE", HeaderValue::from_str(&self.creds.passphrase)?);
+        Ok(headers)
+    }
+
+    /// Signed request; `path` is what gets signed, `query` is appended after.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let body = match body {
+            Some(b) => serde_json::to_string(&b)?,
+            None => String::new(),
+        };
+        let headers = self.l2_headers(method.as_str(), path, &body)?;
+        let url = format!("{}{}{}", self.base_url, path, query);
+
+        let mut req = self.http.request(method.clone(), &url).headers(headers);
+        if !body.is_empty() {
+            req = req
+                .header("Content-Type", "application/json")
+                .body(body);
+        }
+
+        // Order posts aren't idempotent: retried only if never sent
+        let resp = if method == Method::POST {
+            req.send_retry_unsent().await?
+        } else {
+            req.send_retry().await?
+        };
+        let status = resp.status();
+        let text = resp.text().await?;
+
+        if !status.is_success() {
+            warn!("❌ CLOB {} {} → {}", method, path, status);
+            return Err(ClobError::from_response(status, &text));
+        }
+
+        serde_json::from_str(&text)
+            .map_err(|e| anyhow!("Unexpected CLOB response ({}): {}", e, text).into())
+    }
+
+    // ==================================================
+    // ORDERS
+    // ==================================================
+
+    pub async fn post_order(
+        &self,
+        order: &SignedOrderPayload,
+        order_type: OrderType,
+    ) -> Result<PostOrderResponse> {
+        let body = json!({
+            "order": order,
+            "owner": self.creds.api_key,
+            "orderType": order_type.as_str(),
+        });
+
+        let resp: PostOrderResponse = self.request(Method::POST, "/order", "", Some(body)).await?;
+        if !resp.success {
+            return Err(ClobError::rejected(&resp.error_msg));
+        }
+        Ok(resp)
+    }
+
+    /// Post several orders in one request (`POST /orders`). Responses
+    /// come back in request order; check `success` on each.
+    pub async fn post_orders(
+        &self,
+        orders: &[(SignedOrderPayload, OrderType)],
+    ) -> Result<Vec<PostOrderResponse>> {
+        let body: Vec<_> = orders
+            .iter()
+            .map(|(order, order_type)| {
+                json!({
+                    "order": order,
+                    "owner": self.creds.api_key,
+                    "orderType": order_type.as_str(),
+                })
+            })
+            .collect();
+
+        self.request(Method::POST, "/orders", "", Some(json!(body))).await
+    }
+
+    pub async fn cancel_order(&self, order_id: &str) -> Result<CancelResponse> {
+        self.request(Method::DELETE, "/order", "", Some(json!({ "orderID": order_id })))
+            .await
+    }
+
+    pub async fn cancel_orders(&self, order_ids: &[String]) -> Result<CancelResponse> {
+        self.request(Method::DELETE, "/orders", "", Some(json!(order_ids)))
+            .await
+    }
+
+    pub async fn cancel_all(&self) -> Result<CancelResponse> {
+        self.request(Method::DELETE, "/cancel-all", "", None).await
+    }
+
+    /// Cancel every order on a market (condition id) and/or asset (token id)
+    pub async fn cancel_market(
+        &self,
+        market: Option<&str>,
+        asset_id: Option<&str>,
+    ) -> Result<CancelResponse> {
+        let body = json!({
+            "market": market.unwrap_or_default(),
+            "asset_id": asset_id.unwrap_or_default(),
+        });
+        self.request(Method::DELETE, "/cancel-market-orders", "", Some(body))
+            .await
+    }
+
+    pub async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        let path = format!("/data/order/{}", order_id);
+        self.request(Method::GET, &path, "", None).await
+    }
+
+    /// All open orders, optionally filtered by market and/or token id
+    pub async fn get_open_orders(
+        &self,
+        market: Option<&str>,
+        asset_id: Option<&str>,
+    ) -> Result<Vec<OpenOrder>> {
+        let mut orders = Vec::new();
+        let mut cursor = START_CURSOR.to_string();
+
+        loop {
+            let mut query = format!("?next_cursor={}", cursor);
+            if let Some(m) = market {
+                query.push_str(&format!("&market={}", m));
+            }
+            if let Some(a) = asset_id {
+                query.push_str(&format!("&asset_id={}", a));
+            }
+
+            let page: Page<OpenOrder> = self.request(Method::GET, "/data/orders", &query, None).await?;
+            orders.extend(page.data);
+
+            if page.next_cursor.is_empty() || page.next_cursor == END_CURSOR {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        Ok(orders)
+    }
+}
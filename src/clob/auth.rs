@@ -0,0 +1,143 @@
+use anyhow::{anyhow, Result};
+use ethers::abi::{encode, Token};
+use ethers::prelude::*;
+use ethers::types::{H256, U256};
+use ethers::utils::keccak256;
+use log::{info, warn};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Client, Method};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clob::api::ApiCredentials;
+use crate::net::SendRetry;
+
+// ==================================================
+// CLOB AUTH (L1) — EIP-712
+// ==================================================
+
+const AUTH_DOMAIN_NAME: &str = "ClobAuthDomain";
+const AUTH_DOMAIN_VERSION: &str = "1";
+const AUTH_DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId)";
+const CLOB_AUTH_TYPE: &str = "ClobAuth(address address,string timestamp,uint256 nonce,string message)";
+const CLOB_AUTH_MESSAGE: &str = "This message attests that I control the given wallet";
+
+fn auth_digest(address: Address, chain_id: u64, timestamp: &str, nonce: u64) -> H256 {
+    let domain = keccak256(encode(&[
+        Token::FixedBytes(keccak256(AUTH_DOMAIN_TYPE).to_vec()),
+        Token::FixedBytes(keccak256(AUTH_DOMAIN_NAME).to_vec()),
+        Token::FixedBytes(keccak256(AUTH_DOMAIN_VERSION).to_vec()),
+        Token::Uint(U256::from(chain_id)),
+    ]));
+
+    let message = keccak256(encode(&[
+        Token::FixedBytes(keccak256(CLOB_AUTH_TYPE).to_vec()),
+        Token::Address(address),
+        Token::FixedBytes(keccak256(timestamp).to_vec()),
+        Token::Uint(U256::from(nonce)),
+        Token::FixedBytes(keccak256(CLOB_AUTH_MESSAGE).to_vec()),
+    ]));
+
+    let mut buf = Vec::with_capacity(66);
+    buf.extend_from_slice(&[0x19, 0x01]);
+    buf.extend_from_slice(&domain);
+    buf.extend_from_slice(&message);
+    H256::from(keccak256(buf))
+}
+
+/// POLY_ADDRESS / POLY_SIGNATURE / POLY_TIMESTAMP / POLY_NONCE
+fn l1_headers(wallet: &LocalWallet, chain_id: u64, nonce: u64) -> Result<HeaderMap> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_secs()
+        .to_string();
+
+    let digest = auth_digest(wallet.address(), chain_id, &timestamp, nonce);
+    let sig = wallet.sign_hash(digest)?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("POLY_ADDRESS", HeaderValue::from_str(&format!("{:?}", wallet.address()))?);
+    headers.insert("POLY_SIGNATURE", HeaderValue::from_str(&format!("0x{}", sig))?);
+    headers.insert("POLY_TIMESTAMP", HeaderValue::from_str(&timestamp)?);
+    headers.insert("POLY_NONCE", HeaderValue::from_str(&nonce.to_string())?);
+    Ok(headers)
+}
+
+// ==================================================
+// API KEY HANDSHAKE
+// ==================================================
+
+async fn auth_request(
+    base_url: &str,
+    method: Method,
+    path: &str,
+    wallet: &LocalWallet,
+    chain_id: u64,
+    nonce: u64,
+) -> Result<ApiCredentials> {
+    let http = Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let url = format!("{}{}", base_url.trim_end_matches('/'), path);
+    let resp = http
+        .request(method, &url)
+        .headers(l1_headers(wallet, chain_id, nonce)?)
+        .send_retry()
+        .await?;
+
+    let status = resp.status();
+    let body = resp.text().await?;
+
+    if !status.is_success() {
+        return Err(anyhow!("{} failed: {} - {}", path, status, body));
+    }
+
+    let creds: ApiCredentials = serde_json::from_str(&body)
+        .map_err(|e| anyhow!("Unexpected {} response ({}): {}", path, e, body))?;
+
+    if !creds.is_complete() {
+        return Err(anyhow!("{} returned incomplete credentials", path));
+    }
+    Ok(creds)
+}
+
+/// Create a new API key for this wallet (`POST /auth/api-key`)
+pub async fn create_api_key(
+    base_url: &str,
+    wallet: &LocalWallet,
+    chain_id: u64,
+    nonce: u64,
+) -> Result<ApiCredentials> {
+    auth_request(base_url, Method::POST, "/auth/api-key", wallet, chain_id, nonce).await
+}
+
+/// Re-derive the existing API key for this wallet/nonce (`GET /auth/derive-api-key`)
+pub async fn derive_api_key(
+    base_url: &str,
+    wallet: &LocalWallet,
+    chain_id: u64,
+    nonce: u64,
+) -> Result<ApiCredentials> {
+    auth_request(base_url, Method::GET, "/auth/derive-api-key", wallet, chain_id, nonce).await
+}
+
+/// Derive existing credentials, creating them on first use.
+pub async fn create_or_derive_api_key(
+    base_url: &str,
+    wallet: &LocalWallet,
+    chain_id: u64,
+    nonce: u64,
+) -> Result<ApiCredentials> {
+    match derive_api_key(base_url, wallet, chain_id, nonce).await {
+        Ok(creds) => {
+            info!("🔑 API key derived for {:?}", wallet.address());
+            Ok(creds)
+        }
+        Err(e) => {
+            warn!("⚠️  Could not derive API key ({}), creating a new one", e);
+            let creds = create_api_key(base_url, wallet, chain_id, nonce).await?;
+            info!("🔑 New API key created for {:?}", wallet.address());
+            Ok(creds)
+        }
+    }
+}
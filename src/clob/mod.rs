@@ -0,0 +1,4 @@
+pub mod api;
+pub mod auth;
+
+pub use api::{ApiCredentials, ClobApi};
@@ -0,0 +1,154 @@
+use anyhow::{anyhow, Result};
+use ethers::types::Address;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// ==================================================
+// CHAIN PROFILES
+// ==================================================
+
+/// Contract addresses the bot talks to on a given chain.
+///
+/// Presets cover Polygon mainnet and Amoy; anything else (a local
+/// anvil fork, a custom deployment) is loaded from a JSON file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainConfig {
+    pub name: String,
+    pub chain_id: u64,
+    /// CTF exchange (EIP-712 verifying contract for orders)
+    pub exchange: Address,
+    pub neg_risk_exchange: Address,
+    /// Neg-risk adapter (splits, merges, conversions and redemptions of
+    /// neg-risk markets), where its deployment is known
+    #[serde(default)]
+    pub neg_risk_adapter: Option<Address>,
+    /// Conditional Tokens (ERC-1155 outcome positions)
+    pub ctf: Address,
+    /// Exchange collateral (bridged USDC.e on Polygon)
+    pub usdc: Address,
+    /// Circle-native USDC, if deployed on this chain
+    #[serde(default)]
+    pub native_usdc: Option<Address>,
+    /// Uniswap V3 router used to convert native USDC into collateral
+    #[serde(default)]
+    pub swap_router: Option<Address>,
+    /// UMA CTF adapters that resolve Polymarket questions (standard and
+    /// neg-risk)
+    #[serde(default)]
+    pub uma_adapters: Vec<Address>,
+    /// UMA optimistic oracle the adapters request prices from
+    #[serde(default)]
+    pub uma_oracle: Option<Address>,
+}
+
+impl ChainConfig {
+    /// Polygon mainnet (137)
+    pub fn polygon() -> Self {
+        Self {
+            name: "polygon".to_string(),
+            chain_id: 137,
+            exchange: addr("0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"),
+            neg_risk_exchange: addr("0xC5d563A36AE78145C45a50134d48A1215220f80a"),
+            neg_risk_adapter: Some(addr("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296")),
+            ctf: addr("0x4D97DCd97eC945f40cF65F87097ACe5EA0476045"),
+            usdc: addr("0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"),
+            native_usdc: Some(addr("0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359")),
+            swap_router: Some(addr("0xE592427A0AEce92De3Edee1F18E0157C05861564")),
+            uma_adapters: vec![
+                addr("0x157Ce2d672854c848c9b79C49a8Cc6cc89176a49"),
+                addr("0x2F5e3684cb1F318ec51b00Edba38d79Ac2c0aA9d"),
+            ],
+            uma_oracle: Some(addr("0xee3Afe347D5C74317041E2618C49534dAf887c24")),
+        }
+    }
+
+    /// Polygon Amoy testnet (80002), as deployed for py-clob-client.
+    /// No neg-risk adapter is published there; set one with
+    /// `CHAIN_CONFIG` to split, merge or redeem neg-risk markets.
+    pub fn amoy() -> Self {
+        Self {
+            name: "amoy".to_string(),
+            chain_id: 80002,
+            exchange: addr("0xdFE02Eb6733538f8Ea35D585af8DE5958AD99E40"),
+            neg_risk_exchange: addr("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"),
+            neg_risk_adapter: None,
+            ctf: addr("0x69308FB512518e39F9b16112fA8d994F4e2Bf8bB"),
+            usdc: addr("0x9c4e1703476e875070ee25b56a58b008cfb8fa78"),
+            native_usdc: None,
+            swap_router: None,
+            uma_adapters: vec![],
+            uma_oracle: None,
+        }
+    }
+
+    /// Preset for a known chain id
+    pub fn for_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            137 => Some(Self::polygon()),
+            80002 => Some(Self::amoy()),
+            _ => None,
+        }
+    }
+
+    /// Load a profile from a JSON file (same shape as this struct)
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid chain config {}: {}", path.display(), e))
+    }
+
+    /// Resolve the profile for the chain the RPC reports.
+    ///
+    /// `CHAIN_CONFIG` points to a JSON profile; otherwise `CHAIN_PROFILE`
+    /// (polygon | amoy | fork) or the chain id picks a preset. `fork`
+    /// keeps mainnet addresses under the fork's chain id (e.g. anvil 31337).
+    pub fn from_env(chain_id: u64) -> Result<Self> {
+        let cfg = if let Ok(path) = std::env::var("CHAIN_CONFIG") {
+            Self::load(Path::new(&path))?
+        } else {
+            match std::env::var("CHAIN_PROFILE")
+                .unwrap_or_default()
+                .to_lowercase()
+                .as_str()
+            {
+                "polygon" => Self::polygon(),
+                "amoy" => Self::amoy(),
+                "fork" => Self {
+                    name: "fork".to_string(),
+                    chain_id,
+                    ..Self::polygon()
+                },
+                _ => Self::for_chain_id(chain_id).ok_or_else(|| {
+                    anyhow!(
+                        "No chain profile for chain id {} — set CHAIN_PROFILE=fork or CHAIN_CONFIG",
+                        chain_id
+                    )
+                })?,
+            }
+        };
+
+        if cfg.chain_id != chain_id {
+            return Err(anyhow!(
+                "Chain profile '{}' is for chain {}, but RPC reports {}",
+                cfg.name,
+                cfg.chain_id,
+                chain_id
+            ));
+        }
+
+        Ok(cfg)
+    }
+}
+
+// ==================================================
+// COLLATERAL
+// ==================================================
+
+/// Symbol of the exchange collateral. The CTF exchange settles in
+/// bridged USDC.e; native USDC has to be swapped before it can back
+/// orders.
+pub const COLLATERAL_SYMBOL: &str = "USDC.e";
+
+fn addr(s: &str) -> Address {
+    s.parse().expect("invalid preset address")
+}
@@ -0,0 +1,407 @@
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::PathBuf;
+
+pub mod chain;
+pub use chain::{ChainConfig, COLLATERAL_SYMBOL};
+
+/* =======================
+POSITION SIZING MODES
+======================= */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TradeMode {
+    Fixed,
+    Percentage,
+    Dynamic,
+    Free,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSizing {
+    pub mode: TradeMode,
+
+    // FIXED: absolute USDC per trade
+    pub fixed_usdc: Option<f64>,
+
+    // PERCENTAGE: % of wallet balance
+    pub percentage: Option<f64>,
+
+    // DYNAMIC: max risk % of balance
+    pub max_risk_percent: Option<f64>,
+}
+
+impl PositionSizing {
+    pub fn from_env() -> Self {
+        let mode = env::var("TRADE_MODE")
+            .unwrap_or_else(|_| "PERCENTAGE".to_string())
+            .to_uppercase();
+
+        match mode.as_str() {
+            "FIXED" => Self {
+                mode: TradeMode::Fixed,
+                fixed_usdc: Some(
+                    env::var("FIXED_USDC_PER_TRADE")
+                        .unwrap_or_else(|_| "5".to_string())
+                        .parse()
+                        .expect("Invalid FIXED_USDC_PER_TRADE"),
+                ),
+                percentage: None,
+                max_risk_percent: None,
+            },
+
+            "DYNAMIC" => Self {
+                mode: TradeMode::Dynamic,
+                fixed_usdc: None,
+                percentage: None,
+                max_risk_percent: Some(
+                    env::var("MAX_RISK_PERCENT")
+                        .unwrap_or_else(|_| "1".to_string())
+                        .parse()
+                        .expect("Invalid MAX_RISK_PERCENT"),
+                ),
+            },
+
+            "FREE" => Self {
+                mode: TradeMode::Free,
+                fixed_usdc: None,
+                percentage: None,
+                max_risk_percent: None,
+            },
+
+            // DEFAULT = PERCENTAGE
+            _ => Self {
+                mode: TradeMode::Percentage,
+                fixed_usdc: None,
+                percentage: Some(
+                    env::var("PERCENTAGE_PER_TRADE")
+                        .unwrap_or_else(|_| "10".to_string())
+                        .parse()
+                        .expect("Invalid PERCENTAGE_PER_TRADE"),
+                ),
+                max_risk_percent: None,
+            },
+        }
+    }
+}
+
+/* =======================
+WALLET CONFIG
+======================= */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletConfig {
+    pub private_key: Option<String>,
+    pub chain_id: u64,
+    pub proxy_wallet: String,
+}
+
+/* =======================
+CLI ARGS
+======================= */
+
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+pub struct Args {
+    /// Configuration file path
+    #[arg(short, long, default_value = "config.json")]
+    pub config: PathBuf,
+
+    /// Run a one-off command instead of trading
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Export journalled fills to CSV for accounting
+    ExportTrades {
+        /// Output file
+        #[arg(short, long, default_value = "trades.csv")]
+        out: PathBuf,
+        /// Only fills on or after this UTC date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Write a PnL report (JSON, per-market CSV, equity curve) of the
+    /// journalled fills, open positions marked at the current midpoints
+    Report {
+        /// Output directory
+        #[arg(short, long, default_value = "report")]
+        out: PathBuf,
+        /// Only fills on or after this UTC date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Print performance statistics of the journalled fills
+    Stats {
+        /// Only days on or after this UTC date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Move outcome tokens (ERC-1155) between the proxy wallet, the
+    /// signer EOA or another address
+    Transfer {
+        /// Outcome token id
+        #[arg(long)]
+        token: String,
+        /// Tokens to move
+        #[arg(long)]
+        amount: rust_decimal::Decimal,
+        /// Destination: `proxy`, `eoa` or an address
+        #[arg(long)]
+        to: String,
+        /// Send from the signer EOA instead of the proxy wallet
+        #[arg(long)]
+        from_eoa: bool,
+        /// Only check and print the transfer
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Trip the kill switch: the running bot cancels all orders and
+    /// refuses new ones until `resume`
+    Kill {
+        #[arg(long, default_value = "manual")]
+        reason: String,
+    },
+    /// Reset the kill switch and override today's daily loss halt
+    Resume,
+    /// Replay recorded market data through a strategy with simulated
+    /// fills; nothing is sent
+    Backtest {
+        /// Recording file or directory (`RECORD_DIR` output)
+        #[arg(long)]
+        data: PathBuf,
+        /// market-maker or mean-reversion, configured from .env
+        #[arg(long)]
+        strategy: String,
+        /// Tick size assumed for every market
+        #[arg(long, default_value = "0.01")]
+        tick_size: rust_decimal::Decimal,
+        /// Minimum order size assumed for every market
+        #[arg(long, default_value = "5")]
+        min_size: rust_decimal::Decimal,
+        /// Fee rate on taking fills (default SIM_TAKER_FEE_BPS, else 0)
+        #[arg(long)]
+        taker_fee_bps: Option<u32>,
+        /// Fee rate on resting fills (default SIM_MAKER_FEE_BPS, else 0)
+        #[arg(long)]
+        maker_fee_bps: Option<u32>,
+        /// none | fixed:<price> | bps:<n> (default SIM_SLIPPAGE, else none)
+        #[arg(long)]
+        slippage: Option<crate::strategy::Slippage>,
+        /// Order round trip: 0 | fixed:<ms> | uniform:<min>-<max> |
+        /// normal:<mean>,<sd> | exp:<mean> (default SIM_SUBMIT_LATENCY, else 0)
+        #[arg(long)]
+        submit_latency: Option<crate::strategy::Delay>,
+        /// Cancel round trip (default SIM_CANCEL_LATENCY, else 0)
+        #[arg(long)]
+        cancel_latency: Option<crate::strategy::Delay>,
+        /// How stale the strategy's books are (default SIM_BOOK_LATENCY, else 0)
+        #[arg(long)]
+        book_latency: Option<crate::strategy::Delay>,
+        /// Also write a PnL report of the run into this directory
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+    /// Sweep strategy parameters over rolling in-sample/out-of-sample
+    /// windows of recorded data and report which values held up
+    WalkForward {
+        /// Recording file or directory (`RECORD_DIR` output)
+        #[arg(long)]
+        data: PathBuf,
+        /// market-maker or mean-reversion; unswept settings from .env
+        #[arg(long)]
+        strategy: String,
+        /// Variable to sweep, e.g. MM_HALF_SPREAD=0.01,0.02,0.03 (repeatable)
+        #[arg(long = "param", required = true)]
+        params: Vec<crate::strategy::ParamRange>,
+        /// Fit window length
+        #[arg(long, default_value = "60")]
+        in_sample_mins: u64,
+        /// Evaluation window length, also how far windows move on
+        #[arg(long, default_value = "15")]
+        out_of_sample_mins: u64,
+        /// Tick size assumed for every market
+        #[arg(long, default_value = "0.01")]
+        tick_size: rust_decimal::Decimal,
+        /// Minimum order size assumed for every market
+        #[arg(long, default_value = "5")]
+        min_size: rust_decimal::Decimal,
+        /// Write the chosen parameters here as .env lines
+        #[arg(long)]
+        export: Option<PathBuf>,
+    },
+    /// Run a strategy and the risk stack (RISK_* limits, daily loss
+    /// breaker) over Monte Carlo perturbations of recorded data
+    Scenario {
+        /// Recording file or directory (`RECORD_DIR` output)
+        #[arg(long)]
+        data: PathBuf,
+        /// market-maker or mean-reversion, configured from .env
+        #[arg(long)]
+        strategy: String,
+        #[arg(long, default_value = "100")]
+        paths: usize,
+        #[arg(long, default_value = "1")]
+        seed: u64,
+        /// Tokens resolve within this many minutes of their recording's
+        /// end; 0 = no resolution jumps
+        #[arg(long, default_value = "5")]
+        jump_window_mins: u64,
+        /// Chance a token resolves as the winner
+        #[arg(long, default_value = "0.5")]
+        p_win: f64,
+        /// Chance per path that liquidity evaporates; 0 = never
+        #[arg(long, default_value = "0.5")]
+        evaporation_prob: f64,
+        #[arg(long, default_value = "10")]
+        evaporation_mins: u64,
+        /// Share of each level's size left while evaporated
+        #[arg(long, default_value = "0.2")]
+        evaporation_keep: f64,
+        /// Levels per side left while evaporated
+        #[arg(long, default_value = "1")]
+        evaporation_levels: usize,
+        /// Tick size assumed for every market
+        #[arg(long, default_value = "0.01")]
+        tick_size: rust_decimal::Decimal,
+        /// Minimum order size assumed for every market
+        #[arg(long, default_value = "5")]
+        min_size: rust_decimal::Decimal,
+    },
+}
+
+/* =======================
+MAIN CONFIG
+======================= */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub polymarket: PolymarketConfig,
+    pub trading: TradingConfig,
+    pub wallet: WalletConfig,
+}
+
+/* =======================
+POLYMARKET CONFIG
+======================= */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolymarketConfig {
+    pub gamma_api_url: String,
+    pub clob_api_url: String,
+    pub ws_url: String,
+
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub api_passphrase: Option<String>,
+}
+
+/* =======================
+TRADING CONFIG
+======================= */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradingConfig {
+    pub min_profit_threshold: f64,
+
+    // 🧠 POSITION SIZING (THIS WAS MISSING / BROKEN BEFORE)
+    pub position_sizing: PositionSizing,
+
+    pub eth_condition_id: Option<String>,
+    pub btc_condition_id: Option<String>,
+
+    pub check_interval_ms: u64,
+}
+
+/* =======================
+DEFAULT CONFIG
+======================= */
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            polymarket: PolymarketConfig {
+                gamma_api_url: "https://gamma-api.polymarket.com".to_string(),
+                clob_api_url: "https://clob.polymarket.com".to_string(),
+                ws_url: "wss://ws-subscriptions-clob.polymarket.com/ws/market".to_string(),
+                api_key: None,
+                api_secret: None,
+                api_passphrase: None,
+            },
+            trading: TradingConfig {
+                min_profit_threshold: 0.01,
+                position_sizing: PositionSizing {
+                    mode: TradeMode::Percentage,
+                    fixed_usdc: None,
+                    percentage: Some(10.0),
+                    max_risk_percent: None,
+                },
+                eth_condition_id: None,
+                btc_condition_id: None,
+                check_interval_ms: 1000,
+            },
+            wallet: WalletConfig {
+                private_key: None,
+                chain_id: 137,
+                proxy_wallet: String::new(),
+            },
+        }
+    }
+}
+
+/* =======================
+LOAD / CREATE CONFIG
+======================= */
+
+impl Config {
+    pub fn load(path: &PathBuf) -> anyhow::Result<Self> {
+        if path.exists() {
+            let content = std::fs::read_to_string(path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            let cfg = Config::default();
+            let content = serde_json::to_string_pretty(&cfg)?;
+            std::fs::write(path, content)?;
+            Ok(cfg)
+        }
+    }
+}
+// ==================================================
+// ENVIRONMENT HELPERS
+// ==================================================
+
+impl Config {
+    /// Check if running in read-only mode
+    pub fn is_read_only() -> bool {
+        std::env::var("READ_ONLY")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false)
+    }
+
+    /// Get minimum trade size in USDC
+    pub fn min_trade_size() -> f64 {
+        std::env::var("MIN_TRADE_SIZE")
+            .unwrap_or_else(|_| "1.0".to_string())
+            .parse()
+            .unwrap_or(1.0)
+    }
+
+    /// Market types the bot may trade: `RISK_INCLUDE_TAGS` /
+    /// `RISK_EXCLUDE_TAGS` (comma-separated tags or categories)
+    pub fn market_tag_filter() -> crate::markets::TagFilter {
+        crate::markets::TagFilter::from_env("RISK")
+    }
+
+    /// Get maximum trade size in USDC
+    pub fn max_trade_size() -> f64 {
+        std::env::var("MAX_TRADE_SIZE")
+            .unwrap_or_else(|_| "100.0".to_string())
+            .parse()
+            .unwrap_or(100.0)
+    }
+}
@@ -0,0 +1,268 @@
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::wallet::errors::{OrderValidationError, SigningError};
+
+// ==================================================
+// CLIENT ERRORS
+// ==================================================
+// `ClobClient` and `ClobApi` return `ClobError`, so a caller can tell
+// failures apart without matching on message text:
+//
+//   InsufficientBalance, ApprovalMissing  →  fund or approve, then retry
+//   RateLimited, Network, InFlight        →  wait, then retry
+//   ExchangeRejected                      →  the exchange said no (`code`)
+//   Risk, Refused, InvalidOrder, ReadOnly →  refused before anything was sent
+//
+// It converts into `anyhow::Error` like any other error, so `?` in
+// anyhow code keeps working. Errors from code that still returns anyhow
+// come back as `Other`, unless they wrap one of the typed errors.
+
+pub type Result<T, E = ClobError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum ClobError {
+    /// Not enough collateral (BUY) or outcome tokens (SELL). When the
+    /// exchange refuses the order for it, amounts are unknown.
+    #[error("❌ Insufficient {asset} balance{}", shortfall(.needed, .available))]
+    InsufficientBalance {
+        asset: String,
+        needed: Option<Decimal>,
+        available: Option<Decimal>,
+    },
+
+    /// A spender isn't approved and read-only mode won't approve it
+    #[error("❌ {what} approval for {spender} missing (read-only, not approving)")]
+    ApprovalMissing { what: String, spender: String },
+
+    /// The exchange answered with an error. `code` is the CLOB error
+    /// code when the message is a known one (`INVALID_ORDER_MIN_SIZE`,
+    /// ...), else the HTTP status, else `REJECTED`.
+    #[error("CLOB rejected ({code}): {message}")]
+    ExchangeRejected { code: String, message: String },
+
+    /// Ours (`ORDER_RATE_*`, `CANCEL_RATE_*`) or the exchange's 429
+    #[error("🚦 {what} rate limit reached{}", retry_in(.retry_after))]
+    RateLimited {
+        what: String,
+        retry_after: Option<Duration>,
+    },
+
+    /// Timeout or failed connection, once retries gave up. Shared by
+    /// every order of a batch it failed.
+    #[error("Network error: {0}")]
+    Network(#[source] Arc<reqwest::Error>),
+
+    /// The same signed order (by idempotency key) is still being submitted
+    #[error("Order {0} is already being submitted")]
+    InFlight(String),
+
+    #[error("Signing failed: {0}")]
+    Signing(#[from] SigningError),
+
+    #[error(transparent)]
+    InvalidOrder(#[from] OrderValidationError),
+
+    /// Refused by the risk manager; `halted` when it also tripped the
+    /// kill switch
+    #[error("🛑 Risk: {reason}{}", if *.halted { " (kill switch tripped)" } else { "" })]
+    Risk { reason: String, halted: bool },
+
+    /// Refused by the pre-trade check named `check`
+    #[error("{reason} ({check} check)")]
+    Refused { check: String, reason: String },
+
+    /// `READ_ONLY` is set; names what wasn't sent
+    #[error("❌ Read-only mode, not sending {0}")]
+    ReadOnly(String),
+
+    /// RPC failure, revert or dropped transaction
+    #[error("Chain error: {0}")]
+    Chain(String),
+
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+fn shortfall(needed: &Option<Decimal>, available: &Option<Decimal>) -> String {
+    match (needed, available) {
+        (Some(need), Some(have)) => format!(": need {}, have {}", need, have),
+        _ => String::new(),
+    }
+}
+
+fn retry_in(retry_after: &Option<Duration>) -> String {
+    retry_after
+        .map(|d| format!(" (retry in {:.1}s)", d.as_secs_f64()))
+        .unwrap_or_default()
+}
+
+/// Codes of the CLOB's order errors, by a fragment of their message
+const REJECTION_CODES: &[(&str, &str)] = &[
+    ("not enough balance", "INVALID_ORDER_NOT_ENOUGH_BALANCE"),
+    ("minimum tick size", "INVALID_ORDER_MIN_TICK_SIZE"),
+    ("lower than the minimum", "INVALID_ORDER_MIN_SIZE"),
+    ("duplicated", "INVALID_ORDER_DUPLICATED"),
+    ("invalid expiration", "INVALID_ORDER_EXPIRATION"),
+    ("could not insert order", "INVALID_ORDER_ERROR"),
+    ("could not run the execution", "EXECUTION_ERROR"),
+    ("fok orders are fully filled", "FOK_ORDER_NOT_FILLED_ERROR"),
+    ("not yet ready", "MARKET_NOT_READY"),
+];
+
+fn rejection_code(message: &str) -> Option<&'static str> {
+    let lower = message.to_lowercase();
+    REJECTION_CODES
+        .iter()
+        .find(|(fragment, _)| lower.contains(fragment))
+        .map(|(_, code)| *code)
+}
+
+impl ClobError {
+    /// A request the exchange refused with `message` in a success reply
+    pub fn rejected(message: &str) -> Self {
+        ClobError::ExchangeRejected {
+            code: rejection_code(message).unwrap_or("REJECTED").to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// A non-success answer. JSON bodies give up their `error` /
+    /// `errorMsg` field.
+    pub fn from_response(status: StatusCode, body: &str) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return ClobError::RateLimited {
+                what: "CLOB API".to_string(),
+                retry_after: None,
+            };
+        }
+        let message = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| {
+                ["error", "errorMsg"]
+                    .iter()
+                    .find_map(|k| v.get(k).and_then(|m| m.as_str()).map(str::to_string))
+            })
+            .unwrap_or_else(|| body.to_string());
+        let code = rejection_code(&message).map_or_else(|| status.as_u16().to_string(), str::to_string);
+        ClobError::ExchangeRejected { code, message }
+    }
+
+    /// Worth trying again as is, after a wait
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClobError::RateLimited { .. } | ClobError::Network(_) | ClobError::InFlight(_) => true,
+            ClobError::ExchangeRejected { code, .. } => {
+                code.parse::<u16>().is_ok_and(|c| c >= 500) || code == "MARKET_NOT_READY"
+            }
+            _ => false,
+        }
+    }
+
+    /// The same error again, for each order of a batch that failed as a
+    /// whole. `Other` keeps only its message.
+    pub fn share(&self) -> Self {
+        match self {
+            ClobError::InsufficientBalance { asset, needed, available } => ClobError::InsufficientBalance {
+                asset: asset.clone(),
+                needed: *needed,
+                available: *available,
+            },
+            ClobError::ApprovalMissing { what, spender } => ClobError::ApprovalMissing {
+                what: what.clone(),
+                spender: spender.clone(),
+            },
+            ClobError::ExchangeRejected { code, message } => ClobError::ExchangeRejected {
+                code: code.clone(),
+                message: message.clone(),
+            },
+            ClobError::RateLimited { what, retry_after } => ClobError::RateLimited {
+                what: what.clone(),
+                retry_after: *retry_after,
+            },
+            ClobError::Network(e) => ClobError::Network(e.clone()),
+            ClobError::InFlight(key) => ClobError::InFlight(key.clone()),
+            ClobError::Signing(e) => ClobError::Signing(e.clone()),
+            ClobError::InvalidOrder(e) => ClobError::InvalidOrder(e.clone()),
+            ClobError::Risk { reason, halted } => ClobError::Risk {
+                reason: reason.clone(),
+                halted: *halted,
+            },
+            ClobError::Refused { check, reason } => ClobError::Refused {
+                check: check.clone(),
+                reason: reason.clone(),
+            },
+            ClobError::ReadOnly(what) => ClobError::ReadOnly(what.clone()),
+            ClobError::Chain(e) => ClobError::Chain(e.clone()),
+            ClobError::Other(e) => ClobError::Other(anyhow::anyhow!("{:#}", e)),
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClobError {
+    fn from(e: reqwest::Error) -> Self {
+        match e.status() {
+            Some(StatusCode::TOO_MANY_REQUESTS) => ClobError::RateLimited {
+                what: e.url().and_then(|u| u.host_str()).unwrap_or("HTTP").to_string(),
+                retry_after: None,
+            },
+            Some(status) => ClobError::ExchangeRejected {
+                code: status.as_u16().to_string(),
+                message: e.to_string(),
+            },
+            None if e.is_decode() => ClobError::Other(e.into()),
+            None => ClobError::Network(Arc::new(e)),
+        }
+    }
+}
+
+impl From<ethers::providers::ProviderError> for ClobError {
+    fn from(e: ethers::providers::ProviderError) -> Self {
+        ClobError::Chain(e.to_string())
+    }
+}
+
+impl<M: ethers::providers::Middleware> From<ethers::contract::ContractError<M>> for ClobError {
+    fn from(e: ethers::contract::ContractError<M>) -> Self {
+        ClobError::Chain(e.to_string())
+    }
+}
+
+impl<M: ethers::providers::Middleware, S: ethers::signers::Signer> From<ethers::middleware::signer::SignerMiddlewareError<M, S>>
+    for ClobError
+{
+    fn from(e: ethers::middleware::signer::SignerMiddlewareError<M, S>) -> Self {
+        ClobError::Chain(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ClobError {
+    fn from(e: serde_json::Error) -> Self {
+        ClobError::Other(e.into())
+    }
+}
+
+/// Typed errors that passed through anyhow come back out as themselves
+impl From<anyhow::Error> for ClobError {
+    fn from(e: anyhow::Error) -> Self {
+        let e = match e.downcast::<ClobError>() {
+            Ok(clob) => return clob,
+            Err(e) => e,
+        };
+        let e = match e.downcast::<SigningError>() {
+            Ok(signing) => return ClobError::Signing(signing),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<OrderValidationError>() {
+            Ok(invalid) => return ClobError::InvalidOrder(invalid),
+            Err(e) => e,
+        };
+        match e.downcast::<reqwest::Error>() {
+            Ok(http) => http.into(),
+            Err(e) => ClobError::Other(e),
+        }
+    }
+}
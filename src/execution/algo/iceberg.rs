@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rand::Rng;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Mutex;
+
+use crate::domain::order::Side;
+use crate::execution::ClobClient;
+use crate::orders::OrderState;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// ICEBERG
+// ==================================================
+
+#[derive(Debug, Clone)]
+pub struct IcebergConfig {
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    /// Parent size in outcome tokens
+    pub total_size: Decimal,
+    /// Size shown on the book at any time
+    pub visible_size: Decimal,
+    /// ± fraction applied to each clip so refills are harder to spot
+    pub size_jitter: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcebergReport {
+    pub clips: Vec<String>,
+    pub filled: Decimal,
+    pub target: Decimal,
+}
+
+/// Keeps one GTC clip of `visible_size` resting and posts the next
+/// clip from the hidden reserve as soon as the order state machine
+/// reports the current one filled.
+///
+/// Cancelling the live clip (see `live_clip`) stops the iceberg.
+pub struct Iceberg {
+    clob: Arc<ClobClient>,
+    cfg: IcebergConfig,
+    live: Arc<Mutex<Option<String>>>,
+}
+
+impl Iceberg {
+    pub fn new(clob: Arc<ClobClient>, cfg: IcebergConfig) -> Result<Self> {
+        if cfg.visible_size <= Decimal::ZERO || cfg.total_size <= Decimal::ZERO {
+            return Err(anyhow!("Iceberg sizes must be positive"));
+        }
+        Ok(Self {
+            clob,
+            cfg,
+            live: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Order id of the clip currently on the book
+    pub async fn live_clip(&self) -> Option<String> {
+        self.live.lock().await.clone()
+    }
+
+    pub async fn run(&self) -> Result<IcebergReport> {
+        let cfg = &self.cfg;
+        let mut updates = self.clob.subscribe_orders().await;
+
+        let mut report = IcebergReport {
+            target: cfg.total_size,
+            ..Default::default()
+        };
+        // Filled on clips that are already done
+        let mut done_filled = Decimal::ZERO;
+
+        info!(
+            "🧊 Iceberg {} {} of {} @ {} showing {}",
+            cfg.side.as_str(),
+            cfg.total_size,
+            cfg.token_id,
+            cfg.price,
+            cfg.visible_size
+        );
+
+        let mut current = self.place_clip(cfg.total_size, &mut report).await?;
+
+        // Nothing ever fills in read-only mode
+        if self.clob.is_read_only() {
+            return Ok(report);
+        }
+
+        loop {
+            let update = match updates.recv().await {
+                Ok(u) => u,
+                Err(RecvError::Lagged(n)) => {
+                    warn!("⚠️  Iceberg missed {} order updates, resyncing", n);
+                    match self.clob.order_state(&current).await {
+                        Some(o) => o,
+                        None => continue,
+                    }
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            if update.order_id.as_deref() != Some(current.as_str()) {
+                continue;
+            }
+
+            report.filled = done_filled + update.filled;
+
+            match update.state {
+                OrderState::Filled => {
+                    done_filled += update.filled;
+                    let remaining = cfg.total_size - done_filled;
+                    if remaining <= Decimal::ZERO {
+                        break;
+                    }
+                    info!("   clip {} filled, refilling ({} hidden)", current, remaining);
+                    current = self.place_clip(remaining, &mut report).await?;
+                }
+                OrderState::Cancelled | OrderState::Expired | OrderState::Rejected => {
+                    warn!("🧊 Iceberg clip {} {}, stopping", current, update.state);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        *self.live.lock().await = None;
+        info!("✅ Iceberg done: {} / {} filled", report.filled, report.target);
+        Ok(report)
+    }
+
+    async fn place_clip(&self, remaining: Decimal, report: &mut IcebergReport) -> Result<String> {
+        let cfg = &self.cfg;
+        let size = self.jitter(cfg.visible_size).min(remaining);
+
+        let order_id = self
+            .clob
+            .place_order(&cfg.token_id, cfg.side.clone(), cfg.price, size, OrderType::Gtc)
+            .await?;
+
+        report.clips.push(order_id.clone());
+        *self.live.lock().await = Some(order_id.clone());
+        Ok(order_id)
+    }
+
+    fn jitter(&self, size: Decimal) -> Decimal {
+        if self.cfg.size_jitter <= 0.0 {
+            return size;
+        }
+        let j = self.cfg.size_jitter;
+        let f = 1.0 + rand::rng().random_range(-j..j);
+        Decimal::from_f64(size.to_f64().unwrap_or_default() * f).unwrap_or(size)
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::{anyhow, Result};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+
+use crate::domain::order::Side;
+use crate::execution::orderbook::fetch_book;
+use crate::execution::ClobClient;
+
+pub mod iceberg;
+pub mod twap;
+
+pub use iceberg::{Iceberg, IcebergConfig, IcebergReport};
+pub use twap::{Twap, TwapConfig, TwapReport};
+
+/// Price grid used for child orders
+pub const TICK: Decimal = dec!(0.01);
+
+// ==================================================
+// SHARED CHILD-ORDER HELPERS
+// ==================================================
+
+/// Limit price that crosses the spread by up to `max_slippage`,
+/// snapped to the tick grid away from the touch.
+pub async fn marketable_price(
+    clob: &ClobClient,
+    token_id: &str,
+    side: &Side,
+    max_slippage: f64,
+) -> Result<Decimal> {
+    let book = fetch_book(clob.clob_url(), token_id).await?;
+
+    let (raw, strategy) = match side {
+        Side::Buy => {
+            let ask = book.best_ask().ok_or_else(|| anyhow!("No asks on book"))?.price;
+            (ask * (1.0 + max_slippage), RoundingStrategy::ToNegativeInfinity)
+        }
+        Side::Sell => {
+            let bid = book.best_bid().ok_or_else(|| anyhow!("No bids on book"))?.price;
+            (bid * (1.0 - max_slippage), RoundingStrategy::ToPositiveInfinity)
+        }
+    };
+
+    let price = Decimal::from_f64(raw).ok_or_else(|| anyhow!("Invalid price {}", raw))?;
+    let snapped = (price / TICK).round_dp_with_strategy(0, strategy) * TICK;
+    Ok(snapped.clamp(TICK, Decimal::ONE - TICK))
+}
+
+/// Size matched on a child order so far, per the order state machine
+pub async fn filled_size(clob: &ClobClient, order_id: &str) -> Decimal {
+    clob.order_state(order_id)
+        .await
+        .map(|o| o.filled)
+        .unwrap_or_default()
+}
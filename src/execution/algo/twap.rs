@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rand::Rng;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::domain::order::Side;
+use crate::execution::algo::{filled_size, marketable_price};
+use crate::execution::ClobClient;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// TWAP
+// ==================================================
+
+#[derive(Debug, Clone)]
+pub struct TwapConfig {
+    pub token_id: String,
+    pub side: Side,
+    /// Parent size in outcome tokens
+    pub total_size: Decimal,
+    pub duration: Duration,
+    pub slices: u32,
+    /// Child limit; `None` crosses the spread by up to `max_slippage`
+    pub limit_price: Option<Decimal>,
+    pub max_slippage: f64,
+    /// Random delay of up to this fraction of the slice interval
+    pub time_jitter: f64,
+    /// ± fraction of the slice size added to each child
+    pub size_jitter: f64,
+    /// Size children to close the gap to the schedule when fills lag
+    pub catch_up: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TwapReport {
+    pub child_orders: Vec<String>,
+    pub filled: Decimal,
+    pub target: Decimal,
+}
+
+/// Slices a parent order into FAK children spread evenly over
+/// `duration`. Each child only takes what is on the book at that
+/// moment; unfilled size rolls into later slices when `catch_up` is on.
+pub struct Twap {
+    clob: Arc<ClobClient>,
+    cfg: TwapConfig,
+}
+
+impl Twap {
+    pub fn new(clob: Arc<ClobClient>, cfg: TwapConfig) -> Result<Self> {
+        if cfg.slices == 0 {
+            return Err(anyhow!("TWAP needs at least one slice"));
+        }
+        if cfg.total_size <= Decimal::ZERO {
+            return Err(anyhow!("TWAP size must be positive"));
+        }
+        Ok(Self { clob, cfg })
+    }
+
+    pub async fn run(&self) -> Result<TwapReport> {
+        let cfg = &self.cfg;
+        let interval = cfg.duration / cfg.slices;
+        let slice = cfg.total_size / Decimal::from(cfg.slices);
+        let start = Instant::now();
+
+        let mut report = TwapReport {
+            target: cfg.total_size,
+            ..Default::default()
+        };
+
+        info!(
+            "⏱️  TWAP {} {} of {} over {:?} in {} slices",
+            cfg.side.as_str(),
+            cfg.total_size,
+            cfg.token_id,
+            cfg.duration,
+            cfg.slices
+        );
+
+        for i in 0..cfg.slices {
+            let at = start + interval * i + self.jitter_time(interval);
+            tokio::time::sleep_until(at).await;
+
+            let remaining = cfg.total_size - report.filled;
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+
+            let scheduled = slice * Decimal::from(i + 1);
+            let size = if cfg.catch_up || i + 1 == cfg.slices {
+                scheduled - report.filled
+            } else {
+                self.jitter_size(slice)
+            }
+            .min(remaining);
+
+            if size <= Decimal::ZERO {
+                continue;
+            }
+
+            match self.child(size).await {
+                Ok((order_id, filled)) => {
+                    report.filled += filled;
+                    report.child_orders.push(order_id);
+                }
+                Err(e) => warn!("⚠️  TWAP slice {}/{} failed: {}", i + 1, cfg.slices, e),
+            }
+
+            info!(
+                "   slice {}/{}: filled {} / scheduled {}",
+                i + 1,
+                cfg.slices,
+                report.filled,
+                scheduled.min(cfg.total_size)
+            );
+        }
+
+        info!("✅ TWAP done: {} / {} filled", report.filled, report.target);
+        Ok(report)
+    }
+
+    async fn child(&self, size: Decimal) -> Result<(String, Decimal)> {
+        let cfg = &self.cfg;
+        let price = match cfg.limit_price {
+            Some(p) => p,
+            None => marketable_price(&self.clob, &cfg.token_id, &cfg.side, cfg.max_slippage).await?,
+        };
+
+        let order_id = self
+            .clob
+            .place_order(&cfg.token_id, cfg.side.clone(), price, size, OrderType::Fak)
+            .await?;
+        let filled = filled_size(&self.clob, &order_id).await;
+        Ok((order_id, filled))
+    }
+
+    fn jitter_time(&self, interval: Duration) -> Duration {
+        if self.cfg.time_jitter <= 0.0 {
+            return Duration::ZERO;
+        }
+        // Only delay: an early child would overlap the previous slice
+        let f = rand::rng().random_range(0.0..self.cfg.time_jitter);
+        interval.mul_f64(f)
+    }
+
+    fn jitter_size(&self, slice: Decimal) -> Decimal {
+        if self.cfg.size_jitter <= 0.0 {
+            return slice;
+        }
+        let j = self.cfg.size_jitter;
+        let f = 1.0 + rand::rng().random_range(-j..j);
+        Decimal::from_f64(slice.to_f64().unwrap_or_default() * f).unwrap_or(slice)
+    }
+}
@@ -0,0 +1,233 @@
+use anyhow::{anyhow, Result};
+use futures_util::future::BoxFuture;
+use log::info;
+use std::sync::Arc;
+
+use crate::config::COLLATERAL_SYMBOL;
+use crate::domain::order::Side;
+use crate::error::ClobError;
+use crate::execution::clob_client::ClobClient;
+use crate::execution::intents::OrderIntent;
+use crate::execution::orderbook::fetch_book;
+use crate::execution::sanity::PriceSanityCheck;
+use crate::execution::throttle::ThrottleCheck;
+use crate::risk::{OrderRequest, RiskDecision};
+use crate::wallet::signer::ClobOrder;
+
+// ==================================================
+// PRE-TRADE CHECKS
+// ==================================================
+// Every signed order runs through the client's checks, in order, before
+// it is sent; the first failure refuses it. The defaults are:
+//
+//   expiry → [price sanity] → risk → post-only → price impact → [balance] → throttle
+//
+// Custom checks are appended with `ClobClient::pre_trade_check`.
+
+/// A signed order about to be submitted
+pub struct PreTradeOrder<'a> {
+    pub order: &'a ClobOrder,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    pub size: f64,
+    /// Earlier orders of the same batch that already passed
+    pub batch: &'a [OrderIntent],
+}
+
+impl<'a> PreTradeOrder<'a> {
+    pub fn new(order: &'a ClobOrder, batch: &'a [OrderIntent]) -> Self {
+        let (price, size) = order.price_and_size();
+        Self {
+            order,
+            token_id: order.token_id.to_string(),
+            side: if order.side == 0 { Side::Buy } else { Side::Sell },
+            price,
+            size,
+            batch,
+        }
+    }
+
+    pub fn notional(&self) -> f64 {
+        self.price * self.size
+    }
+}
+
+/// One stage of pre-submission validation. `Err` refuses the order: a
+/// `ClobError` comes back to the caller as is, anything else as
+/// `ClobError::Refused`.
+pub trait PreTradeCheck: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, Result<()>>;
+}
+
+/// The built-in pipeline; price sanity is added when
+/// `PRICE_SANITY_MAX_DEVIATION` is set, the balance check when
+/// `PRE_TRADE_BALANCE_CHECK=true`
+pub fn default_checks() -> Vec<Arc<dyn PreTradeCheck>> {
+    let mut checks: Vec<Arc<dyn PreTradeCheck>> = vec![Arc::new(ExpiryCheck)];
+    // Before risk, so a fat-fingered order can't trip the kill switch
+    if let Some(sanity) = PriceSanityCheck::from_env() {
+        checks.push(Arc::new(sanity));
+    }
+    checks.push(Arc::new(RiskCheck));
+    checks.push(Arc::new(PostOnlyCheck));
+    checks.push(Arc::new(ImpactCheck));
+    if std::env::var("PRE_TRADE_BALANCE_CHECK").as_deref() == Ok("true") {
+        checks.push(Arc::new(BalanceCheck));
+    }
+    checks.push(Arc::new(ThrottleCheck));
+    checks
+}
+
+/// Refuse entries on markets about to end (`EXPIRY_GUARD_ENABLED`)
+pub struct ExpiryCheck;
+
+impl PreTradeCheck for ExpiryCheck {
+    fn name(&self) -> &str {
+        "expiry"
+    }
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            match clob.expiry_guard() {
+                Some(guard) => guard.check_order(&order.token_id, &order.side).await,
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+/// Exposure limits, market lists, kill switch and loss breaker
+/// (`RISK_ENABLED`). A signed order can't be downsized, so a resize
+/// refuses it too.
+pub struct RiskCheck;
+
+impl PreTradeCheck for RiskCheck {
+    fn name(&self) -> &str {
+        "risk"
+    }
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some(risk) = clob.risk_manager() else {
+                return Ok(());
+            };
+            // Exact, so an order sized to the limit isn't refused for a rounding error
+            let (price, size) = order.order.exact_price_and_size();
+            let request = OrderRequest {
+                token_id: order.token_id.clone(),
+                side: order.side.clone(),
+                price,
+                size,
+            };
+            let mut resting = clob.order_intents().await;
+            resting.extend_from_slice(order.batch);
+            match risk.evaluate(&request, &resting).await {
+                RiskDecision::Allow => Ok(()),
+                RiskDecision::Resize(room) => Err(ClobError::Risk {
+                    reason: format!("order exceeds limits ({} allowed)", room),
+                    halted: false,
+                }
+                .into()),
+                RiskDecision::Reject(reason) => Err(ClobError::Risk { reason, halted: false }.into()),
+                RiskDecision::Halt(reason) => Err(clob.halt(reason).await.into()),
+            }
+        })
+    }
+}
+
+/// Refuse a post-only order that would take liquidity
+pub struct PostOnlyCheck;
+
+impl PreTradeCheck for PostOnlyCheck {
+    fn name(&self) -> &str {
+        "post-only"
+    }
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if !order.order.post_only {
+                return Ok(());
+            }
+            let book = fetch_book(clob.clob_url(), &order.token_id).await?;
+            if book.crosses(&order.side, order.price) {
+                return Err(anyhow!(
+                    "Post-only {} at {:.4} would cross the book",
+                    order.side.as_str(),
+                    order.price
+                ));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Refuse an order whose expected fills would move more than
+/// `MAX_PRICE_IMPACT` away from the touch
+pub struct ImpactCheck;
+
+impl PreTradeCheck for ImpactCheck {
+    fn name(&self) -> &str {
+        "impact"
+    }
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some(max_impact) = clob.max_impact() else {
+                return Ok(());
+            };
+            let book = fetch_book(clob.clob_url(), &order.token_id).await?;
+            let est = book.estimate_fill(&order.side, order.size, Some(order.price));
+            if est.filled > 0.0 {
+                info!(
+                    "📐 Expected fill {:.2}/{:.2} avg {:.4} worst {:.4} (impact {:.2}%)",
+                    est.filled,
+                    order.size,
+                    est.avg_price,
+                    est.worst_price,
+                    est.impact() * 100.0
+                );
+            }
+            if est.impact() > max_impact {
+                return Err(anyhow!(
+                    "Expected impact {:.2}% exceeds limit of {:.2}%",
+                    est.impact() * 100.0,
+                    max_impact * 100.0
+                ));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Refuse orders the funder can't pay for: USDC for a BUY, outcome
+/// tokens for a SELL. Costs an RPC call per order, so it isn't in the
+/// default pipeline (`PRE_TRADE_BALANCE_CHECK=true` adds it).
+pub struct BalanceCheck;
+
+impl PreTradeCheck for BalanceCheck {
+    fn name(&self) -> &str {
+        "balance"
+    }
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let (price, size) = order.order.exact_price_and_size();
+            let (have, need, unit) = match order.side {
+                Side::Buy => (clob.get_usdc_balance().await?, price * size, COLLATERAL_SYMBOL),
+                Side::Sell => (clob.token_balance(&order.token_id).await?, size, "tokens"),
+            };
+            if have < need {
+                return Err(ClobError::InsufficientBalance {
+                    asset: unit.to_string(),
+                    needed: Some(need.round_dp(6)),
+                    available: Some(have),
+                }
+                .into());
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::wallet::signer::{ClobOrder, OrderType};
+
+// ==================================================
+// ORDER INTENTS (PERSISTED)
+// ==================================================
+
+/// A resting order the bot meant to leave on the book.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderIntent {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: String,
+    pub price: f64,
+    pub size: f64,
+    pub order_type: OrderType,
+    pub created_at: u64,
+}
+
+impl OrderIntent {
+    pub fn from_order(order_id: &str, order: &ClobOrder) -> Self {
+        let (price, size) = order.price_and_size();
+        Self {
+            order_id: order_id.to_string(),
+            token_id: order.token_id.to_string(),
+            side: order.side_str().to_string(),
+            price,
+            size,
+            order_type: order.order_type,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        }
+    }
+}
+
+/// JSON file of live intents, keyed by order id.
+///
+/// Rewritten on every change so a crash never loses more than the
+/// order in flight; reconciled against the exchange on startup.
+#[derive(Debug)]
+pub struct IntentStore {
+    path: PathBuf,
+    intents: HashMap<String, OrderIntent>,
+}
+
+impl IntentStore {
+    /// Load from `path`, starting empty if the file does not exist
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let intents = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self { path, intents })
+    }
+
+    /// `ORDER_INTENTS_PATH`, default `order_intents.json`
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var("ORDER_INTENTS_PATH")
+            .unwrap_or_else(|_| "order_intents.json".to_string());
+        Self::load(path)
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<&OrderIntent> {
+        self.intents.get(order_id)
+    }
+
+    pub fn all(&self) -> Vec<OrderIntent> {
+        self.intents.values().cloned().collect()
+    }
+
+    pub fn insert(&mut self, intent: OrderIntent) -> Result<()> {
+        self.intents.insert(intent.order_id.clone(), intent);
+        self.save()
+    }
+
+    pub fn remove(&mut self, order_ids: &[String]) -> Result<()> {
+        let before = self.intents.len();
+        for id in order_ids {
+            self.intents.remove(id);
+        }
+        if self.intents.len() != before {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) -> Result<()> {
+        self.intents.clear();
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        // Write-then-rename so a crash mid-write keeps the old file
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&self.intents)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+pub mod clob_client;
+use crate::client::PolymarketClient;
+use crate::config::{TradingConfig, WalletConfig, PositionSizing, TradeMode};
+use crate::domain::*;
+use crate::domain::order::Side;
+use crate::wallet::order_builder::OrderBuilder;
+use crate::wallet::signer::{SignatureType, WalletSigner};
+use anyhow::Result;
+use log::{info, warn};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ethers::types::Address;
+pub use clob_client::ClobClient;
+pub mod orderbook;
+pub mod paper;
+pub mod trader;
+pub mod errors;
+pub mod algo;
+pub mod checks;
+pub mod intents;
+pub mod queue;
+pub mod reconcile;
+pub mod router;
+pub mod sanity;
+pub mod tca;
+pub mod throttle;
+
+// ==================================================
+// Trader
+// ==================================================
+
+pub struct Trader {
+    api: Arc<PolymarketClient>,
+    clob: Arc<ClobClient>,
+    config: TradingConfig,
+    wallet: WalletConfig,
+    signer: WalletSigner,
+    sizing: PositionSizing,
+
+    live_usdc_balance: Arc<Mutex<Decimal>>,
+}
+
+impl Trader {
+    pub fn new(
+        api: Arc<PolymarketClient>,
+        clob: Arc<ClobClient>,
+        config: TradingConfig,
+        wallet: WalletConfig,
+        signer: WalletSigner,
+    ) -> Self {
+        Self {
+            api,
+            clob,
+            config,
+            wallet,
+            signer,
+            sizing: PositionSizing::from_env(),
+            live_usdc_balance: Arc::new(Mutex::new(Decimal::ZERO)),
+        }
+    }
+
+    // ==================================================
+    // BALANCE
+    // ==================================================
+
+    async fn refresh_balance(&self) -> Result<()> {
+        let bal = self.api.get_usdc_balance().await?;
+        *self.live_usdc_balance.lock().await = bal;
+        info!("💰 USDC balance: {}", bal);
+        Ok(())
+    }
+
+    // ==================================================
+    // EXECUTION (REAL MONEY)
+    // ==================================================
+
+    pub async fn execute_arbitrage(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+    ) -> Result<()> {
+        // 1️⃣ Refresh balance
+        self.refresh_balance().await?;
+
+        // 2️⃣ Calculate size
+        let units = self.calculate_position_size(opportunity).await?;
+        if units <= 0.0 {
+            return Ok(());
+        }
+
+        let cost = opportunity.total_cost.to_f64().unwrap_or(0.0);
+        let spend = units * cost;
+
+        if spend < 1.0 {
+            warn!("❌ Trade skipped (below $1 minimum)");
+            return Ok(());
+        }
+
+        // 3️⃣ HARD GATE — balance + allowance + ERC1155
+        self.clob
+            .ensure_trading_ready((spend * 1_000_000.0) as u128)
+            .await?;
+
+        info!(
+            "🚀 EXEC | units={} spend=${:.2} expected_profit={}",
+            units,
+            spend,
+            opportunity.expected_profit
+        );
+
+        let size_dec = Decimal::from_f64(units).unwrap();
+
+        // ================= ETH LEG =================
+        // ================= ETH LEG =================
+self.place_leg(
+    &opportunity.eth_up_token_id,
+    Side::Buy,
+    opportunity.eth_up_price,
+    size_dec,
+).await?;
+
+// ================= BTC LEG =================
+self.place_leg(
+    &opportunity.btc_down_token_id,
+    Side::Buy,
+    opportunity.btc_down_price,
+    size_dec,
+).await?;
+
+
+        Ok(())
+    }
+
+    async fn place_leg(
+    &self,
+    token_id: &str,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+) -> Result<()> {
+    let maker: Address = self.wallet.proxy_wallet.parse()?;
+    let order = OrderBuilder::new(maker, self.signer.address())
+        .salts(self.clob.salts())
+        .signature_type(SignatureType::from_env())
+        .market_rules(self.clob.market_rules(token_id).await?)
+        .build(token_id, side, price, size)?;
+
+
+    let sig = self.signer.sign_order(&order).await?;
+
+    match self
+        .clob
+        .submit_order(order, sig, &self.wallet.proxy_wallet)
+        .await
+    {
+        Ok(_) => info!("✅ Order submitted {}", token_id),
+        Err(e) => warn!("❌ Order rejected {} → {}", token_id, e),
+    }
+
+    Ok(())
+}
+
+
+    // ==================================================
+    // POSITION SIZING
+    // ==================================================
+
+    async fn calculate_position_size(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+    ) -> Result<f64> {
+        let bal = self.live_usdc_balance.lock().await;
+        let balance = bal.to_f64().unwrap_or(0.0);
+        let cost = opportunity.total_cost.to_f64().unwrap_or(1.0);
+
+        let spend = match self.sizing.mode {
+            TradeMode::Fixed => self.sizing.fixed_usdc.unwrap_or(0.0),
+            TradeMode::Percentage => {
+                balance * (self.sizing.percentage.unwrap_or(10.0) / 100.0)
+            }
+            TradeMode::Dynamic => {
+                let edge = opportunity.expected_profit.to_f64().unwrap_or(0.0);
+                (balance * 0.01 * (1.0 + edge)).min(balance * 0.25)
+            }
+            TradeMode::Free => balance,
+        };
+
+        Ok((spend / cost).floor())
+    }
+}
@@ -0,0 +1,517 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::client::PolymarketClient;
+use crate::domain::order::Side;
+use crate::net::SendRetry;
+use crate::wallet::order_builder::MarketRules;
+
+/// One price level: aggregate resting size at `price`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Level {
+    pub price: f64,
+    pub size: f64,
+}
+
+impl Level {
+    pub fn new(price: f64, size: f64) -> Self {
+        Self { price, size }
+    }
+}
+
+/// Book depth, best level first on both sides
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<Level> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<Level> {
+        self.asks.first().copied()
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// Spread as a whole number of `tick_size` ticks
+    pub fn spread_ticks(&self, tick_size: f64) -> Option<u32> {
+        if tick_size <= 0.0 {
+            return None;
+        }
+        Some((self.spread()? / tick_size).round().max(0.0) as u32)
+    }
+
+    /// Size resting on the `side` of the book (bids for BUY) within
+    /// `cents` of that side's best price
+    pub fn depth_within(&self, side: &Side, cents: f64) -> f64 {
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let Some(best) = levels.first() else {
+            return 0.0;
+        };
+        let reach = cents / 100.0 + 1e-9;
+        levels
+            .iter()
+            .take_while(|l| (l.price - best.price).abs() <= reach)
+            .map(|l| l.size)
+            .sum()
+    }
+
+    /// Resting size at `price` on the `side` of the book (bids for BUY)
+    pub fn level_size(&self, side: &Side, price: f64) -> f64 {
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        levels
+            .iter()
+            .find(|l| (l.price - price).abs() < 1e-9)
+            .map(|l| l.size)
+            .unwrap_or_default()
+    }
+
+    /// Whether a limit order at `price` would match on arrival
+    pub fn crosses(&self, side: &Side, price: f64) -> bool {
+        match side {
+            Side::Buy => self.best_ask().is_some_and(|ask| price >= ask.price),
+            Side::Sell => self.best_bid().is_some_and(|bid| price <= bid.price),
+        }
+    }
+
+    /// Price to post a marketable BUY spending `usdc` without paying
+    /// more than `best_ask * (1 + max_slippage)` on any level.
+    pub fn quote_buy(&self, usdc: f64, max_slippage: f64) -> Result<SweepQuote> {
+        let best = self.best_ask().ok_or_else(|| anyhow!("No asks on book"))?.price;
+        let cap = best * (1.0 + max_slippage);
+
+        let mut spent = 0.0;
+        let mut size = 0.0;
+        for &Level { price, size: avail } in self.asks.iter().take_while(|l| l.price <= cap) {
+            let take = ((usdc - spent) / price).min(avail);
+            spent += take * price;
+            size += take;
+            if usdc - spent <= 1e-9 {
+                return Ok(SweepQuote {
+                    limit_price: price,
+                    avg_price: spent / size,
+                    size,
+                    notional: spent,
+                });
+            }
+        }
+
+        Err(anyhow!(
+            "Book can only absorb ${:.2} of ${:.2} within {:.2}% of {:.4}",
+            spent,
+            usdc,
+            max_slippage * 100.0,
+            best
+        ))
+    }
+
+    /// Walk the opposite side for a `side` order of `size` tokens.
+    ///
+    /// Unlike the `quote_*` helpers this never fails: depth that runs
+    /// out (or, with `limit`, levels the matching engine wouldn't take)
+    /// is reported as `unfilled`.
+    pub fn estimate_fill(&self, side: &Side, size: f64, limit: Option<f64>) -> FillEstimate {
+        let levels = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+        let in_limit = |p: f64| match (side, limit) {
+            (_, None) => true,
+            (Side::Buy, Some(l)) => p <= l,
+            (Side::Sell, Some(l)) => p >= l,
+        };
+
+        let mut est = FillEstimate {
+            best_price: levels.first().map(|l| l.price).unwrap_or_default(),
+            ..Default::default()
+        };
+        let mut notional = 0.0;
+        for &Level { price, size: avail } in levels.iter().take_while(|l| in_limit(l.price)) {
+            if size - est.filled <= 1e-9 {
+                break;
+            }
+            let take = (size - est.filled).min(avail);
+            est.filled += take;
+            notional += take * price;
+            est.worst_price = price;
+        }
+
+        if est.filled > 0.0 {
+            est.avg_price = notional / est.filled;
+        }
+        est.unfilled = (size - est.filled).max(0.0);
+        est
+    }
+
+    /// Cost of buying exactly `size` tokens off the asks, refusing to
+    /// go more than `max_slippage` above the best ask.
+    pub fn quote_buy_size(&self, size: f64, max_slippage: f64) -> Result<SweepQuote> {
+        let best = self.best_ask().ok_or_else(|| anyhow!("No asks on book"))?.price;
+        let cap = best * (1.0 + max_slippage);
+
+        let mut bought = 0.0;
+        let mut cost = 0.0;
+        for &Level { price, size: avail } in self.asks.iter().take_while(|l| l.price <= cap) {
+            let take = (size - bought).min(avail);
+            bought += take;
+            cost += take * price;
+            if size - bought <= 1e-9 {
+                return Ok(SweepQuote {
+                    limit_price: price,
+                    avg_price: cost / bought,
+                    size: bought,
+                    notional: cost,
+                });
+            }
+        }
+
+        Err(anyhow!(
+            "Book can only supply {:.2} of {:.2} tokens within {:.2}% of {:.4}",
+            bought,
+            size,
+            max_slippage * 100.0,
+            best
+        ))
+    }
+
+    /// Price to post a marketable SELL of `size` tokens without
+    /// selling below `best_bid * (1 - max_slippage)` on any level.
+    pub fn quote_sell(&self, size: f64, max_slippage: f64) -> Result<SweepQuote> {
+        let best = self.best_bid().ok_or_else(|| anyhow!("No bids on book"))?.price;
+        let floor = best * (1.0 - max_slippage);
+
+        let mut sold = 0.0;
+        let mut proceeds = 0.0;
+        for &Level { price, size: avail } in self.bids.iter().take_while(|l| l.price >= floor) {
+            let take = (size - sold).min(avail);
+            sold += take;
+            proceeds += take * price;
+            if size - sold <= 1e-9 {
+                return Ok(SweepQuote {
+                    limit_price: price,
+                    avg_price: proceeds / sold,
+                    size: sold,
+                    notional: proceeds,
+                });
+            }
+        }
+
+        Err(anyhow!(
+            "Book can only absorb {:.2} of {:.2} tokens within {:.2}% of {:.4}",
+            sold,
+            size,
+            max_slippage * 100.0,
+            best
+        ))
+    }
+}
+
+/// Expected outcome of sending `size` tokens into the book
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FillEstimate {
+    /// Touch on the side we trade against
+    pub best_price: f64,
+    /// Volume-weighted average over the levels we would take
+    pub avg_price: f64,
+    /// Deepest level reached
+    pub worst_price: f64,
+    pub filled: f64,
+    /// Size the book (or the limit) can't absorb
+    pub unfilled: f64,
+}
+
+impl FillEstimate {
+    /// Average fill distance from the touch as a fraction of it
+    /// (0.02 = 2% worse than best). Zero if nothing would fill.
+    pub fn impact(&self) -> f64 {
+        if self.filled <= 0.0 || self.best_price <= 0.0 {
+            return 0.0;
+        }
+        (self.avg_price - self.best_price).abs() / self.best_price
+    }
+}
+
+/// Result of walking the book for a marketable order
+#[derive(Debug, Clone, Copy)]
+pub struct SweepQuote {
+    /// Worst level touched — the limit price to post
+    pub limit_price: f64,
+    /// Expected average fill price
+    pub avg_price: f64,
+    /// Outcome tokens filled
+    pub size: f64,
+    /// USDC paid (BUY) or received (SELL)
+    pub notional: f64,
+}
+
+/* ===============================
+PRICE API RESPONSE
+=============================== */
+
+#[derive(Debug, Deserialize)]
+struct PriceResponse {
+    price: String,
+}
+
+/* ===============================
+FETCH ORDERBOOK - Using /price endpoint (CORRECT DATA)
+=============================== */
+
+pub async fn fetch_orderbook(
+    api: &PolymarketClient,
+    token_id: &str,
+) -> Result<OrderBook> {
+    let client = Client::new();
+    
+    // Fetch BID price (what we can SELL for)
+    let bid_url = format!(
+        "{}/price?token_id={}&side=BUY",
+        api.clob_url, token_id
+    );
+    
+    let bid_response = client.get(&bid_url).send_retry().await?;
+    
+    if !bid_response.status().is_success() {
+        return Err(anyhow!("Failed to fetch bid price: {}", bid_response.status()));
+    }
+    
+    let bid_data: PriceResponse = bid_response.json().await?;
+    let bid_price: f64 = bid_data.price.parse()
+        .map_err(|e| anyhow!("Failed to parse bid price: {}", e))?;
+    
+    // Fetch ASK price (what we must PAY to buy)
+    let ask_url = format!(
+        "{}/price?token_id={}&side=SELL",
+        api.clob_url, token_id
+    );
+    
+    let ask_response = client.get(&ask_url).send_retry().await?;
+    
+    if !ask_response.status().is_success() {
+        return Err(anyhow!("Failed to fetch ask price: {}", ask_response.status()));
+    }
+    
+    let ask_data: PriceResponse = ask_response.json().await?;
+    let ask_price: f64 = ask_data.price.parse()
+        .map_err(|e| anyhow!("Failed to parse ask price: {}", e))?;
+
+    // Create orderbook with single best bid/ask
+    Ok(OrderBook {
+        bids: vec![Level::new(bid_price, 1.0)], // Size doesn't matter for best price
+        asks: vec![Level::new(ask_price, 1.0)],
+    })
+}
+
+/* ===============================
+FETCH FULL DEPTH - /book endpoint
+=============================== */
+
+#[derive(Debug, Deserialize)]
+struct BookLevel {
+    price: String,
+    size: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BookResponse {
+    #[serde(default)]
+    asset_id: String,
+    #[serde(default)]
+    bids: Vec<BookLevel>,
+    #[serde(default)]
+    asks: Vec<BookLevel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BookRules {
+    tick_size: String,
+    #[serde(default)]
+    min_order_size: Option<String>,
+    #[serde(default)]
+    neg_risk: Option<bool>,
+}
+
+/// Tick size, minimum order size and neg-risk flag for `token_id`
+pub async fn fetch_market_rules(clob_url: &str, token_id: &str) -> Result<MarketRules> {
+    let url = format!("{}/book?token_id={}", clob_url, token_id);
+    let resp = Client::new().get(&url).send_retry().await?;
+
+    if !resp.status().is_success() {
+        return Err(anyhow!("Failed to fetch market rules: {}", resp.status()));
+    }
+
+    let rules: BookRules = resp.json().await?;
+    let tick_size = Decimal::from_str(&rules.tick_size)
+        .map_err(|e| anyhow!("Invalid tick size {}: {}", rules.tick_size, e))?;
+    let min_size = rules
+        .min_order_size
+        .and_then(|m| Decimal::from_str(&m).ok())
+        .unwrap_or_default();
+
+    Ok(MarketRules {
+        tick_size,
+        min_size,
+        neg_risk: rules.neg_risk.unwrap_or(false),
+    })
+}
+
+/// Full order book for `token_id`, best level first on both sides
+pub async fn fetch_book(clob_url: &str, token_id: &str) -> Result<OrderBook> {
+    let url = format!("{}/book?token_id={}", clob_url, token_id);
+    let resp = Client::new().get(&url).send_retry().await?;
+
+    if !resp.status().is_success() {
+        return Err(anyhow!("Failed to fetch book: {}", resp.status()));
+    }
+
+    let book: BookResponse = resp.json().await?;
+    Ok(book.into_order_book())
+}
+
+impl BookResponse {
+    fn into_order_book(self) -> OrderBook {
+        let parse = |levels: Vec<BookLevel>| -> Vec<Level> {
+            levels
+                .into_iter()
+                .filter_map(|l| Some(Level::new(l.price.parse().ok()?, l.size.parse().ok()?)))
+                .collect()
+        };
+
+        let mut bids = parse(self.bids);
+        let mut asks = parse(self.asks);
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        OrderBook { bids, asks }
+    }
+}
+
+/* ===============================
+MARKS - /midpoint and /last-trade-price
+=============================== */
+
+#[derive(Debug, Deserialize)]
+struct MidResponse {
+    mid: String,
+}
+
+pub async fn fetch_midpoint(clob_url: &str, token_id: &str) -> Result<f64> {
+    let url = format!("{}/midpoint?token_id={}", clob_url, token_id);
+    let resp: MidResponse = Client::new().get(&url).send_retry().await?.json().await?;
+    resp.mid
+        .parse()
+        .map_err(|e| anyhow!("Failed to parse midpoint: {}", e))
+}
+
+pub async fn fetch_last_trade(clob_url: &str, token_id: &str) -> Result<f64> {
+    let url = format!("{}/last-trade-price?token_id={}", clob_url, token_id);
+    let resp: PriceResponse = Client::new().get(&url).send_retry().await?.json().await?;
+    resp.price
+        .parse()
+        .map_err(|e| anyhow!("Failed to parse last trade price: {}", e))
+}
+
+/* ===============================
+BATCH - /books, /prices, /midpoints
+=============================== */
+
+/// Token ids per batch request; larger scans are split and sent
+/// concurrently
+const BATCH_TOKENS: usize = 100;
+
+/// POST `body(chunk)` to `path` for every chunk of `token_ids` at once
+async fn post_batched<T, F>(clob_url: &str, path: &str, token_ids: &[String], body: F) -> Result<Vec<T>>
+where
+    T: serde::de::DeserializeOwned,
+    F: Fn(&[String]) -> serde_json::Value,
+{
+    let client = Client::new();
+    let url = format!("{}{}", clob_url, path);
+
+    let requests = token_ids.chunks(BATCH_TOKENS).map(|chunk| {
+        let req = client.post(&url).json(&body(chunk));
+        async move {
+            let resp = req.send_retry().await?;
+            if !resp.status().is_success() {
+                return Err(anyhow!("POST {} failed: {}", path, resp.status()));
+            }
+            Ok(resp.json::<T>().await?)
+        }
+    });
+
+    futures_util::future::join_all(requests).await.into_iter().collect()
+}
+
+/// Books for many tokens (`POST /books`), keyed by token id. Tokens
+/// the exchange doesn't know are missing from the result.
+pub async fn fetch_books(clob_url: &str, token_ids: &[String]) -> Result<HashMap<String, OrderBook>> {
+    let chunks: Vec<Vec<BookResponse>> = post_batched(clob_url, "/books", token_ids, |chunk| {
+        serde_json::json!(chunk.iter().map(|t| serde_json::json!({ "token_id": t })).collect::<Vec<_>>())
+    })
+    .await?;
+
+    Ok(chunks
+        .into_iter()
+        .flatten()
+        .map(|b| (b.asset_id.clone(), b.into_order_book()))
+        .collect())
+}
+
+/// Midpoints for many tokens (`POST /midpoints`)
+pub async fn fetch_midpoints(clob_url: &str, token_ids: &[String]) -> Result<HashMap<String, f64>> {
+    let chunks: Vec<HashMap<String, String>> =
+        post_batched(clob_url, "/midpoints", token_ids, |chunk| {
+            serde_json::json!(chunk.iter().map(|t| serde_json::json!({ "token_id": t })).collect::<Vec<_>>())
+        })
+        .await?;
+
+    Ok(chunks
+        .into_iter()
+        .flatten()
+        .filter_map(|(token, mid)| Some((token, mid.parse().ok()?)))
+        .collect())
+}
+
+/// Best price on `side` for many tokens (`POST /prices`). BUY is the
+/// price we would pay (best ask), SELL what we would get (best bid).
+pub async fn fetch_prices(
+    clob_url: &str,
+    token_ids: &[String],
+    side: &Side,
+) -> Result<HashMap<String, f64>> {
+    let side = side.as_str().to_uppercase();
+    let chunks: Vec<HashMap<String, HashMap<String, String>>> =
+        post_batched(clob_url, "/prices", token_ids, |chunk| {
+            serde_json::json!(chunk
+                .iter()
+                .map(|t| serde_json::json!({ "token_id": t, "side": side }))
+                .collect::<Vec<_>>())
+        })
+        .await?;
+
+    Ok(chunks
+        .into_iter()
+        .flatten()
+        .filter_map(|(token, by_side)| Some((token, by_side.get(&side)?.parse().ok()?)))
+        .collect())
+}
@@ -0,0 +1,621 @@
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use log::{info, warn};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, Mutex};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::clob::api::{CancelResponse, OpenOrder, PostedOrder};
+use crate::domain::order::Side;
+use crate::execution::orderbook::{fetch_book, OrderBook};
+use crate::execution::queue::QueueTracker;
+use crate::execution::ClobClient;
+use crate::market_ws::{MarketBooks, MARKET_WS_URL};
+use crate::net::{feeds, Connections, Reconnector};
+use crate::orders::OrderEvent;
+use crate::portfolio::{Fill, Positions};
+use crate::wallet::signer::{ClobOrder, OrderType};
+
+const CLOB_API_URL: &str = "https://clob.polymarket.com";
+
+/// Fills kept for slow subscribers
+const UPDATE_BUFFER: usize = 1024;
+
+// ==================================================
+// PAPER TRADING
+// ==================================================
+// Stands in for the exchange behind `ClobClient` (PAPER_TRADING=true).
+// Orders are signed and checked as usual, then matched here against the
+// live market channel instead of being posted:
+//
+//   crossing the book      →  takes the levels up to its limit (taker)
+//   FOK short of its size  →  rejected
+//   FAK remainder          →  dropped
+//   GTC/GTD remainder      →  rests behind the size already at its level
+//
+// A resting order fills at its own price (maker) when
+//   - a trade prints through its price,
+//   - a trade at its price eats past the queue ahead of it
+//     (`QueueTracker`), or
+//   - the book crosses it.
+//
+// Balances are virtual: PAPER_USDC to start, no tokens, no fees. Orders
+// need the cash (buys) or tokens (sells) left after what resting orders
+// already reserve.
+
+#[derive(Debug, Clone)]
+struct PaperOrder {
+    order_id: String,
+    token_id: String,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+    matched: Decimal,
+    order_type: OrderType,
+    expiration: String,
+    created_at: u64,
+    // LIVE | MATCHED | CANCELED
+    status: &'static str,
+}
+
+impl PaperOrder {
+    fn remaining(&self) -> Decimal {
+        self.size - self.matched
+    }
+
+    fn is_live(&self) -> bool {
+        self.status == "LIVE"
+    }
+
+    fn to_open_order(&self) -> OpenOrder {
+        OpenOrder {
+            id: self.order_id.clone(),
+            status: self.status.to_string(),
+            market: String::new(),
+            asset_id: self.token_id.clone(),
+            side: self.side.as_str().to_string(),
+            original_size: self.size.to_string(),
+            size_matched: self.matched.to_string(),
+            price: self.price.to_string(),
+            outcome: String::new(),
+            order_type: self.order_type.as_str().to_string(),
+            created_at: self.created_at,
+            expiration: self.expiration.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PaperState {
+    cash: Decimal,
+    tokens: HashMap<String, Decimal>,
+    orders: HashMap<String, PaperOrder>,
+    queue: QueueTracker,
+    // Every token we ever placed on; the market channel follows these
+    watched: HashSet<String>,
+    next_id: u64,
+    next_trade: u64,
+}
+
+/// Virtual exchange for paper trading: balances, order book matching
+/// and a fill stream shaped like the user channel's
+pub struct PaperExchange {
+    ws_url: String,
+    clob_url: String,
+    state: Mutex<PaperState>,
+    books: MarketBooks,
+    fills: broadcast::Sender<Fill>,
+    connections: Arc<Connections>,
+}
+
+impl PaperExchange {
+    pub fn new(ws_url: impl Into<String>, clob_url: impl Into<String>, usdc: Decimal) -> Self {
+        Self {
+            ws_url: ws_url.into(),
+            clob_url: clob_url.into(),
+            state: Mutex::new(PaperState {
+                cash: usdc,
+                ..Default::default()
+            }),
+            books: MarketBooks::new(),
+            fills: broadcast::channel(UPDATE_BUFFER).0,
+            connections: Arc::new(Connections::new()),
+        }
+    }
+
+    /// Publish connection changes on a shared hub
+    pub fn with_connections(mut self, connections: Arc<Connections>) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    /// `PAPER_USDC` starting cash (default 1000), `MARKET_WS_URL` and
+    /// `CLOB_API_URL`
+    pub fn from_env() -> Self {
+        let usdc = std::env::var("PAPER_USDC")
+            .ok()
+            .and_then(|v| Decimal::from_str(&v).ok())
+            .unwrap_or(Decimal::from(1000));
+        let ws_url = std::env::var("MARKET_WS_URL").unwrap_or_else(|_| MARKET_WS_URL.to_string());
+        let clob_url = std::env::var("CLOB_API_URL").unwrap_or_else(|_| CLOB_API_URL.to_string());
+        info!("📝 PAPER TRADING — orders are simulated against live books (${} to start)", usdc);
+        Self::new(ws_url, clob_url, usdc)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Fill> {
+        self.fills.subscribe()
+    }
+
+    /// Virtual USDC, including what resting buys reserve
+    pub async fn cash(&self) -> Decimal {
+        self.state.lock().await.cash
+    }
+
+    pub async fn token_balance(&self, token_id: &str) -> Decimal {
+        self.state.lock().await.tokens.get(token_id).copied().unwrap_or_default()
+    }
+
+    /// Match a signed order, answering like `POST /order`
+    pub async fn submit(&self, order: &ClobOrder) -> Result<PostedOrder> {
+        let token_id = order.token_id.to_string();
+        let side = if order.side == 0 { Side::Buy } else { Side::Sell };
+        let (price, size) = order.price_and_size();
+        let price = Decimal::from_f64(price)
+            .ok_or_else(|| anyhow!("Bad order price {}", price))?
+            .round_dp(4);
+        let size = Decimal::from_f64(size)
+            .ok_or_else(|| anyhow!("Bad order size {}", size))?
+            .round_dp(6);
+
+        // Our own feed when it's live, REST before it has caught up
+        let book = if self.books.is_degraded(&token_id).await {
+            None
+        } else {
+            self.books.order_book(&token_id).await
+        };
+        let book = match book {
+            Some(book) => book,
+            None => fetch_book(&self.clob_url, &token_id).await?,
+        };
+
+        if order.post_only && book.crosses(&side, price.to_f64().unwrap_or_default()) {
+            return Err(anyhow!("Post-only {} at {} would cross the book", side.as_str(), price));
+        }
+        let levels = crossed(&book, &side, price);
+        let available: Decimal = levels.iter().map(|(_, s)| *s).sum();
+        if order.order_type == OrderType::Fok && available < size {
+            return Err(anyhow!(
+                "FOK {} {} at {} not filled ({} available)",
+                side.as_str(),
+                size,
+                price,
+                available
+            ));
+        }
+
+        let mut state = self.state.lock().await;
+        state.check_balance(&token_id, &side, price, size)?;
+        state.next_id += 1;
+        let order_id = format!("paper-{}", state.next_id);
+        state.watched.insert(token_id.clone());
+        state.orders.insert(
+            order_id.clone(),
+            PaperOrder {
+                order_id: order_id.clone(),
+                token_id: token_id.clone(),
+                side: side.clone(),
+                price,
+                size,
+                matched: Decimal::ZERO,
+                order_type: order.order_type,
+                expiration: order.expiration.to_string(),
+                created_at: now_ms() / 1000,
+                status: "LIVE",
+            },
+        );
+
+        let mut fills = Vec::new();
+        let mut left = size;
+        for (level_price, level_size) in levels {
+            let take = left.min(level_size);
+            if take.is_zero() {
+                break;
+            }
+            fills.push(state.fill(&order_id, level_price, take, false));
+            left -= take;
+        }
+
+        let status = if !left.is_zero() && order.order_type.is_resting() {
+            let level = book.level_size(&side, price.to_f64().unwrap_or_default());
+            state.queue.track(
+                &order_id,
+                &token_id,
+                side.clone(),
+                price.to_f64().unwrap_or_default(),
+                left.to_f64().unwrap_or_default(),
+                level,
+            );
+            "live"
+        } else {
+            if let Some(o) = state.orders.get_mut(&order_id) {
+                // FAK remainder is dropped
+                if o.is_live() {
+                    o.status = "CANCELED";
+                }
+            }
+            if fills.is_empty() { "unmatched" } else { "matched" }
+        };
+        drop(state);
+
+        info!(
+            "📝 [PAPER] {} {} {} @ {} → {} ({} filled)",
+            order.order_type.as_str(),
+            side.as_str(),
+            size,
+            price,
+            order_id,
+            size - left
+        );
+        self.publish(fills);
+        Ok(PostedOrder {
+            order_id,
+            status: status.to_string(),
+            matched: Some(size - left),
+        })
+    }
+
+    pub async fn cancel(&self, order_ids: &[String]) -> CancelResponse {
+        let mut state = self.state.lock().await;
+        let mut resp = CancelResponse::default();
+        for id in order_ids {
+            match state.orders.get_mut(id) {
+                Some(o) if o.is_live() => {
+                    o.status = "CANCELED";
+                    resp.canceled.push(id.clone());
+                }
+                Some(o) => {
+                    resp.not_canceled.insert(id.clone(), format!("order is {}", o.status));
+                }
+                None => {
+                    resp.not_canceled.insert(id.clone(), "order not found".to_string());
+                }
+            }
+        }
+        for id in &resp.canceled {
+            state.queue.remove(id);
+        }
+        resp
+    }
+
+    /// Cancel every live order, or those on one token
+    pub async fn cancel_all(&self, token_id: Option<&str>) -> CancelResponse {
+        let ids: Vec<String> = self
+            .open_orders(token_id)
+            .await
+            .into_iter()
+            .map(|o| o.id)
+            .collect();
+        self.cancel(&ids).await
+    }
+
+    pub async fn get_order(&self, order_id: &str) -> Result<OpenOrder> {
+        self.state
+            .lock()
+            .await
+            .orders
+            .get(order_id)
+            .map(PaperOrder::to_open_order)
+            .ok_or_else(|| anyhow!("Paper order {} not found", order_id))
+    }
+
+    pub async fn open_orders(&self, token_id: Option<&str>) -> Vec<OpenOrder> {
+        self.state
+            .lock()
+            .await
+            .orders
+            .values()
+            .filter(|o| o.is_live() && token_id.is_none_or(|t| o.token_id == t))
+            .map(PaperOrder::to_open_order)
+            .collect()
+    }
+
+    /// Apply one market-channel message (or an array of them): fill
+    /// resting orders it trades through, then update books and queues
+    pub async fn on_market_message(&self, msg: &Value) {
+        let events: Vec<&Value> = match msg.as_array() {
+            Some(arr) => arr.iter().collect(),
+            None => vec![msg],
+        };
+        for event in events {
+            let mut fills = Vec::new();
+            if event.get("event_type").and_then(|t| t.as_str()) == Some("last_trade_price") {
+                fills.extend(self.state.lock().await.on_trade(event));
+            }
+
+            let gaps = self.books.apply(event).await;
+            if !gaps.is_empty() {
+                // Crossing checks pause until the next snapshot
+                self.books.mark_degraded(&gaps).await;
+            }
+
+            let mut state = self.state.lock().await;
+            state.queue.on_market_message(event);
+            let tokens: HashSet<String> = state
+                .orders
+                .values()
+                .filter(|o| o.is_live())
+                .map(|o| o.token_id.clone())
+                .collect();
+            for token_id in tokens {
+                if self.books.is_degraded(&token_id).await {
+                    continue;
+                }
+                if let Some(book) = self.books.order_book(&token_id).await {
+                    fills.extend(state.on_book(&token_id, &book));
+                }
+            }
+            drop(state);
+            self.publish(fills);
+        }
+    }
+
+    /// Run forever: follow the market channel for every token we placed
+    /// on, resubscribing when a new one shows up
+    pub async fn run(self: Arc<Self>) {
+        let mut link = Reconnector::new(feeds::PAPER, self.connections.clone());
+        loop {
+            let tokens = self.watched().await;
+            if tokens.is_empty() {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+            let result = self.stream(&tokens, &mut link).await;
+            let tokens: Vec<String> = tokens.into_iter().collect();
+            self.books.mark_degraded(&tokens).await;
+            link.disconnected(result).await;
+        }
+    }
+
+    async fn watched(&self) -> HashSet<String> {
+        self.state.lock().await.watched.clone()
+    }
+
+    /// Until an error or a token outside `tokens` is placed on
+    async fn stream(&self, tokens: &HashSet<String>, link: &mut Reconnector) -> Result<()> {
+        link.connecting();
+        let (ws, _) = connect_async(self.ws_url.as_str()).await?;
+        let (mut write, mut read) = ws.split();
+
+        let sub = json!({ "type": "market", "assets_ids": tokens });
+        write.send(Message::Text(sub.to_string())).await?;
+        info!("📡 Paper market WS subscribed to {} token(s)", tokens.len());
+        link.connected();
+
+        let mut hb = tokio::time::interval(Duration::from_secs(10));
+        let mut check = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            tokio::select! {
+                _ = hb.tick() => {
+                    write.send(Message::Text("PING".to_string())).await?;
+                }
+                _ = check.tick() => {
+                    if self.watched().await.len() != tokens.len() {
+                        return Ok(());
+                    }
+                }
+                msg = read.next() => {
+                    let msg = msg.ok_or_else(|| anyhow!("WS closed"))??;
+                    if let Message::Text(txt) = msg {
+                        if let Ok(v) = serde_json::from_str::<Value>(&txt) {
+                            self.on_market_message(&v).await;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn publish(&self, fills: Vec<Fill>) {
+        for fill in fills {
+            info!(
+                "📝 [PAPER] Fill {} {} @ {} on {} ({})",
+                fill.side.as_str(),
+                fill.size,
+                fill.price,
+                fill.token_id,
+                if fill.maker { "maker" } else { "taker" }
+            );
+            let _ = self.fills.send(fill);
+        }
+    }
+}
+
+impl PaperState {
+    /// Refuse an order the balances left after resting orders can't cover
+    fn check_balance(&self, token_id: &str, side: &Side, price: Decimal, size: Decimal) -> Result<()> {
+        let resting = self.orders.values().filter(|o| o.is_live() && &o.side == side);
+        match side {
+            Side::Buy => {
+                let reserved: Decimal = resting.map(|o| o.price * o.remaining()).sum();
+                let free = self.cash - reserved;
+                if price * size > free {
+                    return Err(anyhow!("Paper: not enough USDC (need {}, have {})", price * size, free));
+                }
+            }
+            Side::Sell => {
+                let reserved: Decimal = resting.filter(|o| o.token_id == token_id).map(|o| o.remaining()).sum();
+                let held = self.tokens.get(token_id).copied().unwrap_or_default();
+                if size > held - reserved {
+                    return Err(anyhow!("Paper: not enough tokens (need {}, have {})", size, held - reserved));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fill `size` of `order_id` at `price` and move the balances
+    fn fill(&mut self, order_id: &str, price: Decimal, size: Decimal, maker: bool) -> Fill {
+        self.next_trade += 1;
+        let trade_id = format!("paper-t{}", self.next_trade);
+        let order = self.orders.get_mut(order_id).expect("filled order exists");
+        order.matched += size;
+        if order.remaining().is_zero() {
+            order.status = "MATCHED";
+        }
+        let (token_id, side) = (order.token_id.clone(), order.side.clone());
+
+        let tokens = self.tokens.entry(token_id.clone()).or_default();
+        match side {
+            Side::Buy => {
+                self.cash -= price * size;
+                *tokens += size;
+            }
+            Side::Sell => {
+                self.cash += price * size;
+                *tokens -= size;
+            }
+        }
+        if maker {
+            self.queue.on_fill(order_id, size.to_f64().unwrap_or_default());
+            if self.orders.get(order_id).is_some_and(|o| !o.is_live()) {
+                self.queue.remove(order_id);
+            }
+        }
+
+        Fill {
+            trade_id,
+            order_id: order_id.to_string(),
+            token_id,
+            market: String::new(),
+            side,
+            price,
+            size,
+            fee: Decimal::ZERO,
+            maker,
+            at_ms: now_ms(),
+        }
+    }
+
+    /// A `last_trade_price` print: fill resting orders on the maker side
+    /// it traded through, or reached past the queue ahead of them
+    fn on_trade(&mut self, msg: &Value) -> Vec<Fill> {
+        let (Some(token_id), Some(taker), Some(price), Some(size)) = (
+            msg.get("asset_id").and_then(|a| a.as_str()),
+            side(msg),
+            decimal(msg, "price"),
+            decimal(msg, "size"),
+        ) else {
+            return vec![];
+        };
+        let hits: Vec<(String, Decimal)> = self
+            .orders
+            .values()
+            .filter(|o| o.is_live() && o.token_id == token_id && o.side != taker)
+            .filter_map(|o| {
+                let through = match o.side {
+                    Side::Buy => price < o.price,
+                    Side::Sell => price > o.price,
+                };
+                let reached = if through {
+                    size
+                } else if price == o.price {
+                    let ahead = self
+                        .queue
+                        .get(&o.order_id)
+                        .and_then(|q| Decimal::from_f64(q.ahead))
+                        .unwrap_or_default();
+                    size - ahead
+                } else {
+                    Decimal::ZERO
+                };
+                let take = o.remaining().min(reached);
+                (take > Decimal::ZERO).then(|| (o.order_id.clone(), take))
+            })
+            .collect();
+
+        hits.into_iter()
+            .map(|(order_id, take)| {
+                let price = self.orders[&order_id].price;
+                self.fill(&order_id, price, take, true)
+            })
+            .collect()
+    }
+
+    /// Fill resting orders on `token_id` that `book` now crosses
+    fn on_book(&mut self, token_id: &str, book: &OrderBook) -> Vec<Fill> {
+        let hits: Vec<(String, Decimal, Decimal)> = self
+            .orders
+            .values()
+            .filter(|o| o.is_live() && o.token_id == token_id)
+            .filter_map(|o| {
+                let available: Decimal = crossed(book, &o.side, o.price).iter().map(|(_, s)| *s).sum();
+                let take = o.remaining().min(available);
+                (!take.is_zero()).then(|| (o.order_id.clone(), o.price, take))
+            })
+            .collect();
+        hits.into_iter()
+            .map(|(order_id, price, take)| self.fill(&order_id, price, take, true))
+            .collect()
+    }
+}
+
+/// Apply paper fills the way the user channel applies real ones:
+/// positions, and the order state machine for fills after submission
+/// (the post status already covers what filled on arrival)
+pub async fn run_fills(mut fills: broadcast::Receiver<Fill>, clob: Arc<ClobClient>, positions: Arc<Positions>) {
+    loop {
+        match fills.recv().await {
+            Ok(fill) => {
+                positions.apply_fill(&fill).await;
+                if fill.maker {
+                    clob.on_order_event(&fill.order_id, OrderEvent::Fill { size: fill.size }).await;
+                }
+            }
+            Err(RecvError::Lagged(n)) => warn!("⚠️  Paper positions missed {} fill(s)", n),
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Opposite-side levels an order at `price` trades with, best first
+fn crossed(book: &OrderBook, side: &Side, price: Decimal) -> Vec<(Decimal, Decimal)> {
+    let limit = price.to_f64().unwrap_or_default();
+    let levels = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    };
+    levels
+        .iter()
+        .take_while(|l| match side {
+            Side::Buy => l.price <= limit,
+            Side::Sell => l.price >= limit,
+        })
+        .filter_map(|l| Some((Decimal::from_f64(l.price)?.round_dp(4), Decimal::from_f64(l.size)?)))
+        .collect()
+}
+
+fn decimal(v: &Value, key: &str) -> Option<Decimal> {
+    v.get(key).and_then(|x| x.as_str()).and_then(|s| Decimal::from_str(s).ok())
+}
+
+fn side(v: &Value) -> Option<Side> {
+    match v.get("side").and_then(|s| s.as_str())?.to_uppercase().as_str() {
+        "BUY" => Some(Side::Buy),
+        "SELL" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,237 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::domain::order::Side;
+
+// ==================================================
+// QUEUE POSITION
+// ==================================================
+
+/// Price levels closer than this are the same level
+const PRICE_EPS: f64 = 1e-9;
+
+/// Where one of our resting orders sits in its price level's FIFO queue
+#[derive(Debug, Clone)]
+pub struct QueuePosition {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    /// Our unfilled size
+    pub remaining: f64,
+    /// Estimated size queued in front of us
+    pub ahead: f64,
+    /// Last known aggregate size at the level (includes us)
+    pub level_size: f64,
+    /// Volume traded at the level since we joined
+    pub traded: f64,
+    pub placed_at_ms: u64,
+    // Trade volume not yet matched by a level decrease
+    unconfirmed_trades: f64,
+}
+
+impl QueuePosition {
+    /// Share of the level in front of us: 0 = at the front, 1 = at the back
+    pub fn queue_ratio(&self) -> f64 {
+        if self.level_size <= 0.0 {
+            return 0.0;
+        }
+        (self.ahead / self.level_size).clamp(0.0, 1.0)
+    }
+
+    /// Probability that we fill completely within `horizon`, assuming
+    /// trades keep arriving at the level at the rate seen since we joined
+    /// (Poisson arrivals of volume against `ahead + remaining`).
+    pub fn fill_probability(&self, horizon: Duration) -> f64 {
+        let elapsed = now_ms().saturating_sub(self.placed_at_ms) as f64 / 1000.0;
+        if elapsed <= 0.0 || self.traded <= 0.0 {
+            return 0.0;
+        }
+        let rate = self.traded / elapsed;
+        let needed = (self.ahead + self.remaining).max(PRICE_EPS);
+        1.0 - (-rate * horizon.as_secs_f64() / needed).exp()
+    }
+
+    fn is_level(&self, token_id: &str, side: &Side, price: f64) -> bool {
+        self.token_id == token_id && &self.side == side && (self.price - price).abs() < PRICE_EPS
+    }
+
+    /// The level's aggregate size changed to `new_size`. Increases join
+    /// behind us; decreases are trades first, then cancels spread
+    /// pro-rata over the orders ahead of and behind us.
+    fn on_level(&mut self, new_size: f64) {
+        let delta = new_size - self.level_size;
+        self.level_size = new_size;
+        if delta >= 0.0 {
+            return;
+        }
+
+        let removed = -delta;
+        let traded = removed.min(self.unconfirmed_trades);
+        self.unconfirmed_trades -= traded;
+
+        let cancelled = removed - traded;
+        let others = (self.level_size + removed - self.remaining).max(PRICE_EPS);
+        self.ahead = (self.ahead - cancelled * self.ahead / others).max(0.0);
+    }
+
+    /// A trade printed at our level; it consumed the front of the queue
+    fn on_trade(&mut self, size: f64) {
+        self.traded += size;
+        self.unconfirmed_trades += size;
+        self.ahead = (self.ahead - size).max(0.0);
+    }
+}
+
+/// Queue estimates for our resting orders, driven by market-channel
+/// book deltas and trades.
+#[derive(Debug, Default)]
+pub struct QueueTracker {
+    orders: HashMap<String, QueuePosition>,
+}
+
+impl QueueTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a resting order that joined the back of a level
+    /// holding `level_size` without us (e.g. `OrderBook::level_size`
+    /// from the book fetched before submitting).
+    pub fn track(
+        &mut self,
+        order_id: &str,
+        token_id: &str,
+        side: Side,
+        price: f64,
+        size: f64,
+        level_size: f64,
+    ) {
+        self.orders.insert(
+            order_id.to_string(),
+            QueuePosition {
+                order_id: order_id.to_string(),
+                token_id: token_id.to_string(),
+                side,
+                price,
+                remaining: size,
+                ahead: level_size,
+                level_size: level_size + size,
+                traded: 0.0,
+                placed_at_ms: now_ms(),
+                unconfirmed_trades: 0.0,
+            },
+        );
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<&QueuePosition> {
+        self.orders.get(order_id)
+    }
+
+    /// Fill probability within `horizon`, `None` for untracked orders
+    pub fn fill_probability(&self, order_id: &str, horizon: Duration) -> Option<f64> {
+        self.get(order_id).map(|q| q.fill_probability(horizon))
+    }
+
+    /// Our order matched `size`; it must have been at the front
+    pub fn on_fill(&mut self, order_id: &str, size: f64) {
+        if let Some(q) = self.orders.get_mut(order_id) {
+            q.remaining = (q.remaining - size).max(0.0);
+            q.ahead = 0.0;
+        }
+    }
+
+    pub fn remove(&mut self, order_id: &str) -> Option<QueuePosition> {
+        self.orders.remove(order_id)
+    }
+
+    /// Aggregate size at (`token_id`, `side`, `price`) is now `size`
+    pub fn on_level(&mut self, token_id: &str, side: &Side, price: f64, size: f64) {
+        for q in self.orders.values_mut().filter(|q| q.is_level(token_id, side, price)) {
+            q.on_level(size);
+        }
+    }
+
+    /// A trade at `price` where the taker was on `taker_side`
+    pub fn on_trade(&mut self, token_id: &str, taker_side: &Side, price: f64, size: f64) {
+        let maker_side = match taker_side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+        for q in self
+            .orders
+            .values_mut()
+            .filter(|q| q.is_level(token_id, &maker_side, price))
+        {
+            q.on_trade(size);
+        }
+    }
+
+    /// Feed a market-channel message (`book`, `price_change`,
+    /// `last_trade_price`); single events or arrays of them.
+    pub fn on_market_message(&mut self, msg: &Value) {
+        if let Some(events) = msg.as_array() {
+            for event in events {
+                self.on_market_message(event);
+            }
+            return;
+        }
+
+        let asset = msg.get("asset_id").and_then(|a| a.as_str()).unwrap_or_default();
+        match msg.get("event_type").and_then(|t| t.as_str()) {
+            Some("book") => {
+                for (key, side) in [("bids", Side::Buy), ("asks", Side::Sell)] {
+                    for level in msg.get(key).and_then(|l| l.as_array()).into_iter().flatten() {
+                        if let (Some(price), Some(size)) = (num(level, "price"), num(level, "size")) {
+                            self.on_level(asset, &side, price, size);
+                        }
+                    }
+                }
+            }
+            Some("price_change") => {
+                // Older payloads carry `changes` under one asset, newer
+                // ones `price_changes` with an asset per entry
+                let changes = msg
+                    .get("price_changes")
+                    .or_else(|| msg.get("changes"))
+                    .and_then(|c| c.as_array());
+                for change in changes.into_iter().flatten() {
+                    let asset = change.get("asset_id").and_then(|a| a.as_str()).unwrap_or(asset);
+                    if let (Some(side), Some(price), Some(size)) =
+                        (side(change), num(change, "price"), num(change, "size"))
+                    {
+                        self.on_level(asset, &side, price, size);
+                    }
+                }
+            }
+            Some("last_trade_price") => {
+                if let (Some(side), Some(price), Some(size)) =
+                    (side(msg), num(msg, "price"), num(msg, "size"))
+                {
+                    self.on_trade(asset, &side, price, size);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn num(v: &Value, key: &str) -> Option<f64> {
+    v.get(key).and_then(|x| x.as_str()).and_then(|s| s.parse().ok())
+}
+
+fn side(v: &Value) -> Option<Side> {
+    match v.get("side").and_then(|s| s.as_str())?.to_uppercase().as_str() {
+        "BUY" => Some(Side::Buy),
+        "SELL" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
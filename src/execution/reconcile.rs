@@ -0,0 +1,108 @@
+use anyhow::Result;
+use log::{info, warn};
+use std::collections::HashSet;
+
+use crate::execution::ClobClient;
+
+// ==================================================
+// STARTUP RECONCILIATION
+// ==================================================
+
+/// What to do with orders left on the book by a previous run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcilePolicy {
+    /// Keep orders we have an intent for, cancel everything else
+    CancelUnknown,
+    /// Keep every open order and start tracking the unknown ones
+    AdoptAll,
+    /// Start from a clean book
+    CancelAll,
+}
+
+impl ReconcilePolicy {
+    /// `RECONCILE_POLICY` = cancel_unknown (default) | adopt_all | cancel_all
+    pub fn from_env() -> Self {
+        match std::env::var("RECONCILE_POLICY")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "adopt_all" | "adopt" => ReconcilePolicy::AdoptAll,
+            "cancel_all" => ReconcilePolicy::CancelAll,
+            _ => ReconcilePolicy::CancelUnknown,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReconcileReport {
+    /// Open on the exchange and tracked locally
+    pub kept: Vec<String>,
+    /// Open on the exchange, unknown locally, now tracked
+    pub adopted: Vec<String>,
+    /// Cancelled on the exchange
+    pub cancelled: Vec<String>,
+    /// Tracked locally but gone from the book (filled / cancelled / expired)
+    pub dropped: Vec<String>,
+}
+
+/// Compare exchange-side open orders with persisted intents and
+/// bring the two back in line according to `policy`.
+pub async fn reconcile_open_orders(
+    clob: &ClobClient,
+    policy: ReconcilePolicy,
+) -> Result<ReconcileReport> {
+    info!("🔄 Reconciling open orders ({:?})...", policy);
+
+    let open = clob.open_orders(None).await?;
+    let intents = clob.order_intents().await;
+
+    let known: HashSet<&str> = intents.iter().map(|i| i.order_id.as_str()).collect();
+    let live: HashSet<&str> = open.iter().map(|o| o.id.as_str()).collect();
+
+    let mut report = ReconcileReport {
+        dropped: intents
+            .iter()
+            .filter(|i| !live.contains(i.order_id.as_str()))
+            .map(|i| i.order_id.clone())
+            .collect(),
+        ..Default::default()
+    };
+    clob.forget_orders(&report.dropped).await?;
+
+    let mut to_cancel = Vec::new();
+    for order in &open {
+        let is_known = known.contains(order.id.as_str());
+        match (policy, is_known) {
+            (ReconcilePolicy::CancelAll, _) | (ReconcilePolicy::CancelUnknown, false) => {
+                to_cancel.push(order.id.clone());
+            }
+            (ReconcilePolicy::AdoptAll, false) => {
+                clob.adopt_order(order).await?;
+                report.adopted.push(order.id.clone());
+            }
+            (_, true) => {
+                clob.track_order(order).await;
+                report.kept.push(order.id.clone());
+            }
+        }
+    }
+
+    if !to_cancel.is_empty() {
+        let resp = clob.cancel_orders(&to_cancel).await?;
+        if !resp.not_canceled.is_empty() {
+            warn!("⚠️  {} order(s) could not be cancelled", resp.not_canceled.len());
+        }
+        report.cancelled = resp.canceled;
+    }
+
+    info!(
+        "✅ Reconciled: {} kept, {} adopted, {} cancelled, {} dropped",
+        report.kept.len(),
+        report.adopted.len(),
+        report.cancelled.len(),
+        report.dropped.len()
+    );
+
+    Ok(report)
+}
@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Result};
+use ethers::types::{H256, U256};
+use log::info;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+use crate::domain::order::Side;
+use crate::execution::orderbook::{fetch_book, SweepQuote};
+use crate::execution::ClobClient;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// COMPLEMENTARY-OUTCOME ROUTING
+// ==================================================
+// In a binary market 1 YES + 1 NO is always worth 1 USDC (CTF
+// split/merge), so a YES position can also be built from the NO book:
+//
+//   BUY  YES = split USDC → YES+NO, sell the NO
+//   SELL YES = buy NO, merge YES+NO → USDC
+//
+// Only standard (non neg-risk) conditions can be split/merged directly.
+
+/// Binary market the router works on
+#[derive(Debug, Clone)]
+pub struct BinaryMarket {
+    pub condition_id: H256,
+    /// Outcome we want exposure to
+    pub token_id: String,
+    /// The other outcome
+    pub complement_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Trade the outcome's own book
+    Direct,
+    /// BUY via split + sell complement
+    SplitSellComplement,
+    /// SELL via buy complement + merge
+    BuyComplementMerge,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RouteQuote {
+    pub route: Route,
+    /// USDC paid (BUY) or received (SELL) for the whole size
+    pub net_usdc: f64,
+    /// Effective price per outcome token
+    pub price: f64,
+    /// The book leg that has to trade
+    pub leg: SweepQuote,
+}
+
+/// Quote both routes for `size` tokens and return the cheaper
+/// (BUY) or richer (SELL) one. Routes the books can't fill within
+/// `max_slippage` are skipped.
+pub async fn best_route(
+    clob: &ClobClient,
+    market: &BinaryMarket,
+    side: &Side,
+    size: f64,
+    max_slippage: f64,
+) -> Result<RouteQuote> {
+    let own = fetch_book(clob.clob_url(), &market.token_id).await?;
+    let other = fetch_book(clob.clob_url(), &market.complement_id).await?;
+
+    let quote = |route, leg: SweepQuote, net_usdc: f64| RouteQuote {
+        route,
+        net_usdc,
+        price: net_usdc / size,
+        leg,
+    };
+
+    let candidates: Vec<RouteQuote> = match side {
+        Side::Buy => [
+            own.quote_buy_size(size, max_slippage)
+                .map(|q| quote(Route::Direct, q, q.notional)),
+            other
+                .quote_sell(size, max_slippage)
+                .map(|q| quote(Route::SplitSellComplement, q, size - q.notional)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        Side::Sell => [
+            own.quote_sell(size, max_slippage)
+                .map(|q| quote(Route::Direct, q, q.notional)),
+            other
+                .quote_buy_size(size, max_slippage)
+                .map(|q| quote(Route::BuyComplementMerge, q, size - q.notional)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+    };
+
+    let best = match side {
+        Side::Buy => candidates.into_iter().min_by(|a, b| a.net_usdc.total_cmp(&b.net_usdc)),
+        Side::Sell => candidates.into_iter().max_by(|a, b| a.net_usdc.total_cmp(&b.net_usdc)),
+    };
+
+    best.ok_or_else(|| anyhow!("Neither book can fill {} tokens within slippage", size))
+}
+
+/// Pick the best route for `size` tokens and execute it.
+/// Returns the chosen quote and the CLOB order id of the book leg.
+pub async fn execute_routed(
+    clob: &ClobClient,
+    market: &BinaryMarket,
+    side: Side,
+    size: Decimal,
+    max_slippage: f64,
+) -> Result<(RouteQuote, String)> {
+    let size_f = size.to_f64().unwrap_or_default();
+    let q = best_route(clob, market, &side, size_f, max_slippage).await?;
+
+    info!(
+        "🧭 {} {} via {:?}: ${:.4} ({:.4}/token)",
+        side.as_str(),
+        size,
+        q.route,
+        q.net_usdc,
+        q.price
+    );
+
+    let limit = Decimal::from_f64(q.leg.limit_price)
+        .map(|p| p.round_dp(4))
+        .ok_or_else(|| anyhow!("Invalid price {}", q.leg.limit_price))?;
+    let amount = U256::from(
+        (size * Decimal::from(1_000_000))
+            .trunc()
+            .to_u128()
+            .ok_or_else(|| anyhow!("Size {} out of range", size))?,
+    );
+
+    let order_id = match q.route {
+        Route::Direct => {
+            clob.place_order(&market.token_id, side, limit, size, OrderType::Fok)
+                .await?
+        }
+        Route::SplitSellComplement => {
+            clob.split_position(market.condition_id, amount).await?;
+            clob.place_order(&market.complement_id, Side::Sell, limit, size, OrderType::Fok)
+                .await?
+        }
+        Route::BuyComplementMerge => {
+            let id = clob
+                .place_order(&market.complement_id, Side::Buy, limit, size, OrderType::Fok)
+                .await?;
+            clob.merge_positions(market.condition_id, amount).await?;
+            id
+        }
+    };
+
+    Ok((q, order_id))
+}
@@ -0,0 +1,162 @@
+use anyhow::{anyhow, Result};
+use futures_util::future::BoxFuture;
+use log::warn;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::domain::order::Side;
+use crate::execution::checks::{PreTradeCheck, PreTradeOrder};
+use crate::execution::clob_client::ClobClient;
+use crate::execution::orderbook::{fetch_last_trade, fetch_midpoint};
+use crate::net::SendRetry;
+
+// ==================================================
+// PRICE SANITY
+// ==================================================
+// Refuse orders priced too far from a reference, catching fat-finger
+// prices and inverted sides (a BUY at 0.95 on a 0.05 market):
+//
+//   |price - reference| > PRICE_SANITY_MAX_DEVIATION  →  refused
+//
+// The reference is the book mid (last trade on an empty book) unless
+// PRICE_SANITY_REFERENCE_URL points at a fair-value feed.
+
+/// Where the reference price of a token comes from. `None` means no
+/// reference is available right now; the order then passes.
+pub trait PriceReference: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn price<'a>(&'a self, clob: &'a ClobClient, token_id: &'a str) -> BoxFuture<'a, Result<Option<f64>>>;
+}
+
+/// Book midpoint, or the last trade when the book is one-sided
+pub struct MidReference;
+
+impl PriceReference for MidReference {
+    fn name(&self) -> &str {
+        "mid"
+    }
+
+    fn price<'a>(&'a self, clob: &'a ClobClient, token_id: &'a str) -> BoxFuture<'a, Result<Option<f64>>> {
+        Box::pin(async move {
+            if let Ok(mid) = fetch_midpoint(clob.clob_url(), token_id).await {
+                return Ok(Some(mid));
+            }
+            Ok(fetch_last_trade(clob.clob_url(), token_id).await.ok())
+        })
+    }
+}
+
+/// External fair-value feed: `GET {url}?token_id=…` answering
+/// `{"price": 0.42}` (or 404 when it has no view on the token)
+pub struct FeedReference {
+    http: Client,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct FeedPrice {
+    price: f64,
+}
+
+impl FeedReference {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: Client::new(),
+            url: url.into(),
+        }
+    }
+}
+
+impl PriceReference for FeedReference {
+    fn name(&self) -> &str {
+        "feed"
+    }
+
+    fn price<'a>(&'a self, _clob: &'a ClobClient, token_id: &'a str) -> BoxFuture<'a, Result<Option<f64>>> {
+        Box::pin(async move {
+            let resp = self
+                .http
+                .get(&self.url)
+                .query(&[("token_id", token_id)])
+                .send_retry()
+                .await?;
+            if resp.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            let body: FeedPrice = resp.error_for_status()?.json().await?;
+            Ok(Some(body.price))
+        })
+    }
+}
+
+/// Refuse orders more than `max_deviation` (in price, e.g. 0.10 = 10
+/// cents) away from the reference
+pub struct PriceSanityCheck {
+    max_deviation: f64,
+    reference: Arc<dyn PriceReference>,
+}
+
+impl PriceSanityCheck {
+    pub fn new(max_deviation: f64, reference: Arc<dyn PriceReference>) -> Self {
+        Self {
+            max_deviation,
+            reference,
+        }
+    }
+
+    /// `PRICE_SANITY_MAX_DEVIATION` (unset = off) and
+    /// `PRICE_SANITY_REFERENCE_URL` (unset = book mid)
+    pub fn from_env() -> Option<Self> {
+        let max_deviation = std::env::var("PRICE_SANITY_MAX_DEVIATION")
+            .ok()?
+            .parse()
+            .ok()?;
+        let reference: Arc<dyn PriceReference> = match std::env::var("PRICE_SANITY_REFERENCE_URL") {
+            Ok(url) if !url.is_empty() => Arc::new(FeedReference::new(url)),
+            _ => Arc::new(MidReference),
+        };
+        Some(Self::new(max_deviation, reference))
+    }
+}
+
+impl PreTradeCheck for PriceSanityCheck {
+    fn name(&self) -> &str {
+        "price sanity"
+    }
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Some(reference) = self.reference.price(clob, &order.token_id).await? else {
+                warn!(
+                    "⚠️  No {} reference for {}, skipping price sanity check",
+                    self.reference.name(),
+                    order.token_id
+                );
+                return Ok(());
+            };
+            let deviation = order.price - reference;
+            if deviation.abs() <= self.max_deviation {
+                return Ok(());
+            }
+            // Paying above / selling below the reference is the costly way
+            let adverse = match order.side {
+                Side::Buy => deviation > 0.0,
+                Side::Sell => deviation < 0.0,
+            };
+            Err(anyhow!(
+                "🚫 {} {} at {:.4} is {:.4} {} the {} reference {:.4} (limit {:.4}){}",
+                order.side.as_str(),
+                order.token_id,
+                order.price,
+                deviation.abs(),
+                if deviation > 0.0 { "above" } else { "below" },
+                self.reference.name(),
+                reference,
+                self.max_deviation,
+                if adverse { ", wrong side?" } else { "" }
+            ))
+        })
+    }
+}
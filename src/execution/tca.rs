@@ -0,0 +1,286 @@
+use anyhow::Result;
+use futures_util::{SinkExt, StreamExt};
+use log::info;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::domain::order::Side;
+use crate::net::{feeds, Connections, Reconnector};
+
+// ==================================================
+// TRANSACTION-COST ANALYSIS
+// ==================================================
+
+/// Where an order stood when it went out
+#[derive(Debug, Clone)]
+pub struct Arrival {
+    pub token_id: String,
+    pub side: Side,
+    /// Limit price of the order
+    pub price: f64,
+    /// Book midpoint at submission, if it could be fetched
+    pub mid: Option<f64>,
+    pub at_ms: u64,
+}
+
+/// One fill measured against its benchmarks. Slippage is in basis
+/// points and positive when it cost us (bought above / sold below).
+#[derive(Debug, Clone)]
+pub struct FillBenchmark {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    pub size: f64,
+    pub arrival_mid: Option<f64>,
+    /// Market VWAP between submission and fill
+    pub interval_vwap: Option<f64>,
+    pub slippage_vs_mid_bps: Option<f64>,
+    pub slippage_vs_vwap_bps: Option<f64>,
+    pub at_ms: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TcaReport {
+    pub fills: Vec<FillBenchmark>,
+    pub total_size: f64,
+    pub total_notional: f64,
+    /// Size-weighted over fills that have the benchmark
+    pub avg_slippage_vs_mid_bps: Option<f64>,
+    pub avg_slippage_vs_vwap_bps: Option<f64>,
+}
+
+impl TcaReport {
+    pub fn log(&self) {
+        info!(
+            "📊 TCA: {} fills, {:.2} tokens, ${:.2}",
+            self.fills.len(),
+            self.total_size,
+            self.total_notional
+        );
+        if let Some(bps) = self.avg_slippage_vs_mid_bps {
+            info!("   vs arrival mid: {:+.1} bps", bps);
+        }
+        if let Some(bps) = self.avg_slippage_vs_vwap_bps {
+            info!("   vs interval VWAP: {:+.1} bps", bps);
+        }
+    }
+}
+
+/// Market trades per token over a trailing window, plus arrival
+/// snapshots of our own orders so each fill can be benchmarked.
+///
+/// Fill prices are the order's limit price; marketable orders that
+/// sweep to better levels therefore report slippage conservatively.
+#[derive(Debug)]
+pub struct Tca {
+    window: Duration,
+    // token → (unix ms, price, size), oldest first
+    trades: HashMap<String, VecDeque<(u64, f64, f64)>>,
+    // order id → arrival
+    arrivals: HashMap<String, Arrival>,
+    fills: Vec<FillBenchmark>,
+}
+
+impl Tca {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            trades: HashMap::new(),
+            arrivals: HashMap::new(),
+            fills: Vec::new(),
+        }
+    }
+
+    /// `TCA_VWAP_WINDOW_SECS` (default 300)
+    pub fn from_env() -> Self {
+        let secs = std::env::var("TCA_VWAP_WINDOW_SECS")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()
+            .unwrap_or(300);
+        Self::new(Duration::from_secs(secs))
+    }
+
+    /// Record a market trade and drop trades older than the window
+    pub fn record_trade(&mut self, token_id: &str, price: f64, size: f64, at_ms: u64) {
+        let cutoff = now_ms().saturating_sub(self.window.as_millis() as u64);
+        let trades = self.trades.entry(token_id.to_string()).or_default();
+        trades.push_back((at_ms, price, size));
+        while trades.front().is_some_and(|(ts, _, _)| *ts < cutoff) {
+            trades.pop_front();
+        }
+    }
+
+    /// Feed a market-channel message; only `last_trade_price` events
+    /// are used. Messages may arrive singly or batched in an array.
+    pub fn on_market_message(&mut self, msg: &Value) {
+        if let Some(events) = msg.as_array() {
+            for event in events {
+                self.on_market_message(event);
+            }
+            return;
+        }
+        if msg.get("event_type").and_then(|t| t.as_str()) != Some("last_trade_price") {
+            return;
+        }
+
+        let field = |k: &str| msg.get(k).and_then(|v| v.as_str());
+        let (Some(token), Some(price), Some(size)) = (
+            field("asset_id"),
+            field("price").and_then(|p| p.parse().ok()),
+            field("size").and_then(|s| s.parse().ok()),
+        ) else {
+            return;
+        };
+        let at_ms = field("timestamp")
+            .and_then(|t| t.parse().ok())
+            .unwrap_or_else(now_ms);
+
+        self.record_trade(token, price, size, at_ms);
+    }
+
+    /// VWAP of `token_id` trades at or after `since_ms` (within the window)
+    pub fn vwap(&self, token_id: &str, since_ms: u64) -> Option<f64> {
+        let (notional, size) = self
+            .trades
+            .get(token_id)?
+            .iter()
+            .filter(|(ts, _, _)| *ts >= since_ms)
+            .fold((0.0, 0.0), |(n, s), (_, p, q)| (n + p * q, s + q));
+        (size > 0.0).then(|| notional / size)
+    }
+
+    pub fn on_submitted(&mut self, order_id: &str, arrival: Arrival) {
+        self.arrivals.insert(order_id.to_string(), arrival);
+    }
+
+    /// Benchmark `size` tokens matched on `order_id`. Orders submitted
+    /// by another process have no arrival and are skipped.
+    pub fn on_fill(&mut self, order_id: &str, size: f64) -> Option<&FillBenchmark> {
+        let arrival = self.arrivals.get(order_id)?;
+
+        // No market prints since arrival: fall back to the whole window
+        let interval_vwap = self
+            .vwap(&arrival.token_id, arrival.at_ms)
+            .or_else(|| self.vwap(&arrival.token_id, 0));
+
+        let fill = FillBenchmark {
+            order_id: order_id.to_string(),
+            token_id: arrival.token_id.clone(),
+            side: arrival.side.clone(),
+            price: arrival.price,
+            size,
+            arrival_mid: arrival.mid,
+            interval_vwap,
+            slippage_vs_mid_bps: arrival.mid.map(|m| slippage_bps(&arrival.side, arrival.price, m)),
+            slippage_vs_vwap_bps: interval_vwap.map(|v| slippage_bps(&arrival.side, arrival.price, v)),
+            at_ms: now_ms(),
+        };
+
+        info!(
+            "📏 Fill {} {:.2} @ {:.4}: {} vs mid, {} vs VWAP",
+            fill.side.as_str(),
+            fill.size,
+            fill.price,
+            fmt_bps(fill.slippage_vs_mid_bps),
+            fmt_bps(fill.slippage_vs_vwap_bps)
+        );
+
+        self.fills.push(fill);
+        self.fills.last()
+    }
+
+    /// Stop tracking an order once it can no longer fill
+    pub fn forget(&mut self, order_id: &str) {
+        self.arrivals.remove(order_id);
+    }
+
+    pub fn report(&self) -> TcaReport {
+        let weighted = |f: fn(&FillBenchmark) -> Option<f64>| {
+            let (sum, size) = self
+                .fills
+                .iter()
+                .filter_map(|x| f(x).map(|bps| (bps * x.size, x.size)))
+                .fold((0.0, 0.0), |(a, b), (c, d)| (a + c, b + d));
+            (size > 0.0).then(|| sum / size)
+        };
+
+        TcaReport {
+            fills: self.fills.clone(),
+            total_size: self.fills.iter().map(|f| f.size).sum(),
+            total_notional: self.fills.iter().map(|f| f.size * f.price).sum(),
+            avg_slippage_vs_mid_bps: weighted(|f| f.slippage_vs_mid_bps),
+            avg_slippage_vs_vwap_bps: weighted(|f| f.slippage_vs_vwap_bps),
+        }
+    }
+}
+
+// ==================================================
+// TRADE FEED
+// ==================================================
+
+/// Stream market trades for `token_ids` into `tca`, reconnecting on error
+pub async fn run_trade_feed(
+    ws_url: String,
+    token_ids: Vec<String>,
+    tca: Arc<Mutex<Tca>>,
+    connections: Arc<Connections>,
+) {
+    let mut link = Reconnector::new(feeds::TRADE_FEED, connections);
+    loop {
+        let result = stream_trades(&ws_url, &token_ids, &tca, &mut link).await;
+        link.disconnected(result).await;
+    }
+}
+
+async fn stream_trades(ws_url: &str, token_ids: &[String], tca: &Mutex<Tca>, link: &mut Reconnector) -> Result<()> {
+    link.connecting();
+    let (ws, _) = connect_async(ws_url).await?;
+    let (mut write, mut read) = ws.split();
+
+    let sub = json!({ "type": "market", "assets_ids": token_ids });
+    write.send(Message::Text(sub.to_string())).await?;
+    info!("📡 Trade feed subscribed to {} token(s)", token_ids.len());
+    link.connected();
+
+    let mut hb = tokio::time::interval(Duration::from_secs(10));
+    loop {
+        tokio::select! {
+            _ = hb.tick() => {
+                write.send(Message::Text("PING".to_string())).await?;
+            }
+            msg = read.next() => {
+                let msg = msg.ok_or_else(|| anyhow::anyhow!("WS closed"))??;
+                if let Message::Text(txt) = msg {
+                    if let Ok(v) = serde_json::from_str::<Value>(&txt) {
+                        tca.lock().await.on_market_message(&v);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn slippage_bps(side: &Side, price: f64, benchmark: f64) -> f64 {
+    let diff = match side {
+        Side::Buy => price - benchmark,
+        Side::Sell => benchmark - price,
+    };
+    diff / benchmark * 10_000.0
+}
+
+fn fmt_bps(bps: Option<f64>) -> String {
+    bps.map(|b| format!("{:+.1} bps", b))
+        .unwrap_or_else(|| "n/a".to_string())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
@@ -0,0 +1,209 @@
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::ClobError;
+use crate::execution::checks::{PreTradeCheck, PreTradeOrder};
+use crate::execution::clob_client::ClobClient;
+
+// ==================================================
+// ORDER-RATE BUDGETS
+// ==================================================
+// Token buckets on what we send the exchange, so a strategy stuck in a
+// loop gets refused instead of spamming it:
+//
+//   submissions  ORDER_RATE_PER_SEC / _PER_MIN, ORDER_RATE_MARKET_PER_SEC / _PER_MIN
+//   cancels      CANCEL_RATE_PER_SEC / _PER_MIN, CANCEL_RATE_MARKET_PER_SEC / _PER_MIN
+//
+// A market here is one outcome token. Unset limits don't apply.
+// `cancel_all` is never throttled: it's how the safety switches flatten.
+
+/// Refills `capacity` tokens evenly over `window`, holding at most
+/// `capacity`
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    per_sec: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, window: Duration) -> Self {
+        let capacity = capacity as f64;
+        Self {
+            capacity,
+            per_sec: capacity / window.as_secs_f64(),
+            tokens: capacity,
+            last: Instant::now(),
+        }
+    }
+
+    /// Tokens available now
+    pub fn available(&mut self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.last = now;
+        self.tokens
+    }
+
+    fn take(&mut self, n: f64) {
+        self.tokens -= n;
+    }
+
+    /// Time until `n` tokens are available (zero if they already are)
+    fn wait_for(&self, n: f64) -> Duration {
+        Duration::from_secs_f64(((n - self.tokens) / self.per_sec).max(0.0))
+    }
+}
+
+/// Per-second and per-minute caps for one scope
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateBudget {
+    pub per_second: Option<u32>,
+    pub per_minute: Option<u32>,
+}
+
+impl RateBudget {
+    /// `{prefix}_PER_SEC`, `{prefix}_PER_MIN`
+    pub fn from_env(prefix: &str) -> Self {
+        let var = |suffix: &str| {
+            std::env::var(format!("{}_{}", prefix, suffix))
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&n: &u32| n > 0)
+        };
+        Self {
+            per_second: var("PER_SEC"),
+            per_minute: var("PER_MIN"),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.per_second.is_none() && self.per_minute.is_none()
+    }
+
+    fn buckets(&self) -> Vec<TokenBucket> {
+        let mut buckets = Vec::new();
+        if let Some(n) = self.per_second {
+            buckets.push(TokenBucket::new(n, Duration::from_secs(1)));
+        }
+        if let Some(n) = self.per_minute {
+            buckets.push(TokenBucket::new(n, Duration::from_secs(60)));
+        }
+        buckets
+    }
+}
+
+#[derive(Default)]
+struct Buckets {
+    global: Vec<TokenBucket>,
+    markets: HashMap<String, Vec<TokenBucket>>,
+}
+
+/// A global budget plus one per market, for one kind of request
+pub struct RateLimiter {
+    what: &'static str,
+    global: RateBudget,
+    market: RateBudget,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(what: &'static str, global: RateBudget, market: RateBudget) -> Self {
+        Self {
+            what,
+            global,
+            market,
+            buckets: Mutex::new(Buckets {
+                global: global.buckets(),
+                markets: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Order submissions (`ORDER_RATE_*`)
+    pub fn orders_from_env() -> Self {
+        Self::new(
+            "order",
+            RateBudget::from_env("ORDER_RATE"),
+            RateBudget::from_env("ORDER_RATE_MARKET"),
+        )
+    }
+
+    /// Cancels (`CANCEL_RATE_*`)
+    pub fn cancels_from_env() -> Self {
+        Self::new(
+            "cancel",
+            RateBudget::from_env("CANCEL_RATE"),
+            RateBudget::from_env("CANCEL_RATE_MARKET"),
+        )
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.global.is_unlimited() && self.market.is_unlimited()
+    }
+
+    /// Spend `n` requests against each `(market, n)` budget and their
+    /// total against the global one, or spend nothing and refuse
+    pub fn acquire(&self, markets: &[(&str, u32)]) -> Result<(), ClobError> {
+        if self.is_unlimited() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let Buckets { global, markets: per_market } = &mut *buckets;
+
+        let total: u32 = markets.iter().map(|(_, n)| n).sum();
+        for b in global.iter_mut() {
+            b.available(now);
+        }
+        if let Some(b) = global.iter().find(|b| b.tokens < total as f64) {
+            return Err(ClobError::RateLimited {
+                what: format!("Global {}", self.what),
+                retry_after: Some(b.wait_for(total as f64)),
+            });
+        }
+        for (market, n) in markets {
+            let scoped = per_market
+                .entry(market.to_string())
+                .or_insert_with(|| self.market.buckets());
+            for b in scoped.iter_mut() {
+                b.available(now);
+            }
+            if let Some(b) = scoped.iter().find(|b| b.tokens < *n as f64) {
+                return Err(ClobError::RateLimited {
+                    what: format!("{} ({})", self.what, market),
+                    retry_after: Some(b.wait_for(*n as f64)),
+                });
+            }
+        }
+
+        for b in global.iter_mut() {
+            b.take(total as f64);
+        }
+        for (market, n) in markets {
+            if let Some(scoped) = per_market.get_mut(*market) {
+                for b in scoped.iter_mut() {
+                    b.take(*n as f64);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Spend the submission budget; runs after the other built-in checks so
+/// orders they refuse don't use it up
+pub struct ThrottleCheck;
+
+impl PreTradeCheck for ThrottleCheck {
+    fn name(&self) -> &str {
+        "throttle"
+    }
+
+    fn check<'a>(&'a self, clob: &'a ClobClient, order: &'a PreTradeOrder<'a>) -> BoxFuture<'a, anyhow::Result<()>> {
+        Box::pin(async move { Ok(clob.order_rate().acquire(&[(&order.token_id, 1)])?) })
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::net::SendRetry;
+
+const CLOB_API_URL: &str = "https://clob.polymarket.com";
+
+/// Default resolution requested from `/prices-history`, in minutes
+const DEFAULT_FIDELITY_MINS: u64 = 1;
+
+// ==================================================
+// PRICE HISTORY
+// ==================================================
+
+/// One `/prices-history` sample
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct PricePoint {
+    /// Unix seconds
+    pub t: u64,
+    pub p: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryResponse {
+    #[serde(default)]
+    history: Vec<PricePoint>,
+}
+
+/// OHLC over `[start, start + interval)`, built from history samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    /// Unix seconds, aligned to the interval
+    pub start: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Samples in the bucket; 0 for a gap carried from the last close
+    pub samples: usize,
+}
+
+/// Bucket `points` into candles of `interval`. Buckets with no samples
+/// between the first and last are filled flat at the previous close so
+/// consumers see an evenly spaced series.
+pub fn candles(points: &[PricePoint], interval: Duration) -> Vec<Candle> {
+    let secs = interval.as_secs().max(1);
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|p| p.t);
+
+    let mut out: Vec<Candle> = Vec::new();
+    for point in sorted {
+        let start = point.t - point.t % secs;
+        if let Some(c) = out.last_mut().filter(|c| c.start == start) {
+            c.high = c.high.max(point.p);
+            c.low = c.low.min(point.p);
+            c.close = point.p;
+            c.samples += 1;
+            continue;
+        }
+
+        if let Some(prev) = out.last().copied() {
+            let mut gap = prev.start + secs;
+            while gap < start {
+                out.push(Candle {
+                    start: gap,
+                    open: prev.close,
+                    high: prev.close,
+                    low: prev.close,
+                    close: prev.close,
+                    samples: 0,
+                });
+                gap += secs;
+            }
+        }
+        out.push(Candle {
+            start,
+            open: point.p,
+            high: point.p,
+            low: point.p,
+            close: point.p,
+            samples: 1,
+        });
+    }
+    out
+}
+
+/// Client for the CLOB `/prices-history` endpoint
+#[derive(Debug, Clone)]
+pub struct PriceHistory {
+    http: Client,
+    clob_url: String,
+    fidelity_mins: u64,
+}
+
+impl PriceHistory {
+    pub fn new(clob_url: impl Into<String>) -> Self {
+        Self {
+            http: Client::new(),
+            clob_url: clob_url.into(),
+            fidelity_mins: DEFAULT_FIDELITY_MINS,
+        }
+    }
+
+    /// `CLOB_API_URL` (default the public CLOB)
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("CLOB_API_URL").unwrap_or_else(|_| CLOB_API_URL.to_string()))
+    }
+
+    /// Sample resolution to request; coarser is cheaper over long ranges
+    pub fn fidelity(mut self, minutes: u64) -> Self {
+        self.fidelity_mins = minutes.max(1);
+        self
+    }
+
+    /// Raw samples for `token_id` between two unix-second timestamps
+    pub async fn points(&self, token_id: &str, start_ts: u64, end_ts: u64) -> Result<Vec<PricePoint>> {
+        let url = format!(
+            "{}/prices-history?market={}&startTs={}&endTs={}&fidelity={}",
+            self.clob_url, token_id, start_ts, end_ts, self.fidelity_mins
+        );
+        let resp = self.http.get(&url).send_retry().await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("Failed to fetch price history: {}", resp.status()));
+        }
+
+        let body: HistoryResponse = resp.json().await?;
+        Ok(body.history)
+    }
+
+    /// Candles of `interval` for `token_id` between two unix-second timestamps
+    pub async fn candles(
+        &self,
+        token_id: &str,
+        start_ts: u64,
+        end_ts: u64,
+        interval: Duration,
+    ) -> Result<Vec<Candle>> {
+        let points = self.points(token_id, start_ts, end_ts).await?;
+        Ok(candles(&points, interval))
+    }
+
+    /// Candles covering the last `lookback`
+    pub async fn recent_candles(
+        &self,
+        token_id: &str,
+        lookback: Duration,
+        interval: Duration,
+    ) -> Result<Vec<Candle>> {
+        let now = now_secs();
+        self.candles(token_id, now.saturating_sub(lookback.as_secs()), now, interval)
+            .await
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
@@ -0,0 +1,268 @@
+use anyhow::{anyhow, Result};
+use log::info;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use super::auth::KalshiCredentials;
+use crate::execution::orderbook::{Level, OrderBook};
+use crate::net::SendRetry;
+
+// ==================================================
+// KALSHI TRADING API (v2)
+// ==================================================
+// Prices are integer cents (1–99) and sizes whole contracts. The book
+// only lists bids: a NO bid at p is a YES ask at 100 - p and vice versa.
+
+const DEFAULT_API_URL: &str = "https://api.elections.kalshi.com/trade-api/v2";
+
+/// Kalshi taker fee for `count` contracts at `price` (dollars):
+/// 0.07 × count × price × (1 - price), rounded up to the cent
+pub fn taker_fee(count: u64, price: f64) -> f64 {
+    let fee = 0.07 * count as f64 * price * (1.0 - price);
+    (fee * 100.0 - 1e-9).ceil().max(0.0) / 100.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KalshiMarket {
+    pub ticker: String,
+    #[serde(default)]
+    pub event_ticker: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub status: String,
+    /// Cents
+    #[serde(default)]
+    pub yes_bid: i64,
+    #[serde(default)]
+    pub yes_ask: i64,
+    #[serde(default)]
+    pub no_bid: i64,
+    #[serde(default)]
+    pub no_ask: i64,
+    #[serde(default)]
+    pub close_time: Option<String>,
+}
+
+impl KalshiMarket {
+    pub fn is_open(&self) -> bool {
+        matches!(self.status.as_str(), "open" | "active")
+    }
+}
+
+/// Both sides of a Kalshi market as regular books in dollars, best
+/// level first
+#[derive(Debug, Clone, Default)]
+pub struct KalshiBook {
+    pub yes: OrderBook,
+    pub no: OrderBook,
+}
+
+#[derive(Deserialize)]
+struct RawBook {
+    #[serde(default)]
+    yes: Option<Vec<(i64, i64)>>,
+    #[serde(default)]
+    no: Option<Vec<(i64, i64)>>,
+}
+
+impl KalshiBook {
+    fn from_raw(raw: RawBook) -> Self {
+        let levels = |side: &Option<Vec<(i64, i64)>>, flip: bool| -> Vec<Level> {
+            side.iter()
+                .flatten()
+                .map(|&(cents, qty)| {
+                    let cents = if flip { 100 - cents } else { cents };
+                    Level::new(cents as f64 / 100.0, qty as f64)
+                })
+                .collect()
+        };
+        let mut yes = OrderBook {
+            bids: levels(&raw.yes, false),
+            asks: levels(&raw.no, true),
+        };
+        let mut no = OrderBook {
+            bids: levels(&raw.no, false),
+            asks: levels(&raw.yes, true),
+        };
+        for book in [&mut yes, &mut no] {
+            book.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+            book.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        }
+        Self { yes, no }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KalshiSide {
+    Yes,
+    No,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KalshiAction {
+    Buy,
+    Sell,
+}
+
+/// A limit order; `price` in cents on `side`
+#[derive(Debug, Clone, Serialize)]
+pub struct KalshiOrder {
+    pub ticker: String,
+    pub action: KalshiAction,
+    pub side: KalshiSide,
+    pub count: u64,
+    #[serde(rename = "type")]
+    pub order_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub yes_price: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_price: Option<i64>,
+    pub client_order_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<&'static str>,
+}
+
+impl KalshiOrder {
+    /// Limit order at `cents` that fills completely now or not at all
+    pub fn fill_or_kill(ticker: &str, action: KalshiAction, side: KalshiSide, count: u64, cents: i64) -> Self {
+        let (yes_price, no_price) = match side {
+            KalshiSide::Yes => (Some(cents), None),
+            KalshiSide::No => (None, Some(cents)),
+        };
+        Self {
+            ticker: ticker.to_string(),
+            action,
+            side,
+            count,
+            order_type: "limit",
+            yes_price,
+            no_price,
+            client_order_id: format!("pm-{:016x}", rand::random::<u64>()),
+            time_in_force: Some("fill_or_kill"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KalshiOrderAck {
+    pub order_id: String,
+    #[serde(default)]
+    pub status: String,
+    /// Contracts still resting
+    #[serde(default)]
+    pub remaining_count: i64,
+}
+
+impl KalshiOrderAck {
+    pub fn is_filled(&self) -> bool {
+        self.status == "executed" && self.remaining_count == 0
+    }
+}
+
+pub struct KalshiClient {
+    http: Client,
+    base_url: String,
+    credentials: Option<KalshiCredentials>,
+}
+
+impl KalshiClient {
+    pub fn new(base_url: impl Into<String>, credentials: Option<KalshiCredentials>) -> Result<Self> {
+        Ok(Self {
+            http: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            credentials,
+        })
+    }
+
+    /// `KALSHI_API_URL` (default production) plus credentials from
+    /// `KALSHI_API_KEY_ID` / `KALSHI_PRIVATE_KEY_PATH`; without them
+    /// only market data works
+    pub fn from_env() -> Result<Self> {
+        let url = std::env::var("KALSHI_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        Self::new(url, KalshiCredentials::from_env()?)
+    }
+
+    pub fn can_trade(&self) -> bool {
+        self.credentials.is_some()
+    }
+
+    pub async fn market(&self, ticker: &str) -> Result<KalshiMarket> {
+        #[derive(Deserialize)]
+        struct Resp {
+            market: KalshiMarket,
+        }
+        let resp: Resp = self.request(Method::GET, &format!("/markets/{}", ticker), None).await?;
+        Ok(resp.market)
+    }
+
+    pub async fn orderbook(&self, ticker: &str) -> Result<KalshiBook> {
+        #[derive(Deserialize)]
+        struct Resp {
+            orderbook: RawBook,
+        }
+        let resp: Resp = self
+            .request(Method::GET, &format!("/markets/{}/orderbook", ticker), None)
+            .await?;
+        Ok(KalshiBook::from_raw(resp.orderbook))
+    }
+
+    /// Available cash in dollars
+    pub async fn balance(&self) -> Result<f64> {
+        #[derive(Deserialize)]
+        struct Resp {
+            balance: i64,
+        }
+        let resp: Resp = self.request(Method::GET, "/portfolio/balance", None).await?;
+        Ok(resp.balance as f64 / 100.0)
+    }
+
+    pub async fn place_order(&self, order: &KalshiOrder) -> Result<KalshiOrderAck> {
+        #[derive(Deserialize)]
+        struct Resp {
+            order: KalshiOrderAck,
+        }
+        let resp: Resp = self
+            .request(Method::POST, "/portfolio/orders", Some(serde_json::to_value(order)?))
+            .await?;
+        info!(
+            "🅺 Kalshi {:?} {} {:?} on {}: {} ({})",
+            order.action, order.count, order.side, order.ticker, resp.order.order_id, resp.order.status
+        );
+        Ok(resp.order)
+    }
+
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let _: Value = self
+            .request(Method::DELETE, &format!("/portfolio/orders/{}", order_id), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn request<T: DeserializeOwned>(&self, method: Method, path: &str, body: Option<Value>) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut req = self.http.request(method.clone(), &url);
+        if let Some(creds) = &self.credentials {
+            // The signature covers the full URL path, API prefix included
+            let signed_path = reqwest::Url::parse(&url)?.path().to_string();
+            req = req.headers(creds.headers(method.as_str(), &signed_path)?);
+        } else if method != Method::GET {
+            return Err(anyhow!("Kalshi credentials not configured"));
+        }
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+        let resp = req.send_retry().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(anyhow!("Kalshi {} {} failed ({}): {}", method, path, status, text));
+        }
+        Ok(resp.json().await?)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod api;
+pub mod auth;
+
+pub use api::{
+    taker_fee, KalshiAction, KalshiBook, KalshiClient, KalshiMarket, KalshiOrder, KalshiOrderAck, KalshiSide,
+};
+pub use auth::KalshiCredentials;
@@ -0,0 +1,24 @@
+pub mod cli;
+pub mod client;
+pub mod clob;
+pub mod config;
+pub mod domain;
+pub mod error;
+pub mod execution;
+pub mod history;
+pub mod kalshi;
+pub mod market_ws;
+pub mod markets;
+pub mod monitor;
+pub mod net;
+pub mod orders;
+pub mod portfolio;
+pub mod risk;
+pub mod status;
+pub mod storage;
+pub mod strategy;
+pub mod user_ws;
+pub mod ws;
+pub mod cache;
+pub mod wallet;
+pub mod logging;
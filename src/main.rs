@@ -0,0 +1,568 @@
+use polymarket_15m_arbitrage_bot::*;
+
+use anyhow::Result;
+use clap::Parser;
+use config::{Args, Config};
+use log::{info, warn}; // ← CHANGED: Added 'warn' import
+use std::sync::Arc;
+
+use client::PolymarketClient;
+use execution::{Trader, clob_client::ClobClient};
+use monitor::MarketMonitor;
+use strategy::ArbitrageDetector;
+use wallet::signer::WalletSigner;
+use wallet::allowance::verify_allowances;
+use cache::PriceCache;
+use crate::config::{ChainConfig, WalletConfig};
+use ethers::providers::Middleware;
+
+// ===============================
+// TIME HELPERS
+// ===============================
+fn current_15m_period() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    (now / 900) * 900
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+
+    if std::env::var("RUST_LOG").is_err() {
+        std::env::set_var("RUST_LOG", "info");
+    }
+    env_logger::init();
+
+    info!("🚀 Starting Polymarket Arbitrage Bot");
+
+    let args = Args::parse();
+    if let Some(command) = args.command {
+        return cli::run(command).await;
+    }
+    let config = Config::load(&args.config)?;
+
+    // ===============================
+    // PROVIDER
+    // ===============================
+    // One or more comma-separated URLs; calls fail over between them
+    let rpc_url = std::env::var("RPC_URL")
+        .expect("RPC_URL missing in .env");
+
+    let provider = Arc::new(
+        net::rpc::provider(&rpc_url)?
+    );
+
+    // ===============================
+    // WALLET SIGNER (EOA) - READ FROM .ENV
+    // ===============================
+    let private_key = std::env::var("PRIVATE_KEY")
+        .expect("PRIVATE_KEY missing in .env file");
+
+    let proxy_wallet = std::env::var("PROXY_WALLET")
+        .expect("PROXY_WALLET missing in .env file");
+
+    let chain_id = provider.get_chainid().await?.as_u64();
+    let chain = ChainConfig::from_env(chain_id)?;
+    info!("⛓️  Chain profile: {} ({})", chain.name, chain.chain_id);
+
+    let signer = WalletSigner::new(&private_key, chain.chain_id)?
+        .with_exchange(chain.exchange);
+
+    info!("🔑 Signer loaded");
+    info!("🧾 Proxy wallet: {}", proxy_wallet);
+
+    // ===============================
+    // STAGE 2 — WALLET / ALLOWANCE PREFLIGHT
+    // ===============================
+    verify_allowances(
+        provider.clone(),
+        &proxy_wallet,
+        &chain,
+    )
+    .await?;
+
+    info!("✅ STAGE 2 COMPLETE — wallet, allowance, approvals verified");
+
+    // ===============================
+    // API CREDENTIALS (Load before CLOB Client)
+    // Left empty → derived from the wallet at startup
+    // ===============================
+    let api_key = std::env::var("POLY_API_KEY").unwrap_or_default();
+    let api_secret = std::env::var("POLY_API_SECRET").unwrap_or_default();
+    let api_passphrase = std::env::var("POLY_API_PASSPHRASE").unwrap_or_default();
+
+    let read_only = std::env::var("READ_ONLY")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    // ===============================
+    // CLOB CLIENT (Now with API credentials)
+    // ===============================
+    let positions = Arc::new(portfolio::Positions::new());
+    // State of every WS feed, for strategies and the dead man's switch
+    let connections = Arc::new(net::Connections::new());
+    let mut clob = ClobClient::new(
+        &rpc_url,
+        &private_key,
+        &proxy_wallet,
+        api_key.clone(),
+        api_secret.clone(),
+        api_passphrase.clone(),
+    )
+    .await?;
+
+    // ===============================
+    // PAPER TRADING (orders matched against live books, virtual balances)
+    // ===============================
+    let paper = (std::env::var("PAPER_TRADING").as_deref() == Ok("true"))
+        .then(|| Arc::new(execution::paper::PaperExchange::from_env().with_connections(connections.clone())));
+    if let Some(paper) = &paper {
+        clob = clob.paper(paper.clone());
+        tokio::spawn(paper.clone().run());
+    }
+
+    // ===============================
+    // RISK MANAGER (pre-trade exposure limits)
+    // ===============================
+    if std::env::var("RISK_ENABLED").as_deref() == Ok("true") {
+        clob = clob.risk(Arc::new(risk::RiskManager::from_env(positions.clone())?));
+    }
+    let clob = Arc::new(clob);
+    if let Some(risk) = clob.risk_manager() {
+        if let Some(reason) = risk.kill_switch().tripped() {
+            warn!("🛑 Kill switch is tripped ({}); no orders until `resume`", reason);
+        }
+        tokio::spawn(risk.kill_switch().run(clob.clone(), std::time::Duration::from_secs(2)));
+    }
+
+    // ===============================
+    // RESTART RECONCILIATION
+    // Orders left on the book by a previous run
+    // ===============================
+    execution::reconcile::reconcile_open_orders(
+        &clob,
+        execution::reconcile::ReconcilePolicy::from_env(),
+    )
+    .await?;
+
+    // ===============================
+    // STOP ENGINE (synthetic stop-losses)
+    // ===============================
+    let stops = Arc::new(orders::StopEngine::from_env(clob.clone())?);
+    tokio::spawn({
+        let stops = stops.clone();
+        async move { stops.run().await }
+    });
+
+    // ===============================
+    // USER CHANNEL (order updates + fills → positions, PnL)
+    // ===============================
+    let marker = Arc::new(portfolio::Marker::from_env(clob.clob_url()));
+    let pnl = Arc::new(tokio::sync::Mutex::new(portfolio::PnlEngine::from_env()));
+    let journal = if std::env::var("JOURNAL_ENABLED").as_deref() == Ok("true") {
+        let journal = Arc::new(storage::Journal::from_env()?);
+        journal.restore_positions(&positions).await?;
+        Some(journal)
+    } else {
+        None
+    };
+    // Subscribed before seeding so the seed is journalled too
+    let position_updates = positions.subscribe();
+    if paper.is_none() {
+        if let Err(e) =
+            portfolio::sync_positions(&portfolio::DataApiClient::from_env(), &positions, &proxy_wallet).await
+        {
+            warn!("⚠️  Couldn't seed positions from data API: {}", e);
+        }
+    }
+    let mut fill_updates = None;
+    let mut strategy_fills = None;
+    if let Some(paper) = &paper {
+        // Paper fills stand in for the user channel
+        fill_updates = Some(paper.subscribe());
+        strategy_fills = Some(paper.subscribe());
+        tokio::spawn(portfolio::run_pnl(paper.subscribe(), pnl.clone()));
+        tokio::spawn(execution::paper::run_fills(paper.subscribe(), clob.clone(), positions.clone()));
+    } else if std::env::var("USER_WS_ENABLED").as_deref() != Ok("false") {
+        match user_ws::UserWs::from_env(clob.clone(), positions.clone()) {
+            Ok(user) => {
+                let user = user.with_connections(connections.clone());
+                fill_updates = Some(user.subscribe());
+                strategy_fills = Some(user.subscribe());
+                tokio::spawn(portfolio::run_pnl(user.subscribe(), pnl.clone()));
+                tokio::spawn(async move { user.run().await });
+            }
+            Err(e) => warn!("⚠️  User channel disabled: {}", e),
+        }
+    }
+    if let Some(journal) = journal {
+        tokio::spawn(storage::run_journal(
+            journal,
+            clob.subscribe_orders().await,
+            fill_updates,
+            position_updates,
+        ));
+    }
+
+    // ===============================
+    // DAILY LOSS BREAKER
+    // ===============================
+    if let Some(breaker) = clob.risk_manager().and_then(|r| r.breaker()) {
+        tokio::spawn(breaker.run(
+            clob.clone(),
+            pnl.clone(),
+            marker.clone(),
+            positions.clone(),
+            std::time::Duration::from_secs(30),
+        ));
+    }
+
+    // ===============================
+    // STATUS API (positions, PnL, performance stats)
+    // ===============================
+    if std::env::var("STATUS_API_ENABLED").as_deref() == Ok("true") {
+        let server = Arc::new(
+            status::StatusServer::from_env(positions.clone(), pnl.clone()).marker(marker.clone()),
+        );
+        tokio::spawn(async move {
+            if let Err(e) = server.run().await {
+                warn!("⚠️  Status API stopped: {}", e);
+            }
+        });
+    }
+
+    // ===============================
+    // NEW-LISTING ALERTS
+    // ===============================
+    if std::env::var("LISTING_ALERTS_ENABLED").as_deref() == Ok("true") {
+        let listings = Arc::new(markets::ListingWatcher::from_env());
+        tokio::spawn(listings.run(markets::ListingWatcher::poll_interval()));
+    }
+
+    // ===============================
+    // EXTERNAL FAIR VALUES (pushed by a model over HTTP)
+    // ===============================
+    let fair_values = if std::env::var("FAIR_VALUE_ENABLED").as_deref() == Ok("true") {
+        let store = Arc::new(strategy::FairValueStore::from_env());
+        let push = Arc::new(strategy::FairValuePush::from_env(store.clone()));
+        tokio::spawn(async move {
+            if let Err(e) = push.run().await {
+                warn!("⚠️  Fair-value push endpoint stopped: {}", e);
+            }
+        });
+        Some(store)
+    } else {
+        None
+    };
+
+    // ===============================
+    // STRATEGIES (hosted by the strategy runner)
+    // ===============================
+    let mut runner = strategy::StrategyRunner::new(clob.clone()).connections(connections.clone());
+
+    // Shared inventory bands (quote bias + optional reducing trades)
+    if std::env::var("INVENTORY_ENABLED").as_deref() == Ok("true") {
+        let inventory = Arc::new(portfolio::InventoryController::from_env(positions.clone())?);
+        let every = std::env::var("INVENTORY_CHECK_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+        tokio::spawn(inventory.clone().run(clob.clone(), std::time::Duration::from_secs(every)));
+        runner = runner.inventory(inventory);
+    }
+
+    // YES+NO mispricing arbitrage
+    if std::env::var("COMPLEMENT_ARB_ENABLED").as_deref() == Ok("true") {
+        runner = runner.add_from_env(Arc::new(strategy::ComplementArb::from_env(clob.clone())));
+    }
+
+    // Cross-venue arbitrage against Kalshi
+    if std::env::var("KALSHI_ARB_ENABLED").as_deref() == Ok("true") {
+        runner = runner.add_from_env(Arc::new(strategy::KalshiArb::from_env(clob.clone())?));
+    }
+
+    // Two-sided liquidity (split + resting sells on YES and NO)
+    if std::env::var("LP_ENABLED").as_deref() == Ok("true") {
+        let slug = std::env::var("LP_MARKET_SLUG").unwrap_or_default();
+        if strategy_fills.is_none() {
+            warn!("⚠️  Liquidity provision needs the user channel for fills");
+        } else if slug.is_empty() {
+            warn!("⚠️  LP_ENABLED but LP_MARKET_SLUG is not set");
+        } else {
+            let market = markets::GammaClient::from_env().market_by_slug(&slug).await?;
+            runner = runner.add_from_env(Arc::new(strategy::TwoSidedLp::new(
+                clob.clone(),
+                &market,
+                strategy::LiquidityConfig::from_env(),
+            )?));
+        }
+    }
+
+    // Copy trading (mirror a leader wallet's trades)
+    if std::env::var("COPY_ENABLED").as_deref() == Ok("true") {
+        if strategy_fills.is_none() {
+            warn!("⚠️  Copy trading needs the user channel to mirror sells");
+        }
+        runner = runner.add_from_env(Arc::new(strategy::CopyTrader::from_env()?));
+    }
+
+    // Market making (post-only ladder around the mid, skewed by inventory)
+    if std::env::var("MM_ENABLED").as_deref() == Ok("true") {
+        let token_id = std::env::var("MM_TOKEN_ID").unwrap_or_default();
+        if strategy_fills.is_none() {
+            warn!("⚠️  Market making needs the user channel for fills");
+        } else if token_id.is_empty() {
+            warn!("⚠️  MM_ENABLED but MM_TOKEN_ID is not set");
+        } else {
+            let mut mm = strategy::MarketMaker::new(token_id, strategy::MarketMakerConfig::from_env());
+            if let Some(store) = &fair_values {
+                mm = mm.fair_values(store.clone());
+            }
+            runner = runner.add_from_env(Arc::new(mm));
+        }
+    }
+
+    // Momentum (moving-average cross on candles)
+    if std::env::var("MOMENTUM_ENABLED").as_deref() == Ok("true") {
+        if strategy_fills.is_none() {
+            warn!("⚠️  Momentum needs the user channel to know what it holds");
+        } else {
+            runner = runner.add_from_env(Arc::new(strategy::Momentum::from_env()));
+        }
+    }
+
+    // Mean reversion (fade last-trade dislocations from the microprice)
+    if std::env::var("MEANREV_ENABLED").as_deref() == Ok("true") {
+        if strategy_fills.is_none() {
+            warn!("⚠️  Mean reversion needs the user channel to know what it holds");
+        } else {
+            runner = runner.add_from_env(Arc::new(strategy::MeanReversion::from_env()));
+        }
+    }
+
+    if !runner.is_empty() {
+        tokio::spawn(runner.run(strategy_fills));
+    }
+
+    // ===============================
+    // DEAD MAN'S SWITCH (cancel-all when the loop or exchange goes quiet)
+    // ===============================
+    let dead_man = (std::env::var("DEAD_MAN_ENABLED").as_deref() == Ok("true")).then(|| {
+        let dead_man = Arc::new(risk::DeadMansSwitch::from_env().connections(connections.clone()));
+        tokio::spawn(dead_man.clone().run(clob.clone(), std::time::Duration::from_secs(5)));
+        dead_man
+    });
+
+    // ===============================
+    // END-OF-MARKET GUARD (pulls resting quotes)
+    // ===============================
+    if let Some(guard) = clob.expiry_guard() {
+        tokio::spawn(guard.run(clob.clone(), std::time::Duration::from_secs(30)));
+    }
+
+    // Prefer the credentials the client actually uses (may be derived)
+    let (api_key, api_secret, api_passphrase) = match clob.api_credentials() {
+        Some(c) => (c.api_key, c.secret, c.passphrase),
+        None => (api_key, api_secret, api_passphrase),
+    };
+
+    // ===============================
+    // API CLIENT
+    // ===============================
+    let api = Arc::new(PolymarketClient::new(
+        config.polymarket.gamma_api_url.clone(),
+        config.polymarket.clob_api_url.clone(),
+        api_key,
+        api_secret,
+        api_passphrase,
+        read_only,
+        clob.clone(),
+    ));
+
+    // ===============================
+    // CORE OBJECTS
+    // ===============================
+    let price_cache = PriceCache::new();
+
+    let detector = Arc::new(
+        ArbitrageDetector::new(
+            config.trading.min_profit_threshold,
+        ),
+    );
+
+    let wallet_config = WalletConfig {
+        private_key: Some(private_key.clone()),
+        chain_id: chain.chain_id,
+        proxy_wallet: proxy_wallet.clone(),
+    };
+
+    let trader = Arc::new(Trader::new(
+        api.clone(),
+        clob.clone(),
+        config.trading.clone(),
+        wallet_config,
+        signer,
+    ));
+
+    let mut current_period = current_15m_period();
+
+    // ===============================
+    // MAIN LOOP
+    // ===============================
+    loop {
+        info!("🔍 Discovering current 15m markets...");
+
+        let (eth_market, btc_market) = discover_markets(&api).await?;
+
+        info!("✅ ETH Market: {}", eth_market.slug);
+        info!("✅ BTC Market: {}", btc_market.slug);
+
+        // Market trades for VWAP / TCA benchmarks
+        let token_ids: Vec<String> = [&eth_market, &btc_market]
+            .iter()
+            .filter_map(|m| m.clob_token_ids.as_deref())
+            .filter_map(|ids| serde_json::from_str::<Vec<String>>(ids).ok())
+            .flatten()
+            .collect();
+        let market_ws_url = std::env::var("MARKET_WS_URL")
+            .unwrap_or_else(|_| market_ws::MARKET_WS_URL.to_string());
+        let recorder = (std::env::var("RECORDER_ENABLED").as_deref() == Ok("true")).then(|| {
+            tokio::spawn(market_ws::run_recorder(
+                market_ws_url.clone(),
+                token_ids.clone(),
+                market_ws::Recorder::from_env(),
+                connections.clone(),
+            ))
+        });
+        let trade_feed = tokio::spawn(execution::tca::run_trade_feed(
+            market_ws_url,
+            token_ids,
+            clob.tca(),
+            connections.clone(),
+        ));
+
+        let monitor = MarketMonitor::new(
+            api.clone(),
+            eth_market,
+            btc_market,
+            config.trading.check_interval_ms,
+        );
+
+        // ╔═══════════════════════════════════════════════════════════╗
+        // ║  CHANGED SECTION - Lines 166-199                         ║
+        // ║  What: Fixed error handling and added debug logging      ║
+        // ║  Why: Silent failures prevented seeing trader errors     ║
+        // ╚═══════════════════════════════════════════════════════════╝
+        let monitor_handle = tokio::spawn({
+            let detector = detector.clone();
+            let trader = trader.clone();
+            let dead_man = dead_man.clone();
+
+            async move {
+                monitor
+                    .start_monitoring(move |snapshot| {
+                        let detector = detector.clone();
+                        let trader = trader.clone();
+                        if let Some(dead_man) = &dead_man {
+                            dead_man.beat();
+                        }
+
+                        async move {
+                            // CHANGED: Store opportunities instead of inline iteration
+                            let opportunities = detector.detect_opportunities(&snapshot);
+                            
+                            // CHANGED: Log how many opportunities found
+                            if !opportunities.is_empty() {
+                                info!("🔔 Found {} arbitrage opportunity(ies)!", opportunities.len());
+                            }
+                            
+                            // CHANGED: Explicit enumeration with proper error handling
+                            for (i, o) in opportunities.iter().enumerate() {
+                                info!("📋 Processing opportunity {} of {}", i + 1, opportunities.len());
+                                
+                                // CHANGED: Use match instead of let _ to catch errors
+                                match trader.execute_arbitrage(&o).await {
+                                    Ok(_) => {
+                                        info!("✅ Opportunity {} handled successfully", i + 1);
+                                    }
+                                    Err(e) => {
+                                        warn!("❌ Opportunity {} failed: {}", i + 1, e);
+                                    }
+                                }
+                            }
+                        }
+                    })
+                    .await;
+            }
+        });
+        // ╔═══════════════════════════════════════════════════════════╗
+        // ║  END OF CHANGED SECTION                                   ║
+        // ╚═══════════════════════════════════════════════════════════╝
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            let new_period = current_15m_period();
+
+            if new_period != current_period {
+                info!("⏰ 15m rollover — restarting monitor");
+                current_period = new_period;
+                monitor_handle.abort();
+                trade_feed.abort();
+                if let Some(recorder) = &recorder {
+                    recorder.abort();
+                }
+                clob.tca_report().await.log();
+                break;
+            }
+        }
+    }
+}
+
+// ===============================
+// MARKET DISCOVERY (OUTSIDE MAIN)
+// ===============================
+async fn discover_markets(
+    api: &PolymarketClient,
+) -> Result<(domain::Market, domain::Market)> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+
+    let mut seen = std::collections::HashSet::new();
+
+    let eth = discover_market(api, "ETH", "eth", now, &mut seen).await?;
+    seen.insert(eth.condition_id.clone());
+
+    let btc = discover_market(api, "BTC", "btc", now, &mut seen).await?;
+
+    Ok((eth, btc))
+}
+
+async fn discover_market(
+    api: &PolymarketClient,
+    name: &str,
+    prefix: &str,
+    now: u64,
+    seen: &mut std::collections::HashSet<String>,
+) -> Result<domain::Market> {
+    let base = (now / 900) * 900;
+
+    for i in 0..=3 {
+        let ts = base - i * 900;
+        let slug = format!("{}-updown-15m-{}", prefix, ts);
+
+        if let Ok(market) = api.get_market_by_slug(&slug).await {
+            if !seen.contains(&market.condition_id) && market.active {
+                info!("Found {} market: {}", name, market.slug);
+                return Ok(market);
+            }
+        }
+    }
+
+    anyhow::bail!("No active {} market found", name)
+}
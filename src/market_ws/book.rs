@@ -0,0 +1,179 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::domain::order::Side;
+use crate::execution::orderbook::{Level, OrderBook};
+
+// ==================================================
+// LOCAL ORDER BOOK
+// ==================================================
+
+/// One token's book rebuilt from market-channel snapshots and deltas.
+/// Levels are kept by exact price so deltas replace them cleanly.
+#[derive(Debug, Clone, Default)]
+pub struct LocalBook {
+    pub token_id: String,
+    /// Condition id, as sent with snapshots
+    pub market: String,
+    pub bids: BTreeMap<Decimal, Decimal>,
+    pub asks: BTreeMap<Decimal, Decimal>,
+    /// Exchange timestamp (ms) of the last applied message
+    pub timestamp: u64,
+    /// Book hash sent with the last applied message
+    pub hash: Option<String>,
+    /// A gap was detected and a fresh snapshot is pending; don't trust
+    /// the levels until it arrives
+    pub degraded: bool,
+    /// Whether our hash of the last snapshot matched the exchange's.
+    /// `Some(false)` means the payload can't be reproduced (e.g. a
+    /// format change) and hashes are not checked for this book.
+    pub hash_verifiable: Option<bool>,
+    // Snapshot metadata that takes part in the hash
+    min_order_size: Option<String>,
+    neg_risk: Option<bool>,
+    tick_size: Option<String>,
+}
+
+impl LocalBook {
+    pub fn new(token_id: &str) -> Self {
+        Self {
+            token_id: token_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Replace the whole book with a `book` event (or a REST `/book`
+    /// response, which has the same shape). Clears `degraded`.
+    pub fn apply_snapshot(&mut self, msg: &Value) {
+        // Older payloads name the sides buys/sells
+        let side = |a: &str, b: &str| msg.get(a).or_else(|| msg.get(b)).map(levels);
+        self.bids = side("bids", "buys").unwrap_or_default();
+        self.asks = side("asks", "sells").unwrap_or_default();
+        self.timestamp = 0;
+        self.stamp(msg);
+        self.degraded = false;
+
+        let text = |k: &str| msg.get(k).and_then(|v| v.as_str()).map(str::to_string);
+        self.market = text("market").unwrap_or_default();
+        self.min_order_size = text("min_order_size");
+        self.neg_risk = msg.get("neg_risk").and_then(|v| v.as_bool());
+        self.tick_size = text("tick_size");
+        self.hash_verifiable = self.hash.as_ref().map(|h| *h == self.compute_hash());
+    }
+
+    /// Exchange book hash: SHA-1 of the compact JSON summary with an
+    /// empty `hash`, bids ascending and asks descending (best last), as
+    /// `/book` serves them.
+    pub fn compute_hash(&self) -> String {
+        let levels = |it: &mut dyn Iterator<Item = (&Decimal, &Decimal)>| {
+            it.map(|(p, s)| format!(r#"{{"price":"{}","size":"{}"}}"#, p, s))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let json = |v: &Option<String>| serde_json::to_string(v).unwrap_or_default();
+
+        let summary = format!(
+            r#"{{"market":{},"asset_id":{},"timestamp":"{}","bids":[{}],"asks":[{}],"min_order_size":{},"neg_risk":{},"tick_size":{},"hash":""}}"#,
+            serde_json::to_string(&self.market).unwrap_or_default(),
+            serde_json::to_string(&self.token_id).unwrap_or_default(),
+            self.timestamp,
+            levels(&mut self.bids.iter()),
+            levels(&mut self.asks.iter().rev()),
+            json(&self.min_order_size),
+            serde_json::to_string(&self.neg_risk).unwrap_or_default(),
+            json(&self.tick_size),
+        );
+        hex::encode(Sha1::digest(summary.as_bytes()))
+    }
+
+    /// Check the local book against the last hash the exchange sent.
+    /// `None` when there is nothing to compare or hashes aren't verifiable.
+    pub fn verify_hash(&self) -> Option<bool> {
+        if self.hash_verifiable != Some(true) {
+            return None;
+        }
+        Some(*self.hash.as_ref()? == self.compute_hash())
+    }
+
+    /// Whether a delta stamped `timestamp` arrived after one we already
+    /// applied, i.e. the feed is out of order and we may have missed or
+    /// misordered updates
+    pub fn is_stale(&self, timestamp: Option<u64>) -> bool {
+        timestamp.is_some_and(|ts| ts < self.timestamp)
+    }
+
+    /// Best bid at or above best ask: deltas were lost
+    pub fn is_crossed(&self) -> bool {
+        matches!((self.best_bid(), self.best_ask()), (Some((b, _)), Some((a, _))) if b >= a)
+    }
+
+    /// Set the aggregate size at one level; zero removes it
+    pub fn apply_change(&mut self, side: &Side, price: Decimal, size: Decimal) {
+        let levels = match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        if size.is_zero() {
+            levels.remove(&price);
+        } else {
+            levels.insert(price, size);
+        }
+    }
+
+    /// Record the timestamp and hash carried by `msg`
+    pub fn stamp(&mut self, msg: &Value) {
+        if let Some(ts) = timestamp(msg) {
+            self.timestamp = self.timestamp.max(ts);
+        }
+        if let Some(hash) = msg.get("hash").and_then(|h| h.as_str()) {
+            self.hash = Some(hash.to_string());
+        }
+    }
+
+    pub fn best_bid(&self) -> Option<(Decimal, Decimal)> {
+        self.bids.iter().next_back().map(|(p, s)| (*p, *s))
+    }
+
+    pub fn best_ask(&self) -> Option<(Decimal, Decimal)> {
+        self.asks.iter().next().map(|(p, s)| (*p, *s))
+    }
+
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.best_bid()?.0 + self.best_ask()?.0) / Decimal::TWO)
+    }
+
+    /// Convert for the depth-walking helpers in `execution::orderbook`
+    pub fn to_order_book(&self) -> OrderBook {
+        let f = |(p, s): (&Decimal, &Decimal)| {
+            Level::new(p.to_f64().unwrap_or_default(), s.to_f64().unwrap_or_default())
+        };
+        OrderBook {
+            bids: self.bids.iter().rev().map(f).collect(),
+            asks: self.asks.iter().map(f).collect(),
+        }
+    }
+}
+
+/// Exchange timestamp (unix ms, sent as a string) of a message
+pub fn timestamp(msg: &Value) -> Option<u64> {
+    msg.get("timestamp")
+        .and_then(|t| t.as_str())
+        .and_then(|t| t.parse().ok())
+}
+
+/// `[{price, size}, ...]` → price → size
+fn levels(v: &Value) -> BTreeMap<Decimal, Decimal> {
+    v.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|l| {
+            let price = Decimal::from_str(l.get("price")?.as_str()?).ok()?;
+            let size = Decimal::from_str(l.get("size")?.as_str()?).ok()?;
+            (!size.is_zero()).then_some((price, size))
+        })
+        .collect()
+}
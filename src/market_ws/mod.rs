@@ -0,0 +1,456 @@
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, info, warn};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex, Notify, RwLock};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::domain::order::Side;
+use crate::execution::orderbook::OrderBook;
+use crate::net::{feeds, Connections, Reconnector, SendRetry};
+
+pub mod book;
+pub mod recorder;
+pub mod replay;
+pub mod signals;
+pub mod trades;
+
+pub use book::LocalBook;
+pub use recorder::{run_recorder, Recorder};
+pub use replay::{Replay, ReplayEvent};
+pub use signals::{BookSignals, Signals};
+pub use trades::{MarketTrades, Trade, TradeStats};
+
+/// Public market channel (no auth)
+pub const MARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+const CLOB_API_URL: &str = "https://clob.polymarket.com";
+
+/// Buffered book-update notifications per subscriber
+const UPDATE_BUFFER: usize = 1024;
+
+// ==================================================
+// SHARED BOOKS
+// ==================================================
+
+/// Live books per token, written by `MarketWs` and read by strategies.
+/// Every applied message notifies subscribers with the token id.
+///
+/// A book whose feed shows a gap is flagged `degraded` until a fresh
+/// snapshot replaces it; readers should stop quoting off it meanwhile.
+#[derive(Debug)]
+pub struct MarketBooks {
+    books: RwLock<HashMap<String, LocalBook>>,
+    updates: broadcast::Sender<String>,
+    // Updates after which the local book didn't match the exchange hash
+    hash_mismatches: AtomicU64,
+}
+
+impl Default for MarketBooks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarketBooks {
+    pub fn new() -> Self {
+        Self {
+            books: RwLock::new(HashMap::new()),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+            hash_mismatches: AtomicU64::new(0),
+        }
+    }
+
+    /// Book hash mismatches since startup (each one triggered a resync)
+    pub fn hash_mismatches(&self) -> u64 {
+        self.hash_mismatches.load(Ordering::Relaxed)
+    }
+
+    /// Token ids whose book just changed
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.updates.subscribe()
+    }
+
+    pub async fn get(&self, token_id: &str) -> Option<LocalBook> {
+        self.books.read().await.get(token_id).cloned()
+    }
+
+    /// Snapshot in the shape the depth-walking helpers expect
+    pub async fn order_book(&self, token_id: &str) -> Option<OrderBook> {
+        self.books.read().await.get(token_id).map(|b| b.to_order_book())
+    }
+
+    /// True while a gap on `token_id` is being resynced (or it has no book)
+    pub async fn is_degraded(&self, token_id: &str) -> bool {
+        self.books
+            .read()
+            .await
+            .get(token_id)
+            .is_none_or(|b| b.degraded)
+    }
+
+    /// Flag books as untrustworthy until their next snapshot
+    pub async fn mark_degraded(&self, token_ids: &[String]) {
+        let mut books = self.books.write().await;
+        for token in token_ids {
+            if let Some(book) = books.get_mut(token) {
+                if !book.degraded {
+                    warn!("⚠️  Book {} degraded, resyncing", token);
+                    book.degraded = true;
+                }
+            }
+        }
+        drop(books);
+        for token in token_ids {
+            self.notify(token);
+        }
+    }
+
+    /// Replace a book with a snapshot (`book` event or REST `/book`)
+    pub async fn apply_snapshot(&self, token_id: &str, snapshot: &Value) {
+        let mut books = self.books.write().await;
+        let book = books
+            .entry(token_id.to_string())
+            .or_insert_with(|| LocalBook::new(token_id));
+        let was_verifiable = book.hash_verifiable;
+        book.apply_snapshot(snapshot);
+
+        if book.hash_verifiable == Some(false) && was_verifiable != Some(false) {
+            warn!("⚠️  Can't reproduce book hash for {}, skipping hash checks", token_id);
+        }
+        drop(books);
+        self.notify(token_id);
+    }
+
+    pub async fn best_bid_ask(&self, token_id: &str) -> (Option<Decimal>, Option<Decimal>) {
+        match self.books.read().await.get(token_id) {
+            Some(b) => (b.best_bid().map(|l| l.0), b.best_ask().map(|l| l.0)),
+            None => (None, None),
+        }
+    }
+
+    /// Apply one market-channel message (or an array of them).
+    /// Returns the tokens that showed a gap and need a fresh snapshot.
+    pub async fn apply(&self, msg: &Value) -> Vec<String> {
+        match msg.as_array() {
+            Some(events) => {
+                let mut gaps = Vec::new();
+                for event in events {
+                    gaps.extend(self.apply_event(event).await);
+                }
+                gaps
+            }
+            None => self.apply_event(msg).await,
+        }
+    }
+
+    async fn apply_event(&self, msg: &Value) -> Vec<String> {
+        let asset = msg.get("asset_id").and_then(|a| a.as_str());
+        match msg.get("event_type").and_then(|t| t.as_str()) {
+            Some("book") => {
+                if let Some(asset) = asset {
+                    self.apply_snapshot(asset, msg).await;
+                }
+                vec![]
+            }
+            Some("price_change") => self.apply_price_change(msg, asset).await,
+            _ => vec![],
+        }
+    }
+
+    /// Older payloads carry `changes` under one asset, newer ones
+    /// `price_changes` with an asset (and hash) per entry
+    ///
+    /// A delta older than the book, or one that leaves it crossed,
+    /// means updates were dropped or reordered: the book is flagged
+    /// degraded and returned for a resync.
+    async fn apply_price_change(&self, msg: &Value, asset: Option<&str>) -> Vec<String> {
+        let changes = msg
+            .get("price_changes")
+            .or_else(|| msg.get("changes"))
+            .and_then(|c| c.as_array());
+        let ts = book::timestamp(msg);
+
+        let mut touched: Vec<String> = Vec::new();
+        let mut gaps: Vec<String> = Vec::new();
+        let mut books = self.books.write().await;
+        for change in changes.into_iter().flatten() {
+            let Some(token) = change.get("asset_id").and_then(|a| a.as_str()).or(asset) else {
+                continue;
+            };
+            let Some(book) = books.get_mut(token) else {
+                // A delta before any snapshot: we missed the snapshot
+                debug!("Delta for {} before snapshot", token);
+                if !gaps.iter().any(|t| t == token) {
+                    gaps.push(token.to_string());
+                }
+                continue;
+            };
+            let (Some(side), Some(price), Some(size)) =
+                (side(change), decimal(change, "price"), decimal(change, "size"))
+            else {
+                continue;
+            };
+
+            if book.is_stale(ts) {
+                warn!(
+                    "⚠️  Out-of-order delta for {} ({:?} < {})",
+                    token, ts, book.timestamp
+                );
+                book.degraded = true;
+            } else {
+                book.apply_change(&side, price, size);
+                book.stamp(msg);
+                book.stamp(change);
+                if book.is_crossed() {
+                    warn!("⚠️  Book {} crossed after delta", token);
+                    book.degraded = true;
+                } else if book.verify_hash() == Some(false) {
+                    let n = self.hash_mismatches.fetch_add(1, Ordering::Relaxed) + 1;
+                    warn!(
+                        "🚨 Book hash mismatch for {} (expected {:?}, {} total)",
+                        token, book.hash, n
+                    );
+                    book.degraded = true;
+                }
+            }
+
+            if book.degraded && !gaps.iter().any(|t| t == token) {
+                gaps.push(token.to_string());
+            }
+            if !touched.iter().any(|t| t == token) {
+                touched.push(token.to_string());
+            }
+        }
+        drop(books);
+
+        for token in touched {
+            self.notify(&token);
+        }
+        gaps
+    }
+
+    fn notify(&self, token_id: &str) {
+        // No subscribers is fine
+        let _ = self.updates.send(token_id.to_string());
+    }
+}
+
+// ==================================================
+// MARKET CHANNEL CONNECTION
+// ==================================================
+
+/// Wait after (re)subscribing before books still without a fresh
+/// snapshot are fetched over REST
+const SNAPSHOT_GRACE: Duration = Duration::from_secs(5);
+
+/// Keeps `books` in sync with the market channel for a set of tokens,
+/// and `trades` with the trades printed on it.
+///
+/// Gaps trigger a REST snapshot for the affected token. A reconnect
+/// degrades every book and resubscribes to every token so far; books
+/// the channel's fresh snapshots haven't restored within a few seconds
+/// are fetched over REST.
+pub struct MarketWs {
+    url: String,
+    clob_url: String,
+    // Every token subscribed to, in order
+    token_ids: std::sync::Mutex<Vec<String>>,
+    added: Notify,
+    books: Arc<MarketBooks>,
+    trades: Arc<MarketTrades>,
+    connections: Arc<Connections>,
+    // Tokens with a REST resync in flight
+    resyncing: Arc<Mutex<HashSet<String>>>,
+}
+
+impl MarketWs {
+    pub fn new(
+        url: impl Into<String>,
+        clob_url: impl Into<String>,
+        token_ids: Vec<String>,
+        books: Arc<MarketBooks>,
+    ) -> Self {
+        Self {
+            url: url.into(),
+            clob_url: clob_url.into(),
+            token_ids: std::sync::Mutex::new(token_ids),
+            added: Notify::new(),
+            books,
+            trades: Arc::new(MarketTrades::default()),
+            connections: Arc::new(Connections::new()),
+            resyncing: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Record trades into a shared `MarketTrades` instead of a private one
+    pub fn with_trades(mut self, trades: Arc<MarketTrades>) -> Self {
+        self.trades = trades;
+        self
+    }
+
+    /// Publish connection changes on a shared hub
+    pub fn with_connections(mut self, connections: Arc<Connections>) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    /// `MARKET_WS_URL` (default the public market channel),
+    /// `CLOB_API_URL` for resync snapshots and `TRADE_STATS_WINDOW_SECS`
+    pub fn from_env(token_ids: Vec<String>, books: Arc<MarketBooks>) -> Self {
+        let url = std::env::var("MARKET_WS_URL").unwrap_or_else(|_| MARKET_WS_URL.to_string());
+        let clob_url = std::env::var("CLOB_API_URL").unwrap_or_else(|_| CLOB_API_URL.to_string());
+        Self::new(url, clob_url, token_ids, books).with_trades(Arc::new(MarketTrades::from_env()))
+    }
+
+    pub fn books(&self) -> Arc<MarketBooks> {
+        self.books.clone()
+    }
+
+    pub fn trades(&self) -> Arc<MarketTrades> {
+        self.trades.clone()
+    }
+
+    pub fn token_ids(&self) -> Vec<String> {
+        self.token_ids.lock().unwrap().clone()
+    }
+
+    /// Follow more tokens, on the live connection and every later one
+    pub fn add_tokens(&self, token_ids: &[String]) {
+        let mut tokens = self.token_ids.lock().unwrap();
+        for token in token_ids {
+            if !tokens.contains(token) {
+                tokens.push(token.clone());
+            }
+        }
+        drop(tokens);
+        self.added.notify_one();
+    }
+
+    /// Run forever
+    pub async fn run(&self) {
+        let mut link = Reconnector::new(feeds::MARKET, self.connections.clone());
+        loop {
+            let result = self.stream(&mut link).await;
+            // Whatever happened while disconnected is lost
+            self.books.mark_degraded(&self.token_ids()).await;
+            link.disconnected(result).await;
+        }
+    }
+
+    /// Refetch `token_id` over REST in the background (once at a time)
+    fn resync(&self, token_id: String) {
+        spawn_resync(self.books.clone(), self.resyncing.clone(), self.clob_url.clone(), token_id);
+    }
+
+    /// After `SNAPSHOT_GRACE`, refetch whichever of `tokens` the channel
+    /// hasn't sent a fresh snapshot for
+    fn resync_stale(&self, tokens: Vec<String>) {
+        let books = self.books.clone();
+        let resyncing = self.resyncing.clone();
+        let clob_url = self.clob_url.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(SNAPSHOT_GRACE).await;
+            for token in tokens {
+                if books.is_degraded(&token).await {
+                    spawn_resync(books.clone(), resyncing.clone(), clob_url.clone(), token);
+                }
+            }
+        });
+    }
+
+    async fn stream(&self, link: &mut Reconnector) -> Result<()> {
+        link.connecting();
+        let (ws, _) = connect_async(self.url.as_str()).await?;
+        let (mut write, mut read) = ws.split();
+
+        // The channel answers with a `book` snapshot per token
+        let mut subscribed = self.token_ids();
+        let sub = json!({ "type": "market", "assets_ids": subscribed });
+        write.send(Message::Text(sub.to_string())).await?;
+        info!("📡 Market WS subscribed to {} token(s)", subscribed.len());
+        link.connected();
+        self.resync_stale(subscribed.clone());
+
+        let mut hb = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            tokio::select! {
+                _ = hb.tick() => {
+                    write.send(Message::Text("PING".to_string())).await?;
+                }
+                _ = self.added.notified() => {
+                    let new: Vec<String> = self
+                        .token_ids()
+                        .into_iter()
+                        .filter(|t| !subscribed.contains(t))
+                        .collect();
+                    if !new.is_empty() {
+                        let sub = json!({ "assets_ids": new, "operation": "subscribe" });
+                        write.send(Message::Text(sub.to_string())).await?;
+                        info!("📡 Market WS subscribed to {} more token(s)", new.len());
+                        self.resync_stale(new.clone());
+                        subscribed.extend(new);
+                    }
+                }
+                msg = read.next() => {
+                    let msg = msg.ok_or_else(|| anyhow!("WS closed"))??;
+                    if let Message::Text(txt) = msg {
+                        if let Ok(v) = serde_json::from_str::<Value>(&txt) {
+                            self.trades.on_market_message(&v).await;
+                            for token in self.books.apply(&v).await {
+                                self.books.mark_degraded(std::slice::from_ref(&token)).await;
+                                self.resync(token);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Refetch `token_id` over REST in the background, unless a refetch of
+/// it is already in flight
+fn spawn_resync(books: Arc<MarketBooks>, resyncing: Arc<Mutex<HashSet<String>>>, clob_url: String, token_id: String) {
+    tokio::spawn(async move {
+        if !resyncing.lock().await.insert(token_id.clone()) {
+            return;
+        }
+        match fetch_snapshot(&clob_url, &token_id).await {
+            Ok(snapshot) => {
+                books.apply_snapshot(&token_id, &snapshot).await;
+                info!("✅ Book {} resynced from REST", token_id);
+            }
+            // Still degraded; the next gap retries
+            Err(e) => warn!("⚠️  Resync of {} failed: {}", token_id, e),
+        }
+        resyncing.lock().await.remove(&token_id);
+    });
+}
+
+/// Raw `GET /book`: same shape as the channel's `book` event
+async fn fetch_snapshot(clob_url: &str, token_id: &str) -> Result<Value> {
+    let url = format!("{}/book?token_id={}", clob_url, token_id);
+    let resp = reqwest::Client::new().get(&url).send_retry().await?;
+    if !resp.status().is_success() {
+        return Err(anyhow!("Failed to fetch book: {}", resp.status()));
+    }
+    Ok(resp.json().await?)
+}
+
+fn decimal(v: &Value, key: &str) -> Option<Decimal> {
+    v.get(key).and_then(|x| x.as_str()).and_then(|s| Decimal::from_str(s).ok())
+}
+
+fn side(v: &Value) -> Option<Side> {
+    match v.get("side").and_then(|s| s.as_str())?.to_uppercase().as_str() {
+        "BUY" => Some(Side::Buy),
+        "SELL" => Some(Side::Sell),
+        _ => None,
+    }
+}
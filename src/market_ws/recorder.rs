@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use log::{info, warn};
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::net::{feeds, Connections, Reconnector};
+
+/// Column order of every recording
+pub const CSV_HEADER: &str = "seq,recv_ms,exchange_ms,event,market,token_id,side,price,size,hash";
+
+// ==================================================
+// MARKET DATA RECORDER
+// ==================================================
+
+/// Archives market-channel messages to rotating CSV files, one row per
+/// book level, delta or trade:
+///
+/// - `book`: one row per level of the snapshot (a single row with an
+///   empty side for an empty book)
+/// - `price_change`: one row per changed level
+/// - `trade`: one row per `last_trade_price`, side is the taker's
+///
+/// Rows from the same message share `seq`, so a snapshot can be
+/// rebuilt whole. Prices and sizes are kept as the exchange sent them.
+pub struct Recorder {
+    dir: PathBuf,
+    rotate: Duration,
+    file: Option<(BufWriter<File>, Instant)>,
+    seq: u64,
+}
+
+impl Recorder {
+    pub fn new(dir: impl Into<PathBuf>, rotate: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            rotate,
+            file: None,
+            seq: 0,
+        }
+    }
+
+    /// `RECORDER_DIR` (default `data/market`) and
+    /// `RECORDER_ROTATE_SECS` (default 3600)
+    pub fn from_env() -> Self {
+        let dir = std::env::var("RECORDER_DIR").unwrap_or_else(|_| "data/market".to_string());
+        let secs = std::env::var("RECORDER_ROTATE_SECS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()
+            .unwrap_or(3600);
+        Self::new(dir, Duration::from_secs(secs))
+    }
+
+    /// Append one market-channel message (or an array of them)
+    pub fn record(&mut self, msg: &Value) -> Result<()> {
+        if let Some(events) = msg.as_array() {
+            for event in events {
+                self.record(event)?;
+            }
+            return Ok(());
+        }
+
+        let rows = rows(msg);
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        self.seq += 1;
+        let seq = self.seq;
+        let recv_ms = chrono::Utc::now().timestamp_millis();
+        let out = self.writer()?;
+        for row in rows {
+            writeln!(out, "{},{},{}", seq, recv_ms, row)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some((out, _)) = self.file.as_mut() {
+            out.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Current file, rotating to a new one once it is `rotate` old
+    fn writer(&mut self) -> Result<&mut BufWriter<File>> {
+        if self
+            .file
+            .as_ref()
+            .is_some_and(|(_, opened)| opened.elapsed() >= self.rotate)
+        {
+            self.flush()?;
+            self.file = None;
+        }
+
+        if self.file.is_none() {
+            fs::create_dir_all(&self.dir)?;
+            let path = self.dir.join(format!(
+                "market_{}.csv",
+                chrono::Utc::now().format("%Y%m%d_%H%M%S")
+            ));
+            let mut out = BufWriter::new(File::create(&path)?);
+            writeln!(out, "{}", CSV_HEADER)?;
+            info!("💾 Recording market data to {}", path.display());
+            self.file = Some((out, Instant::now()));
+        }
+
+        Ok(&mut self.file.as_mut().ok_or_else(|| anyhow!("No recording open"))?.0)
+    }
+}
+
+/// Rows for one event, without the `seq,recv_ms` prefix
+fn rows(msg: &Value) -> Vec<String> {
+    let text = |v: &Value, k: &str| v.get(k).and_then(|x| x.as_str()).unwrap_or_default().to_string();
+    let exchange_ms = text(msg, "timestamp");
+    let market = text(msg, "market");
+    let asset = text(msg, "asset_id");
+    let row = |event: &str, token: &str, side: &str, price: &str, size: &str, hash: &str| {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            exchange_ms, event, market, token, side, price, size, hash
+        )
+    };
+
+    match msg.get("event_type").and_then(|t| t.as_str()) {
+        Some("book") => {
+            let hash = text(msg, "hash");
+            let side = |a: &str, b: &str| {
+                msg.get(a)
+                    .or_else(|| msg.get(b))
+                    .and_then(|l| l.as_array())
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            let mut out = Vec::new();
+            for (name, levels) in [("BUY", side("bids", "buys")), ("SELL", side("asks", "sells"))] {
+                for l in &levels {
+                    out.push(row("book", &asset, name, &text(l, "price"), &text(l, "size"), &hash));
+                }
+            }
+            if out.is_empty() {
+                out.push(row("book", &asset, "", "", "", &hash));
+            }
+            out
+        }
+        Some("price_change") => {
+            // Older payloads carry `changes` under one asset, newer ones
+            // `price_changes` with an asset (and hash) per entry
+            let changes = msg
+                .get("price_changes")
+                .or_else(|| msg.get("changes"))
+                .and_then(|c| c.as_array());
+            changes
+                .into_iter()
+                .flatten()
+                .map(|c| {
+                    let token = c.get("asset_id").and_then(|a| a.as_str()).unwrap_or(&asset);
+                    let hash = c.get("hash").or_else(|| msg.get("hash")).and_then(|h| h.as_str());
+                    row(
+                        "price_change",
+                        token,
+                        &text(c, "side").to_uppercase(),
+                        &text(c, "price"),
+                        &text(c, "size"),
+                        hash.unwrap_or_default(),
+                    )
+                })
+                .collect()
+        }
+        Some("last_trade_price") => vec![row(
+            "trade",
+            &asset,
+            &text(msg, "side").to_uppercase(),
+            &text(msg, "price"),
+            &text(msg, "size"),
+            "",
+        )],
+        _ => vec![],
+    }
+}
+
+/// Record the market channel for `token_ids` until aborted, reconnecting
+/// on error
+pub async fn run_recorder(
+    ws_url: String,
+    token_ids: Vec<String>,
+    mut recorder: Recorder,
+    connections: Arc<Connections>,
+) {
+    let mut link = Reconnector::new(feeds::RECORDER, connections);
+    loop {
+        let result = stream(&ws_url, &token_ids, &mut recorder, &mut link).await;
+        if let Err(e) = recorder.flush() {
+            warn!("⚠️  Recorder flush failed: {}", e);
+        }
+        link.disconnected(result).await;
+    }
+}
+
+async fn stream(ws_url: &str, token_ids: &[String], recorder: &mut Recorder, link: &mut Reconnector) -> Result<()> {
+    link.connecting();
+    let (ws, _) = connect_async(ws_url).await?;
+    let (mut write, mut read) = ws.split();
+
+    let sub = json!({ "type": "market", "assets_ids": token_ids });
+    write.send(Message::Text(sub.to_string())).await?;
+    info!("📡 Recorder subscribed to {} token(s)", token_ids.len());
+    link.connected();
+
+    let mut hb = tokio::time::interval(Duration::from_secs(10));
+    loop {
+        tokio::select! {
+            _ = hb.tick() => {
+                write.send(Message::Text("PING".to_string())).await?;
+                recorder.flush()?;
+            }
+            msg = read.next() => {
+                let msg = msg.ok_or_else(|| anyhow!("WS closed"))??;
+                if let Message::Text(txt) = msg {
+                    if let Ok(v) = serde_json::from_str::<Value>(&txt) {
+                        recorder.record(&v)?;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use anyhow::{anyhow, Result};
+use log::info;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::recorder::CSV_HEADER;
+use super::{MarketBooks, MarketTrades};
+
+// ==================================================
+// RECORDED MARKET DATA REPLAY
+// ==================================================
+
+/// One recorded market-channel message, rebuilt in the shape the live
+/// feed delivers it
+#[derive(Debug, Clone)]
+pub struct ReplayEvent {
+    pub seq: u64,
+    /// When the recorder received it (unix ms)
+    pub recv_ms: u64,
+    pub msg: Value,
+}
+
+/// Plays recordings written by `Recorder` through `MarketBooks` and
+/// `MarketTrades`, exactly as `MarketWs` feeds them live.
+///
+/// Events come out in file order. With no `speed` set they are applied
+/// back to back, so a strategy stepping the replay sees the same
+/// sequence of books on every run.
+///
+/// Recorded hashes are not replayed: snapshots don't keep every field
+/// the exchange hashes, so the books would never verify.
+#[derive(Debug)]
+pub struct Replay {
+    events: Vec<ReplayEvent>,
+    next: usize,
+    speed: Option<f64>,
+}
+
+impl Replay {
+    pub fn new(events: Vec<ReplayEvent>) -> Self {
+        Self {
+            events,
+            next: 0,
+            speed: None,
+        }
+    }
+
+    /// Load recordings in the order given
+    pub fn from_files<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let mut events = Vec::new();
+        for path in paths {
+            events.extend(read_recording(path.as_ref())?);
+        }
+        Ok(Self::new(events))
+    }
+
+    /// Load every `market_*.csv` in `dir`; file names sort by start time
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir.as_ref())?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("market_") && n.ends_with(".csv"))
+            })
+            .collect();
+        paths.sort();
+        info!("📼 Replaying {} recording(s) from {}", paths.len(), dir.as_ref().display());
+        Self::from_files(&paths)
+    }
+
+    /// Pace events by their recorded spacing, `speed` times faster than
+    /// real time
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = (speed > 0.0).then_some(speed);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Events not yet applied
+    pub fn remaining(&self) -> usize {
+        self.events.len() - self.next
+    }
+
+    /// Receive time of the next event to apply
+    pub fn next_ms(&self) -> Option<u64> {
+        self.events.get(self.next).map(|e| e.recv_ms)
+    }
+
+    /// Apply the next event to `books` and `trades` and return it, so the
+    /// caller can run its strategy (or feed TCA, queue tracking, ...)
+    /// before stepping again. `None` once the recording is exhausted.
+    pub async fn step(&mut self, books: &MarketBooks, trades: &MarketTrades) -> Option<&ReplayEvent> {
+        let index = self.next;
+        if index >= self.events.len() {
+            return None;
+        }
+        self.next += 1;
+
+        let event = &self.events[index];
+        if let (Some(speed), Some(prev)) = (self.speed, index.checked_sub(1)) {
+            let gap = event.recv_ms.saturating_sub(self.events[prev].recv_ms);
+            tokio::time::sleep(Duration::from_millis(gap).div_f64(speed)).await;
+        }
+
+        // Trades are stamped with exchange time; age the window on the same clock
+        trades.set_clock(super::book::timestamp(&event.msg).unwrap_or(event.recv_ms));
+        trades.on_market_message(&event.msg).await;
+        // A replayed gap has no REST snapshot to resync from; the book
+        // stays degraded until the recording's next snapshot
+        books.apply(&event.msg).await;
+        Some(event)
+    }
+
+    /// Apply every remaining event
+    pub async fn run(&mut self, books: &MarketBooks, trades: &MarketTrades) {
+        while self.step(books, trades).await.is_some() {}
+    }
+
+    /// Start over from the first event
+    pub fn rewind(&mut self) {
+        self.next = 0;
+    }
+
+    /// Every event, applied or not
+    pub fn events(&self) -> &[ReplayEvent] {
+        &self.events
+    }
+
+    /// First and last receive time in the recording
+    pub fn span(&self) -> Option<(u64, u64)> {
+        let first = self.events.iter().map(|e| e.recv_ms).min()?;
+        let last = self.events.iter().map(|e| e.recv_ms).max()?;
+        Some((first, last))
+    }
+
+    /// A new replay of the events received in `[from_ms, to_ms)`. Books
+    /// start empty, so a window only sees a market once the recording
+    /// has a snapshot for it inside the window.
+    pub fn window(&self, from_ms: u64, to_ms: u64) -> Replay {
+        let events = self
+            .events
+            .iter()
+            .filter(|e| e.recv_ms >= from_ms && e.recv_ms < to_ms)
+            .cloned()
+            .collect();
+        Replay {
+            events,
+            next: 0,
+            speed: self.speed,
+        }
+    }
+}
+
+/// Parsed CSV row: seq, recv_ms, exchange_ms, event, market, token, side, price, size
+struct Row<'a> {
+    seq: u64,
+    recv_ms: u64,
+    exchange_ms: &'a str,
+    event: &'a str,
+    market: &'a str,
+    token: &'a str,
+    side: &'a str,
+    price: &'a str,
+    size: &'a str,
+}
+
+impl<'a> Row<'a> {
+    fn parse(line: &'a str) -> Result<Self> {
+        let f: Vec<&str> = line.split(',').collect();
+        if f.len() != CSV_HEADER.split(',').count() {
+            return Err(anyhow!("Malformed recording row: {}", line));
+        }
+        Ok(Self {
+            seq: f[0].parse()?,
+            recv_ms: f[1].parse()?,
+            exchange_ms: f[2],
+            event: f[3],
+            market: f[4],
+            token: f[5],
+            side: f[6],
+            price: f[7],
+            size: f[8],
+        })
+    }
+
+    fn level(&self) -> Value {
+        json!({ "price": self.price, "size": self.size })
+    }
+}
+
+/// Rebuild the market-channel messages stored in one recording
+pub fn read_recording(path: &Path) -> Result<Vec<ReplayEvent>> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let mut lines = text.lines();
+    if lines.next() != Some(CSV_HEADER) {
+        return Err(anyhow!("{} is not a market recording", path.display()));
+    }
+
+    let rows = lines
+        .filter(|l| !l.is_empty())
+        .map(Row::parse)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut events = Vec::new();
+    let mut start = 0;
+    while start < rows.len() {
+        // Rows of one message are contiguous and share `seq`
+        let end = rows[start..]
+            .iter()
+            .position(|r| r.seq != rows[start].seq)
+            .map_or(rows.len(), |n| start + n);
+        if let Some(msg) = message(&rows[start..end]) {
+            events.push(ReplayEvent {
+                seq: rows[start].seq,
+                recv_ms: rows[start].recv_ms,
+                msg,
+            });
+        }
+        start = end;
+    }
+    Ok(events)
+}
+
+fn message(rows: &[Row]) -> Option<Value> {
+    let first = rows.first()?;
+    let mut msg = Map::new();
+    msg.insert("market".into(), json!(first.market));
+    msg.insert("timestamp".into(), json!(first.exchange_ms));
+
+    match first.event {
+        "book" => {
+            let side = |name: &str| -> Vec<Value> {
+                rows.iter().filter(|r| r.side == name).map(Row::level).collect()
+            };
+            msg.insert("event_type".into(), json!("book"));
+            msg.insert("asset_id".into(), json!(first.token));
+            msg.insert("bids".into(), json!(side("BUY")));
+            msg.insert("asks".into(), json!(side("SELL")));
+        }
+        "price_change" => {
+            let changes: Vec<Value> = rows
+                .iter()
+                .map(|r| json!({ "asset_id": r.token, "side": r.side, "price": r.price, "size": r.size }))
+                .collect();
+            msg.insert("event_type".into(), json!("price_change"));
+            msg.insert("price_changes".into(), json!(changes));
+        }
+        "trade" => {
+            msg.insert("event_type".into(), json!("last_trade_price"));
+            msg.insert("asset_id".into(), json!(first.token));
+            msg.insert("side".into(), json!(first.side));
+            msg.insert("price".into(), json!(first.price));
+            msg.insert("size".into(), json!(first.size));
+        }
+        _ => return None,
+    }
+    Some(Value::Object(msg))
+}
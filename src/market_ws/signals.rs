@@ -0,0 +1,251 @@
+use log::warn;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Mutex;
+
+use super::{LocalBook, MarketBooks, MarketTrades};
+use crate::domain::order::Side;
+
+// ==================================================
+// BOOK SIGNALS
+// ==================================================
+
+/// Microstructure signals for one token as of its last book update
+#[derive(Debug, Clone, Default)]
+pub struct BookSignals {
+    pub token_id: String,
+    pub mid: f64,
+    /// Touch prices weighted by the opposite side's touch size: leans
+    /// toward the side that is about to be taken out
+    pub microprice: f64,
+    /// Microprice over the top `depth_levels` levels, using each side's
+    /// VWAP and total size
+    pub depth_microprice: f64,
+    /// (bid size - ask size) / total over the top `depth_levels`, in [-1, 1]
+    pub imbalance: f64,
+    /// Order-flow imbalance at the touch summed over the flow window:
+    /// size added to bids / removed from asks minus the reverse
+    pub ofi: f64,
+    /// Taker buy minus taker sell volume over the trade window, when
+    /// trades are being tracked
+    pub trade_flow: Option<f64>,
+    /// Exchange time of the book these were computed from (unix ms)
+    pub at_ms: u64,
+}
+
+/// Touch (best bid, bid size, best ask, ask size)
+type Touch = (f64, f64, f64, f64);
+
+#[derive(Debug, Default)]
+struct FlowState {
+    last: Option<Touch>,
+    // (exchange ms, OFI contribution), oldest first
+    events: VecDeque<(u64, f64)>,
+}
+
+/// Signal API over the live books in `MarketBooks`.
+///
+/// Level signals are computed on demand from the current book. OFI needs
+/// every change of the touch, so `run` must follow the book updates.
+/// Windows are measured on exchange time, so replays give the same
+/// values as the live session did.
+pub struct Signals {
+    books: Arc<MarketBooks>,
+    trades: Option<Arc<MarketTrades>>,
+    depth_levels: usize,
+    flow_window: Duration,
+    flow: Mutex<HashMap<String, FlowState>>,
+}
+
+impl Signals {
+    pub fn new(books: Arc<MarketBooks>, depth_levels: usize, flow_window: Duration) -> Self {
+        Self {
+            books,
+            trades: None,
+            depth_levels: depth_levels.max(1),
+            flow_window,
+            flow: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `SIGNAL_DEPTH_LEVELS` (default 5) and `SIGNAL_FLOW_WINDOW_SECS`
+    /// (default 30)
+    pub fn from_env(books: Arc<MarketBooks>) -> Self {
+        let levels = std::env::var("SIGNAL_DEPTH_LEVELS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .unwrap_or(5);
+        let secs = std::env::var("SIGNAL_FLOW_WINDOW_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+        Self::new(books, levels, Duration::from_secs(secs))
+    }
+
+    /// Include taker flow from `trades` in the signals
+    pub fn with_trades(mut self, trades: Arc<MarketTrades>) -> Self {
+        self.trades = Some(trades);
+        self
+    }
+
+    /// Follow book updates forever, accumulating order-flow imbalance
+    pub async fn run(&self) {
+        let mut updates = self.books.subscribe();
+        loop {
+            match updates.recv().await {
+                Ok(token) => self.on_update(&token).await,
+                Err(RecvError::Lagged(n)) => {
+                    warn!("⚠️  Signals lagged {} book updates, OFI may be off", n)
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    }
+
+    /// Account for a change to `token_id`'s book
+    pub async fn on_update(&self, token_id: &str) {
+        let Some(book) = self.books.get(token_id).await else {
+            return;
+        };
+        let mut flow = self.flow.lock().await;
+        let state = flow.entry(token_id.to_string()).or_default();
+
+        // Don't diff across a gap: the next clean touch starts afresh
+        if book.degraded {
+            state.last = None;
+            return;
+        }
+        let Some(touch) = touch(&book) else {
+            state.last = None;
+            return;
+        };
+
+        if let Some(prev) = state.last.replace(touch) {
+            let e = ofi(prev, touch);
+            if e != 0.0 {
+                state.events.push_back((book.timestamp, e));
+            }
+        }
+        let cutoff = book.timestamp.saturating_sub(self.flow_window.as_millis() as u64);
+        while state.events.front().is_some_and(|(ts, _)| *ts < cutoff) {
+            state.events.pop_front();
+        }
+    }
+
+    /// Current signals for `token_id`; `None` without a two-sided,
+    /// non-degraded book
+    pub async fn get(&self, token_id: &str) -> Option<BookSignals> {
+        let book = self.books.get(token_id).await.filter(|b| !b.degraded)?;
+        let (bid, bid_size, ask, ask_size) = touch(&book)?;
+
+        let cutoff = book.timestamp.saturating_sub(self.flow_window.as_millis() as u64);
+        let ofi = self
+            .flow
+            .lock()
+            .await
+            .get(token_id)
+            .map(|s| {
+                s.events
+                    .iter()
+                    .filter(|(ts, _)| *ts >= cutoff)
+                    .map(|(_, e)| e)
+                    .sum()
+            })
+            .unwrap_or_default();
+
+        let trade_flow = match &self.trades {
+            Some(trades) => Some(
+                trades
+                    .stats(token_id)
+                    .await
+                    .map(|s| s.side_volume(&Side::Buy) - s.side_volume(&Side::Sell))
+                    .unwrap_or_default(),
+            ),
+            None => None,
+        };
+
+        Some(BookSignals {
+            token_id: token_id.to_string(),
+            mid: (bid + ask) / 2.0,
+            microprice: weighted_mid(bid, bid_size, ask, ask_size),
+            depth_microprice: depth_microprice(&book, self.depth_levels)?,
+            imbalance: imbalance(&book, self.depth_levels)?,
+            ofi,
+            trade_flow,
+            at_ms: book.timestamp,
+        })
+    }
+}
+
+/// Book imbalance over the top `levels` of each side, in [-1, 1];
+/// positive when bids outweigh asks
+pub fn imbalance(book: &LocalBook, levels: usize) -> Option<f64> {
+    let (_, bids) = side_depth(book.bids.iter().rev(), levels)?;
+    let (_, asks) = side_depth(book.asks.iter(), levels)?;
+    Some((bids - asks) / (bids + asks))
+}
+
+/// Microprice from each side's VWAP and total size over the top `levels`
+pub fn depth_microprice(book: &LocalBook, levels: usize) -> Option<f64> {
+    let (bid, bid_size) = side_depth(book.bids.iter().rev(), levels)?;
+    let (ask, ask_size) = side_depth(book.asks.iter(), levels)?;
+    Some(weighted_mid(bid, bid_size, ask, ask_size))
+}
+
+/// Touch-level microprice
+pub fn microprice(book: &LocalBook) -> Option<f64> {
+    let (bid, bid_size, ask, ask_size) = touch(book)?;
+    Some(weighted_mid(bid, bid_size, ask, ask_size))
+}
+
+fn weighted_mid(bid: f64, bid_size: f64, ask: f64, ask_size: f64) -> f64 {
+    let total = bid_size + ask_size;
+    if total <= 0.0 {
+        return (bid + ask) / 2.0;
+    }
+    (bid * ask_size + ask * bid_size) / total
+}
+
+/// (VWAP, total size) of the first `levels` levels, best first
+fn side_depth<'a>(
+    levels: impl Iterator<Item = (&'a Decimal, &'a Decimal)>,
+    n: usize,
+) -> Option<(f64, f64)> {
+    let (notional, size) = levels
+        .take(n)
+        .map(|(p, s)| (p.to_f64().unwrap_or_default(), s.to_f64().unwrap_or_default()))
+        .fold((0.0, 0.0), |(n, t), (p, s)| (n + p * s, t + s));
+    (size > 0.0).then(|| (notional / size, size))
+}
+
+fn touch(book: &LocalBook) -> Option<Touch> {
+    let f = |d: Decimal| d.to_f64().unwrap_or_default();
+    let (bid, bid_size) = book.best_bid()?;
+    let (ask, ask_size) = book.best_ask()?;
+    Some((f(bid), f(bid_size), f(ask), f(ask_size)))
+}
+
+/// Cont–Kukanov–Stoikov OFI contribution of one touch change
+fn ofi(prev: Touch, cur: Touch) -> f64 {
+    let (pb, pq, pa, pr) = prev;
+    let (b, q, a, r) = cur;
+
+    let mut e = 0.0;
+    if b >= pb {
+        e += q;
+    }
+    if b <= pb {
+        e -= pq;
+    }
+    if a <= pa {
+        e -= r;
+    }
+    if a >= pa {
+        e += pr;
+    }
+    e
+}
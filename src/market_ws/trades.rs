@@ -0,0 +1,227 @@
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+use crate::domain::order::Side;
+
+/// Trailing window for rolling trade stats unless configured
+const DEFAULT_WINDOW_SECS: u64 = 300;
+
+// ==================================================
+// TRADE TAPE
+// ==================================================
+
+/// One market trade from the `last_trade_price` event
+#[derive(Debug, Clone)]
+pub struct Trade {
+    pub price: f64,
+    pub size: f64,
+    /// Taker side, when the event carries it
+    pub side: Option<Side>,
+    pub at_ms: u64,
+}
+
+/// Last trade and the trades inside the rolling window for one token
+#[derive(Debug, Clone)]
+pub struct TradeStats {
+    pub token_id: String,
+    pub last: Option<Trade>,
+    window: Duration,
+    // Replay time the window ends at; wall clock when unset
+    clock: Option<u64>,
+    // Oldest first
+    trades: VecDeque<Trade>,
+}
+
+impl TradeStats {
+    fn new(token_id: &str, window: Duration) -> Self {
+        Self {
+            token_id: token_id.to_string(),
+            last: None,
+            window,
+            clock: None,
+            trades: VecDeque::new(),
+        }
+    }
+
+    fn record(&mut self, trade: Trade) {
+        if self.last.as_ref().is_none_or(|l| trade.at_ms >= l.at_ms) {
+            self.last = Some(trade.clone());
+        }
+        self.trades.push_back(trade);
+        let cutoff = self.cutoff();
+        while self.trades.front().is_some_and(|t| t.at_ms < cutoff) {
+            self.trades.pop_front();
+        }
+    }
+
+    fn cutoff(&self) -> u64 {
+        self.clock
+            .unwrap_or_else(now_ms)
+            .saturating_sub(self.window.as_millis() as u64)
+    }
+
+    /// Trades still inside the window as of now
+    pub fn recent(&self) -> impl Iterator<Item = &Trade> {
+        let cutoff = self.cutoff();
+        self.trades.iter().filter(move |t| t.at_ms >= cutoff)
+    }
+
+    pub fn last_price(&self) -> Option<f64> {
+        self.last.as_ref().map(|t| t.price)
+    }
+
+    /// Tokens traded over the window
+    pub fn volume(&self) -> f64 {
+        self.recent().map(|t| t.size).sum()
+    }
+
+    /// USDC traded over the window
+    pub fn notional(&self) -> f64 {
+        self.recent().map(|t| t.size * t.price).sum()
+    }
+
+    /// Tokens traded over the window where the taker was on `side`
+    pub fn side_volume(&self, side: &Side) -> f64 {
+        self.recent()
+            .filter(|t| t.side.as_ref() == Some(side))
+            .map(|t| t.size)
+            .sum()
+    }
+
+    /// Trades per minute over the window
+    pub fn trade_rate(&self) -> f64 {
+        let mins = self.window.as_secs_f64() / 60.0;
+        if mins <= 0.0 {
+            return 0.0;
+        }
+        self.recent().count() as f64 / mins
+    }
+
+    pub fn vwap(&self) -> Option<f64> {
+        let volume = self.volume();
+        (volume > 0.0).then(|| self.notional() / volume)
+    }
+}
+
+/// Per-token trade stats fed by the market channel's
+/// `last_trade_price` events. Shared with strategies and risk checks.
+#[derive(Debug)]
+pub struct MarketTrades {
+    window: Duration,
+    stats: RwLock<HashMap<String, TradeStats>>,
+    // Unix ms set by a replay; 0 = wall clock
+    clock: AtomicU64,
+}
+
+impl Default for MarketTrades {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_WINDOW_SECS))
+    }
+}
+
+impl MarketTrades {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            stats: RwLock::new(HashMap::new()),
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    /// `TRADE_STATS_WINDOW_SECS` (default 300)
+    pub fn from_env() -> Self {
+        let secs = std::env::var("TRADE_STATS_WINDOW_SECS")
+            .unwrap_or_else(|_| DEFAULT_WINDOW_SECS.to_string())
+            .parse()
+            .unwrap_or(DEFAULT_WINDOW_SECS);
+        Self::new(Duration::from_secs(secs))
+    }
+
+    /// Measure windows up to `at_ms` instead of now, so recorded trades
+    /// aren't aged out against the wall clock during a replay
+    pub fn set_clock(&self, at_ms: u64) {
+        self.clock.store(at_ms, Ordering::Relaxed);
+    }
+
+    fn clock(&self) -> Option<u64> {
+        Some(self.clock.load(Ordering::Relaxed)).filter(|c| *c > 0)
+    }
+
+    pub async fn record(&self, token_id: &str, trade: Trade) {
+        let mut stats = self.stats.write().await;
+        let stats = stats
+            .entry(token_id.to_string())
+            .or_insert_with(|| TradeStats::new(token_id, self.window));
+        stats.clock = self.clock();
+        stats.record(trade);
+    }
+
+    /// Feed a market-channel message; only `last_trade_price` events
+    /// are used. Messages may arrive singly or batched in an array.
+    pub async fn on_market_message(&self, msg: &Value) {
+        let events: Vec<&Value> = match msg.as_array() {
+            Some(events) => events.iter().collect(),
+            None => vec![msg],
+        };
+
+        for event in events {
+            if event.get("event_type").and_then(|t| t.as_str()) != Some("last_trade_price") {
+                continue;
+            }
+            let field = |k: &str| event.get(k).and_then(|v| v.as_str());
+            let (Some(token), Some(price), Some(size)) = (
+                field("asset_id"),
+                field("price").and_then(|p| p.parse().ok()),
+                field("size").and_then(|s| s.parse().ok()),
+            ) else {
+                continue;
+            };
+            let side = match field("side").map(str::to_uppercase).as_deref() {
+                Some("BUY") => Some(Side::Buy),
+                Some("SELL") => Some(Side::Sell),
+                _ => None,
+            };
+            let at_ms = field("timestamp")
+                .and_then(|t| t.parse().ok())
+                .unwrap_or_else(now_ms);
+
+            self.record(token, Trade { price, size, side, at_ms }).await;
+        }
+    }
+
+    pub async fn stats(&self, token_id: &str) -> Option<TradeStats> {
+        let mut stats = self.stats.read().await.get(token_id).cloned()?;
+        stats.clock = self.clock();
+        Some(stats)
+    }
+
+    pub async fn last_price(&self, token_id: &str) -> Option<f64> {
+        self.stats.read().await.get(token_id)?.last_price()
+    }
+
+    /// Tokens traded over the window; zero for tokens with no trades
+    pub async fn volume(&self, token_id: &str) -> f64 {
+        self.stats(token_id)
+            .await
+            .map(|s| s.volume())
+            .unwrap_or_default()
+    }
+
+    /// Trades per minute over the window; zero for tokens with no trades
+    pub async fn trade_rate(&self, token_id: &str) -> f64 {
+        self.stats(token_id)
+            .await
+            .map(|s| s.trade_rate())
+            .unwrap_or_default()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
@@ -0,0 +1,225 @@
+use anyhow::{anyhow, Result};
+use ethers::types::U256;
+use log::info;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use super::gamma::{GammaClient, GammaEvent, MarketQuery};
+
+// ==================================================
+// EVENT GROUPINGS
+// ==================================================
+
+/// One outcome market of a neg-risk event
+#[derive(Debug, Clone)]
+pub struct NegRiskLeg {
+    pub condition_id: String,
+    pub question: String,
+    pub yes_token: String,
+    pub no_token: String,
+    /// Position of the question in the neg-risk market (last byte of
+    /// its question id), used to build conversion index sets
+    pub index: Option<u8>,
+}
+
+/// Markets of a neg-risk event: exactly one leg resolves YES, so the YES
+/// prices should sum to 1 and a NO on one leg converts into YES on all
+/// the others
+#[derive(Debug, Clone)]
+pub struct NegRiskGroup {
+    pub event_id: String,
+    pub title: String,
+    pub neg_risk_market_id: Option<String>,
+    pub legs: Vec<NegRiskLeg>,
+}
+
+/// What converting `amount` NO tokens on each of `k` legs yields: the
+/// NOs are burnt for `(k - 1) * amount` USDC plus `amount` YES on every
+/// other leg
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegRiskConversion {
+    pub market_id: String,
+    /// Bit i set = NO of question i is converted
+    pub index_set: U256,
+    pub usdc_out: Decimal,
+    /// YES tokens received, `amount` each
+    pub yes_tokens: Vec<String>,
+}
+
+impl NegRiskGroup {
+    /// Plan converting `amount` NO on each of the legs with the given
+    /// condition ids. Conversions only cover legs the group knows, so
+    /// legs that have since closed must be left out.
+    pub fn conversion(&self, no_conditions: &[String], amount: Decimal) -> Result<NegRiskConversion> {
+        let market_id = self
+            .neg_risk_market_id
+            .clone()
+            .ok_or_else(|| anyhow!("Event {} has no neg-risk market id", self.event_id))?;
+        if no_conditions.is_empty() {
+            return Err(anyhow!("Nothing to convert"));
+        }
+
+        let mut index_set = U256::zero();
+        for condition in no_conditions {
+            let leg = self
+                .legs
+                .iter()
+                .find(|l| &l.condition_id == condition)
+                .ok_or_else(|| anyhow!("{} is not a leg of event {}", condition, self.event_id))?;
+            let index = leg
+                .index
+                .ok_or_else(|| anyhow!("No question index for {}", condition))?;
+            index_set |= U256::one() << index;
+        }
+
+        Ok(NegRiskConversion {
+            market_id,
+            index_set,
+            usdc_out: amount * Decimal::from(no_conditions.len() - 1),
+            yes_tokens: self
+                .legs
+                .iter()
+                .filter(|l| !no_conditions.contains(&l.condition_id))
+                .map(|l| l.yes_token.clone())
+                .collect(),
+        })
+    }
+}
+
+/// Question index within its neg-risk market: the last byte of the
+/// question id
+pub fn question_index(question_id: &str) -> Option<u8> {
+    let hex = question_id.trim_start_matches("0x");
+    if hex.len() != 64 {
+        return None;
+    }
+    u8::from_str_radix(&hex[62..], 16).ok()
+}
+
+/// Which markets belong to the same real-world event, looked up by
+/// token id or condition id. Outcomes of one event are correlated, so
+/// exposure limits and neg-risk arbitrage work per event.
+#[derive(Debug, Clone, Default)]
+pub struct EventIndex {
+    events: HashMap<String, GammaEvent>,
+    // condition id → event id
+    by_condition: HashMap<String, String>,
+    // token id → event id
+    by_token: HashMap<String, String>,
+}
+
+impl EventIndex {
+    pub fn new(events: Vec<GammaEvent>) -> Self {
+        let mut index = Self::default();
+        for event in events {
+            index.insert(event);
+        }
+        index
+    }
+
+    /// Index every open event
+    pub async fn load(gamma: &GammaClient) -> Result<Self> {
+        let events = gamma.all_events(&MarketQuery::open()).await?;
+        let index = Self::new(events);
+        info!(
+            "🗂️  Indexed {} events ({} markets)",
+            index.events.len(),
+            index.by_condition.len()
+        );
+        Ok(index)
+    }
+
+    /// Add or replace an event
+    pub fn insert(&mut self, event: GammaEvent) {
+        if let Some(old) = self.events.remove(&event.id) {
+            self.by_condition.retain(|_, e| *e != old.id);
+            self.by_token.retain(|_, e| *e != old.id);
+        }
+        for market in &event.markets {
+            self.by_condition
+                .insert(market.condition_id.clone(), event.id.clone());
+            for token in market.token_ids() {
+                self.by_token.insert(token, event.id.clone());
+            }
+        }
+        self.events.insert(event.id.clone(), event);
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn event(&self, event_id: &str) -> Option<&GammaEvent> {
+        self.events.get(event_id)
+    }
+
+    pub fn event_of_token(&self, token_id: &str) -> Option<&GammaEvent> {
+        self.event(self.by_token.get(token_id)?)
+    }
+
+    pub fn event_of_condition(&self, condition_id: &str) -> Option<&GammaEvent> {
+        self.event(self.by_condition.get(condition_id)?)
+    }
+
+    /// Event id for a token, for grouping exposure
+    pub fn event_id(&self, token_id: &str) -> Option<&str> {
+        self.by_token.get(token_id).map(String::as_str)
+    }
+
+    /// Both tokens trade on the same event (including the same market)
+    pub fn same_event(&self, token_a: &str, token_b: &str) -> bool {
+        matches!((self.event_id(token_a), self.event_id(token_b)), (Some(a), Some(b)) if a == b)
+    }
+
+    /// Tokens of the other markets in `token_id`'s event
+    pub fn siblings(&self, token_id: &str) -> Vec<String> {
+        let Some(event) = self.event_of_token(token_id) else {
+            return vec![];
+        };
+        event
+            .markets
+            .iter()
+            .map(|m| m.token_ids())
+            .filter(|tokens| !tokens.iter().any(|t| t == token_id))
+            .flatten()
+            .collect()
+    }
+
+    /// Neg-risk grouping of an event, if it is one
+    pub fn neg_risk_group(&self, event_id: &str) -> Option<NegRiskGroup> {
+        let event = self.event(event_id).filter(|e| e.neg_risk)?;
+        let legs = event
+            .markets
+            .iter()
+            .filter(|m| m.is_tradeable())
+            .filter_map(|m| {
+                Some(NegRiskLeg {
+                    condition_id: m.condition_id.clone(),
+                    question: m.question.clone(),
+                    yes_token: m.token_for("Yes")?,
+                    no_token: m.token_for("No")?,
+                    index: m.question_id.as_deref().and_then(question_index),
+                })
+            })
+            .collect();
+        Some(NegRiskGroup {
+            event_id: event.id.clone(),
+            title: event.title.clone(),
+            neg_risk_market_id: event.neg_risk_market_id.clone(),
+            legs,
+        })
+    }
+
+    /// Every neg-risk event with at least two tradeable legs
+    pub fn neg_risk_groups(&self) -> Vec<NegRiskGroup> {
+        self.events
+            .keys()
+            .filter_map(|id| self.neg_risk_group(id))
+            .filter(|g| g.legs.len() >= 2)
+            .collect()
+    }
+}
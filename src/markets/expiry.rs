@@ -0,0 +1,192 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::metadata::{MetadataCache, TokenMeta};
+use crate::domain::order::Side;
+use crate::execution::clob_client::ClobClient;
+
+// ==================================================
+// END-OF-MARKET GUARD
+// ==================================================
+
+/// How close to the deadline each rule kicks in
+#[derive(Debug, Clone)]
+pub struct ExpiryConfig {
+    /// Quote wider from here on
+    pub widen_within: Duration,
+    /// Spread multiplier while widening
+    pub widen_factor: f64,
+    /// Refuse orders that open or add to a position
+    pub block_entries_within: Duration,
+    /// Cancel resting quotes
+    pub pull_within: Duration,
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            widen_within: Duration::from_secs(60 * 60),
+            widen_factor: 2.0,
+            block_entries_within: Duration::from_secs(30 * 60),
+            pull_within: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+impl ExpiryConfig {
+    /// `EXPIRY_WIDEN_MINS`, `EXPIRY_WIDEN_FACTOR`,
+    /// `EXPIRY_BLOCK_ENTRY_MINS` and `EXPIRY_PULL_MINS`; unset ones keep
+    /// their defaults
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |key: &str| std::env::var(key).ok().and_then(|v| v.parse::<f64>().ok());
+        let mins = |key: &str, default: Duration| {
+            var(key)
+                .map(|m| Duration::from_secs_f64(m.max(0.0) * 60.0))
+                .unwrap_or(default)
+        };
+        Self {
+            widen_within: mins("EXPIRY_WIDEN_MINS", d.widen_within),
+            widen_factor: var("EXPIRY_WIDEN_FACTOR").unwrap_or(d.widen_factor),
+            block_entries_within: mins("EXPIRY_BLOCK_ENTRY_MINS", d.block_entries_within),
+            pull_within: mins("EXPIRY_PULL_MINS", d.pull_within),
+        }
+    }
+}
+
+/// What a quoting strategy should do on a token right now
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuoteAction {
+    Normal,
+    /// Multiply the quoted spread by this factor
+    Widen(f64),
+    /// Don't quote; cancel what's resting
+    Pull,
+}
+
+/// Winds quoting down as a market approaches its deadline: the earlier
+/// of its end date and the start of the underlying event (a game's
+/// kick-off, after which prices jump on every play).
+///
+/// The client consults it before every order and refuses buys inside the
+/// entry window; `run` cancels resting orders inside the pull window.
+/// Tokens whose metadata can't be fetched are let through.
+pub struct ExpiryGuard {
+    metadata: Arc<MetadataCache>,
+    config: ExpiryConfig,
+}
+
+impl ExpiryGuard {
+    pub fn new(metadata: Arc<MetadataCache>, config: ExpiryConfig) -> Self {
+        Self { metadata, config }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(
+            Arc::new(MetadataCache::from_env()?),
+            ExpiryConfig::from_env(),
+        ))
+    }
+
+    pub fn config(&self) -> &ExpiryConfig {
+        &self.config
+    }
+
+    /// Earlier of the end date and the event start
+    pub fn deadline(meta: &TokenMeta) -> Option<DateTime<Utc>> {
+        [meta.end_date.as_deref(), meta.event_start.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.with_timezone(&Utc))
+            .min()
+    }
+
+    /// Rule for `meta` at `now`
+    pub fn action_at(&self, meta: &TokenMeta, now: DateTime<Utc>) -> QuoteAction {
+        let Some(left) = time_left(meta, now) else {
+            return QuoteAction::Normal;
+        };
+        if left <= self.config.pull_within {
+            QuoteAction::Pull
+        } else if left <= self.config.widen_within {
+            QuoteAction::Widen(self.config.widen_factor)
+        } else {
+            QuoteAction::Normal
+        }
+    }
+
+    pub async fn action(&self, token_id: &str) -> QuoteAction {
+        match self.meta(token_id).await {
+            Some(meta) => self.action_at(&meta, Utc::now()),
+            None => QuoteAction::Normal,
+        }
+    }
+
+    /// Whether a new position may still be opened on `token_id`
+    pub async fn allows_entry(&self, token_id: &str) -> bool {
+        let Some(meta) = self.meta(token_id).await else {
+            return true;
+        };
+        time_left(&meta, Utc::now()).is_none_or(|left| left > self.config.block_entries_within)
+    }
+
+    /// Pre-submit hook: buys open or add to positions and are refused
+    /// inside the entry window; sells reduce them and always pass
+    pub async fn check_order(&self, token_id: &str, side: &Side) -> Result<()> {
+        if *side == Side::Sell || self.allows_entry(token_id).await {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "Market for token {} is within {} min of its end or event start; new entries blocked",
+            token_id,
+            self.config.block_entries_within.as_secs() / 60
+        ))
+    }
+
+    /// Cancel resting orders on tokens inside the pull window every
+    /// `every`, forever
+    pub async fn run(self: Arc<Self>, clob: Arc<ClobClient>, every: Duration) {
+        let mut tick = tokio::time::interval(every);
+        loop {
+            tick.tick().await;
+            let orders = match clob.open_orders(None).await {
+                Ok(orders) => orders,
+                Err(e) => {
+                    warn!("⚠️  Expiry guard couldn't list open orders: {}", e);
+                    continue;
+                }
+            };
+            let tokens: HashSet<String> = orders.into_iter().map(|o| o.asset_id).collect();
+            for token in tokens {
+                if self.action(&token).await != QuoteAction::Pull {
+                    continue;
+                }
+                info!("⏰ Pulling quotes on {} ahead of market end", token);
+                if let Err(e) = clob.cancel_market(&token).await {
+                    warn!("⚠️  Failed to pull quotes on {}: {}", token, e);
+                }
+            }
+        }
+    }
+
+    async fn meta(&self, token_id: &str) -> Option<TokenMeta> {
+        match self.metadata.get_or_fetch(token_id).await {
+            Ok(meta) => Some(meta),
+            Err(e) => {
+                warn!("⚠️  No metadata for {}, expiry guard skipped: {}", token_id, e);
+                None
+            }
+        }
+    }
+}
+
+/// Time until the deadline; zero once it has passed
+fn time_left(meta: &TokenMeta, now: DateTime<Utc>) -> Option<Duration> {
+    let deadline = ExpiryGuard::deadline(meta)?;
+    Some((deadline - now).to_std().unwrap_or(Duration::ZERO))
+}
@@ -0,0 +1,478 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::debug;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::net::SendRetry;
+
+const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com";
+
+/// Markets per `/markets` page
+const PAGE_SIZE: usize = 100;
+/// Stop paginating after this many pages
+const MAX_PAGES: usize = 50;
+/// Token ids per `/markets?clob_token_ids=` lookup, to bound the URL
+const TOKENS_PER_LOOKUP: usize = 50;
+
+// ==================================================
+// GAMMA MARKETS
+// ==================================================
+
+/// One market as listed by the Gamma API. List-valued fields come back
+/// JSON-encoded in strings; use the accessors.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GammaMarket {
+    pub id: String,
+    #[serde(default)]
+    pub question: String,
+    #[serde(default)]
+    pub condition_id: String,
+    /// UMA adapter question id (keccak256 of the ancillary data)
+    #[serde(default, rename = "questionID")]
+    pub question_id: Option<String>,
+    #[serde(default)]
+    pub slug: String,
+    /// ISO-8601 resolution date
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// Kick-off of the underlying game, for sports markets
+    #[serde(default)]
+    pub game_start_time: Option<String>,
+    #[serde(default, rename = "liquidityNum")]
+    pub liquidity: Option<f64>,
+    /// Lifetime volume (USDC)
+    #[serde(default, rename = "volumeNum")]
+    pub volume: Option<f64>,
+    #[serde(default, rename = "volume24hr")]
+    pub volume_24h: Option<f64>,
+    #[serde(default)]
+    pub best_bid: Option<f64>,
+    #[serde(default)]
+    pub best_ask: Option<f64>,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub accepting_orders: bool,
+    #[serde(default)]
+    pub enable_order_book: bool,
+    #[serde(default)]
+    pub neg_risk: bool,
+    /// UMA oracle state once a resolution is proposed: `proposed`,
+    /// `disputed` or `resolved`
+    #[serde(default)]
+    pub uma_resolution_status: Option<String>,
+    #[serde(default)]
+    pub order_price_min_tick_size: Option<f64>,
+    #[serde(default)]
+    pub order_min_size: Option<f64>,
+    /// Legacy free-text category, e.g. "Sports"
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<GammaTag>,
+    /// Sports bet type, e.g. `moneyline`, `spreads`, `totals`
+    #[serde(default)]
+    pub sports_market_type: Option<String>,
+    /// Parent event(s), embedded without their markets
+    #[serde(default)]
+    pub events: Vec<GammaEvent>,
+    #[serde(default)]
+    clob_token_ids: Option<String>,
+    #[serde(default)]
+    outcomes: Option<String>,
+    #[serde(default)]
+    outcome_prices: Option<String>,
+}
+
+impl GammaMarket {
+    /// CLOB token ids, in the same order as `outcomes()`
+    pub fn token_ids(&self) -> Vec<String> {
+        decode_list(&self.clob_token_ids)
+    }
+
+    /// Outcome labels, e.g. `["Yes", "No"]`
+    pub fn outcomes(&self) -> Vec<String> {
+        decode_list(&self.outcomes)
+    }
+
+    /// Last prices per outcome; after resolution, the reported payout
+    /// (e.g. `[1.0, 0.0]`)
+    pub fn outcome_prices(&self) -> Vec<f64> {
+        decode_list(&self.outcome_prices)
+            .iter()
+            .filter_map(|p| p.parse().ok())
+            .collect()
+    }
+
+    /// Token id of the outcome labelled `outcome` (case-insensitive)
+    pub fn token_for(&self, outcome: &str) -> Option<String> {
+        self.outcomes()
+            .iter()
+            .position(|o| o.eq_ignore_ascii_case(outcome))
+            .and_then(|i| self.token_ids().get(i).cloned())
+    }
+
+    pub fn end_date(&self) -> Option<DateTime<Utc>> {
+        let end = self.end_date.as_deref()?;
+        DateTime::parse_from_rfc3339(end)
+            .ok()
+            .map(|d| d.with_timezone(&Utc))
+    }
+
+    /// Tag slugs and labels plus the category, for filtering
+    pub fn labels(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .flat_map(|t| [t.slug.clone(), t.label.clone()])
+            .chain(self.category.clone())
+            .filter(|l| !l.is_empty())
+            .collect()
+    }
+
+    /// Open, accepting orders and backed by a CLOB book
+    pub fn is_tradeable(&self) -> bool {
+        self.active
+            && !self.closed
+            && self.accepting_orders
+            && self.enable_order_book
+            && !self.token_ids().is_empty()
+    }
+}
+
+/// Gamma tag, e.g. `{ label: "Crypto", slug: "crypto" }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct GammaTag {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub slug: String,
+}
+
+/// A real-world event and the markets on its outcomes. In a neg-risk
+/// event exactly one market resolves YES.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GammaEvent {
+    pub id: String,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// When the real-world event starts (game time for sports)
+    #[serde(default)]
+    pub start_time: Option<String>,
+    /// Recurring series, e.g. a league season
+    #[serde(default)]
+    pub series_slug: Option<String>,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub neg_risk: bool,
+    #[serde(default, rename = "negRiskMarketID")]
+    pub neg_risk_market_id: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<GammaTag>,
+    #[serde(default)]
+    pub markets: Vec<GammaMarket>,
+}
+
+/// A league as listed by `/sports`
+#[derive(Debug, Clone, Deserialize)]
+pub struct GammaSport {
+    /// League code, e.g. `nba`, `epl`
+    pub sport: String,
+    /// Comma-separated tag ids the league's events carry
+    #[serde(default)]
+    tags: String,
+    /// Series id of the current season
+    #[serde(default)]
+    pub series: Option<String>,
+}
+
+impl GammaSport {
+    pub fn tag_ids(&self) -> Vec<String> {
+        self.tags
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+}
+
+/// `"[\"a\", \"b\"]"` → `["a", "b"]`; empty if missing or malformed
+fn decode_list(raw: &Option<String>) -> Vec<String> {
+    raw.as_deref()
+        .and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_default()
+}
+
+/// Filters for `/markets` and `/events`. Unset fields aren't sent.
+#[derive(Debug, Clone, Default)]
+pub struct MarketQuery {
+    pub active: Option<bool>,
+    pub closed: Option<bool>,
+    pub liquidity_min: Option<f64>,
+    pub volume_min: Option<f64>,
+    pub end_date_min: Option<DateTime<Utc>>,
+    pub end_date_max: Option<DateTime<Utc>>,
+    pub tag_id: Option<u64>,
+    /// Field to sort by, e.g. `volume24hr`, `liquidity`, `endDate`
+    pub order: Option<String>,
+    pub ascending: bool,
+}
+
+impl MarketQuery {
+    /// Open markets only
+    pub fn open() -> Self {
+        Self {
+            active: Some(true),
+            closed: Some(false),
+            ..Default::default()
+        }
+    }
+
+    pub fn liquidity_min(mut self, usdc: f64) -> Self {
+        self.liquidity_min = Some(usdc);
+        self
+    }
+
+    pub fn volume_min(mut self, usdc: f64) -> Self {
+        self.volume_min = Some(usdc);
+        self
+    }
+
+    pub fn ending_between(mut self, min: Option<DateTime<Utc>>, max: Option<DateTime<Utc>>) -> Self {
+        self.end_date_min = min;
+        self.end_date_max = max;
+        self
+    }
+
+    pub fn tag_id(mut self, tag_id: u64) -> Self {
+        self.tag_id = Some(tag_id);
+        self
+    }
+
+    pub fn order_by(mut self, field: &str, ascending: bool) -> Self {
+        self.order = Some(field.to_string());
+        self.ascending = ascending;
+        self
+    }
+
+    fn params(&self, limit: usize, offset: usize) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("limit", limit.to_string()),
+            ("offset", offset.to_string()),
+            ("include_tag", "true".to_string()),
+        ];
+        if let Some(v) = self.active {
+            params.push(("active", v.to_string()));
+        }
+        if let Some(v) = self.closed {
+            params.push(("closed", v.to_string()));
+        }
+        if let Some(v) = self.liquidity_min {
+            params.push(("liquidity_num_min", v.to_string()));
+        }
+        if let Some(v) = self.volume_min {
+            params.push(("volume_num_min", v.to_string()));
+        }
+        if let Some(v) = self.end_date_min {
+            params.push(("end_date_min", v.to_rfc3339()));
+        }
+        if let Some(v) = self.end_date_max {
+            params.push(("end_date_max", v.to_rfc3339()));
+        }
+        if let Some(v) = self.tag_id {
+            params.push(("tag_id", v.to_string()));
+        }
+        if let Some(v) = &self.order {
+            params.push(("order", v.clone()));
+            params.push(("ascending", self.ascending.to_string()));
+        }
+        params
+    }
+}
+
+/// Market discovery over the Gamma API
+#[derive(Debug, Clone)]
+pub struct GammaClient {
+    http: Client,
+    base_url: String,
+}
+
+impl GammaClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: Client::new(),
+            base_url: base_url.into(),
+        }
+    }
+
+    /// `GAMMA_API_URL` (default the public Gamma API)
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("GAMMA_API_URL").unwrap_or_else(|_| GAMMA_API_URL.to_string()))
+    }
+
+    /// One page of markets matching `query`
+    pub async fn markets(&self, query: &MarketQuery, limit: usize, offset: usize) -> Result<Vec<GammaMarket>> {
+        self.page("/markets", query, limit, offset).await
+    }
+
+    /// Every market matching `query`, following pagination
+    pub async fn all_markets(&self, query: &MarketQuery) -> Result<Vec<GammaMarket>> {
+        self.paginate("/markets", query).await
+    }
+
+    /// One page of events matching `query`, each with its markets
+    pub async fn events(&self, query: &MarketQuery, limit: usize, offset: usize) -> Result<Vec<GammaEvent>> {
+        self.page("/events", query, limit, offset).await
+    }
+
+    /// Every event matching `query`, following pagination
+    pub async fn all_events(&self, query: &MarketQuery) -> Result<Vec<GammaEvent>> {
+        self.paginate("/events", query).await
+    }
+
+    pub async fn event_by_slug(&self, slug: &str) -> Result<GammaEvent> {
+        let url = format!("{}/events/slug/{}", self.base_url, slug);
+        let resp = self.http.get(&url).send_retry().await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("Failed to fetch event {}: {}", slug, resp.status()));
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &MarketQuery,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<T>> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self
+            .http
+            .get(&url)
+            .query(&query.params(limit, offset))
+            .send_retry()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("Failed to list {}: {}", path, resp.status()));
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn paginate<T: DeserializeOwned>(&self, path: &str, query: &MarketQuery) -> Result<Vec<T>> {
+        let mut all = Vec::new();
+        for page in 0..MAX_PAGES {
+            let batch: Vec<T> = self.page(path, query, PAGE_SIZE, page * PAGE_SIZE).await?;
+            let done = batch.len() < PAGE_SIZE;
+            all.extend(batch);
+            if done {
+                return Ok(all);
+            }
+        }
+        debug!("{} listing truncated at {} pages", path, MAX_PAGES);
+        Ok(all)
+    }
+
+    pub async fn market_by_slug(&self, slug: &str) -> Result<GammaMarket> {
+        self.first(&[("slug", slug)])
+            .await?
+            .ok_or_else(|| anyhow!("No market with slug {}", slug))
+    }
+
+    pub async fn market_by_condition(&self, condition_id: &str) -> Result<GammaMarket> {
+        self.first(&[("condition_ids", condition_id)])
+            .await?
+            .ok_or_else(|| anyhow!("No market with condition id {}", condition_id))
+    }
+
+    /// Markets trading any of `token_ids`, open or not
+    pub async fn markets_by_tokens(&self, token_ids: &[String]) -> Result<Vec<GammaMarket>> {
+        let url = format!("{}/markets", self.base_url);
+        let mut markets = Vec::new();
+        for chunk in token_ids.chunks(TOKENS_PER_LOOKUP) {
+            let mut params: Vec<(&str, &str)> = chunk.iter().map(|t| ("clob_token_ids", t.as_str())).collect();
+            params.push(("include_tag", "true"));
+            let resp = self.http.get(&url).query(&params).send_retry().await?;
+
+            if !resp.status().is_success() {
+                return Err(anyhow!("Failed to look up markets by token: {}", resp.status()));
+            }
+            let batch: Vec<GammaMarket> = resp.json().await?;
+            markets.extend(batch);
+        }
+        Ok(markets)
+    }
+
+    /// Full-text search over market and event titles; open markets only
+    pub async fn search(&self, text: &str) -> Result<Vec<GammaMarket>> {
+        let url = format!("{}/public-search", self.base_url);
+        let resp = self
+            .http
+            .get(&url)
+            .query(&[("q", text), ("events_status", "active")])
+            .send_retry()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("Market search failed: {}", resp.status()));
+        }
+
+        // Results are grouped by event
+        let body: Value = resp.json().await?;
+        let markets = body["events"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e["markets"].as_array())
+            .flatten()
+            .filter_map(|m| serde_json::from_value::<GammaMarket>(m.clone()).ok())
+            .filter(|m| m.active && !m.closed)
+            .collect();
+        Ok(markets)
+    }
+
+    /// Sports leagues and the tags identifying their events
+    pub async fn sports(&self) -> Result<Vec<GammaSport>> {
+        let url = format!("{}/sports", self.base_url);
+        let resp = self.http.get(&url).send_retry().await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("Failed to fetch sports: {}", resp.status()));
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn first(&self, params: &[(&str, &str)]) -> Result<Option<GammaMarket>> {
+        let url = format!("{}/markets", self.base_url);
+        let resp = self
+            .http
+            .get(&url)
+            .query(params)
+            .query(&[("include_tag", "true")])
+            .send_retry()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("Failed to fetch market: {}", resp.status()));
+        }
+        let markets: Vec<GammaMarket> = resp.json().await?;
+        Ok(markets.into_iter().next())
+    }
+}
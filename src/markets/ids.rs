@@ -0,0 +1,241 @@
+use anyhow::{anyhow, Result};
+use ethers::types::{Address, H256, U256, U512};
+use ethers::utils::keccak256;
+
+use super::gamma::GammaMarket;
+use crate::config::ChainConfig;
+
+/// alt_bn128 field modulus; collection ids are points on y² = x³ + 3
+const P: &str = "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+const B: u64 = 3;
+
+// ==================================================
+// CTF ID DERIVATION
+// ==================================================
+// Pure re-implementations of the ConditionalTokens helpers, so market
+// metadata can be checked without trusting an off-chain API:
+//
+//   conditionId  = keccak256(oracle ‖ questionId ‖ outcomeSlotCount)
+//   collectionId = CTHelpers.getCollectionId(0, conditionId, indexSet)
+//   positionId   = uint256(keccak256(collateral ‖ collectionId))
+//
+// Polymarket only uses root collections (no nested conditions), so the
+// parent collection is always zero here.
+
+/// `getConditionId(oracle, questionId, outcomeSlotCount)`
+pub fn condition_id(oracle: Address, question_id: H256, outcome_slots: u64) -> H256 {
+    let mut packed = Vec::with_capacity(84);
+    packed.extend_from_slice(oracle.as_bytes());
+    packed.extend_from_slice(question_id.as_bytes());
+    packed.extend_from_slice(&u256_bytes(U256::from(outcome_slots)));
+    H256(keccak256(packed))
+}
+
+/// `getCollectionId(bytes32(0), conditionId, indexSet)`: hash onto the
+/// curve, with the parity of y folded into bit 254 of x
+pub fn collection_id(condition_id: H256, index_set: U256) -> H256 {
+    let p = modulus();
+    let mut packed = Vec::with_capacity(64);
+    packed.extend_from_slice(condition_id.as_bytes());
+    packed.extend_from_slice(&u256_bytes(index_set));
+
+    let mut x = U256::from_big_endian(&keccak256(packed));
+    let odd = x.bit(255);
+    let mut y;
+    loop {
+        x = add_mod(x, U256::one(), p);
+        let yy = add_mod(mul_mod(x, mul_mod(x, x, p), p), U256::from(B), p);
+        y = sqrt_mod(yy, p);
+        if mul_mod(y, y, p) == yy {
+            break;
+        }
+    }
+    if odd != y.bit(0) {
+        y = p - y;
+    }
+    if y.bit(0) {
+        x ^= U256::one() << 254;
+    }
+    H256(u256_bytes(x))
+}
+
+/// ERC-1155 id of a position: `getPositionId(collateral, collectionId)`
+pub fn position_id(collateral: Address, collection_id: H256) -> U256 {
+    let mut packed = Vec::with_capacity(52);
+    packed.extend_from_slice(collateral.as_bytes());
+    packed.extend_from_slice(collection_id.as_bytes());
+    U256::from_big_endian(&keccak256(packed))
+}
+
+/// Token ids of outcome 0 and 1 (YES, NO) of a binary condition
+pub fn binary_token_ids(collateral: Address, condition_id: H256) -> [U256; 2] {
+    [1u64, 2].map(|index_set| position_id(collateral, collection_id(condition_id, U256::from(index_set))))
+}
+
+/// Result of checking a market's ids against what the chain would derive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdCheck {
+    /// Oracle whose derivation matched the condition id, if any
+    pub oracle: Option<Address>,
+    /// Token ids match the derived YES/NO position ids (not checked for
+    /// neg-risk markets, whose collateral is the adapter's wrapped USDC)
+    pub tokens_match: Option<bool>,
+}
+
+impl IdCheck {
+    pub fn is_valid(&self) -> bool {
+        self.oracle.is_some() && self.tokens_match != Some(false)
+    }
+}
+
+/// Re-derive a Gamma market's condition id (trying every known oracle:
+/// the UMA adapters and, for neg-risk, the neg-risk adapter) and its
+/// token ids
+pub fn verify_market(market: &GammaMarket, chain: &ChainConfig) -> Result<IdCheck> {
+    let question_id = parse_h256(
+        market
+            .question_id
+            .as_deref()
+            .ok_or_else(|| anyhow!("Market {} has no question id", market.id))?,
+    )?;
+    let condition = parse_h256(&market.condition_id)?;
+
+    let mut oracles = chain.uma_adapters.clone();
+    if let Some(adapter) = chain.neg_risk_adapter.filter(|_| market.neg_risk) {
+        oracles.insert(0, adapter);
+    }
+    let oracle = oracles
+        .into_iter()
+        .find(|o| condition_id(*o, question_id, 2) == condition);
+
+    let tokens_match = (!market.neg_risk).then(|| {
+        let derived = binary_token_ids(chain.usdc, condition).map(|id| id.to_string());
+        market.token_ids() == derived
+    });
+
+    Ok(IdCheck { oracle, tokens_match })
+}
+
+fn parse_h256(hex: &str) -> Result<H256> {
+    hex.parse::<H256>()
+        .map_err(|e| anyhow!("Invalid bytes32 {}: {}", hex, e))
+}
+
+fn u256_bytes(v: U256) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    v.to_big_endian(&mut out);
+    out
+}
+
+fn modulus() -> U256 {
+    U256::from_dec_str(P).expect("valid modulus")
+}
+
+// Operands are reduced (< P < 2^254), so sums can't overflow
+fn add_mod(a: U256, b: U256, p: U256) -> U256 {
+    (a % p + b % p) % p
+}
+
+fn mul_mod(a: U256, b: U256, p: U256) -> U256 {
+    let r = a.full_mul(b) % U512::from(p);
+    U256::try_from(r).expect("reduced below modulus")
+}
+
+/// P ≡ 3 (mod 4), so a root (if any) is yy^((P+1)/4)
+fn sqrt_mod(yy: U256, p: U256) -> U256 {
+    let mut exp = (p + U256::one()) >> 2;
+    let mut base = yy % p;
+    let mut acc = U256::one();
+    while !exp.is_zero() {
+        if exp.bit(0) {
+            acc = mul_mod(acc, base, p);
+        }
+        base = mul_mod(base, base, p);
+        exp >>= 1;
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Polygon UMA CTF adapter and USDC.e
+    const ORACLE: &str = "0x157Ce2d672854c848c9b79C49a8Cc6cc89176a49";
+    const USDC: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+
+    // Reference values from an independent implementation of the
+    // ConditionalTokens helpers, for questionId =
+    // keccak256("Will it rain in London tomorrow?")
+    const QUESTION: &str = "0x6592e9c4118489539b565a37270dc980b7ea5beef7e931f585b9f0d388409f51";
+    const CONDITION: &str = "0x123a21eed87e333b48ca6adca08e0b41b0ece9bec627ecd6d727ad70b7279ebd";
+    const YES_COLLECTION: &str = "0x0c006cabc8e2ab05226ff0b00b1ad65c68568d24113ca5ad4ccf9f013dfb7676";
+    const NO_COLLECTION: &str = "0x6e6a0f2820f6cee2b495b0a270bb1413e79f23c0aa70418c9b9d3f43d075292c";
+    const YES_TOKEN: &str = "60662579522780852420281731819909625807177396895443862027569736877928853263813";
+    const NO_TOKEN: &str = "54164236673720202123676551522268229997639681199594330660794877871927874644078";
+
+    fn h(s: &str) -> H256 {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn question_id_fixture() {
+        assert_eq!(H256(keccak256("Will it rain in London tomorrow?")), h(QUESTION));
+    }
+
+    #[test]
+    fn derives_condition_id() {
+        let oracle: Address = ORACLE.parse().unwrap();
+        assert_eq!(condition_id(oracle, h(QUESTION), 2), h(CONDITION));
+        assert_ne!(condition_id(oracle, h(QUESTION), 3), h(CONDITION));
+    }
+
+    #[test]
+    fn derives_collection_ids() {
+        assert_eq!(collection_id(h(CONDITION), U256::from(1)), h(YES_COLLECTION));
+        assert_eq!(collection_id(h(CONDITION), U256::from(2)), h(NO_COLLECTION));
+    }
+
+    #[test]
+    fn collection_id_is_a_curve_point() {
+        let p = modulus();
+        for index_set in [1u64, 2, 3] {
+            let id = U256::from_big_endian(collection_id(h(CONDITION), U256::from(index_set)).as_bytes());
+            let x = id & !(U256::one() << 254);
+            let yy = add_mod(mul_mod(x, mul_mod(x, x, p), p), U256::from(B), p);
+            let y = sqrt_mod(yy, p);
+            assert_eq!(mul_mod(y, y, p), yy, "index set {} is off the curve", index_set);
+        }
+    }
+
+    #[test]
+    fn derives_token_ids() {
+        let usdc: Address = USDC.parse().unwrap();
+        let [yes, no] = binary_token_ids(usdc, h(CONDITION));
+        assert_eq!(yes.to_string(), YES_TOKEN);
+        assert_eq!(no.to_string(), NO_TOKEN);
+    }
+
+    fn market(tokens: [&str; 2]) -> GammaMarket {
+        serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "conditionId": CONDITION,
+            "questionID": QUESTION,
+            "clobTokenIds": format!("[\"{}\", \"{}\"]", tokens[0], tokens[1]),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn verifies_market_metadata() {
+        let chain = ChainConfig::polygon();
+
+        let check = verify_market(&market([YES_TOKEN, NO_TOKEN]), &chain).unwrap();
+        assert_eq!(check.oracle, Some(ORACLE.parse().unwrap()));
+        assert_eq!(check.tokens_match, Some(true));
+        assert!(check.is_valid());
+
+        // Outcomes swapped
+        assert!(!verify_market(&market([NO_TOKEN, YES_TOKEN]), &chain).unwrap().is_valid());
+    }
+}
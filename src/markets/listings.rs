@@ -0,0 +1,157 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use reqwest::Client;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+
+use super::gamma::{GammaClient, GammaMarket, MarketQuery};
+use super::tags::TagFilter;
+use crate::net::SendRetry;
+
+/// Listings kept for slow subscribers
+const UPDATE_BUFFER: usize = 1024;
+/// Newest markets fetched per poll; listings arrive far slower than this
+const NEWEST: usize = 100;
+
+// ==================================================
+// NEW-MARKET ALERTS
+// ==================================================
+
+/// Polls Gamma for markets listed since the last poll and broadcasts the
+/// ones passing the filters, optionally posting each to a webhook.
+///
+/// The first poll only records what's already listed, so a restart
+/// doesn't replay old listings as new.
+pub struct ListingWatcher {
+    gamma: GammaClient,
+    tags: TagFilter,
+    min_liquidity: f64,
+    webhook: Option<(Client, String)>,
+    seen: Mutex<Option<HashSet<String>>>,
+    updates: broadcast::Sender<GammaMarket>,
+}
+
+impl ListingWatcher {
+    pub fn new(gamma: GammaClient) -> Self {
+        Self {
+            gamma,
+            tags: TagFilter::default(),
+            min_liquidity: 0.0,
+            webhook: None,
+            seen: Mutex::new(None),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        }
+    }
+
+    pub fn tags(mut self, filter: TagFilter) -> Self {
+        self.tags = filter;
+        self
+    }
+
+    pub fn min_liquidity(mut self, usdc: f64) -> Self {
+        self.min_liquidity = usdc;
+        self
+    }
+
+    /// Post a message per listing to a Slack/Discord-style webhook
+    pub fn webhook(mut self, url: impl Into<String>) -> Self {
+        self.webhook = Some((Client::new(), url.into()));
+        self
+    }
+
+    /// `LISTING_INCLUDE_TAGS` / `LISTING_EXCLUDE_TAGS`,
+    /// `LISTING_MIN_LIQUIDITY` (default 0) and `LISTING_WEBHOOK_URL`
+    pub fn from_env() -> Self {
+        let min_liquidity = std::env::var("LISTING_MIN_LIQUIDITY")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .unwrap_or(0.0);
+        let watcher = Self::new(GammaClient::from_env())
+            .tags(TagFilter::from_env("LISTING"))
+            .min_liquidity(min_liquidity);
+        match std::env::var("LISTING_WEBHOOK_URL") {
+            Ok(url) if !url.is_empty() => watcher.webhook(url),
+            _ => watcher,
+        }
+    }
+
+    /// Poll interval from `LISTING_POLL_SECS` (default 60)
+    pub fn poll_interval() -> Duration {
+        let secs = std::env::var("LISTING_POLL_SECS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()
+            .unwrap_or(60);
+        Duration::from_secs(secs)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<GammaMarket> {
+        self.updates.subscribe()
+    }
+
+    /// New listings passing the filters since the last poll; they are
+    /// also broadcast
+    pub async fn poll(&self) -> Result<Vec<GammaMarket>> {
+        // Gamma ids increase with listing order
+        let query = MarketQuery::open().order_by("id", false);
+        let newest = self.gamma.markets(&query, NEWEST, 0).await?;
+
+        let mut seen = self.seen.lock().await;
+        let Some(seen) = seen.as_mut() else {
+            info!("📋 Tracking listings ({} already open)", newest.len());
+            *seen = Some(newest.into_iter().map(|m| m.id).collect());
+            return Ok(vec![]);
+        };
+
+        let fresh: Vec<GammaMarket> = newest
+            .into_iter()
+            .filter(|m| seen.insert(m.id.clone()))
+            .filter(|m| self.tags.allows(m))
+            .filter(|m| m.liquidity.unwrap_or_default() >= self.min_liquidity)
+            .collect();
+
+        for market in &fresh {
+            info!("🆕 New market: {} ({})", market.question, market.slug);
+            let _ = self.updates.send(market.clone());
+            if let Err(e) = self.post(market).await {
+                warn!("⚠️  Listing webhook failed: {}", e);
+            }
+        }
+        Ok(fresh)
+    }
+
+    /// Poll every `every`, forever
+    pub async fn run(self: Arc<Self>, every: Duration) {
+        let mut tick = tokio::time::interval(every);
+        loop {
+            tick.tick().await;
+            if let Err(e) = self.poll().await {
+                warn!("⚠️  Listing poll failed: {}", e);
+            }
+        }
+    }
+
+    async fn post(&self, market: &GammaMarket) -> Result<()> {
+        let Some((http, url)) = &self.webhook else {
+            return Ok(());
+        };
+        let text = format!(
+            "New Polymarket market: {}\nhttps://polymarket.com/market/{}\nEnds: {}",
+            market.question,
+            market.slug,
+            market.end_date.as_deref().unwrap_or("unknown"),
+        );
+        // Slack reads `text`, Discord reads `content`
+        let resp = http
+            .post(url)
+            .json(&json!({ "text": text, "content": text }))
+            .send_retry()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("Webhook returned {}", resp.status()));
+        }
+        Ok(())
+    }
+}
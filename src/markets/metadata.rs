@@ -0,0 +1,256 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{OnceCell, RwLock};
+
+use super::gamma::{GammaClient, GammaMarket};
+use super::sports::{GamePhase, Leagues, SportsInfo};
+
+// ==================================================
+// TOKEN METADATA CACHE
+// ==================================================
+
+/// Static facts about one outcome token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenMeta {
+    pub token_id: String,
+    pub condition_id: String,
+    /// Market question
+    pub market: String,
+    pub slug: String,
+    pub outcome: String,
+    pub tick_size: f64,
+    pub min_order_size: f64,
+    pub neg_risk: bool,
+    /// ISO-8601 resolution date
+    pub end_date: Option<String>,
+    /// When the underlying event starts (sports kick-off), if known
+    #[serde(default)]
+    pub event_start: Option<String>,
+    /// League, teams and game time, for sports markets
+    #[serde(default)]
+    pub sports: Option<SportsInfo>,
+    /// Tag slugs/labels and category (see `GammaMarket::labels`)
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Parent Gamma event, shared by every market of e.g. an election
+    #[serde(default)]
+    pub event_id: Option<String>,
+    /// Unix seconds of the Gamma fetch
+    pub fetched_at: u64,
+}
+
+impl TokenMeta {
+    /// One entry per outcome token of `market`
+    pub fn from_market(market: &GammaMarket, leagues: &Leagues) -> Vec<Self> {
+        let fetched_at = now_secs();
+        let sports = SportsInfo::from_market(market, leagues);
+        let labels = market.labels();
+        let event_id = market.events.first().map(|e| e.id.clone());
+        let event_start = match &sports {
+            Some(s) => s.game_start.clone(),
+            None => market.game_start_time.clone(),
+        };
+        market
+            .token_ids()
+            .into_iter()
+            .zip(market.outcomes())
+            .map(|(token_id, outcome)| Self {
+                token_id,
+                condition_id: market.condition_id.clone(),
+                market: market.question.clone(),
+                slug: market.slug.clone(),
+                outcome,
+                tick_size: market.order_price_min_tick_size.unwrap_or(0.01),
+                min_order_size: market.order_min_size.unwrap_or_default(),
+                neg_risk: market.neg_risk,
+                end_date: market.end_date.clone(),
+                event_start: event_start.clone(),
+                sports: sports.clone(),
+                labels: labels.clone(),
+                event_id: event_id.clone(),
+                fetched_at,
+            })
+            .collect()
+    }
+
+    /// Kick-off time for sports markets
+    pub fn game_start(&self) -> Option<DateTime<Utc>> {
+        self.sports.as_ref()?.game_start()
+    }
+
+    /// Pre-game or in-game; `None` for non-sports markets or an unknown
+    /// start
+    pub fn phase(&self, now: DateTime<Utc>) -> Option<GamePhase> {
+        self.sports.as_ref()?.phase(now)
+    }
+
+    fn is_stale(&self, ttl: Duration) -> bool {
+        now_secs().saturating_sub(self.fetched_at) >= ttl.as_secs()
+    }
+}
+
+/// token id → metadata, persisted to disk and refreshed in the
+/// background so hot paths read it without an HTTP call.
+///
+/// `get` never fetches: stale entries are still served (metadata rarely
+/// changes) until the refresher replaces them.
+pub struct MetadataCache {
+    gamma: GammaClient,
+    path: PathBuf,
+    ttl: Duration,
+    entries: RwLock<HashMap<String, TokenMeta>>,
+    // League tags from `/sports`, fetched once
+    leagues: OnceCell<Leagues>,
+}
+
+impl MetadataCache {
+    /// Open the cache at `path`, starting empty if it doesn't exist
+    pub fn load(gamma: GammaClient, path: impl Into<PathBuf>, ttl: Duration) -> Result<Self> {
+        let path = path.into();
+        let entries = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            gamma,
+            path,
+            ttl,
+            entries: RwLock::new(entries),
+            leagues: OnceCell::new(),
+        })
+    }
+
+    /// `MARKET_METADATA_PATH` (default `market_metadata.json`) and
+    /// `MARKET_METADATA_TTL_SECS` (default 3600)
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var("MARKET_METADATA_PATH")
+            .unwrap_or_else(|_| "market_metadata.json".to_string());
+        let ttl = std::env::var("MARKET_METADATA_TTL_SECS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()
+            .unwrap_or(3600);
+        Self::load(GammaClient::from_env(), path, Duration::from_secs(ttl))
+    }
+
+    /// Cached metadata, however old; never touches the network
+    pub async fn get(&self, token_id: &str) -> Option<TokenMeta> {
+        self.entries.read().await.get(token_id).cloned()
+    }
+
+    /// Cached metadata, fetching it on a miss
+    pub async fn get_or_fetch(&self, token_id: &str) -> Result<TokenMeta> {
+        if let Some(meta) = self.get(token_id).await {
+            return Ok(meta);
+        }
+        self.fetch(&[token_id.to_string()]).await?;
+        self.get(token_id)
+            .await
+            .ok_or_else(|| anyhow!("No market found for token {}", token_id))
+    }
+
+    /// Fetch any of `token_ids` not yet cached, e.g. before trading them
+    pub async fn warm(&self, token_ids: &[String]) -> Result<()> {
+        let cached = self.entries.read().await;
+        let missing: Vec<String> = token_ids
+            .iter()
+            .filter(|t| !cached.contains_key(*t))
+            .cloned()
+            .collect();
+        drop(cached);
+        if missing.is_empty() {
+            return Ok(());
+        }
+        self.fetch(&missing).await
+    }
+
+    /// Store every token of `market` (e.g. from a screen) without a fetch
+    pub async fn insert_market(&self, market: &GammaMarket) -> Result<()> {
+        let leagues = self.leagues().await;
+        let mut entries = self.entries.write().await;
+        for meta in TokenMeta::from_market(market, &leagues) {
+            entries.insert(meta.token_id.clone(), meta);
+        }
+        self.save(&entries)
+    }
+
+    /// Refetch entries older than the TTL
+    pub async fn refresh_stale(&self) -> Result<usize> {
+        let stale: Vec<String> = self
+            .entries
+            .read()
+            .await
+            .values()
+            .filter(|m| m.is_stale(self.ttl))
+            .map(|m| m.token_id.clone())
+            .collect();
+        if !stale.is_empty() {
+            self.fetch(&stale).await?;
+        }
+        Ok(stale.len())
+    }
+
+    /// Refresh stale entries every `every`, forever
+    pub async fn run_refresh(self: Arc<Self>, every: Duration) {
+        let mut tick = tokio::time::interval(every);
+        loop {
+            tick.tick().await;
+            match self.refresh_stale().await {
+                Ok(0) => {}
+                Ok(n) => info!("🔄 Refreshed metadata for {} token(s)", n),
+                Err(e) => warn!("⚠️  Metadata refresh failed: {}", e),
+            }
+        }
+    }
+
+    async fn fetch(&self, token_ids: &[String]) -> Result<()> {
+        let markets = self.gamma.markets_by_tokens(token_ids).await?;
+        let leagues = self.leagues().await;
+        let mut entries = self.entries.write().await;
+        for market in &markets {
+            for meta in TokenMeta::from_market(market, &leagues) {
+                entries.insert(meta.token_id.clone(), meta);
+            }
+        }
+        self.save(&entries)
+    }
+
+    /// League lookup; retried on the next call if `/sports` fails, with
+    /// sports markets left unlabelled meanwhile
+    async fn leagues(&self) -> Leagues {
+        let loaded = self
+            .leagues
+            .get_or_try_init(|| async {
+                self.gamma.sports().await.map(|sports| Leagues::new(&sports))
+            })
+            .await;
+        match loaded {
+            Ok(leagues) => leagues.clone(),
+            Err(e) => {
+                warn!("⚠️  Failed to load sports leagues: {}", e);
+                Leagues::default()
+            }
+        }
+    }
+
+    fn save(&self, entries: &HashMap<String, TokenMeta>) -> Result<()> {
+        // Write-then-rename so a crash mid-write keeps the old file
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(entries)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
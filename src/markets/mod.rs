@@ -0,0 +1,25 @@
+pub mod events;
+pub mod expiry;
+pub mod gamma;
+pub mod ids;
+pub mod listings;
+pub mod metadata;
+pub mod resolution;
+pub mod screener;
+pub mod sports;
+pub mod tags;
+pub mod uma;
+
+pub use events::{question_index, EventIndex, NegRiskConversion, NegRiskGroup, NegRiskLeg};
+pub use expiry::{ExpiryConfig, ExpiryGuard, QuoteAction};
+pub use gamma::{GammaClient, GammaEvent, GammaMarket, GammaSport, GammaTag, MarketQuery};
+pub use ids::{binary_token_ids, collection_id, condition_id, position_id, verify_market, IdCheck};
+pub use listings::ListingWatcher;
+pub use metadata::{MetadataCache, TokenMeta};
+pub use resolution::{
+    read_payouts, verify_payouts, ResolutionCTF, ResolutionEvent, ResolutionStatus, ResolutionWatcher,
+};
+pub use screener::{Candidate, Screener, ScreenerConfig};
+pub use sports::{GamePhase, Leagues, SportsInfo};
+pub use tags::TagFilter;
+pub use uma::{UmaAlert, UmaAlertKind, UmaMonitor};
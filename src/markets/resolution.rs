@@ -0,0 +1,286 @@
+use anyhow::{anyhow, Result};
+use ethers::prelude::*;
+use ethers::types::{H256, U256};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+
+use super::gamma::{GammaClient, GammaMarket};
+use crate::config::ChainConfig;
+
+/// Resolution events kept for slow subscribers
+const UPDATE_BUFFER: usize = 1024;
+/// On-chain and reported payout fractions must agree this closely
+const PAYOUT_TOLERANCE: f64 = 1e-6;
+
+abigen!(
+    ResolutionCTF,
+    r#"[
+        function payoutDenominator(bytes32 conditionId) view returns (uint256)
+        function payoutNumerators(bytes32 conditionId, uint256 index) view returns (uint256)
+    ]"#
+);
+
+// ==================================================
+// MARKET RESOLUTION
+// ==================================================
+
+/// Where a market is on its way to redemption
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolutionStatus {
+    Open,
+    /// Trading halted, no outcome proposed yet
+    Closed,
+    /// UMA proposal pending its challenge window
+    Proposed,
+    Disputed,
+    /// Payout reported on the CTF; positions are redeemable. One
+    /// fraction per outcome slot, summing to 1.
+    Resolved { payouts: Vec<f64> },
+}
+
+impl ResolutionStatus {
+    /// Quoting must stop: anything past `Open`
+    pub fn is_halted(&self) -> bool {
+        *self != ResolutionStatus::Open
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, ResolutionStatus::Resolved { .. })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResolutionStatus::Open => "open",
+            ResolutionStatus::Closed => "closed",
+            ResolutionStatus::Proposed => "proposed",
+            ResolutionStatus::Disputed => "disputed",
+            ResolutionStatus::Resolved { .. } => "resolved",
+        }
+    }
+
+    /// Status as far as Gamma knows; on-chain payouts override it
+    fn from_gamma(market: &GammaMarket) -> Self {
+        match market.uma_resolution_status.as_deref() {
+            Some(s) if s.eq_ignore_ascii_case("disputed") => ResolutionStatus::Disputed,
+            Some(s) if s.eq_ignore_ascii_case("proposed") => ResolutionStatus::Proposed,
+            // Resolved by the oracle but not yet reported to the CTF
+            Some(s) if s.eq_ignore_ascii_case("resolved") => ResolutionStatus::Proposed,
+            _ if market.closed => ResolutionStatus::Closed,
+            _ => ResolutionStatus::Open,
+        }
+    }
+}
+
+/// A watched market changed resolution status
+#[derive(Debug, Clone)]
+pub struct ResolutionEvent {
+    pub condition_id: String,
+    pub question: String,
+    /// Outcome tokens, in payout slot order
+    pub token_ids: Vec<String>,
+    pub previous: ResolutionStatus,
+    pub status: ResolutionStatus,
+}
+
+struct Watched {
+    question: String,
+    token_ids: Vec<String>,
+    status: ResolutionStatus,
+}
+
+/// Polls the markets positions are held in and broadcasts every
+/// resolution status change.
+///
+/// Gamma reports trading halts and the UMA proposal/dispute state; the
+/// CTF's `payoutDenominator` is the final word that a payout has been
+/// reported and positions can be redeemed. Strategies should stop
+/// quoting on any halted status and hand `Resolved` markets to the
+/// redemption flow.
+pub struct ResolutionWatcher<M: Middleware> {
+    gamma: GammaClient,
+    ctf: ResolutionCTF<M>,
+    watched: RwLock<HashMap<String, Watched>>,
+    updates: broadcast::Sender<ResolutionEvent>,
+}
+
+impl<M: Middleware + 'static> ResolutionWatcher<M> {
+    pub fn new(gamma: GammaClient, provider: Arc<M>, chain: &ChainConfig) -> Self {
+        Self {
+            gamma,
+            ctf: ResolutionCTF::new(chain.ctf, provider),
+            watched: RwLock::new(HashMap::new()),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        }
+    }
+
+    pub fn from_env(provider: Arc<M>, chain: &ChainConfig) -> Self {
+        Self::new(GammaClient::from_env(), provider, chain)
+    }
+
+    /// Poll interval from `RESOLUTION_POLL_SECS` (default 60)
+    pub fn poll_interval() -> Duration {
+        let secs = std::env::var("RESOLUTION_POLL_SECS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()
+            .unwrap_or(60);
+        Duration::from_secs(secs)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ResolutionEvent> {
+        self.updates.subscribe()
+    }
+
+    /// Start watching a market; its current status is checked on the
+    /// next poll and broadcast if it isn't `Open`
+    pub async fn watch(&self, condition_id: &str) {
+        self.watched
+            .write()
+            .await
+            .entry(condition_id.to_string())
+            .or_insert_with(|| Watched {
+                question: String::new(),
+                token_ids: vec![],
+                status: ResolutionStatus::Open,
+            });
+    }
+
+    /// Stop watching, e.g. once redeemed
+    pub async fn unwatch(&self, condition_id: &str) {
+        self.watched.write().await.remove(condition_id);
+    }
+
+    /// Last polled status; `None` if not watched
+    pub async fn status(&self, condition_id: &str) -> Option<ResolutionStatus> {
+        self.watched
+            .read()
+            .await
+            .get(condition_id)
+            .map(|w| w.status.clone())
+    }
+
+    /// Whether quoting on `condition_id` must stop
+    pub async fn is_halted(&self, condition_id: &str) -> bool {
+        self.status(condition_id)
+            .await
+            .is_some_and(|s| s.is_halted())
+    }
+
+    /// Check every watched market once; returns the changes, which are
+    /// also broadcast
+    pub async fn poll(&self) -> Result<Vec<ResolutionEvent>> {
+        let ids: Vec<String> = self.watched.read().await.keys().cloned().collect();
+        let mut changes = Vec::new();
+
+        for condition_id in ids {
+            let (market, status) = match self.check(&condition_id).await {
+                Ok(checked) => checked,
+                Err(e) => {
+                    warn!("⚠️  Resolution check failed for {}: {}", condition_id, e);
+                    continue;
+                }
+            };
+
+            let mut watched = self.watched.write().await;
+            let Some(entry) = watched.get_mut(&condition_id) else {
+                continue; // unwatched meanwhile
+            };
+            entry.question = market.question.clone();
+            entry.token_ids = market.token_ids();
+            if entry.status == status {
+                continue;
+            }
+
+            let event = ResolutionEvent {
+                condition_id: condition_id.clone(),
+                question: entry.question.clone(),
+                token_ids: entry.token_ids.clone(),
+                previous: std::mem::replace(&mut entry.status, status.clone()),
+                status,
+            };
+            info!(
+                "⚖️  {}: {} → {}",
+                event.question,
+                event.previous.as_str(),
+                event.status.as_str()
+            );
+            let _ = self.updates.send(event.clone());
+            changes.push(event);
+        }
+        Ok(changes)
+    }
+
+    /// Poll every `every`, forever
+    pub async fn run(self: Arc<Self>, every: Duration) {
+        let mut tick = tokio::time::interval(every);
+        loop {
+            tick.tick().await;
+            if let Err(e) = self.poll().await {
+                warn!("⚠️  Resolution poll failed: {}", e);
+            }
+        }
+    }
+
+    async fn check(&self, condition_id: &str) -> Result<(GammaMarket, ResolutionStatus)> {
+        let market = self.gamma.market_by_condition(condition_id).await?;
+        let slots = market.token_ids().len().max(2);
+        let status = match self.payouts(condition_id, slots).await? {
+            Some(payouts) => ResolutionStatus::Resolved { payouts },
+            None => ResolutionStatus::from_gamma(&market),
+        };
+        Ok((market, status))
+    }
+
+    async fn payouts(&self, condition_id: &str, slots: usize) -> Result<Option<Vec<f64>>> {
+        read_payouts(&self.ctf, condition_id, slots).await
+    }
+}
+
+/// Payout fractions reported on the CTF for each of `slots` outcomes, or
+/// `None` while unreported
+pub async fn read_payouts<M: Middleware + 'static>(
+    ctf: &ResolutionCTF<M>,
+    condition_id: &str,
+    slots: usize,
+) -> Result<Option<Vec<f64>>> {
+    let id = H256::from_str(condition_id)
+        .map_err(|e| anyhow!("Bad condition id {}: {}", condition_id, e))?
+        .0;
+    let denominator = ctf.payout_denominator(id).call().await?;
+    if denominator.is_zero() {
+        return Ok(None);
+    }
+
+    let mut payouts = Vec::with_capacity(slots);
+    for i in 0..slots {
+        let numerator = ctf.payout_numerators(id, U256::from(i)).call().await?;
+        payouts.push(numerator.as_u128() as f64 / denominator.as_u128() as f64);
+    }
+    Ok(Some(payouts))
+}
+
+/// Check the on-chain payout against the resolution reported off-chain
+/// (Gamma's final outcome prices). Errors when either is missing or
+/// they disagree on any outcome.
+pub fn verify_payouts(onchain: Option<&[f64]>, reported: &[f64]) -> Result<()> {
+    let onchain = onchain.ok_or_else(|| anyhow!("No payout reported on-chain yet"))?;
+    if reported.is_empty() {
+        return Err(anyhow!("No off-chain resolution to compare against"));
+    }
+    if onchain.len() != reported.len()
+        || onchain
+            .iter()
+            .zip(reported)
+            .any(|(a, b)| (a - b).abs() > PAYOUT_TOLERANCE)
+    {
+        return Err(anyhow!(
+            "On-chain payout {:?} disagrees with reported resolution {:?}",
+            onchain,
+            reported
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use anyhow::Result;
+use chrono::{Duration as ChronoDuration, Utc};
+use log::info;
+
+use super::gamma::{GammaClient, GammaMarket, MarketQuery};
+use super::tags::TagFilter;
+use crate::config::Config;
+use crate::execution::orderbook::{fetch_books, Level, OrderBook};
+
+const CLOB_API_URL: &str = "https://clob.polymarket.com";
+
+// ==================================================
+// MARKET SCREENER
+// ==================================================
+
+/// Thresholds a market must meet to be quoted
+#[derive(Debug, Clone)]
+pub struct ScreenerConfig {
+    /// USDC traded over the last 24h
+    pub min_volume_24h: f64,
+    /// USDC resting within `depth_cents` of the touch, both sides together
+    pub min_depth_usdc: f64,
+    pub depth_cents: f64,
+    /// Widest acceptable spread, in price units (0.02 = 2¢)
+    pub max_spread: f64,
+    pub min_days_to_resolution: f64,
+    pub max_days_to_resolution: f64,
+    /// Market types this screen looks for
+    pub tags: TagFilter,
+}
+
+impl Default for ScreenerConfig {
+    fn default() -> Self {
+        Self {
+            min_volume_24h: 1_000.0,
+            min_depth_usdc: 200.0,
+            depth_cents: 2.0,
+            max_spread: 0.05,
+            min_days_to_resolution: 0.0,
+            max_days_to_resolution: 90.0,
+            tags: TagFilter::default(),
+        }
+    }
+}
+
+impl ScreenerConfig {
+    /// `SCREEN_MIN_VOLUME_24H`, `SCREEN_MIN_DEPTH_USDC`,
+    /// `SCREEN_DEPTH_CENTS`, `SCREEN_MAX_SPREAD`, `SCREEN_MIN_DAYS` and
+    /// `SCREEN_MAX_DAYS`, plus `SCREEN_INCLUDE_TAGS` /
+    /// `SCREEN_EXCLUDE_TAGS`; unset ones keep their defaults
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |key: &str, default: f64| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            min_volume_24h: var("SCREEN_MIN_VOLUME_24H", d.min_volume_24h),
+            min_depth_usdc: var("SCREEN_MIN_DEPTH_USDC", d.min_depth_usdc),
+            depth_cents: var("SCREEN_DEPTH_CENTS", d.depth_cents),
+            max_spread: var("SCREEN_MAX_SPREAD", d.max_spread),
+            min_days_to_resolution: var("SCREEN_MIN_DAYS", d.min_days_to_resolution),
+            max_days_to_resolution: var("SCREEN_MAX_DAYS", d.max_days_to_resolution),
+            tags: TagFilter::from_env("SCREEN"),
+        }
+    }
+}
+
+/// A market that passed the screen, measured on its first outcome's book
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub market: GammaMarket,
+    pub token_id: String,
+    pub spread: f64,
+    pub depth_usdc: f64,
+    pub volume_24h: f64,
+    pub days_to_resolution: f64,
+    /// Ranking score, higher is better
+    pub score: f64,
+}
+
+/// Lists open markets from Gamma, drops the ones failing `config` and
+/// ranks the rest.
+///
+/// Volume, resolution date and tags are screened on the Gamma listing
+/// first; spread and depth are then measured on live CLOB books for the
+/// survivors only. Markets outside the operator's approved types
+/// (`risk_tags`) never pass, whatever the screen's own tags say.
+pub struct Screener {
+    gamma: GammaClient,
+    clob_url: String,
+    config: ScreenerConfig,
+    risk_tags: TagFilter,
+}
+
+impl Screener {
+    pub fn new(gamma: GammaClient, clob_url: impl Into<String>, config: ScreenerConfig) -> Self {
+        Self {
+            gamma,
+            clob_url: clob_url.into(),
+            config,
+            risk_tags: TagFilter::default(),
+        }
+    }
+
+    /// Restrict candidates to approved market types
+    pub fn risk_tags(mut self, filter: TagFilter) -> Self {
+        self.risk_tags = filter;
+        self
+    }
+
+    /// Gamma and CLOB URLs, thresholds and the risk tag filter from the
+    /// environment
+    pub fn from_env() -> Self {
+        let clob_url = std::env::var("CLOB_API_URL").unwrap_or_else(|_| CLOB_API_URL.to_string());
+        Self::new(GammaClient::from_env(), clob_url, ScreenerConfig::from_env())
+            .risk_tags(Config::market_tag_filter())
+    }
+
+    pub fn config(&self) -> &ScreenerConfig {
+        &self.config
+    }
+
+    /// Candidates, best first
+    pub async fn screen(&self) -> Result<Vec<Candidate>> {
+        let cfg = &self.config;
+        let now = Utc::now();
+        let in_days = |d: f64| ChronoDuration::seconds((d * 86_400.0) as i64);
+
+        let query = MarketQuery::open().ending_between(
+            Some(now + in_days(cfg.min_days_to_resolution)),
+            Some(now + in_days(cfg.max_days_to_resolution)),
+        );
+        let listed = self.gamma.all_markets(&query).await?;
+        let total = listed.len();
+
+        let markets: Vec<(GammaMarket, String, f64)> = listed
+            .into_iter()
+            .filter(|m| m.is_tradeable())
+            .filter(|m| self.risk_tags.allows(m) && cfg.tags.allows(m))
+            .filter(|m| m.volume_24h.unwrap_or_default() >= cfg.min_volume_24h)
+            // Gamma's quote can lag, so only drop clearly wide markets here
+            .filter(|m| match (m.best_bid, m.best_ask) {
+                (Some(b), Some(a)) => a - b <= cfg.max_spread * 2.0,
+                _ => true,
+            })
+            .filter_map(|m| {
+                let days = (m.end_date()? - now).num_seconds() as f64 / 86_400.0;
+                let token = m.token_ids().into_iter().next()?;
+                let in_range =
+                    days >= cfg.min_days_to_resolution && days <= cfg.max_days_to_resolution;
+                in_range.then_some((m, token, days))
+            })
+            .collect();
+
+        let tokens: Vec<String> = markets.iter().map(|(_, t, _)| t.clone()).collect();
+        let books = fetch_books(&self.clob_url, &tokens).await?;
+
+        let mut candidates: Vec<Candidate> = markets
+            .into_iter()
+            .filter_map(|(market, token_id, days)| {
+                let book = books.get(&token_id)?;
+                let spread = book.spread()?;
+                let depth_usdc = depth_usdc(book, cfg.depth_cents);
+                if spread > cfg.max_spread || depth_usdc < cfg.min_depth_usdc {
+                    return None;
+                }
+                let volume_24h = market.volume_24h.unwrap_or_default();
+                let tick = market.order_price_min_tick_size.unwrap_or(0.01);
+                Some(Candidate {
+                    score: score(volume_24h, depth_usdc, spread, tick),
+                    market,
+                    token_id,
+                    spread,
+                    depth_usdc,
+                    volume_24h,
+                    days_to_resolution: days,
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        info!(
+            "🔎 Screened {} markets: {} candidate(s)",
+            total,
+            candidates.len()
+        );
+        Ok(candidates)
+    }
+}
+
+/// USDC resting within `cents` of the touch on both sides
+fn depth_usdc(book: &OrderBook, cents: f64) -> f64 {
+    let reach = cents / 100.0 + 1e-9;
+    let notional = |levels: &[Level]| -> f64 {
+        let Some(best) = levels.first() else {
+            return 0.0;
+        };
+        levels
+            .iter()
+            .take_while(|l| (l.price - best.price).abs() <= reach)
+            .map(|l| l.price * l.size)
+            .sum()
+    };
+    notional(&book.bids) + notional(&book.asks)
+}
+
+/// Geometric mean of 24h volume and depth per tick of spread: liquid,
+/// active, tight markets first
+fn score(volume_24h: f64, depth_usdc: f64, spread: f64, tick: f64) -> f64 {
+    let ticks = (spread / tick).round().max(1.0);
+    (volume_24h * depth_usdc).sqrt() / ticks
+}
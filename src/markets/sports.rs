@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::gamma::{GammaMarket, GammaSport};
+
+// ==================================================
+// SPORTS METADATA
+// ==================================================
+
+/// Before or after kick-off; strategies quote very differently in play
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamePhase {
+    PreGame,
+    InGame,
+}
+
+/// League, teams and game time of a sports market
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SportsInfo {
+    /// League code from `/sports`, e.g. `nba`
+    pub league: Option<String>,
+    /// Team names as they appear in the event title, in title order
+    pub teams: Vec<String>,
+    /// ISO-8601 kick-off
+    pub game_start: Option<String>,
+    /// Bet type, e.g. `moneyline`
+    pub market_type: Option<String>,
+}
+
+impl SportsInfo {
+    /// Sports details of `market`, or `None` if it isn't a sports market
+    pub fn from_market(market: &GammaMarket, leagues: &Leagues) -> Option<Self> {
+        let event = market.events.first();
+        let tag_ids = market
+            .tags
+            .iter()
+            .chain(event.iter().flat_map(|e| e.tags.iter()))
+            .filter_map(|t| t.id.as_deref());
+        let league = leagues.league_of(tag_ids);
+        let game_start = market
+            .game_start_time
+            .clone()
+            .or_else(|| event.and_then(|e| e.start_time.clone()));
+
+        if league.is_none() && game_start.is_none() && market.sports_market_type.is_none() {
+            return None;
+        }
+        Some(Self {
+            league,
+            teams: event.map(|e| parse_teams(&e.title)).unwrap_or_default(),
+            game_start,
+            market_type: market.sports_market_type.clone(),
+        })
+    }
+
+    pub fn game_start(&self) -> Option<DateTime<Utc>> {
+        let start = self.game_start.as_deref()?;
+        DateTime::parse_from_rfc3339(start)
+            .ok()
+            .map(|d| d.with_timezone(&Utc))
+    }
+
+    /// `None` when the game time is unknown
+    pub fn phase(&self, now: DateTime<Utc>) -> Option<GamePhase> {
+        let start = self.game_start()?;
+        Some(if now < start {
+            GamePhase::PreGame
+        } else {
+            GamePhase::InGame
+        })
+    }
+}
+
+/// Tag id → league code, from the `/sports` listing.
+///
+/// Tags shared by several leagues (the generic "Sports" and "Games"
+/// tags) are dropped so a tag match pins down one league.
+#[derive(Debug, Clone, Default)]
+pub struct Leagues {
+    by_tag: HashMap<String, String>,
+}
+
+impl Leagues {
+    pub fn new(sports: &[GammaSport]) -> Self {
+        let mut leagues_per_tag: HashMap<String, Vec<&str>> = HashMap::new();
+        for sport in sports {
+            for tag in sport.tag_ids() {
+                leagues_per_tag.entry(tag).or_default().push(&sport.sport);
+            }
+        }
+        let by_tag = leagues_per_tag
+            .into_iter()
+            .filter(|(_, leagues)| leagues.len() == 1)
+            .map(|(tag, leagues)| (tag, leagues[0].to_string()))
+            .collect();
+        Self { by_tag }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_tag.is_empty()
+    }
+
+    /// First league matching any of `tag_ids`
+    pub fn league_of<'a>(&self, mut tag_ids: impl Iterator<Item = &'a str>) -> Option<String> {
+        tag_ids.find_map(|t| self.by_tag.get(t).cloned())
+    }
+}
+
+/// `"Lakers vs. Celtics"` → `["Lakers", "Celtics"]`; empty when the
+/// title isn't a matchup
+pub fn parse_teams(title: &str) -> Vec<String> {
+    for sep in [" vs. ", " vs ", " v ", " @ "] {
+        if let Some((a, b)) = title.split_once(sep) {
+            // Drop a leading "NBA: " and trailing "(Game 3)" or ": 1st Half"
+            let first = a.rsplit(':').next().unwrap_or_default().trim();
+            let second = b.split(['(', ':']).next().unwrap_or_default().trim();
+            return [first.to_string(), second.to_string()]
+                .into_iter()
+                .filter(|t| !t.is_empty())
+                .collect();
+        }
+    }
+    vec![]
+}
@@ -0,0 +1,62 @@
+use super::gamma::GammaMarket;
+
+// ==================================================
+// TAG / CATEGORY FILTERS
+// ==================================================
+
+/// Include/exclude lists over market tags and category, matched
+/// case-insensitively against tag slugs, tag labels and the category.
+///
+/// An empty include list admits every market; exclusions always win.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl TagFilter {
+    pub fn new<S: AsRef<str>>(include: &[S], exclude: &[S]) -> Self {
+        let norm = |v: &[S]| v.iter().map(|s| s.as_ref().trim().to_lowercase()).collect();
+        Self {
+            include: norm(include),
+            exclude: norm(exclude),
+        }
+    }
+
+    /// Comma-separated `{prefix}_INCLUDE_TAGS` and `{prefix}_EXCLUDE_TAGS`
+    pub fn from_env(prefix: &str) -> Self {
+        let list = |key: String| -> Vec<String> {
+            std::env::var(key)
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+        Self {
+            include: list(format!("{}_INCLUDE_TAGS", prefix)),
+            exclude: list(format!("{}_EXCLUDE_TAGS", prefix)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether a market carrying `labels` (tags and category) passes
+    pub fn allows_labels(&self, labels: &[String]) -> bool {
+        let has = |wanted: &[String]| {
+            labels
+                .iter()
+                .any(|l| wanted.iter().any(|w| l.eq_ignore_ascii_case(w)))
+        };
+        if has(&self.exclude) {
+            return false;
+        }
+        self.include.is_empty() || has(&self.include)
+    }
+
+    pub fn allows(&self, market: &GammaMarket) -> bool {
+        self.is_empty() || self.allows_labels(&market.labels())
+    }
+}
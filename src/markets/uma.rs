@@ -0,0 +1,346 @@
+use anyhow::{anyhow, Result};
+use ethers::contract::EthLogDecode;
+use ethers::prelude::*;
+use ethers::types::{Address, Filter, H256, I256, U64};
+use ethers::utils::keccak256;
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex, RwLock};
+
+use super::gamma::GammaMarket;
+use crate::config::ChainConfig;
+
+/// Alerts kept for slow subscribers
+const UPDATE_BUFFER: usize = 1024;
+/// Blocks per `eth_getLogs` call; public Polygon RPCs cap the range
+const LOG_RANGE: u64 = 2_000;
+
+abigen!(
+    UmaAdapter,
+    r#"[
+        event QuestionInitialized(bytes32 indexed questionID, uint256 indexed requestTimestamp, address indexed creator, bytes ancillaryData, address rewardToken, uint256 reward, uint256 proposalBond)
+        event QuestionReset(bytes32 indexed questionID)
+        event QuestionFlagged(bytes32 indexed questionID)
+        event QuestionResolved(bytes32 indexed questionID, int256 indexed settledPrice, uint256[] payouts)
+        event QuestionEmergencyResolved(bytes32 indexed questionID, uint256[] payouts)
+    ]"#
+);
+
+abigen!(
+    OptimisticOracle,
+    r#"[
+        event ProposePrice(address indexed requester, address indexed proposer, bytes32 identifier, uint256 timestamp, bytes ancillaryData, int256 proposedPrice, uint256 expirationTimestamp, address currency)
+        event DisputePrice(address indexed requester, address indexed proposer, address indexed disputer, bytes32 identifier, uint256 timestamp, bytes ancillaryData, int256 proposedPrice)
+    ]"#
+);
+
+// ==================================================
+// UMA ORACLE MONITOR
+// ==================================================
+
+/// What happened to a watched question on the oracle
+#[derive(Debug, Clone, PartialEq)]
+pub enum UmaAlertKind {
+    /// Outcome proposed; settles at `expires_at` (unix secs) unless
+    /// disputed. `price` is the proposed YES payout (1.0, 0.0 or 0.5).
+    Proposed { price: f64, expires_at: u64 },
+    /// Proposal challenged; the outcome is contested
+    Disputed { price: f64 },
+    /// Adapter re-requested a price after a dispute
+    Reset,
+    /// Flagged by the admin for manual resolution
+    Flagged,
+    /// Settled; payouts reported to the CTF
+    Resolved { payouts: Vec<u64> },
+}
+
+impl UmaAlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UmaAlertKind::Proposed { .. } => "proposed",
+            UmaAlertKind::Disputed { .. } => "disputed",
+            UmaAlertKind::Reset => "reset",
+            UmaAlertKind::Flagged => "flagged",
+            UmaAlertKind::Resolved { .. } => "resolved",
+        }
+    }
+}
+
+/// An oracle event on a market we hold
+#[derive(Debug, Clone)]
+pub struct UmaAlert {
+    pub condition_id: String,
+    pub question_id: H256,
+    pub question: String,
+    pub kind: UmaAlertKind,
+    pub block: u64,
+    pub tx_hash: Option<H256>,
+}
+
+#[derive(Debug, Clone)]
+struct WatchedQuestion {
+    condition_id: String,
+    question: String,
+}
+
+/// Follows UMA adapter and optimistic-oracle logs for held markets and
+/// broadcasts proposals, disputes and settlements.
+///
+/// Prices often gap while a resolution is being fought over, so a
+/// dispute on a market we're exposed to is logged as a warning and the
+/// market stays `in_dispute` until it resolves.
+pub struct UmaMonitor<M: Middleware> {
+    provider: Arc<M>,
+    adapters: Vec<Address>,
+    oracle: Option<Address>,
+    lookback: u64,
+    watched: RwLock<HashMap<H256, WatchedQuestion>>,
+    disputed: RwLock<HashSet<String>>,
+    // Next block to scan; `None` until the first poll
+    next_block: Mutex<Option<u64>>,
+    updates: broadcast::Sender<UmaAlert>,
+}
+
+impl<M: Middleware + 'static> UmaMonitor<M> {
+    pub fn new(provider: Arc<M>, chain: &ChainConfig) -> Self {
+        Self {
+            provider,
+            adapters: chain.uma_adapters.clone(),
+            oracle: chain.uma_oracle,
+            lookback: 0,
+            watched: RwLock::new(HashMap::new()),
+            disputed: RwLock::new(HashSet::new()),
+            next_block: Mutex::new(None),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        }
+    }
+
+    /// Scan this many blocks back on the first poll, to catch proposals
+    /// made before startup
+    pub fn lookback(mut self, blocks: u64) -> Self {
+        self.lookback = blocks;
+        self
+    }
+
+    /// `UMA_LOOKBACK_BLOCKS` (default 43200, about a day on Polygon)
+    pub fn from_env(provider: Arc<M>, chain: &ChainConfig) -> Self {
+        let lookback = std::env::var("UMA_LOOKBACK_BLOCKS")
+            .unwrap_or_else(|_| "43200".to_string())
+            .parse()
+            .unwrap_or(43_200);
+        Self::new(provider, chain).lookback(lookback)
+    }
+
+    /// Poll interval from `UMA_POLL_SECS` (default 30)
+    pub fn poll_interval() -> Duration {
+        let secs = std::env::var("UMA_POLL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+        Duration::from_secs(secs)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<UmaAlert> {
+        self.updates.subscribe()
+    }
+
+    /// Watch a market's question; markets without a UMA question id
+    /// can't be followed
+    pub async fn watch(&self, market: &GammaMarket) -> Result<()> {
+        let raw = market
+            .question_id
+            .as_deref()
+            .ok_or_else(|| anyhow!("Market {} has no UMA question id", market.condition_id))?;
+        let question_id =
+            H256::from_str(raw).map_err(|e| anyhow!("Bad question id {}: {}", raw, e))?;
+        self.watched.write().await.insert(
+            question_id,
+            WatchedQuestion {
+                condition_id: market.condition_id.clone(),
+                question: market.question.clone(),
+            },
+        );
+        Ok(())
+    }
+
+    pub async fn unwatch(&self, condition_id: &str) {
+        self.watched
+            .write()
+            .await
+            .retain(|_, q| q.condition_id != condition_id);
+        self.disputed.write().await.remove(condition_id);
+    }
+
+    /// Whether a dispute on `condition_id` is still unresolved
+    pub async fn in_dispute(&self, condition_id: &str) -> bool {
+        self.disputed.read().await.contains(condition_id)
+    }
+
+    /// Scan new blocks once; returns the alerts, which are also broadcast
+    pub async fn poll(&self) -> Result<Vec<UmaAlert>> {
+        let head = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+            .as_u64();
+
+        let mut next = self.next_block.lock().await;
+        let mut from = next.unwrap_or_else(|| head.saturating_sub(self.lookback));
+        let mut alerts = Vec::new();
+
+        while from <= head {
+            let to = (from + LOG_RANGE - 1).min(head);
+            alerts.extend(self.scan(from, to).await?);
+            from = to + 1;
+            // Progress survives a failed later chunk
+            *next = Some(from);
+        }
+        drop(next);
+
+        for alert in &alerts {
+            self.record(alert).await;
+        }
+        Ok(alerts)
+    }
+
+    /// Poll every `every`, forever
+    pub async fn run(self: Arc<Self>, every: Duration) {
+        let mut tick = tokio::time::interval(every);
+        loop {
+            tick.tick().await;
+            if let Err(e) = self.poll().await {
+                warn!("⚠️  UMA poll failed: {}", e);
+            }
+        }
+    }
+
+    async fn scan(&self, from: u64, to: u64) -> Result<Vec<UmaAlert>> {
+        let watched = self.watched.read().await.clone();
+        if watched.is_empty() || self.adapters.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut alerts = Vec::new();
+
+        let adapter_logs = self
+            .logs(Filter::new().address(self.adapters.clone()), from, to)
+            .await?;
+        for log in adapter_logs {
+            let (block, tx_hash) = (log.block_number, log.transaction_hash);
+            let Ok(event) = UmaAdapterEvents::decode_log(&log.into()) else {
+                continue;
+            };
+            let (question_id, kind) = match event {
+                UmaAdapterEvents::QuestionResetFilter(e) => (e.question_id, UmaAlertKind::Reset),
+                UmaAdapterEvents::QuestionFlaggedFilter(e) => (e.question_id, UmaAlertKind::Flagged),
+                UmaAdapterEvents::QuestionResolvedFilter(e) => (
+                    e.question_id,
+                    UmaAlertKind::Resolved {
+                        payouts: e.payouts.iter().map(|p| p.as_u64()).collect(),
+                    },
+                ),
+                UmaAdapterEvents::QuestionEmergencyResolvedFilter(e) => (
+                    e.question_id,
+                    UmaAlertKind::Resolved {
+                        payouts: e.payouts.iter().map(|p| p.as_u64()).collect(),
+                    },
+                ),
+                UmaAdapterEvents::QuestionInitializedFilter(_) => continue,
+            };
+            let question_id = H256::from(question_id);
+            if let Some(q) = watched.get(&question_id) {
+                alerts.push(alert(q, question_id, kind, block, tx_hash));
+            }
+        }
+
+        // Proposals and disputes are only visible on the oracle, keyed by
+        // the adapter's ancillary data
+        if let Some(oracle) = self.oracle {
+            let requesters: Vec<H256> = self.adapters.iter().map(|a| H256::from(*a)).collect();
+            let oracle_logs = self
+                .logs(Filter::new().address(oracle).topic1(requesters), from, to)
+                .await?;
+            for log in oracle_logs {
+                let (block, tx_hash) = (log.block_number, log.transaction_hash);
+                let Ok(event) = OptimisticOracleEvents::decode_log(&log.into()) else {
+                    continue;
+                };
+                let (ancillary, kind) = match event {
+                    OptimisticOracleEvents::ProposePriceFilter(e) => (
+                        e.ancillary_data,
+                        UmaAlertKind::Proposed {
+                            price: payout_price(e.proposed_price),
+                            expires_at: e.expiration_timestamp.as_u64(),
+                        },
+                    ),
+                    OptimisticOracleEvents::DisputePriceFilter(e) => (
+                        e.ancillary_data,
+                        UmaAlertKind::Disputed {
+                            price: payout_price(e.proposed_price),
+                        },
+                    ),
+                };
+                let question_id = H256::from(keccak256(&ancillary));
+                if let Some(q) = watched.get(&question_id) {
+                    alerts.push(alert(q, question_id, kind, block, tx_hash));
+                }
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    async fn logs(&self, filter: Filter, from: u64, to: u64) -> Result<Vec<Log>> {
+        self.provider
+            .get_logs(&filter.from_block(U64::from(from)).to_block(U64::from(to)))
+            .await
+            .map_err(|e| anyhow!("Failed to get logs {}..{}: {}", from, to, e))
+    }
+
+    async fn record(&self, alert: &UmaAlert) {
+        match &alert.kind {
+            UmaAlertKind::Disputed { price } => {
+                warn!(
+                    "⚔️  DISPUTE on held market: {} (proposed {:.1}) — expect gaps",
+                    alert.question, price
+                );
+                self.disputed
+                    .write()
+                    .await
+                    .insert(alert.condition_id.clone());
+            }
+            UmaAlertKind::Resolved { .. } => {
+                info!("⚖️  UMA resolved: {}", alert.question);
+                self.disputed.write().await.remove(&alert.condition_id);
+            }
+            kind => info!("⚖️  UMA {}: {}", kind.as_str(), alert.question),
+        }
+        let _ = self.updates.send(alert.clone());
+    }
+}
+
+fn alert(
+    q: &WatchedQuestion,
+    question_id: H256,
+    kind: UmaAlertKind,
+    block: Option<U64>,
+    tx_hash: Option<H256>,
+) -> UmaAlert {
+    UmaAlert {
+        condition_id: q.condition_id.clone(),
+        question_id,
+        question: q.question.clone(),
+        kind,
+        block: block.map(|b| b.as_u64()).unwrap_or_default(),
+        tx_hash,
+    }
+}
+
+/// Oracle prices are 18-decimal fixed point: 1e18 = YES, 0 = NO,
+/// 0.5e18 = unresolvable (50/50)
+fn payout_price(price: I256) -> f64 {
+    price.low_i128() as f64 / 1e18
+}
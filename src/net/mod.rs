@@ -0,0 +1,7 @@
+pub mod retry;
+pub mod rpc;
+pub mod ws;
+
+pub use retry::{RetryPolicy, SendRetry};
+pub use rpc::{RpcPool, RpcPoolConfig, RpcProvider, RpcStatus};
+pub use ws::{feeds, ConnectionEvent, ConnectionState, Connections, ReconnectPolicy, Reconnector};
@@ -0,0 +1,246 @@
+use log::warn;
+use rand::Rng;
+use reqwest::{RequestBuilder, Response, StatusCode, Url};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// ==================================================
+// HTTP RETRIES
+// ==================================================
+// Outbound requests go through `send_retry` instead of `send`:
+//
+//   timeout, connection error, 429, 5xx  →  retried
+//   anything else (4xx, bad request)     →  returned on the first try
+//
+// Waits double from HTTP_RETRY_BASE_MS up to HTTP_RETRY_MAX_MS with full
+// jitter (a uniform draw below the cap), so clients knocked over
+// together don't come back together. A longer Retry-After on a 429 wins.
+// Attempts stop at HTTP_RETRY_MAX_ATTEMPTS and the last response or
+// error goes back to the caller unchanged.
+//
+// Each endpoint (host and path, ids masked) may retry HTTP_RETRY_BUDGET
+// times per minute. Once that's spent, failures come back on the first
+// try: an outage costs one request per call, not a pile of retries.
+// Requests with a streamed body can't be replayed and go out once.
+//
+// Order posts aren't idempotent: a timeout or a 5xx may come after the
+// exchange took the order, and a resend would come back as a duplicate.
+// They go through `send_retry_unsent`, which only retries what the
+// server never acted on: connection failures and 429s.
+
+/// Longest Retry-After honoured
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts per request, the first included
+    pub max_attempts: u32,
+    /// Cap of the first retry's wait; doubles per retry
+    pub base: Duration,
+    /// Cap of any wait
+    pub max: Duration,
+    /// Retries per endpoint per minute
+    pub budget_per_min: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base: Duration::from_millis(200),
+            max: Duration::from_secs(5),
+            budget_per_min: 30,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `HTTP_RETRY_MAX_ATTEMPTS` (3), `HTTP_RETRY_BASE_MS` (200),
+    /// `HTTP_RETRY_MAX_MS` (5000), `HTTP_RETRY_BUDGET` (30 per minute)
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            max_attempts: var("HTTP_RETRY_MAX_ATTEMPTS").map_or(d.max_attempts, |n| n.max(1) as u32),
+            base: var("HTTP_RETRY_BASE_MS").map_or(d.base, Duration::from_millis),
+            max: var("HTTP_RETRY_MAX_MS").map_or(d.max, Duration::from_millis),
+            budget_per_min: var("HTTP_RETRY_BUDGET").map_or(d.budget_per_min, |n| n as u32),
+        }
+    }
+
+    /// Process-wide policy, read from the environment once
+    pub fn global() -> &'static RetryPolicy {
+        static POLICY: OnceLock<RetryPolicy> = OnceLock::new();
+        POLICY.get_or_init(RetryPolicy::from_env)
+    }
+
+    /// Wait before retry `n` (1 = first retry): uniform below
+    /// min(max, base × 2^(n-1))
+    pub fn backoff(&self, n: u32) -> Duration {
+        let cap = self
+            .base
+            .saturating_mul(2u32.saturating_pow(n.saturating_sub(1)))
+            .min(self.max);
+        Duration::from_millis(rand::rng().random_range(0..=cap.as_millis() as u64))
+    }
+}
+
+/// 429 and server errors that may pass on their own (not 501/505)
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status.is_server_error()
+            && status != StatusCode::NOT_IMPLEMENTED
+            && status != StatusCode::HTTP_VERSION_NOT_SUPPORTED)
+}
+
+/// Timeouts and failures to connect or send; not bad requests or bodies
+pub fn is_retryable_error(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect() || (e.is_request() && !e.is_builder())
+}
+
+/// Which failures a request may be resent after
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Replay {
+    /// Any transient failure (idempotent requests)
+    Transient,
+    /// Only failures that leave the request unprocessed: no connection,
+    /// or a 429
+    Unsent,
+}
+
+/// Why `result` is worth another try, and how long the server asked us
+/// to wait; `None` when it isn't
+fn retry_reason(result: &reqwest::Result<Response>, replay: Replay) -> Option<(String, Option<Duration>)> {
+    match result {
+        Ok(resp) if replay == Replay::Unsent && resp.status() != StatusCode::TOO_MANY_REQUESTS => None,
+        Err(e) if replay == Replay::Unsent && !e.is_connect() => None,
+        Ok(resp) if is_retryable_status(resp.status()) => {
+            let after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs);
+            Some((resp.status().to_string(), after))
+        }
+        Ok(_) => None,
+        Err(e) if is_retryable_error(e) => Some((e.to_string(), None)),
+        Err(_) => None,
+    }
+}
+
+/// Budget key: host and path, with id-like segments (hex, long numbers)
+/// masked so every order or token shares one budget
+pub fn endpoint(url: &Url) -> String {
+    let path: Vec<&str> = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .map(|s| {
+            let id = s.starts_with("0x") || (s.len() >= 8 && s.chars().all(|c| c.is_ascii_digit()));
+            if id {
+                ":id"
+            } else {
+                s
+            }
+        })
+        .collect();
+    format!("{}/{}", url.host_str().unwrap_or_default(), path.join("/"))
+}
+
+#[derive(Debug)]
+struct Budget {
+    tokens: f64,
+    updated: Instant,
+    // Warned that it ran out, since it last had a token
+    warned: bool,
+}
+
+/// Take one retry from `endpoint`'s budget; false (warning once) when
+/// it's spent
+fn take_retry(endpoint: &str, policy: &RetryPolicy) -> bool {
+    static BUDGETS: OnceLock<Mutex<HashMap<String, Budget>>> = OnceLock::new();
+    let cap = policy.budget_per_min as f64;
+    let mut budgets = BUDGETS.get_or_init(Default::default).lock().unwrap();
+    let now = Instant::now();
+    let budget = budgets.entry(endpoint.to_string()).or_insert(Budget {
+        tokens: cap,
+        updated: now,
+        warned: false,
+    });
+    budget.tokens = (budget.tokens + now.duration_since(budget.updated).as_secs_f64() * cap / 60.0).min(cap);
+    budget.updated = now;
+    if budget.tokens >= 1.0 {
+        budget.tokens -= 1.0;
+        budget.warned = false;
+        return true;
+    }
+    if !budget.warned {
+        warn!("⚠️  Retry budget for {} spent; failing fast until it refills", endpoint);
+        budget.warned = true;
+    }
+    false
+}
+
+/// `send` with retries per the global `RetryPolicy`
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    send_with(request, RetryPolicy::global(), Replay::Transient).await
+}
+
+pub async fn send_with(request: RequestBuilder, policy: &RetryPolicy, replay: Replay) -> reqwest::Result<Response> {
+    let endpoint = match request.try_clone().map(RequestBuilder::build) {
+        Some(Ok(built)) => endpoint(built.url()),
+        // A streamed body can't be replayed; a builder error should surface as is
+        _ => return request.send().await,
+    };
+
+    let mut attempt = 1;
+    loop {
+        let result = request.try_clone().expect("cloned above").send().await;
+        let Some((reason, after)) = retry_reason(&result, replay) else {
+            return result;
+        };
+        if attempt >= policy.max_attempts {
+            if policy.max_attempts > 1 {
+                warn!("❌ {} failed after {} attempts: {}", endpoint, attempt, reason);
+            }
+            return result;
+        }
+        if !take_retry(&endpoint, policy) {
+            return result;
+        }
+        let wait = policy
+            .backoff(attempt)
+            .max(after.unwrap_or_default().min(MAX_RETRY_AFTER));
+        warn!(
+            "🔁 {} {}: retry {}/{} in {}ms",
+            endpoint,
+            reason,
+            attempt,
+            policy.max_attempts - 1,
+            wait.as_millis()
+        );
+        tokio::time::sleep(wait).await;
+        attempt += 1;
+    }
+}
+
+/// `.send_retry()` wherever a request would be `.send()`
+pub trait SendRetry {
+    fn send_retry(self) -> impl Future<Output = reqwest::Result<Response>> + Send;
+
+    /// For requests that mustn't reach the server twice
+    fn send_retry_unsent(self) -> impl Future<Output = reqwest::Result<Response>> + Send;
+}
+
+impl SendRetry for RequestBuilder {
+    fn send_retry(self) -> impl Future<Output = reqwest::Result<Response>> + Send {
+        send(self)
+    }
+
+    fn send_retry_unsent(self) -> impl Future<Output = reqwest::Result<Response>> + Send {
+        send_with(self, RetryPolicy::global(), Replay::Unsent)
+    }
+}
@@ -0,0 +1,357 @@
+use anyhow::{anyhow, Result};
+use ethers::providers::{Http, HttpClientError, JsonRpcClient, Provider};
+use ethers::utils::keccak256;
+use futures_util::future::BoxFuture;
+use log::{info, warn};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{self, Debug};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use url::Url;
+
+// ==================================================
+// RPC FAILOVER
+// ==================================================
+// RPC_URL takes one or more comma-separated Polygon RPC URLs. Every
+// call goes to the healthiest one and moves on to the next when it
+// fails in a way another node may not:
+//
+//   timeout, connection error, unparseable reply, rate limit  →  next URL
+//   any other JSON-RPC error (revert, nonce too low, ...)     →  returned
+//
+// Health is a moving average of successes, discounted for replies
+// slower than RPC_SLOW_MS. RPC_MAX_FAILURES failures in a row bench a
+// URL for RPC_COOLDOWN_SECS; benched URLs are only tried once every
+// other one has failed. Ties go to the order the URLs were listed in,
+// so the first one stays primary while it's healthy.
+//
+// A raw transaction resent to another node is the same transaction:
+// when that node already has it, its hash is returned.
+
+/// Weight of the latest call in a URL's score
+const SCORE_ALPHA: f64 = 0.2;
+
+/// Score gaps smaller than this don't reorder URLs
+const SCORE_STEP: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RpcPoolConfig {
+    /// Per call, so a stalled node fails over instead of hanging
+    pub timeout: Duration,
+    /// Replies slower than this count against a URL's score
+    pub slow: Duration,
+    /// Failures in a row that bench a URL
+    pub max_failures: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for RpcPoolConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            slow: Duration::from_secs(2),
+            max_failures: 3,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RpcPoolConfig {
+    /// `RPC_TIMEOUT_MS` (10000), `RPC_SLOW_MS` (2000), `RPC_MAX_FAILURES`
+    /// (3), `RPC_COOLDOWN_SECS` (30)
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            timeout: var("RPC_TIMEOUT_MS").map_or(d.timeout, Duration::from_millis),
+            slow: var("RPC_SLOW_MS").map_or(d.slow, Duration::from_millis),
+            max_failures: var("RPC_MAX_FAILURES").map_or(d.max_failures, |n| n.max(1) as u32),
+            cooldown: var("RPC_COOLDOWN_SECS").map_or(d.cooldown, Duration::from_secs),
+        }
+    }
+}
+
+/// One URL's health, as reported by `RpcPool::status`
+#[derive(Debug, Clone)]
+pub struct RpcStatus {
+    /// Host only: paths and queries often carry API keys
+    pub label: String,
+    /// 0 (always failing or slow) to 1
+    pub score: f64,
+    /// Moving average, successful calls only
+    pub latency_ms: f64,
+    pub calls: u64,
+    pub failures: u64,
+    /// Benched for this much longer
+    pub benched: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Health {
+    score: f64,
+    latency_ms: f64,
+    failures_in_row: u32,
+    benched_until: Option<Instant>,
+    calls: u64,
+    failures: u64,
+}
+
+struct Endpoint {
+    http: Http,
+    label: String,
+    health: Mutex<Health>,
+}
+
+impl Debug for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Endpoint").field("label", &self.label).finish()
+    }
+}
+
+/// JSON-RPC transport over several HTTP RPCs, failing over between them
+#[derive(Debug)]
+pub struct RpcPool {
+    endpoints: Vec<Endpoint>,
+    config: RpcPoolConfig,
+    // Endpoint that answered last, to log switches
+    current: AtomicUsize,
+}
+
+pub type RpcProvider = Provider<RpcPool>;
+
+/// Provider over every URL in `urls` (comma-separated), configured from
+/// the environment
+pub fn provider(urls: &str) -> Result<RpcProvider> {
+    Ok(Provider::new(RpcPool::new(urls, RpcPoolConfig::from_env())?))
+}
+
+impl RpcPool {
+    pub fn new(urls: &str, config: RpcPoolConfig) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(config.timeout).build()?;
+        let endpoints = urls
+            .split(',')
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .map(|u| {
+                let url = Url::parse(u).map_err(|e| anyhow!("Invalid RPC URL {}: {}", u, e))?;
+                Ok(Endpoint {
+                    label: url.host_str().unwrap_or(u).to_string(),
+                    http: Http::new_with_client(url, client.clone()),
+                    health: Mutex::new(Health {
+                        score: 1.0,
+                        latency_ms: 0.0,
+                        failures_in_row: 0,
+                        benched_until: None,
+                        calls: 0,
+                        failures: 0,
+                    }),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if endpoints.is_empty() {
+            return Err(anyhow!("No RPC URL given"));
+        }
+        Ok(Self {
+            endpoints,
+            config,
+            current: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// Host of every URL, in the order they were given
+    pub fn labels(&self) -> Vec<String> {
+        self.endpoints.iter().map(|e| e.label.clone()).collect()
+    }
+
+    pub fn status(&self) -> Vec<RpcStatus> {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .map(|e| {
+                let h = e.health.lock().unwrap();
+                RpcStatus {
+                    label: e.label.clone(),
+                    score: h.score,
+                    latency_ms: h.latency_ms,
+                    calls: h.calls,
+                    failures: h.failures,
+                    benched: h.benched_until.filter(|t| *t > now).map(|t| t - now),
+                }
+            })
+            .collect()
+    }
+
+    /// Endpoint indexes, best first: unbenched before benched, then by
+    /// score, then in the order given
+    fn ranked(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut keys: Vec<(bool, i64, usize)> = self
+            .endpoints
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let h = e.health.lock().unwrap();
+                let benched = h.benched_until.is_some_and(|t| t > now);
+                (benched, -((h.score / SCORE_STEP).round() as i64), i)
+            })
+            .collect();
+        keys.sort();
+        keys.into_iter().map(|(_, _, i)| i).collect()
+    }
+
+    fn succeeded(&self, i: usize, elapsed: Duration) {
+        let endpoint = &self.endpoints[i];
+        let mut h = endpoint.health.lock().unwrap();
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        h.latency_ms = if h.calls == h.failures {
+            ms
+        } else {
+            h.latency_ms + SCORE_ALPHA * (ms - h.latency_ms)
+        };
+        // A slow answer scores in proportion to how slow it was
+        let slow = self.config.slow.as_secs_f64() * 1000.0;
+        let outcome = if ms > slow { slow / ms } else { 1.0 };
+        h.score += SCORE_ALPHA * (outcome - h.score);
+        h.calls += 1;
+        if h.benched_until.take().is_some() {
+            info!("✅ RPC {} recovered", endpoint.label);
+        }
+        h.failures_in_row = 0;
+        drop(h);
+
+        let previous = self.current.swap(i, Ordering::Relaxed);
+        if previous != i {
+            info!(
+                "🔀 RPC switched from {} to {}",
+                self.endpoints[previous].label, endpoint.label
+            );
+        }
+    }
+
+    fn failed(&self, i: usize, error: &HttpClientError) {
+        let endpoint = &self.endpoints[i];
+        let mut h = endpoint.health.lock().unwrap();
+        h.score -= SCORE_ALPHA * h.score;
+        h.calls += 1;
+        h.failures += 1;
+        h.failures_in_row += 1;
+        if h.failures_in_row < self.config.max_failures {
+            warn!("⚠️  RPC {} failed: {}", endpoint.label, error);
+            return;
+        }
+        let now = Instant::now();
+        let benched = h.benched_until.is_some_and(|t| t > now);
+        // Tried as a last resort and failed again: stays benched
+        h.benched_until = Some(now + self.config.cooldown);
+        if !benched {
+            warn!(
+                "⚠️  RPC {} benched for {}s after {} failures: {}",
+                endpoint.label,
+                self.config.cooldown.as_secs(),
+                h.failures_in_row,
+                error
+            );
+        }
+    }
+}
+
+/// Whether another node may answer where this one failed
+fn should_fail_over(error: &HttpClientError) -> bool {
+    match error {
+        HttpClientError::ReqwestError(_) | HttpClientError::SerdeJson { .. } => true,
+        HttpClientError::JsonRpcError(e) => {
+            let message = e.message.to_lowercase();
+            // -32005: limit exceeded
+            e.code == -32005
+                || e.code == 429
+                || message.contains("rate limit")
+                || message.contains("too many requests")
+                || message.contains("capacity")
+        }
+    }
+}
+
+/// Hash of the raw transaction in `params`, when a node answered that it
+/// already has it (sent there by an earlier attempt)
+fn known_transaction(method: &str, params: &Value, error: &HttpClientError) -> Option<Value> {
+    let HttpClientError::JsonRpcError(e) = error else {
+        return None;
+    };
+    let message = e.message.to_lowercase();
+    if method != "eth_sendRawTransaction" || !(message.contains("already known") || message.contains("known transaction"))
+    {
+        return None;
+    }
+    let raw = params.get(0)?.as_str()?;
+    let bytes = hex::decode(raw.trim_start_matches("0x")).ok()?;
+    Some(Value::String(format!("0x{}", hex::encode(keccak256(bytes)))))
+}
+
+impl JsonRpcClient for RpcPool {
+    type Error = HttpClientError;
+
+    fn request<'life0, 'life1, 'async_trait, T, R>(
+        &'life0 self,
+        method: &'life1 str,
+        params: T,
+    ) -> BoxFuture<'async_trait, Result<R, Self::Error>>
+    where
+        T: Debug + Serialize + Send + Sync + 'async_trait,
+        R: DeserializeOwned + Send + 'async_trait,
+        'life0: 'async_trait,
+        'life1: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move {
+            // Serialized once, so every attempt sends the same thing
+            let params = serde_json::to_value(&params).map_err(|err| HttpClientError::SerdeJson {
+                err,
+                text: format!("{:?}", params),
+            })?;
+            let mut last = None;
+            for (attempt, i) in self.ranked().into_iter().enumerate() {
+                let started = Instant::now();
+                match self.endpoints[i].http.request::<_, Value>(method, &params).await {
+                    Ok(value) => {
+                        self.succeeded(i, started.elapsed());
+                        return serde_json::from_value(value.clone()).map_err(|err| HttpClientError::SerdeJson {
+                            err,
+                            text: value.to_string(),
+                        });
+                    }
+                    Err(e) if should_fail_over(&e) => {
+                        self.failed(i, &e);
+                        last = Some(e);
+                    }
+                    Err(e) => {
+                        // The node answered: it's healthy, the call isn't
+                        self.succeeded(i, started.elapsed());
+                        if attempt > 0 {
+                            if let Some(hash) = known_transaction(method, &params, &e) {
+                                return serde_json::from_value(hash.clone()).map_err(|err| {
+                                    HttpClientError::SerdeJson {
+                                        err,
+                                        text: hash.to_string(),
+                                    }
+                                });
+                            }
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+            Err(last.expect("RpcPool has at least one endpoint"))
+        })
+    }
+}
@@ -0,0 +1,267 @@
+use anyhow::Result;
+use log::{info, warn};
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+// ==================================================
+// WEBSOCKET RECONNECTS
+// ==================================================
+// Every WS feed runs under a `Reconnector`:
+//
+//   connect → subscribe to every topic so far → `connected` → stream
+//      ↑                                                      │ error
+//      └──────────── backoff ← `disconnected` ←───────────────┘
+//
+// Waits double from WS_RECONNECT_BASE_MS up to WS_RECONNECT_MAX_MS, each
+// drawn between half and all of that so feeds dropped together don't
+// come back together. A connection that stayed up WS_STABLE_SECS starts
+// the count over.
+//
+// Each step is published on a `Connections` hub. A feed that comes back
+// refetches what it may have missed (books over REST, the state of our
+// orders); strategies hear about it in `on_connection`, and the dead
+// man's switch can cancel when a trading feed stays down.
+
+/// Feed names, as published on `Connections`
+pub mod feeds {
+    /// Market channel behind strategies' books
+    pub const MARKET: &str = "market";
+    /// Our orders and fills
+    pub const USER: &str = "user";
+    pub const RECORDER: &str = "recorder";
+    pub const TRADE_FEED: &str = "trade-feed";
+    pub const PAPER: &str = "paper";
+}
+
+/// Buffered connection events per subscriber
+const EVENT_BUFFER: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connecting,
+    /// Subscribed; messages are flowing
+    Connected,
+    Disconnected,
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ConnectionState::Connecting => "connecting",
+            ConnectionState::Connected => "connected",
+            ConnectionState::Disconnected => "disconnected",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectionEvent {
+    pub feed: String,
+    pub state: ConnectionState,
+    /// Failed attempts in a row so far
+    pub failures: u32,
+    /// Connected again after an outage: anything published meanwhile
+    /// was missed
+    pub resumed: bool,
+    /// Why it disconnected
+    pub error: Option<String>,
+    /// Wait before the next attempt
+    pub retry_in: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct FeedStatus {
+    state: ConnectionState,
+    since: Instant,
+}
+
+/// Current state of every feed, and a stream of their changes
+#[derive(Debug)]
+pub struct Connections {
+    feeds: Mutex<HashMap<String, FeedStatus>>,
+    events: broadcast::Sender<ConnectionEvent>,
+}
+
+impl Default for Connections {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Connections {
+    pub fn new() -> Self {
+        Self {
+            feeds: Mutex::new(HashMap::new()),
+            events: broadcast::channel(EVENT_BUFFER).0,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events.subscribe()
+    }
+
+    /// `None` for a feed that never started
+    pub fn state(&self, feed: &str) -> Option<ConnectionState> {
+        self.feeds.lock().unwrap().get(feed).map(|f| f.state)
+    }
+
+    /// How long `feed` has been without a connection (since it started,
+    /// if it never had one); `None` while connected or never started
+    pub fn down_for(&self, feed: &str) -> Option<Duration> {
+        let feeds = self.feeds.lock().unwrap();
+        let status = feeds.get(feed)?;
+        (status.state != ConnectionState::Connected).then(|| status.since.elapsed())
+    }
+
+    fn publish(&self, event: ConnectionEvent) {
+        let mut feeds = self.feeds.lock().unwrap();
+        let status = feeds.entry(event.feed.clone()).or_insert(FeedStatus {
+            state: event.state,
+            since: Instant::now(),
+        });
+        // Down time runs from the first failure, not the latest attempt
+        let was_up = status.state == ConnectionState::Connected;
+        if was_up || event.state == ConnectionState::Connected {
+            status.since = Instant::now();
+        }
+        status.state = event.state;
+        drop(feeds);
+        // No subscribers is fine
+        let _ = self.events.send(event);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    /// Cap of the first wait; doubles per failure
+    pub base: Duration,
+    /// Cap of any wait
+    pub max: Duration,
+    /// Up this long, a connection resets the failure count
+    pub stable: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            stable: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// `WS_RECONNECT_BASE_MS` (1000), `WS_RECONNECT_MAX_MS` (60000),
+    /// `WS_STABLE_SECS` (60)
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            base: var("WS_RECONNECT_BASE_MS").map_or(d.base, Duration::from_millis),
+            max: var("WS_RECONNECT_MAX_MS").map_or(d.max, Duration::from_millis),
+            stable: var("WS_STABLE_SECS").map_or(d.stable, Duration::from_secs),
+        }
+    }
+
+    /// Wait after `failures` failures in a row: between half and all of
+    /// min(max, base × 2^(failures-1))
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let cap = self
+            .base
+            .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+            .min(self.max)
+            .as_millis() as u64;
+        Duration::from_millis(rand::rng().random_range(cap / 2..=cap))
+    }
+}
+
+/// Reconnect bookkeeping of one feed
+pub struct Reconnector {
+    feed: String,
+    connections: Arc<Connections>,
+    policy: ReconnectPolicy,
+    failures: u32,
+    connected_at: Option<Instant>,
+    ever_connected: bool,
+}
+
+impl Reconnector {
+    /// Reconnects per `ReconnectPolicy::from_env`
+    pub fn new(feed: &str, connections: Arc<Connections>) -> Self {
+        Self {
+            feed: feed.to_string(),
+            connections,
+            policy: ReconnectPolicy::from_env(),
+            failures: 0,
+            connected_at: None,
+            ever_connected: false,
+        }
+    }
+
+    fn event(&self, state: ConnectionState) -> ConnectionEvent {
+        ConnectionEvent {
+            feed: self.feed.clone(),
+            state,
+            failures: self.failures,
+            resumed: false,
+            error: None,
+            retry_in: None,
+        }
+    }
+
+    /// About to connect
+    pub fn connecting(&self) {
+        self.connections.publish(self.event(ConnectionState::Connecting));
+    }
+
+    /// Connected and subscribed. True when this is a reconnect, and
+    /// whatever the feed published meanwhile should be refetched.
+    pub fn connected(&mut self) -> bool {
+        let resumed = self.ever_connected;
+        if resumed {
+            info!("🔌 {} feed back after {} failed attempt(s)", self.feed, self.failures);
+        }
+        self.ever_connected = true;
+        self.connected_at = Some(Instant::now());
+        self.connections.publish(ConnectionEvent {
+            resumed,
+            ..self.event(ConnectionState::Connected)
+        });
+        resumed
+    }
+
+    /// The connection ended with `result`; wait out the backoff. A clean
+    /// end (`Ok`, e.g. to resubscribe) reconnects straight away.
+    pub async fn disconnected(&mut self, result: Result<()>) {
+        if self.connected_at.take().is_some_and(|t| t.elapsed() >= self.policy.stable) {
+            self.failures = 0;
+        }
+        let (error, wait) = match result {
+            Ok(()) => (None, Duration::ZERO),
+            Err(e) => {
+                self.failures += 1;
+                let wait = self.policy.backoff(self.failures);
+                warn!(
+                    "⚠️  {} feed error: {} — reconnecting in {:.1}s (attempt {})",
+                    self.feed,
+                    e,
+                    wait.as_secs_f64(),
+                    self.failures + 1
+                );
+                (Some(e.to_string()), wait)
+            }
+        };
+        self.connections.publish(ConnectionEvent {
+            error,
+            retry_in: Some(wait),
+            ..self.event(ConnectionState::Disconnected)
+        });
+        tokio::time::sleep(wait).await;
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::domain::order::Side;
+use crate::execution::orderbook::{fetch_book, OrderBook};
+use crate::execution::ClobClient;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// CONDITIONS
+// ==================================================
+
+/// Top-of-book snapshot a condition is evaluated against
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarketView {
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+}
+
+impl MarketView {
+    pub fn from_book(book: &OrderBook) -> Self {
+        Self {
+            best_bid: book.best_bid().map(|l| l.price),
+            best_ask: book.best_ask().map(|l| l.price),
+        }
+    }
+
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid? + self.best_ask?) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask? - self.best_bid?)
+    }
+}
+
+/// Predicate that releases a conditional order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    MidAbove(f64),
+    MidBelow(f64),
+    SpreadBelow(f64),
+    /// Raised from outside via `ConditionalEngine::raise_signal`
+    Signal(String),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    pub fn is_met(&self, view: &MarketView, signals: &HashSet<String>) -> bool {
+        match self {
+            Condition::MidAbove(x) => view.mid().is_some_and(|m| m > *x),
+            Condition::MidBelow(x) => view.mid().is_some_and(|m| m < *x),
+            Condition::SpreadBelow(x) => view.spread().is_some_and(|s| s < *x),
+            Condition::Signal(name) => signals.contains(name),
+            Condition::All(cs) => cs.iter().all(|c| c.is_met(view, signals)),
+            Condition::Any(cs) => cs.iter().any(|c| c.is_met(view, signals)),
+        }
+    }
+}
+
+// ==================================================
+// CONDITIONAL ORDERS
+// ==================================================
+
+/// What to submit once the condition fires
+#[derive(Debug, Clone)]
+pub struct OrderSpec {
+    pub token_id: String,
+    pub side: Side,
+    pub size: Decimal,
+    /// `None` = market order within `max_slippage`
+    pub price: Option<Decimal>,
+    pub order_type: OrderType,
+    pub max_slippage: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConditionalOrder {
+    pub id: String,
+    pub condition: Condition,
+    pub order: OrderSpec,
+}
+
+/// Holds orders back until their condition is met, so strategies
+/// only describe *when* to trade and not how to watch for it.
+pub struct ConditionalEngine {
+    clob: Arc<ClobClient>,
+    pending: Mutex<HashMap<String, ConditionalOrder>>,
+    signals: Mutex<HashSet<String>>,
+    poll_interval: Duration,
+}
+
+impl ConditionalEngine {
+    pub fn new(clob: Arc<ClobClient>, poll_interval: Duration) -> Self {
+        Self {
+            clob,
+            pending: Mutex::new(HashMap::new()),
+            signals: Mutex::new(HashSet::new()),
+            poll_interval,
+        }
+    }
+
+    pub async fn add(&self, order: ConditionalOrder) {
+        info!("⏳ Conditional {} armed: {:?}", order.id, order.condition);
+        self.pending.lock().await.insert(order.id.clone(), order);
+    }
+
+    pub async fn cancel(&self, id: &str) -> Option<ConditionalOrder> {
+        self.pending.lock().await.remove(id)
+    }
+
+    /// Latch an external signal; it is consumed by the next tick
+    pub async fn raise_signal(&self, name: &str) {
+        self.signals.lock().await.insert(name.to_string());
+    }
+
+    pub async fn run(&self) {
+        loop {
+            if let Err(e) = self.tick().await {
+                warn!("⚠️  Conditional engine tick failed: {}", e);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Evaluate every pending order once and submit those whose
+    /// condition holds. Failed submissions stay armed.
+    pub async fn tick(&self) -> Result<()> {
+        let pending: Vec<ConditionalOrder> = self.pending.lock().await.values().cloned().collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let signals = std::mem::take(&mut *self.signals.lock().await);
+        let mut views: HashMap<String, MarketView> = HashMap::new();
+
+        for cond in pending {
+            let token = &cond.order.token_id;
+            if !views.contains_key(token) {
+                let view = match fetch_book(self.clob.clob_url(), token).await {
+                    Ok(book) => MarketView::from_book(&book),
+                    Err(e) => {
+                        warn!("⚠️  No book for {}: {}", token, e);
+                        MarketView::default()
+                    }
+                };
+                views.insert(token.clone(), view);
+            }
+
+            if cond.condition.is_met(&views[token], &signals) {
+                self.fire(&cond).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn fire(&self, cond: &ConditionalOrder) {
+        info!("🎯 Conditional {} triggered", cond.id);
+        let o = &cond.order;
+
+        let result = match o.price {
+            Some(price) => {
+                self.clob
+                    .place_order(&o.token_id, o.side.clone(), price, o.size, o.order_type)
+                    .await
+            }
+            None => match o.side {
+                Side::Sell => self.clob.market_sell(&o.token_id, o.size, o.max_slippage).await,
+                Side::Buy => {
+                    let view = fetch_book(self.clob.clob_url(), &o.token_id)
+                        .await
+                        .map(|b| MarketView::from_book(&b));
+                    match view.ok().and_then(|v| v.best_ask) {
+                        Some(ask) => {
+                            let usdc = o.size * Decimal::try_from(ask).unwrap_or_default();
+                            self.clob.market_buy(&o.token_id, usdc, o.max_slippage).await
+                        }
+                        None => Err(anyhow!("No asks on book").into()),
+                    }
+                }
+            },
+        };
+
+        match result {
+            Ok(order_id) => {
+                info!("✅ Conditional {} submitted as {}", cond.id, order_id);
+                self.pending.lock().await.remove(&cond.id);
+            }
+            Err(e) => warn!("❌ Conditional {} failed, still armed: {}", cond.id, e),
+        }
+    }
+}
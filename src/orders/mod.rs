@@ -0,0 +1,9 @@
+pub mod conditional;
+pub mod state;
+pub mod stops;
+pub mod tracker;
+
+pub use conditional::{Condition, ConditionalEngine, ConditionalOrder, MarketView, OrderSpec};
+pub use state::{OrderEvent, OrderState, TrackedOrder, TransitionError};
+pub use tracker::OrderTracker;
+pub use stops::{MarkSource, StopEngine, StopExit, StopTrigger, Trail, TriggerKind};
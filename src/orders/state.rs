@@ -0,0 +1,222 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ==================================================
+// ORDER STATES
+// ==================================================
+
+/// Lifecycle of a single order, from build to a terminal state.
+///
+/// ```text
+/// Created → Signed → Submitted → Live → PartiallyFilled → Filled
+///                        │         │            │
+///                        ├─────────┴────────────┴→ Cancelled / Expired
+///                        └→ Rejected
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderState {
+    Created,
+    Signed,
+    Submitted,
+    Live,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Rejected,
+    Expired,
+}
+
+impl OrderState {
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderState::Filled | OrderState::Cancelled | OrderState::Rejected | OrderState::Expired
+        )
+    }
+
+    /// Accepted by the exchange and still able to match
+    pub fn is_open(&self) -> bool {
+        matches!(self, OrderState::Live | OrderState::PartiallyFilled)
+    }
+}
+
+impl fmt::Display for OrderState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+// ==================================================
+// EVENTS
+// ==================================================
+
+/// Something that happened to an order — a local step, an exchange
+/// response, or a user-channel update.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderEvent {
+    Signed,
+    Submitted,
+    /// Exchange accepted the order and it rests on the book
+    Accepted { order_id: String },
+    Rejected { reason: String },
+    /// `size` outcome tokens matched (incremental, not cumulative)
+    Fill { size: Decimal },
+    Cancelled,
+    Expired,
+}
+
+impl OrderEvent {
+    /// Map a `POST /order` status (matched | live | delayed | unmatched).
+    ///
+    /// `matched` means the order crossed on arrival: `matched` tokens
+    /// of it (all of `size` if unknown), a FAK's remainder is killed.
+    /// `unmatched` is a FOK/FAK that found nothing and was killed.
+    /// `delayed` orders stay Submitted until a user-channel update arrives.
+    pub fn from_post_status(status: &str, order_id: &str, size: Decimal, matched: Option<Decimal>) -> Vec<Self> {
+        let accepted = OrderEvent::Accepted {
+            order_id: order_id.to_string(),
+        };
+        match status.to_lowercase().as_str() {
+            "live" => vec![accepted],
+            "matched" => {
+                let filled = matched.filter(|m| !m.is_zero()).unwrap_or(size).min(size);
+                let mut events = vec![accepted, OrderEvent::Fill { size: filled }];
+                if filled < size {
+                    events.push(OrderEvent::Cancelled);
+                }
+                events
+            }
+            "unmatched" => vec![accepted, OrderEvent::Cancelled],
+            _ => vec![],
+        }
+    }
+
+    /// Map a user-channel `order` message type (PLACEMENT | UPDATE | CANCELLATION)
+    pub fn from_user_order(kind: &str, order_id: &str, matched_delta: Decimal) -> Option<Self> {
+        match kind.to_uppercase().as_str() {
+            "PLACEMENT" => Some(OrderEvent::Accepted {
+                order_id: order_id.to_string(),
+            }),
+            "UPDATE" if matched_delta > Decimal::ZERO => Some(OrderEvent::Fill { size: matched_delta }),
+            "CANCELLATION" => Some(OrderEvent::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+// ==================================================
+// TRANSITION ERRORS
+// ==================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionError {
+    pub from: OrderState,
+    pub event: OrderEvent,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid order transition from {} on {:?}", self.from, self.event)
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+// ==================================================
+// TRACKED ORDER
+// ==================================================
+
+/// One order's current state plus fill accounting and history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedOrder {
+    /// Idempotency key (struct hash) — known before the exchange id
+    pub key: String,
+    pub order_id: Option<String>,
+    pub state: OrderState,
+    pub size: Decimal,
+    pub filled: Decimal,
+    pub reject_reason: Option<String>,
+    /// (state, unix ms) for every transition, oldest first
+    pub history: Vec<(OrderState, u64)>,
+}
+
+impl TrackedOrder {
+    pub fn new(key: impl Into<String>, size: Decimal) -> Self {
+        Self {
+            key: key.into(),
+            order_id: None,
+            state: OrderState::Created,
+            size,
+            filled: Decimal::ZERO,
+            reject_reason: None,
+            history: vec![(OrderState::Created, now_ms())],
+        }
+    }
+
+    pub fn remaining(&self) -> Decimal {
+        (self.size - self.filled).max(Decimal::ZERO)
+    }
+
+    /// Apply `event`, returning the new state.
+    ///
+    /// Terminal states absorb duplicate terminal events (e.g. a
+    /// cancel ack after the user channel already reported it), but
+    /// reject anything that would reopen the order.
+    pub fn apply(&mut self, event: OrderEvent) -> Result<OrderState, TransitionError> {
+        use OrderState::*;
+
+        let next = match (self.state, &event) {
+            (Created, OrderEvent::Signed) => Signed,
+            (Signed, OrderEvent::Submitted) => Submitted,
+
+            (Submitted, OrderEvent::Accepted { order_id }) => {
+                self.order_id = Some(order_id.clone());
+                Live
+            }
+            // User channel can confirm placement after the HTTP response
+            (Live | PartiallyFilled, OrderEvent::Accepted { .. }) => self.state,
+
+            (Created | Signed | Submitted, OrderEvent::Rejected { reason }) => {
+                self.reject_reason = Some(reason.clone());
+                Rejected
+            }
+
+            (Submitted | Live | PartiallyFilled, OrderEvent::Fill { size }) => {
+                self.filled = (self.filled + size).min(self.size);
+                if self.remaining().is_zero() {
+                    Filled
+                } else {
+                    PartiallyFilled
+                }
+            }
+
+            (Submitted | Live | PartiallyFilled, OrderEvent::Cancelled) => Cancelled,
+            (Live | PartiallyFilled, OrderEvent::Expired) => Expired,
+
+            (s, OrderEvent::Cancelled | OrderEvent::Expired) if s.is_terminal() => s,
+            (Filled, OrderEvent::Fill { .. }) => Filled,
+
+            _ => {
+                return Err(TransitionError {
+                    from: self.state,
+                    event,
+                })
+            }
+        };
+
+        if next != self.state {
+            self.state = next;
+            self.history.push((next, now_ms()));
+        }
+        Ok(next)
+    }
+}
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
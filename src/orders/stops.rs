@@ -0,0 +1,350 @@
+use anyhow::Result;
+use log::{info, warn};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::domain::order::Side;
+use crate::portfolio::Marker;
+use crate::execution::ClobClient;
+pub use crate::portfolio::MarkSource;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// STOP TRIGGERS
+// ==================================================
+
+/// How to get out once the stop fires
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopExit {
+    /// FOK sweep of the bids, capped at `max_slippage` below best bid
+    Market { max_slippage: f64 },
+    /// Resting GTC sell at `price`
+    Limit { price: Decimal },
+}
+
+/// Which way the mark has to cross `trigger_price`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerKind {
+    /// Fires at or below the trigger
+    #[default]
+    StopLoss,
+    /// Fires at or above the trigger
+    TakeProfit,
+}
+
+/// Distance a trailing stop keeps below the best mark seen
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trail {
+    /// Fixed price distance (0.05 = 5 cents)
+    Absolute(f64),
+    /// Fraction of the peak (0.1 = 10%)
+    Percent(f64),
+}
+
+impl Trail {
+    pub fn trigger_for(&self, peak: f64) -> f64 {
+        match *self {
+            Trail::Absolute(d) => peak - d,
+            Trail::Percent(p) => peak * (1.0 - p),
+        }
+    }
+}
+
+/// Sell `size` tokens of `token_id` once the mark crosses
+/// `trigger_price`. Outcome tokens can't be shorted, so every
+/// trigger exits a long position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopTrigger {
+    pub id: String,
+    pub token_id: String,
+    #[serde(default)]
+    pub kind: TriggerKind,
+    pub trigger_price: f64,
+    pub size: Decimal,
+    pub mark: MarkSource,
+    pub exit: StopExit,
+    /// One-cancels-other group: when one member fires the rest are dropped
+    #[serde(default)]
+    pub oco_group: Option<String>,
+    /// Trailing stop: `trigger_price` follows the peak mark upwards
+    #[serde(default)]
+    pub trail: Option<Trail>,
+    /// Highest mark seen since a trailing stop was armed
+    #[serde(default)]
+    pub peak: f64,
+}
+
+impl StopTrigger {
+    /// Raise the trigger of a trailing stop if `mark` is a new peak.
+    /// Returns true when the trigger moved.
+    pub fn ratchet(&mut self, mark: f64) -> bool {
+        let Some(trail) = self.trail else {
+            return false;
+        };
+        if mark <= self.peak {
+            return false;
+        }
+        self.peak = mark;
+        let trigger = trail.trigger_for(mark);
+        if trigger > self.trigger_price {
+            self.trigger_price = trigger;
+            return true;
+        }
+        false
+    }
+
+    pub fn is_hit(&self, mark: f64) -> bool {
+        match self.kind {
+            TriggerKind::StopLoss => mark <= self.trigger_price,
+            TriggerKind::TakeProfit => mark >= self.trigger_price,
+        }
+    }
+}
+
+// ==================================================
+// STOP ENGINE
+// ==================================================
+
+/// Polls marks for every token with an armed stop and fires exits
+/// through `ClobClient`. Triggers live in a JSON file so they survive
+/// restarts; a fired trigger is removed only after its exit order is
+/// accepted, otherwise it is retried on the next tick.
+pub struct StopEngine {
+    clob: Arc<ClobClient>,
+    path: PathBuf,
+    stops: Mutex<HashMap<String, StopTrigger>>,
+    poll_interval: Duration,
+}
+
+impl StopEngine {
+    pub fn new(clob: Arc<ClobClient>, path: impl Into<PathBuf>, poll_interval: Duration) -> Result<Self> {
+        let path = path.into();
+        let stops = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(&path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            clob,
+            path,
+            stops: Mutex::new(stops),
+            poll_interval,
+        })
+    }
+
+    /// `STOP_TRIGGERS_PATH` (default `stops.json`), `STOP_POLL_MS` (default 1000)
+    pub fn from_env(clob: Arc<ClobClient>) -> Result<Self> {
+        let path = std::env::var("STOP_TRIGGERS_PATH").unwrap_or_else(|_| "stops.json".to_string());
+        let poll_ms = std::env::var("STOP_POLL_MS")
+            .unwrap_or_else(|_| "1000".to_string())
+            .parse()
+            .unwrap_or(1000);
+        Self::new(clob, path, Duration::from_millis(poll_ms))
+    }
+
+    pub async fn add(&self, stop: StopTrigger) -> Result<()> {
+        info!(
+            "🛑 {:?} {} armed: sell {} of {} at {:.4} ({:?})",
+            stop.kind, stop.id, stop.size, stop.token_id, stop.trigger_price, stop.mark
+        );
+        let mut stops = self.stops.lock().await;
+        stops.insert(stop.id.clone(), stop);
+        self.save(&stops)
+    }
+
+    /// Arm a stop-loss and a take-profit for a filled entry as an OCO
+    /// pair: whichever fires first removes the other.
+    pub async fn arm_bracket(
+        &self,
+        token_id: &str,
+        size: Decimal,
+        stop_price: f64,
+        target_price: f64,
+        mark: MarkSource,
+        exit: StopExit,
+    ) -> Result<String> {
+        let group = format!("oco-{}-{}", token_id, chrono::Utc::now().timestamp_millis());
+
+        for (kind, price, suffix) in [
+            (TriggerKind::StopLoss, stop_price, "sl"),
+            (TriggerKind::TakeProfit, target_price, "tp"),
+        ] {
+            self.add(StopTrigger {
+                id: format!("{}-{}", group, suffix),
+                token_id: token_id.to_string(),
+                kind,
+                trigger_price: price,
+                size,
+                mark,
+                exit,
+                oco_group: Some(group.clone()),
+                trail: None,
+                peak: 0.0,
+            })
+            .await?;
+        }
+
+        Ok(group)
+    }
+
+    /// Arm a trailing stop starting from the current mark
+    pub async fn arm_trailing(
+        &self,
+        token_id: &str,
+        size: Decimal,
+        trail: Trail,
+        mark: MarkSource,
+        exit: StopExit,
+    ) -> Result<String> {
+        let current = self.fetch_mark(token_id, size, mark).await?;
+        let id = format!("trail-{}-{}", token_id, chrono::Utc::now().timestamp_millis());
+
+        self.add(StopTrigger {
+            id: id.clone(),
+            token_id: token_id.to_string(),
+            kind: TriggerKind::StopLoss,
+            trigger_price: trail.trigger_for(current),
+            size,
+            mark,
+            exit,
+            oco_group: None,
+            trail: Some(trail),
+            peak: current,
+        })
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn remove(&self, id: &str) -> Result<Option<StopTrigger>> {
+        let mut stops = self.stops.lock().await;
+        let removed = stops.remove(id);
+        if removed.is_some() {
+            self.save(&stops)?;
+        }
+        Ok(removed)
+    }
+
+    pub async fn stops(&self) -> Vec<StopTrigger> {
+        self.stops.lock().await.values().cloned().collect()
+    }
+
+    /// Run forever, checking every `poll_interval`
+    pub async fn run(&self) {
+        info!("🛑 Stop engine running ({} armed)", self.stops.lock().await.len());
+        loop {
+            if let Err(e) = self.tick().await {
+                warn!("⚠️  Stop engine tick failed: {}", e);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// One pass: fetch marks, fire every stop that has been crossed
+    pub async fn tick(&self) -> Result<()> {
+        let armed = self.stops().await;
+        let mut marks: HashMap<(String, MarkSource), f64> = HashMap::new();
+
+        for stop in armed {
+            let key = (stop.token_id.clone(), stop.mark);
+            let mark = match marks.get(&key) {
+                Some(m) => *m,
+                None => match self.fetch_mark(&stop.token_id, stop.size, stop.mark).await {
+                    Ok(m) => *marks.entry(key).or_insert(m),
+                    Err(e) => {
+                        warn!("⚠️  No mark for {}: {}", stop.token_id, e);
+                        continue;
+                    }
+                },
+            };
+
+            let stop = {
+                let mut stops = self.stops.lock().await;
+                // An OCO sibling may have fired earlier in this pass
+                let Some(current) = stops.get_mut(&stop.id) else {
+                    continue;
+                };
+                if current.ratchet(mark) {
+                    info!(
+                        "📈 Trailing stop {} raised to {:.4} (peak {:.4})",
+                        current.id, current.trigger_price, current.peak
+                    );
+                    let updated = current.clone();
+                    self.save(&stops)?;
+                    updated
+                } else {
+                    current.clone()
+                }
+            };
+
+            if stop.is_hit(mark) {
+                self.fire(&stop, mark).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops only exit longs, so a conservative mark is the best bid
+    async fn fetch_mark(&self, token_id: &str, size: Decimal, source: MarkSource) -> Result<f64> {
+        Marker::new(self.clob.clob_url(), source).mark(token_id, size).await
+    }
+
+    async fn fire(&self, stop: &StopTrigger, mark: f64) {
+        warn!(
+            "🚨 {:?} {} hit: mark {:.4} vs trigger {:.4}, exiting {} tokens",
+            stop.kind, stop.id, mark, stop.trigger_price, stop.size
+        );
+
+        let result = match stop.exit {
+            StopExit::Market { max_slippage } => {
+                self.clob.market_sell(&stop.token_id, stop.size, max_slippage).await
+            }
+            StopExit::Limit { price } => {
+                self.clob
+                    .place_order(&stop.token_id, Side::Sell, price, stop.size, OrderType::Gtc)
+                    .await
+            }
+        };
+
+        match result {
+            Ok(order_id) => {
+                info!("✅ Stop {} exit order {}", stop.id, order_id);
+                if let Err(e) = self.remove_fired(stop).await {
+                    warn!("⚠️  Failed to persist stop removal: {}", e);
+                }
+            }
+            Err(e) => warn!("❌ Stop {} exit failed, will retry: {}", stop.id, e),
+        }
+    }
+
+    /// Drop a fired trigger together with its OCO siblings
+    async fn remove_fired(&self, fired: &StopTrigger) -> Result<()> {
+        let mut stops = self.stops.lock().await;
+        stops.remove(&fired.id);
+        if let Some(group) = &fired.oco_group {
+            stops.retain(|id, s| {
+                let sibling = s.oco_group.as_ref() == Some(group);
+                if sibling {
+                    info!("   OCO: cancelling sibling {}", id);
+                }
+                !sibling
+            });
+        }
+        self.save(&stops)
+    }
+
+    fn save(&self, stops: &HashMap<String, StopTrigger>) -> Result<()> {
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(stops)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
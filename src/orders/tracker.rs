@@ -0,0 +1,150 @@
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio::sync::broadcast;
+
+use crate::orders::state::{now_ms, OrderEvent, OrderState, TrackedOrder, TransitionError};
+
+// ==================================================
+// ORDER TRACKER
+// ==================================================
+
+/// Buffered state changes per subscriber before it starts lagging
+const UPDATE_BUFFER: usize = 1024;
+
+/// How long a finished order stays queryable before it's pruned
+const TERMINAL_RETENTION_MS: u64 = 10 * 60 * 1000;
+
+/// All orders this process has built, keyed by idempotency key,
+/// with an index from exchange order id once one is assigned.
+///
+/// Every state change is broadcast so algos can react to fills
+/// instead of polling.
+#[derive(Debug)]
+pub struct OrderTracker {
+    orders: HashMap<String, TrackedOrder>,
+    by_id: HashMap<String, String>,
+    updates: broadcast::Sender<TrackedOrder>,
+}
+
+impl Default for OrderTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self {
+            orders: HashMap::new(),
+            by_id: HashMap::new(),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        }
+    }
+
+    /// Receive a copy of every order whose state (or fill) changes
+    pub fn subscribe(&self) -> broadcast::Receiver<TrackedOrder> {
+        self.updates.subscribe()
+    }
+
+    /// Start tracking a freshly built order. A rejected order with the
+    /// same key starts over, so a retried submission can be tracked.
+    /// Orders finished long ago are pruned on the way.
+    pub fn create(&mut self, key: &str, size: Decimal) {
+        self.prune();
+        let retry = matches!(self.orders.get(key), Some(o) if o.state == OrderState::Rejected);
+        if retry || !self.orders.contains_key(key) {
+            self.orders.insert(key.to_string(), TrackedOrder::new(key, size));
+        }
+    }
+
+    /// Track an order an earlier run left on the book as live, keyed by
+    /// its exchange id (its idempotency key is long gone), with what's
+    /// left of it as its size
+    pub fn adopt(&mut self, order_id: &str, remaining: Decimal) {
+        if self.by_id.contains_key(order_id) {
+            return;
+        }
+        self.create(order_id, remaining);
+        let accepted = OrderEvent::Accepted {
+            order_id: order_id.to_string(),
+        };
+        if let Err(e) = self.apply_all(order_id, vec![OrderEvent::Signed, OrderEvent::Submitted, accepted]) {
+            warn!("⚠️  Order {}: {}", order_id, e);
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&TrackedOrder> {
+        self.orders.get(key)
+    }
+
+    pub fn get_by_id(&self, order_id: &str) -> Option<&TrackedOrder> {
+        self.by_id.get(order_id).and_then(|k| self.orders.get(k))
+    }
+
+    /// Orders still able to match
+    pub fn open(&self) -> Vec<&TrackedOrder> {
+        self.orders.values().filter(|o| o.state.is_open()).collect()
+    }
+
+    /// Apply an event to the order with idempotency key `key`
+    pub fn apply(&mut self, key: &str, event: OrderEvent) -> Result<OrderState, TransitionError> {
+        let order = self.orders.get_mut(key).ok_or(TransitionError {
+            from: OrderState::Created,
+            event: event.clone(),
+        })?;
+
+        let (prev, prev_filled) = (order.state, order.filled);
+        let next = order.apply(event)?;
+
+        if let Some(id) = &order.order_id {
+            self.by_id.entry(id.clone()).or_insert_with(|| key.to_string());
+        }
+        if prev != next || order.filled != prev_filled {
+            debug!("📑 Order {} {} → {}", key, prev, next);
+            // No subscribers is fine
+            let _ = self.updates.send(order.clone());
+        }
+        Ok(next)
+    }
+
+    /// Apply an event to the order with exchange id `order_id`.
+    /// Unknown ids (orders from another process) are ignored.
+    pub fn apply_by_id(&mut self, order_id: &str, event: OrderEvent) -> Option<OrderState> {
+        let key = self.by_id.get(order_id)?.clone();
+        match self.apply(&key, event) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                warn!("⚠️  Order {}: {}", order_id, e);
+                None
+            }
+        }
+    }
+
+    /// Apply events in order, stopping at the first invalid one
+    pub fn apply_all(&mut self, key: &str, events: Vec<OrderEvent>) -> Result<OrderState, TransitionError> {
+        let mut state = self.get(key).map(|o| o.state).unwrap_or(OrderState::Created);
+        for event in events {
+            state = self.apply(key, event)?;
+        }
+        Ok(state)
+    }
+
+    /// Drop orders terminal for longer than the retention, to bound memory
+    pub fn prune(&mut self) {
+        let cutoff = now_ms().saturating_sub(TERMINAL_RETENTION_MS);
+        let done: Vec<String> = self
+            .orders
+            .iter()
+            .filter(|(_, o)| o.state.is_terminal() && o.history.last().is_some_and(|(_, at)| *at < cutoff))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in done {
+            if let Some(o) = self.orders.remove(&key) {
+                if let Some(id) = o.order_id {
+                    self.by_id.remove(&id);
+                }
+            }
+        }
+    }
+}
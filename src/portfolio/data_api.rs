@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use reqwest::Client;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use super::positions::{Position, Positions};
+use crate::net::SendRetry;
+
+const DATA_API_URL: &str = "https://data-api.polymarket.com";
+
+/// Positions per `/positions` page
+const PAGE_SIZE: usize = 500;
+/// Stop paginating after this many pages
+const MAX_PAGES: usize = 20;
+/// Size differences below this are rounding, not a mismatch
+pub(super) const SIZE_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 4);
+
+// ==================================================
+// DATA API POSITIONS
+// ==================================================
+
+/// One holding as reported by the data API
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataApiPosition {
+    /// Token id
+    pub asset: String,
+    #[serde(default)]
+    pub condition_id: String,
+    pub size: f64,
+    #[serde(default)]
+    pub avg_price: f64,
+    #[serde(default)]
+    pub cur_price: f64,
+    #[serde(default)]
+    pub current_value: f64,
+    #[serde(default)]
+    pub cash_pnl: f64,
+    #[serde(default)]
+    pub realized_pnl: f64,
+    /// Market resolved and the position can be redeemed
+    #[serde(default)]
+    pub redeemable: bool,
+    /// Both outcomes held; can be merged back into USDC
+    #[serde(default)]
+    pub mergeable: bool,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub outcome: String,
+}
+
+impl DataApiPosition {
+    pub fn to_position(&self) -> Position {
+        Position {
+            token_id: self.asset.clone(),
+            market: self.condition_id.clone(),
+            size: Decimal::from_f64(self.size).unwrap_or_default(),
+            avg_price: Decimal::from_f64(self.avg_price).unwrap_or_default(),
+        }
+    }
+}
+
+/// One trade of a user as reported by the data API
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataApiTrade {
+    #[serde(default)]
+    pub proxy_wallet: String,
+    /// BUY or SELL
+    pub side: String,
+    /// Token id
+    pub asset: String,
+    #[serde(default)]
+    pub condition_id: String,
+    pub size: f64,
+    pub price: f64,
+    /// Unix seconds
+    pub timestamp: i64,
+    #[serde(default)]
+    pub transaction_hash: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub outcome: String,
+}
+
+impl DataApiTrade {
+    /// Identifies the trade across polls (one transaction can fill
+    /// several tokens)
+    pub fn key(&self) -> String {
+        format!("{}:{}:{}:{}", self.transaction_hash, self.asset, self.side, self.size)
+    }
+}
+
+/// A token whose local size disagrees with the data API
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionMismatch {
+    pub token_id: String,
+    pub local: Decimal,
+    pub remote: Decimal,
+}
+
+/// Read-only client for the Polymarket data API
+#[derive(Debug, Clone)]
+pub struct DataApiClient {
+    http: Client,
+    base_url: String,
+}
+
+impl DataApiClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: Client::new(),
+            base_url: base_url.into(),
+        }
+    }
+
+    /// `DATA_API_URL` (default the public data API)
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("DATA_API_URL").unwrap_or_else(|_| DATA_API_URL.to_string()))
+    }
+
+    /// Every open position of `user` (the proxy wallet address)
+    pub async fn positions(&self, user: &str) -> Result<Vec<DataApiPosition>> {
+        let url = format!("{}/positions", self.base_url);
+        let mut all = Vec::new();
+        for page in 0..MAX_PAGES {
+            let resp = self
+                .http
+                .get(&url)
+                .query(&[
+                    ("user", user.to_string()),
+                    ("limit", PAGE_SIZE.to_string()),
+                    ("offset", (page * PAGE_SIZE).to_string()),
+                    ("sizeThreshold", "0".to_string()),
+                ])
+                .send_retry()
+                .await?;
+
+            if !resp.status().is_success() {
+                return Err(anyhow!("Failed to fetch positions: {}", resp.status()));
+            }
+            let batch: Vec<DataApiPosition> = resp.json().await?;
+            let done = batch.len() < PAGE_SIZE;
+            all.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(all)
+    }
+
+    /// The latest `limit` trades of `user`, newest first
+    pub async fn trades(&self, user: &str, limit: usize) -> Result<Vec<DataApiTrade>> {
+        let resp = self
+            .http
+            .get(format!("{}/trades", self.base_url))
+            .query(&[
+                ("user", user.to_string()),
+                ("limit", limit.to_string()),
+                ("takerOnly", "false".to_string()),
+            ])
+            .send_retry()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(anyhow!("Failed to fetch trades: {}", resp.status()));
+        }
+        Ok(resp.json().await?)
+    }
+}
+
+/// Cross-check the local tracker against the data API, then adopt the
+/// API's view: every reported position is written into `positions`, and
+/// local positions the API doesn't know are zeroed.
+///
+/// Returns the tokens that disagreed. At startup the tracker is empty,
+/// so this just seeds it.
+pub async fn sync_positions(
+    api: &DataApiClient,
+    positions: &Positions,
+    user: &str,
+) -> Result<Vec<PositionMismatch>> {
+    let remote = api.positions(user).await?;
+    let mut mismatches = Vec::new();
+
+    for p in &remote {
+        let position = p.to_position();
+        let local = positions.size(&position.token_id).await;
+        if (local - position.size).abs() > SIZE_TOLERANCE && !local.is_zero() {
+            mismatches.push(PositionMismatch {
+                token_id: position.token_id.clone(),
+                local,
+                remote: position.size,
+            });
+        }
+        positions.set(position).await;
+    }
+
+    for local in positions.open().await {
+        if remote.iter().any(|p| p.asset == local.token_id) {
+            continue;
+        }
+        mismatches.push(PositionMismatch {
+            token_id: local.token_id.clone(),
+            local: local.size,
+            remote: Decimal::ZERO,
+        });
+        positions
+            .set(Position {
+                size: Decimal::ZERO,
+                avg_price: Decimal::ZERO,
+                ..local
+            })
+            .await;
+    }
+
+    for m in &mismatches {
+        warn!(
+            "⚠️  Position mismatch on {}: local {} vs data API {}",
+            m.token_id, m.local, m.remote
+        );
+    }
+    info!(
+        "📒 Synced {} position(s) from data API ({} mismatch(es))",
+        remote.len(),
+        mismatches.len()
+    );
+    Ok(mismatches)
+}
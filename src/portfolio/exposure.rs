@@ -0,0 +1,194 @@
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+use super::positions::Position;
+use crate::markets::{EventIndex, GammaEvent};
+
+// ==================================================
+// PER-EVENT EXPOSURE
+// ==================================================
+
+/// Our holdings of one binary market, both outcome tokens together
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketExposure {
+    pub condition_id: String,
+    pub question: String,
+    /// First outcome (YES) token; the only token for an unindexed holding
+    pub yes_token: String,
+    /// Second outcome (NO) token, when the market is indexed
+    pub no_token: Option<String>,
+    pub yes: Decimal,
+    pub no: Decimal,
+    pub cost_basis: Decimal,
+}
+
+impl MarketExposure {
+    /// Directional size in YES terms: a NO token is a short YES
+    pub fn net(&self) -> Decimal {
+        self.yes - self.no
+    }
+
+    /// YES+NO pairs, worth $1 whatever happens (mergeable)
+    pub fn hedged(&self) -> Decimal {
+        self.yes.min(self.no)
+    }
+}
+
+/// Everything we hold on one event, netted across its markets and
+/// complementary tokens.
+///
+/// `worst_payoff`/`best_payoff` are what the holdings pay at resolution
+/// in the least and most favourable outcome. For neg-risk events exactly
+/// one market resolves YES, so a NO on one leg offsets YES on another;
+/// otherwise markets are treated as independent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventExposure {
+    /// Gamma event id, or the condition id when the event isn't indexed
+    pub event_id: String,
+    pub title: String,
+    pub neg_risk: bool,
+    pub markets: Vec<MarketExposure>,
+    /// Sum of raw token sizes
+    pub gross: Decimal,
+    pub cost_basis: Decimal,
+    pub worst_payoff: Decimal,
+    pub best_payoff: Decimal,
+}
+
+impl EventExposure {
+    /// Capital lost if the event resolves the worst way for us; this is
+    /// the figure limits should apply to
+    pub fn max_loss(&self) -> Decimal {
+        (self.cost_basis - self.worst_payoff).max(Decimal::ZERO)
+    }
+
+    pub fn max_gain(&self) -> Decimal {
+        self.best_payoff - self.cost_basis
+    }
+
+    /// Sum of per-market net sizes in YES terms
+    pub fn net(&self) -> Decimal {
+        self.markets.iter().map(|m| m.net()).sum()
+    }
+}
+
+/// Group positions by event and net them. Tokens missing from `events`
+/// are reported per market and can't be netted against a complement, so
+/// each counts as a standalone long that may resolve worthless.
+pub fn event_exposures(positions: &[Position], events: &EventIndex) -> Vec<EventExposure> {
+    let mut indexed: HashMap<String, Vec<&Position>> = HashMap::new();
+    let mut unindexed: BTreeMap<String, Vec<&Position>> = BTreeMap::new();
+    for p in positions.iter().filter(|p| !p.size.is_zero()) {
+        match events.event_id(&p.token_id) {
+            Some(id) => indexed.entry(id.to_string()).or_default().push(p),
+            None => unindexed.entry(p.market.clone()).or_default().push(p),
+        }
+    }
+
+    let mut exposures: Vec<EventExposure> = indexed
+        .into_iter()
+        .filter_map(|(id, held)| Some(from_event(events.event(&id)?, &held)))
+        .collect();
+
+    for (condition_id, held) in unindexed {
+        let markets: Vec<MarketExposure> = held
+            .iter()
+            .map(|p| MarketExposure {
+                condition_id: condition_id.clone(),
+                question: String::new(),
+                yes_token: p.token_id.clone(),
+                no_token: None,
+                yes: p.size,
+                no: Decimal::ZERO,
+                cost_basis: p.cost_basis(),
+            })
+            .collect();
+        exposures.push(summarize(condition_id, String::new(), false, markets, None));
+    }
+
+    exposures.sort_by_key(|e| std::cmp::Reverse(e.max_loss()));
+    exposures
+}
+
+fn from_event(event: &GammaEvent, held: &[&Position]) -> EventExposure {
+    let size_of = |token: &str| {
+        held.iter()
+            .filter(|p| p.token_id == token)
+            .map(|p| (p.size, p.cost_basis()))
+            .fold((Decimal::ZERO, Decimal::ZERO), |a, b| (a.0 + b.0, a.1 + b.1))
+    };
+
+    // Every market of the event, held or not: an unheld leg can still
+    // be the one that resolves YES
+    let all: Vec<MarketExposure> = event
+        .markets
+        .iter()
+        .filter_map(|m| {
+            let tokens = m.token_ids();
+            let yes_token = tokens.first()?.clone();
+            let no_token = tokens.get(1).cloned();
+            let (yes, yes_cost) = size_of(&yes_token);
+            let (no, no_cost) = no_token.as_deref().map(size_of).unwrap_or_default();
+            Some(MarketExposure {
+                condition_id: m.condition_id.clone(),
+                question: m.question.clone(),
+                yes_token,
+                no_token,
+                yes,
+                no,
+                cost_basis: yes_cost + no_cost,
+            })
+        })
+        .collect();
+
+    let neg_risk_scenarios = event.neg_risk.then(|| {
+        // Leg i resolves YES, every other leg NO
+        let all_no: Decimal = all.iter().map(|m| m.no).sum();
+        all.iter()
+            .map(|m| all_no - m.no + m.yes)
+            .collect::<Vec<_>>()
+    });
+
+    let markets = all
+        .into_iter()
+        .filter(|m| !m.yes.is_zero() || !m.no.is_zero())
+        .collect();
+    summarize(
+        event.id.clone(),
+        event.title.clone(),
+        event.neg_risk,
+        markets,
+        neg_risk_scenarios,
+    )
+}
+
+/// Fill in totals; `scenarios` are the payoffs of each possible
+/// resolution when known, else markets resolve independently
+fn summarize(
+    event_id: String,
+    title: String,
+    neg_risk: bool,
+    markets: Vec<MarketExposure>,
+    scenarios: Option<Vec<Decimal>>,
+) -> EventExposure {
+    let (worst_payoff, best_payoff) = match scenarios.filter(|s| !s.is_empty()) {
+        Some(s) => (
+            s.iter().copied().min().unwrap_or_default(),
+            s.iter().copied().max().unwrap_or_default(),
+        ),
+        None => (
+            markets.iter().map(|m| m.yes.min(m.no)).sum(),
+            markets.iter().map(|m| m.yes.max(m.no)).sum(),
+        ),
+    };
+    EventExposure {
+        event_id,
+        title,
+        neg_risk,
+        gross: markets.iter().map(|m| m.yes + m.no).sum(),
+        cost_basis: markets.iter().map(|m| m.cost_basis).sum(),
+        worst_payoff,
+        best_payoff,
+        markets,
+    }
+}
@@ -0,0 +1,254 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::order::Side;
+use crate::execution::ClobClient;
+use crate::portfolio::Positions;
+
+// ==================================================
+// INVENTORY CONTROL
+// ==================================================
+// Target bands per token, shared by every strategy trading it:
+//
+//   size inside  target ± tolerance  →  quotes biased toward target
+//   size outside the band            →  reducing trade back to its edge
+//                                       (INVENTORY_REDUCE=true)
+//
+// The bias is a price shift for quote centers, -max_skew at or above
+// the upper edge and +max_skew at or below the lower one, linear in
+// between: long inventory lowers quotes so we sell more and buy less.
+
+/// Where inventory of one token should sit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InventoryBand {
+    pub target: Decimal,
+    /// Allowed distance from `target` either way
+    pub tolerance: Decimal,
+}
+
+impl InventoryBand {
+    pub fn lower(&self) -> Decimal {
+        (self.target - self.tolerance).max(Decimal::ZERO)
+    }
+
+    pub fn upper(&self) -> Decimal {
+        self.target + self.tolerance
+    }
+
+    /// Tokens to sell (positive) or buy (negative) to get back inside
+    /// the band; zero inside it
+    pub fn excess(&self, size: Decimal) -> Decimal {
+        if size > self.upper() {
+            size - self.upper()
+        } else if size < self.lower() {
+            size - self.lower()
+        } else {
+            Decimal::ZERO
+        }
+    }
+
+    /// Where `size` sits relative to the target, clamped to [-1, 1]
+    /// (±1 at the band edges)
+    pub fn ratio(&self, size: Decimal) -> f64 {
+        if self.tolerance <= Decimal::ZERO {
+            return match size.cmp(&self.target) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Less => -1.0,
+                std::cmp::Ordering::Equal => 0.0,
+            };
+        }
+        ((size - self.target) / self.tolerance)
+            .to_f64()
+            .unwrap_or_default()
+            .clamp(-1.0, 1.0)
+    }
+}
+
+/// Inventory of one token against its band
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryState {
+    pub token_id: String,
+    pub size: Decimal,
+    pub band: InventoryBand,
+    /// See `InventoryBand::excess`
+    pub excess: Decimal,
+    /// Price shift to apply to quote centers
+    pub bias: f64,
+}
+
+/// Watches positions against target bands, biases quotes and optionally
+/// trades inventory back into its band
+pub struct InventoryController {
+    positions: Arc<Positions>,
+    bands: HashMap<String, InventoryBand>,
+    max_skew: f64,
+    reduce: bool,
+    reduce_slippage: f64,
+}
+
+impl InventoryController {
+    pub fn new(positions: Arc<Positions>) -> Self {
+        Self {
+            positions,
+            bands: HashMap::new(),
+            max_skew: 0.02,
+            reduce: false,
+            reduce_slippage: 0.02,
+        }
+    }
+
+    /// Keep `token_id` within `band`
+    pub fn band(mut self, token_id: impl Into<String>, band: InventoryBand) -> Self {
+        self.bands.insert(token_id.into(), band);
+        self
+    }
+
+    /// Quote shift at the band edges
+    pub fn max_skew(mut self, max_skew: f64) -> Self {
+        self.max_skew = max_skew;
+        self
+    }
+
+    /// Trade inventory outside its band back to the edge, walking the
+    /// book at most `max_slippage` from the touch
+    pub fn reduce(mut self, max_slippage: f64) -> Self {
+        self.reduce = true;
+        self.reduce_slippage = max_slippage;
+        self
+    }
+
+    /// `INVENTORY_BANDS` (`token:target:tolerance,…`), `INVENTORY_MAX_SKEW`
+    /// (default 0.02), `INVENTORY_REDUCE` and `INVENTORY_REDUCE_SLIPPAGE`
+    /// (default 0.02)
+    pub fn from_env(positions: Arc<Positions>) -> Result<Self> {
+        let mut controller = Self::new(positions);
+        for entry in std::env::var("INVENTORY_BANDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let parts: Vec<&str> = entry.split(':').collect();
+            let [token_id, target, tolerance] = parts[..] else {
+                return Err(anyhow!("Bad INVENTORY_BANDS entry {:?}, want token:target:tolerance", entry));
+            };
+            let band = InventoryBand {
+                target: target.parse().map_err(|e| anyhow!("Bad target in {:?}: {}", entry, e))?,
+                tolerance: tolerance.parse().map_err(|e| anyhow!("Bad tolerance in {:?}: {}", entry, e))?,
+            };
+            controller = controller.band(token_id, band);
+        }
+        if let Some(skew) = std::env::var("INVENTORY_MAX_SKEW").ok().and_then(|v| v.parse().ok()) {
+            controller = controller.max_skew(skew);
+        }
+        if std::env::var("INVENTORY_REDUCE").as_deref() == Ok("true") {
+            let slippage = std::env::var("INVENTORY_REDUCE_SLIPPAGE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.02);
+            controller = controller.reduce(slippage);
+        }
+        Ok(controller)
+    }
+
+    pub fn bands(&self) -> &HashMap<String, InventoryBand> {
+        &self.bands
+    }
+
+    /// `None` for tokens without a band
+    pub async fn state(&self, token_id: &str) -> Option<InventoryState> {
+        let band = *self.bands.get(token_id)?;
+        let size = self.positions.size(token_id).await;
+        Some(InventoryState {
+            token_id: token_id.to_string(),
+            size,
+            band,
+            excess: band.excess(size),
+            bias: -self.max_skew * band.ratio(size),
+        })
+    }
+
+    /// Price shift for quotes on `token_id`; zero without a band
+    pub async fn bias(&self, token_id: &str) -> f64 {
+        self.state(token_id).await.map(|s| s.bias).unwrap_or_default()
+    }
+
+    /// Every banded token, out-of-band ones first
+    pub async fn states(&self) -> Vec<InventoryState> {
+        let mut states = Vec::new();
+        for token_id in self.bands.keys() {
+            states.extend(self.state(token_id).await);
+        }
+        states.sort_by_key(|s| std::cmp::Reverse(s.excess.abs()));
+        states
+    }
+
+    /// Check the bands every `every`, trading back into them when
+    /// reducing is on
+    pub async fn run(self: Arc<Self>, clob: Arc<ClobClient>, every: Duration) {
+        info!(
+            "📦 Inventory control on {} token(s){}",
+            self.bands.len(),
+            if self.reduce { ", reducing trades on" } else { "" }
+        );
+        let mut ticker = tokio::time::interval(every);
+        loop {
+            ticker.tick().await;
+            for state in self.states().await {
+                if state.excess.is_zero() {
+                    continue;
+                }
+                warn!(
+                    "📦 {} inventory {} outside {}..{} (target {})",
+                    state.token_id,
+                    state.size,
+                    state.band.lower(),
+                    state.band.upper(),
+                    state.band.target
+                );
+                if !self.reduce {
+                    continue;
+                }
+                if let Err(e) = self.reduce_one(&clob, &state).await {
+                    warn!("⚠️  Inventory reduction on {} failed: {}", state.token_id, e);
+                }
+            }
+        }
+    }
+
+    /// One marketable order taking `state` back to its band edge
+    async fn reduce_one(&self, clob: &ClobClient, state: &InventoryState) -> Result<()> {
+        let side = match state.excess > Decimal::ZERO {
+            true => Side::Sell,
+            false => Side::Buy,
+        };
+        let size = state.excess.abs().round_dp_with_strategy(2, rust_decimal::RoundingStrategy::ToZero);
+        let rules = clob.market_rules(&state.token_id).await?;
+        if size < rules.min_size {
+            return Ok(());
+        }
+        let order_id = match side {
+            Side::Sell => clob.market_sell(&state.token_id, size, self.reduce_slippage).await?,
+            Side::Buy => {
+                let book = clob.get_orderbook(&state.token_id).await?;
+                let ask = ClobClient::best_price(&book, &Side::Buy)?;
+                let usdc = size * Decimal::from_f64_retain(ask).unwrap_or_default();
+                clob.market_buy(&state.token_id, usdc, self.reduce_slippage).await?
+            }
+        };
+        info!(
+            "📦 {} {} of {} toward target {} ({})",
+            side.as_str(),
+            size,
+            state.token_id,
+            state.band.target,
+            order_id
+        );
+        Ok(())
+    }
+}
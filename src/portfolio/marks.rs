@@ -0,0 +1,199 @@
+use anyhow::{anyhow, Result};
+use log::warn;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::positions::Position;
+use crate::domain::order::Side;
+use crate::execution::orderbook::{fetch_last_trade, fetch_midpoints, fetch_prices};
+use crate::market_ws::{MarketBooks, MarketTrades};
+
+// ==================================================
+// MARKING
+// ==================================================
+
+/// How a position is valued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkSource {
+    Midpoint,
+    LastTrade,
+    /// What it could be closed at now: best bid for longs, best ask
+    /// for shorts
+    Conservative,
+}
+
+impl MarkSource {
+    /// `MARK_METHOD` = midpoint (default) | last_trade | conservative
+    pub fn from_env() -> Self {
+        match std::env::var("MARK_METHOD")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "last_trade" | "last" => MarkSource::LastTrade,
+            "conservative" => MarkSource::Conservative,
+            _ => MarkSource::Midpoint,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarkSource::Midpoint => "midpoint",
+            MarkSource::LastTrade => "last_trade",
+            MarkSource::Conservative => "conservative",
+        }
+    }
+}
+
+/// Marks positions with one methodology everywhere: PnL, stops and the
+/// status API all value a token the same way.
+///
+/// Uses the live market-channel books and trades when attached and the
+/// token is covered, otherwise the CLOB REST endpoints.
+pub struct Marker {
+    source: MarkSource,
+    clob_url: String,
+    books: Option<Arc<MarketBooks>>,
+    trades: Option<Arc<MarketTrades>>,
+}
+
+impl Marker {
+    pub fn new(clob_url: impl Into<String>, source: MarkSource) -> Self {
+        Self {
+            source,
+            clob_url: clob_url.into(),
+            books: None,
+            trades: None,
+        }
+    }
+
+    /// `MARK_METHOD`
+    pub fn from_env(clob_url: impl Into<String>) -> Self {
+        Self::new(clob_url, MarkSource::from_env())
+    }
+
+    /// Prefer these local books over REST
+    pub fn books(mut self, books: Arc<MarketBooks>) -> Self {
+        self.books = Some(books);
+        self
+    }
+
+    /// Prefer these local trades for last-trade marks
+    pub fn trades(mut self, trades: Arc<MarketTrades>) -> Self {
+        self.trades = Some(trades);
+        self
+    }
+
+    pub fn source(&self) -> MarkSource {
+        self.source
+    }
+
+    /// Mark of `token_id` for a holding of `size` (negative = short)
+    pub async fn mark(&self, token_id: &str, size: Decimal) -> Result<f64> {
+        if let Some(mark) = self.local_mark(token_id, size).await {
+            return Ok(mark);
+        }
+        let tokens = [token_id.to_string()];
+        let mark = match (self.source, size < Decimal::ZERO) {
+            (MarkSource::LastTrade, _) => return fetch_last_trade(&self.clob_url, token_id).await,
+            (MarkSource::Midpoint, _) => fetch_midpoints(&self.clob_url, &tokens).await?,
+            (MarkSource::Conservative, false) => fetch_prices(&self.clob_url, &tokens, &Side::Sell).await?,
+            (MarkSource::Conservative, true) => fetch_prices(&self.clob_url, &tokens, &Side::Buy).await?,
+        };
+        mark.get(token_id)
+            .copied()
+            .ok_or_else(|| anyhow!("No {} mark for {}", self.source.as_str(), token_id))
+    }
+
+    /// Marks for every position, keyed by token id. Tokens that can't
+    /// be marked are left out (and so carry no unrealized PnL).
+    pub async fn marks(&self, positions: &[Position]) -> HashMap<String, f64> {
+        let mut marks = HashMap::new();
+        let mut missing: Vec<&Position> = Vec::new();
+        for p in positions {
+            match self.local_mark(&p.token_id, p.size).await {
+                Some(m) => {
+                    marks.insert(p.token_id.clone(), m);
+                }
+                None => missing.push(p),
+            }
+        }
+        if missing.is_empty() {
+            return marks;
+        }
+
+        let fetched = match self.source {
+            MarkSource::Midpoint => fetch_midpoints(&self.clob_url, &ids(&missing, |_| true)).await,
+            MarkSource::Conservative => {
+                let long = ids(&missing, |p| p.size >= Decimal::ZERO);
+                let short = ids(&missing, |p| p.size < Decimal::ZERO);
+                let bids = fetch_prices(&self.clob_url, &long, &Side::Sell).await;
+                let asks = fetch_prices(&self.clob_url, &short, &Side::Buy).await;
+                bids.and_then(|mut b| {
+                    b.extend(asks?);
+                    Ok(b)
+                })
+            }
+            MarkSource::LastTrade => {
+                let mut last = HashMap::new();
+                for p in &missing {
+                    match fetch_last_trade(&self.clob_url, &p.token_id).await {
+                        Ok(m) => {
+                            last.insert(p.token_id.clone(), m);
+                        }
+                        Err(e) => warn!("⚠️  No last trade for {}: {}", p.token_id, e),
+                    }
+                }
+                Ok(last)
+            }
+        };
+        match fetched {
+            Ok(fetched) => marks.extend(fetched),
+            Err(e) => warn!("⚠️  Failed to fetch {} marks: {}", self.source.as_str(), e),
+        }
+        marks
+    }
+
+    /// Value of each position at its mark (size × mark)
+    pub async fn market_values(&self, positions: &[Position]) -> HashMap<String, f64> {
+        let marks = self.marks(positions).await;
+        positions
+            .iter()
+            .filter_map(|p| {
+                let mark = marks.get(&p.token_id)?;
+                Some((p.token_id.clone(), p.size.to_f64()? * mark))
+            })
+            .collect()
+    }
+
+    async fn local_mark(&self, token_id: &str, size: Decimal) -> Option<f64> {
+        match self.source {
+            MarkSource::LastTrade => self.trades.as_ref()?.last_price(token_id).await,
+            MarkSource::Midpoint | MarkSource::Conservative => {
+                let books = self.books.as_ref()?;
+                if books.is_degraded(token_id).await {
+                    return None;
+                }
+                let book = books.get(token_id).await?;
+                let mark = match (self.source, size < Decimal::ZERO) {
+                    (MarkSource::Midpoint, _) => book.mid()?,
+                    (_, false) => book.best_bid()?.0,
+                    (_, true) => book.best_ask()?.0,
+                };
+                mark.to_f64()
+            }
+        }
+    }
+}
+
+fn ids(positions: &[&Position], keep: impl Fn(&Position) -> bool) -> Vec<String> {
+    positions
+        .iter()
+        .filter(|p| keep(p))
+        .map(|p| p.token_id.clone())
+        .collect()
+}
@@ -0,0 +1,17 @@
+pub mod data_api;
+pub mod exposure;
+pub mod inventory;
+pub mod marks;
+pub mod onchain;
+pub mod pnl;
+pub mod positions;
+pub mod stats;
+
+pub use data_api::{sync_positions, DataApiClient, DataApiPosition, DataApiTrade, PositionMismatch};
+pub use exposure::{event_exposures, EventExposure, MarketExposure};
+pub use inventory::{InventoryBand, InventoryController, InventoryState};
+pub use marks::{MarkSource, Marker};
+pub use onchain::check_onchain_positions;
+pub use pnl::{run_pnl, CostMethod, DailyPnl, PnlEngine, PnlLine};
+pub use positions::{Fill, Position, Positions};
+pub use stats::PerformanceStats;
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use log::{info, warn};
+
+use super::data_api::{PositionMismatch, SIZE_TOLERANCE};
+use super::positions::Positions;
+use crate::execution::ClobClient;
+
+// ==================================================
+// ON-CHAIN RECONCILIATION
+// ==================================================
+
+/// Compare the tracker against the funder's actual ERC-1155 balances of
+/// `token_ids`. Reports only; the tracker is left as is so the caller
+/// can decide whether to trust the chain.
+///
+/// Balances lag fills until the trade is mined, so a mismatch right
+/// after a fill is expected; persistent ones are not.
+pub async fn check_onchain_positions(
+    clob: &ClobClient,
+    positions: &Positions,
+    token_ids: &[String],
+) -> Result<Vec<PositionMismatch>> {
+    let balances = clob.token_balances(token_ids).await?;
+    let mut mismatches = Vec::new();
+    for token in token_ids {
+        let local = positions.size(token).await;
+        let chain = balances.get(token).copied().unwrap_or_default();
+        if (local - chain).abs() > SIZE_TOLERANCE {
+            warn!(
+                "⚠️  On-chain mismatch on {}: tracker {} vs chain {}",
+                token, local, chain
+            );
+            mismatches.push(PositionMismatch {
+                token_id: token.clone(),
+                local,
+                remote: chain,
+            });
+        }
+    }
+    info!(
+        "⛓️  Checked {} token balance(s) on-chain ({} mismatch(es))",
+        token_ids.len(),
+        mismatches.len()
+    );
+    Ok(mismatches)
+}
@@ -0,0 +1,328 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use log::warn;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, Mutex};
+
+use super::positions::Fill;
+use super::stats::PerformanceStats;
+use crate::domain::order::Side;
+
+/// Bucket for fills whose order wasn't tagged with a strategy
+pub const UNTAGGED: &str = "untagged";
+
+// ==================================================
+// PNL ENGINE
+// ==================================================
+
+/// Which entry price a sale is matched against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostMethod {
+    /// Oldest lot first
+    Fifo,
+    /// Running average of all open lots
+    AverageCost,
+}
+
+impl CostMethod {
+    /// `PNL_COST_METHOD` = average (default) | fifo
+    pub fn from_env() -> Self {
+        match std::env::var("PNL_COST_METHOD")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "fifo" => CostMethod::Fifo,
+            _ => CostMethod::AverageCost,
+        }
+    }
+}
+
+/// PnL of one token, market, strategy or the whole book
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PnlLine {
+    pub key: String,
+    pub realized: Decimal,
+    /// Against the supplied marks; zero where no mark was given
+    pub unrealized: Decimal,
+    pub position: Decimal,
+    pub cost_basis: Decimal,
+    /// Exchange fees paid; already taken out of realized and cost basis
+    pub fees: Decimal,
+}
+
+impl PnlLine {
+    pub fn total(&self) -> Decimal {
+        self.realized + self.unrealized
+    }
+
+    fn add(&mut self, other: &PnlLine) {
+        self.realized += other.realized;
+        self.unrealized += other.unrealized;
+        self.position += other.position;
+        self.cost_basis += other.cost_basis;
+        self.fees += other.fees;
+    }
+}
+
+/// Realized PnL and activity of one UTC day
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DailyPnl {
+    pub date: NaiveDate,
+    pub realized: Decimal,
+    pub fills: usize,
+    /// Notional traded (USDC)
+    pub volume: Decimal,
+    /// Sales that realized a gain / a loss
+    pub wins: usize,
+    pub losses: usize,
+    /// Sum of gains and of losses (the latter negative)
+    pub gross_win: Decimal,
+    pub gross_loss: Decimal,
+    /// Exchange fees paid (USDC)
+    pub fees: Decimal,
+}
+
+/// Open lots of one token held by one strategy
+#[derive(Debug, Clone)]
+struct Lots {
+    market: String,
+    method: CostMethod,
+    // (price, size), oldest first; a single merged lot under AverageCost
+    lots: VecDeque<(Decimal, Decimal)>,
+    realized: Decimal,
+    fees: Decimal,
+}
+
+impl Lots {
+    fn new(market: &str, method: CostMethod) -> Self {
+        Self {
+            market: market.to_string(),
+            method,
+            lots: VecDeque::new(),
+            realized: Decimal::ZERO,
+            fees: Decimal::ZERO,
+        }
+    }
+
+    fn size(&self) -> Decimal {
+        self.lots.iter().map(|(_, s)| *s).sum()
+    }
+
+    fn cost_basis(&self) -> Decimal {
+        self.lots.iter().map(|(p, s)| p * s).sum()
+    }
+
+    fn buy(&mut self, price: Decimal, size: Decimal) {
+        match (self.method, self.lots.front_mut()) {
+            (CostMethod::AverageCost, Some((avg, held))) => {
+                let total = *held + size;
+                *avg = (*avg * *held + price * size) / total;
+                *held = total;
+            }
+            _ => self.lots.push_back((price, size)),
+        }
+    }
+
+    /// Realize against open lots; returns the PnL realized. Selling
+    /// more than is held (a position opened before tracking began)
+    /// realizes nothing on the excess.
+    fn sell(&mut self, price: Decimal, mut size: Decimal) -> Decimal {
+        let mut realized = Decimal::ZERO;
+        while size > Decimal::ZERO {
+            let Some((lot_price, lot_size)) = self.lots.front_mut() else {
+                break;
+            };
+            let take = size.min(*lot_size);
+            realized += (price - *lot_price) * take;
+            *lot_size -= take;
+            size -= take;
+            if lot_size.is_zero() {
+                self.lots.pop_front();
+            }
+        }
+        self.realized += realized;
+        realized
+    }
+
+    fn line(&self, key: &str, mark: Option<Decimal>) -> PnlLine {
+        let size = self.size();
+        let cost_basis = self.cost_basis();
+        PnlLine {
+            key: key.to_string(),
+            realized: self.realized,
+            unrealized: mark.map(|m| m * size - cost_basis).unwrap_or_default(),
+            position: size,
+            cost_basis,
+            fees: self.fees,
+        }
+    }
+}
+
+/// Realized PnL from fills and unrealized PnL from marks, sliced per
+/// token, market, strategy and in total, with realized daily rollups.
+///
+/// Inventory is kept per (strategy, token), so each strategy's sales are
+/// matched against its own entries; per-token figures sum the
+/// strategies. Orders are attributed with `tag_order`.
+#[derive(Debug)]
+pub struct PnlEngine {
+    method: CostMethod,
+    // (strategy, token) → open lots
+    books: HashMap<(String, String), Lots>,
+    // order id → strategy
+    strategy_of: HashMap<String, String>,
+    daily: BTreeMap<NaiveDate, DailyPnl>,
+}
+
+impl PnlEngine {
+    pub fn new(method: CostMethod) -> Self {
+        Self {
+            method,
+            books: HashMap::new(),
+            strategy_of: HashMap::new(),
+            daily: BTreeMap::new(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(CostMethod::from_env())
+    }
+
+    pub fn method(&self) -> CostMethod {
+        self.method
+    }
+
+    /// Attribute fills of `order_id` to `strategy`
+    pub fn tag_order(&mut self, order_id: &str, strategy: &str) {
+        self.strategy_of
+            .insert(order_id.to_string(), strategy.to_string());
+    }
+
+    /// Book a fill; returns the PnL it realized. Its fee is charged per
+    /// token: added to a buy's cost, taken off a sale's proceeds.
+    pub fn on_fill(&mut self, fill: &Fill) -> Decimal {
+        let strategy = self
+            .strategy_of
+            .get(&fill.order_id)
+            .cloned()
+            .unwrap_or_else(|| UNTAGGED.to_string());
+        let method = self.method;
+        let lots = self
+            .books
+            .entry((strategy, fill.token_id.clone()))
+            .or_insert_with(|| Lots::new(&fill.market, method));
+
+        let fee_per_token = if fill.size.is_zero() {
+            Decimal::ZERO
+        } else {
+            fill.fee / fill.size
+        };
+        lots.fees += fill.fee;
+        let realized = match fill.side {
+            Side::Buy => {
+                lots.buy(fill.price + fee_per_token, fill.size);
+                Decimal::ZERO
+            }
+            Side::Sell => lots.sell(fill.price - fee_per_token, fill.size),
+        };
+
+        let date = Utc
+            .timestamp_millis_opt(fill.at_ms as i64)
+            .single()
+            .unwrap_or_else(Utc::now)
+            .date_naive();
+        let day = self.daily.entry(date).or_insert_with(|| DailyPnl {
+            date,
+            ..Default::default()
+        });
+        day.realized += realized;
+        day.fills += 1;
+        day.volume += fill.price * fill.size;
+        day.fees += fill.fee;
+        if realized > Decimal::ZERO {
+            day.wins += 1;
+            day.gross_win += realized;
+        } else if realized < Decimal::ZERO {
+            day.losses += 1;
+            day.gross_loss += realized;
+        }
+        realized
+    }
+
+    pub fn by_token(&self, marks: &HashMap<String, f64>) -> Vec<PnlLine> {
+        self.group(marks, |_, token, _| token.to_string())
+    }
+
+    /// Per condition id
+    pub fn by_market(&self, marks: &HashMap<String, f64>) -> Vec<PnlLine> {
+        self.group(marks, |_, _, lots| lots.market.clone())
+    }
+
+    pub fn by_strategy(&self, marks: &HashMap<String, f64>) -> Vec<PnlLine> {
+        self.group(marks, |strategy, _, _| strategy.to_string())
+    }
+
+    pub fn total(&self, marks: &HashMap<String, f64>) -> PnlLine {
+        let mut total = PnlLine {
+            key: "total".to_string(),
+            ..Default::default()
+        };
+        for line in self.by_token(marks) {
+            total.add(&line);
+        }
+        total
+    }
+
+    /// Realized PnL per UTC day, oldest first
+    pub fn daily(&self) -> Vec<DailyPnl> {
+        self.daily.values().cloned().collect()
+    }
+
+    /// Performance statistics over the realized daily series
+    pub fn stats(&self, capital: Option<f64>) -> PerformanceStats {
+        PerformanceStats::from_daily(&self.daily(), capital)
+    }
+
+    pub fn realized_on(&self, date: NaiveDate) -> Decimal {
+        self.daily
+            .get(&date)
+            .map(|d| d.realized)
+            .unwrap_or_default()
+    }
+
+    fn group<F>(&self, marks: &HashMap<String, f64>, key: F) -> Vec<PnlLine>
+    where
+        F: Fn(&str, &str, &Lots) -> String,
+    {
+        let mut lines: BTreeMap<String, PnlLine> = BTreeMap::new();
+        for ((strategy, token), lots) in &self.books {
+            let k = key(strategy, token, lots);
+            let mark = marks.get(token).and_then(|m| Decimal::from_f64(*m));
+            lines
+                .entry(k.clone())
+                .or_insert_with(|| PnlLine {
+                    key: k,
+                    ..Default::default()
+                })
+                .add(&lots.line("", mark));
+        }
+        lines.into_values().collect()
+    }
+}
+
+/// Book every fill from `fills` (e.g. `UserWs::subscribe`) into `pnl`
+pub async fn run_pnl(mut fills: broadcast::Receiver<Fill>, pnl: Arc<Mutex<PnlEngine>>) {
+    loop {
+        match fills.recv().await {
+            Ok(fill) => {
+                pnl.lock().await.on_fill(&fill);
+            }
+            Err(RecvError::Lagged(n)) => warn!("⚠️  PnL engine missed {} fill(s)", n),
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio::sync::{broadcast, RwLock};
+
+use crate::domain::order::Side;
+
+/// Position updates kept for slow subscribers
+const UPDATE_BUFFER: usize = 1024;
+
+// ==================================================
+// FILLS
+// ==================================================
+
+/// One of our orders matching, on either side of a trade
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fill {
+    pub trade_id: String,
+    pub order_id: String,
+    pub token_id: String,
+    /// Condition id
+    pub market: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    /// Exchange fee, in USDC
+    pub fee: Decimal,
+    /// Our order was resting (maker) rather than taking
+    pub maker: bool,
+    pub at_ms: u64,
+}
+
+// ==================================================
+// POSITIONS
+// ==================================================
+
+/// Net holding of one outcome token at average cost
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub token_id: String,
+    pub market: String,
+    pub size: Decimal,
+    /// Average entry price of `size`
+    pub avg_price: Decimal,
+}
+
+impl Position {
+    fn new(token_id: &str, market: &str) -> Self {
+        Self {
+            token_id: token_id.to_string(),
+            market: market.to_string(),
+            size: Decimal::ZERO,
+            avg_price: Decimal::ZERO,
+        }
+    }
+
+    /// Buys move the average entry; sells reduce size at it
+    fn apply(&mut self, side: &Side, price: Decimal, size: Decimal) {
+        match side {
+            Side::Buy => {
+                let total = self.size + size;
+                if total > Decimal::ZERO {
+                    self.avg_price = (self.avg_price * self.size + price * size) / total;
+                }
+                self.size = total;
+            }
+            Side::Sell => {
+                self.size -= size;
+                if self.size <= Decimal::ZERO {
+                    self.avg_price = Decimal::ZERO;
+                }
+            }
+        }
+    }
+
+    pub fn cost_basis(&self) -> Decimal {
+        self.size * self.avg_price
+    }
+}
+
+/// Positions per token, built from fills as they arrive (user channel)
+/// rather than polled. Every change is broadcast.
+#[derive(Debug)]
+pub struct Positions {
+    positions: RwLock<HashMap<String, Position>>,
+    updates: broadcast::Sender<Position>,
+}
+
+impl Default for Positions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Positions {
+    pub fn new() -> Self {
+        Self {
+            positions: RwLock::new(HashMap::new()),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Position> {
+        self.updates.subscribe()
+    }
+
+    pub async fn apply_fill(&self, fill: &Fill) -> Position {
+        self.apply(&fill.token_id, &fill.market, &fill.side, fill.price, fill.size)
+            .await
+    }
+
+    /// Undo a fill whose trade failed on-chain
+    pub async fn reverse_fill(&self, fill: &Fill) -> Position {
+        let side = match fill.side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+        self.apply(&fill.token_id, &fill.market, &side, fill.price, fill.size)
+            .await
+    }
+
+    /// Overwrite a position, e.g. from an exchange snapshot
+    pub async fn set(&self, position: Position) {
+        self.positions
+            .write()
+            .await
+            .insert(position.token_id.clone(), position.clone());
+        let _ = self.updates.send(position);
+    }
+
+    pub async fn get(&self, token_id: &str) -> Option<Position> {
+        self.positions.read().await.get(token_id).cloned()
+    }
+
+    /// Net size held; zero if flat or unknown
+    pub async fn size(&self, token_id: &str) -> Decimal {
+        self.get(token_id)
+            .await
+            .map(|p| p.size)
+            .unwrap_or_default()
+    }
+
+    /// Non-flat positions
+    pub async fn open(&self) -> Vec<Position> {
+        self.positions
+            .read()
+            .await
+            .values()
+            .filter(|p| !p.size.is_zero())
+            .cloned()
+            .collect()
+    }
+
+    async fn apply(
+        &self,
+        token_id: &str,
+        market: &str,
+        side: &Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Position {
+        let mut positions = self.positions.write().await;
+        let position = positions
+            .entry(token_id.to_string())
+            .or_insert_with(|| Position::new(token_id, market));
+        position.apply(side, price, size);
+        let position = position.clone();
+        drop(positions);
+
+        let _ = self.updates.send(position.clone());
+        position
+    }
+}
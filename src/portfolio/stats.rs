@@ -0,0 +1,136 @@
+use chrono::Days;
+use log::info;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use super::pnl::DailyPnl;
+
+/// Days per year used to annualize (prediction markets trade every day)
+const PERIODS_PER_YEAR: f64 = 365.0;
+
+// ==================================================
+// PERFORMANCE STATISTICS
+// ==================================================
+
+/// Summary statistics of the realized PnL series. Figures are in USDC
+/// unless noted; ratios are `None` where there isn't enough data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PerformanceStats {
+    /// Calendar days from first to last fill
+    pub days: usize,
+    pub fills: usize,
+    pub realized: f64,
+    /// Annualized mean / stdev of daily realized PnL (idle days count
+    /// as zero)
+    pub sharpe: Option<f64>,
+    /// Largest peak-to-trough fall of cumulative realized PnL
+    pub max_drawdown: f64,
+    /// Winning sales / sales that realized a gain or loss
+    pub hit_rate: Option<f64>,
+    pub avg_win: Option<f64>,
+    /// Negative
+    pub avg_loss: Option<f64>,
+    /// Notional traded
+    pub volume: f64,
+    /// Volume / capital per day, when capital is known
+    pub turnover: Option<f64>,
+}
+
+impl PerformanceStats {
+    /// `STATS_CAPITAL_USDC`: bankroll for turnover; unset = not reported
+    pub fn capital_from_env() -> Option<f64> {
+        std::env::var("STATS_CAPITAL_USDC")
+            .ok()
+            .and_then(|c| c.parse().ok())
+    }
+
+    /// From daily rollups (oldest first, as `PnlEngine::daily` returns).
+    /// `capital` is the bankroll turnover is measured against.
+    pub fn from_daily(daily: &[DailyPnl], capital: Option<f64>) -> Self {
+        let (Some(first), Some(last)) = (daily.first(), daily.last()) else {
+            return Self::default();
+        };
+
+        // Dense series: one entry per calendar day, idle days zero
+        let mut series = Vec::new();
+        let mut rows = daily.iter().peekable();
+        let mut date = first.date;
+        while date <= last.date {
+            let realized = match rows.peek() {
+                Some(d) if d.date == date => rows.next().map(|d| f(d.realized)).unwrap_or(0.0),
+                _ => 0.0,
+            };
+            series.push(realized);
+            date = match date.checked_add_days(Days::new(1)) {
+                Some(d) => d,
+                None => break,
+            };
+        }
+
+        let n = series.len() as f64;
+        let mean = series.iter().sum::<f64>() / n;
+        let sharpe = if series.len() >= 2 {
+            let var = series.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let sd = var.sqrt();
+            (sd > 0.0).then(|| mean / sd * PERIODS_PER_YEAR.sqrt())
+        } else {
+            None
+        };
+
+        let (mut cumulative, mut peak, mut max_drawdown) = (0.0_f64, 0.0_f64, 0.0_f64);
+        for x in &series {
+            cumulative += x;
+            peak = peak.max(cumulative);
+            max_drawdown = max_drawdown.max(peak - cumulative);
+        }
+
+        let wins: usize = daily.iter().map(|d| d.wins).sum();
+        let losses: usize = daily.iter().map(|d| d.losses).sum();
+        let gross_win: f64 = daily.iter().map(|d| f(d.gross_win)).sum();
+        let gross_loss: f64 = daily.iter().map(|d| f(d.gross_loss)).sum();
+        let volume: f64 = daily.iter().map(|d| f(d.volume)).sum();
+
+        Self {
+            days: series.len(),
+            fills: daily.iter().map(|d| d.fills).sum(),
+            realized: series.iter().sum(),
+            sharpe,
+            max_drawdown,
+            hit_rate: (wins + losses > 0).then(|| wins as f64 / (wins + losses) as f64),
+            avg_win: (wins > 0).then(|| gross_win / wins as f64),
+            avg_loss: (losses > 0).then(|| gross_loss / losses as f64),
+            volume,
+            turnover: capital
+                .filter(|c| *c > 0.0)
+                .map(|c| volume / c / series.len() as f64),
+        }
+    }
+
+    pub fn log(&self) {
+        info!(
+            "📈 Performance over {} day(s), {} fill(s): realized ${:.2}, volume ${:.2}",
+            self.days, self.fills, self.realized, self.volume
+        );
+        info!(
+            "   Sharpe {} | max drawdown ${:.2} | hit rate {}",
+            fmt_opt(self.sharpe, |x| format!("{:.2}", x)),
+            self.max_drawdown,
+            fmt_opt(self.hit_rate, |x| format!("{:.1}%", x * 100.0)),
+        );
+        info!(
+            "   avg win {} | avg loss {} | turnover {}",
+            fmt_opt(self.avg_win, |x| format!("${:.2}", x)),
+            fmt_opt(self.avg_loss, |x| format!("${:.2}", x)),
+            fmt_opt(self.turnover, |x| format!("{:.2}x/day", x)),
+        );
+    }
+}
+
+fn f(d: Decimal) -> f64 {
+    d.to_f64().unwrap_or(0.0)
+}
+
+fn fmt_opt(x: Option<f64>, fmt: impl Fn(f64) -> String) -> String {
+    x.map(fmt).unwrap_or_else(|| "n/a".to_string())
+}
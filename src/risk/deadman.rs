@@ -0,0 +1,138 @@
+use log::{info, warn};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::execution::clob_client::ClobClient;
+use crate::net::{feeds, Connections};
+
+// ==================================================
+// DEAD MAN'S SWITCH
+// ==================================================
+
+/// Cancels everything resting when the bot stops looking after its
+/// orders, so stale quotes can't be picked off.
+///
+/// It fires when any of
+///
+///   the heartbeat stalls  →  nothing called `beat` for `timeout`
+///   the exchange is gone  →  `ClobClient::ping` failed for `timeout`
+///   a watched feed is down →  no connection for `timeout` (with
+///                             `connections`; market and user by default)
+///
+/// The CLOB has no cancel-on-disconnect, so the cancel is ours: while
+/// the exchange is unreachable it is retried every tick and lands as
+/// soon as connectivity returns. Once all recover the switch re-arms.
+pub struct DeadMansSwitch {
+    timeout: Duration,
+    started: Instant,
+    /// Milliseconds after `started` of the last heartbeat
+    last_beat: AtomicU64,
+    connections: Option<Arc<Connections>>,
+    feeds: Vec<String>,
+}
+
+impl DeadMansSwitch {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            started: Instant::now(),
+            last_beat: AtomicU64::new(0),
+            connections: None,
+            feeds: vec![feeds::MARKET.to_string(), feeds::USER.to_string()],
+        }
+    }
+
+    /// `DEAD_MAN_TIMEOUT_SECS` (default 30), `DEAD_MAN_FEEDS` (default
+    /// market,user; empty for none)
+    pub fn from_env() -> Self {
+        let secs = std::env::var("DEAD_MAN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+        let switch = Self::new(Duration::from_secs(secs));
+        match std::env::var("DEAD_MAN_FEEDS") {
+            Ok(list) => switch.feeds(list.split(',').map(str::trim).filter(|f| !f.is_empty()).collect()),
+            Err(_) => switch,
+        }
+    }
+
+    /// Fire when one of `feeds` stays down (see `net::feeds`)
+    pub fn feeds(mut self, feeds: Vec<&str>) -> Self {
+        self.feeds = feeds.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    /// Watch the feeds' state on this hub
+    pub fn connections(mut self, connections: Arc<Connections>) -> Self {
+        self.connections = Some(connections);
+        self
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// The trading loop is alive
+    pub fn beat(&self) {
+        let now = self.started.elapsed().as_millis() as u64;
+        self.last_beat.store(now, Ordering::Relaxed);
+    }
+
+    /// Time since the last heartbeat (or since creation)
+    pub fn since_beat(&self) -> Duration {
+        let last = Duration::from_millis(self.last_beat.load(Ordering::Relaxed));
+        self.started.elapsed().saturating_sub(last)
+    }
+
+    /// The first watched feed down for longer than `timeout`
+    fn feed_down(&self) -> Option<String> {
+        let connections = self.connections.as_ref()?;
+        self.feeds.iter().find_map(|feed| {
+            let down = connections.down_for(feed).filter(|d| *d > self.timeout)?;
+            Some(format!("{} feed down for {}s", feed, down.as_secs()))
+        })
+    }
+
+    /// Check the heartbeat, the exchange and the feeds every `every`,
+    /// forever
+    pub async fn run(self: Arc<Self>, clob: Arc<ClobClient>, every: Duration) {
+        let mut tick = tokio::time::interval(every);
+        let mut last_reachable = Instant::now();
+        let mut fired = false;
+        loop {
+            tick.tick().await;
+            match clob.ping().await {
+                Ok(()) => last_reachable = Instant::now(),
+                Err(e) if !fired => warn!("⚠️  Exchange unreachable: {}", e),
+                Err(_) => {}
+            }
+
+            let stalled = self.since_beat();
+            let offline = last_reachable.elapsed();
+            let reason = if stalled > self.timeout {
+                Some(format!("no heartbeat for {}s", stalled.as_secs()))
+            } else if offline > self.timeout {
+                Some(format!("exchange unreachable for {}s", offline.as_secs()))
+            } else {
+                self.feed_down()
+            };
+
+            let Some(reason) = reason else {
+                if fired {
+                    info!("✅ Dead man's switch re-armed");
+                    fired = false;
+                }
+                continue;
+            };
+            if fired {
+                continue;
+            }
+            warn!("💀 Dead man's switch: cancelling all orders ({})", reason);
+            match clob.cancel_all().await {
+                Ok(_) => fired = true,
+                Err(e) => warn!("⚠️  Dead man's switch cancel-all failed, retrying: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, Utc};
+use ethers::types::{TransactionReceipt, U256};
+use log::{info, warn};
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// ==================================================
+// DAILY GAS BUDGET
+// ==================================================
+
+/// How much an on-chain action matters once the budget is spent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxPriority {
+    /// Turns tokens back into USDC (merges, redemptions): always sent
+    Critical,
+    /// Approvals, splits, swaps, conversions, transfers: refused once
+    /// the day's budget is spent
+    Routine,
+}
+
+/// Tracks the POL spent on gas per UTC day and refuses routine
+/// transactions once `max_daily` is used up.
+///
+/// The tally is kept in a state file (`DAY SPENT`) so a restart doesn't
+/// reset it. Every receipt is counted, critical ones included.
+pub struct GasBudget {
+    max_daily: Decimal,
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl GasBudget {
+    pub fn new(max_daily: Decimal, path: impl Into<PathBuf>) -> Self {
+        Self {
+            max_daily,
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// `GAS_DAILY_BUDGET` (POL, unset = no budget) and `GAS_BUDGET_PATH`
+    /// (default `gas_spend`)
+    pub fn from_env() -> Option<Self> {
+        let max_daily = std::env::var("GAS_DAILY_BUDGET").ok()?.parse().ok()?;
+        Some(Self::new(
+            max_daily,
+            std::env::var("GAS_BUDGET_PATH").unwrap_or_else(|_| "gas_spend".to_string()),
+        ))
+    }
+
+    pub fn max_daily(&self) -> Decimal {
+        self.max_daily
+    }
+
+    /// POL spent on gas today
+    pub fn spent_today(&self) -> Decimal {
+        match self.state() {
+            Some((day, spent)) if day == today() => spent,
+            _ => Decimal::ZERO,
+        }
+    }
+
+    pub fn remaining(&self) -> Decimal {
+        (self.max_daily - self.spent_today()).max(Decimal::ZERO)
+    }
+
+    /// Refuse a routine `what` once today's budget is spent
+    pub fn allow(&self, what: &str, priority: TxPriority) -> Result<()> {
+        let spent = self.spent_today();
+        if spent < self.max_daily {
+            return Ok(());
+        }
+        match priority {
+            TxPriority::Critical => {
+                warn!("⛽ Gas budget spent ({} POL), sending critical {} anyway", spent, what);
+                Ok(())
+            }
+            TxPriority::Routine => Err(anyhow!(
+                "⛽ Daily gas budget of {} POL spent ({} POL), not sending {}",
+                self.max_daily,
+                spent.round_dp(4),
+                what
+            )),
+        }
+    }
+
+    /// Add the gas a mined transaction paid to today's tally
+    pub fn record(&self, receipt: &TransactionReceipt) {
+        let Some(cost) = gas_cost(receipt) else {
+            return;
+        };
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let spent = self.spent_today() + cost;
+        if let Err(e) = std::fs::write(&self.path, format!("{} {}", today(), spent)) {
+            warn!("⚠️  Failed to persist gas spend: {}", e);
+        }
+        info!("⛽ Gas {} POL (today {} / {} POL)", cost.round_dp(6), spent.round_dp(4), self.max_daily);
+    }
+
+    fn state(&self) -> Option<(NaiveDate, Decimal)> {
+        let text = std::fs::read_to_string(&self.path).ok()?;
+        let (day, spent) = text.trim().split_once(' ')?;
+        Some((day.parse().ok()?, spent.parse().ok()?))
+    }
+}
+
+/// `gasUsed × effectiveGasPrice` in POL
+pub fn gas_cost(receipt: &TransactionReceipt) -> Option<Decimal> {
+    let wei = receipt.gas_used? * receipt.effective_gas_price?;
+    // Gwei keeps the product inside Decimal's 96-bit mantissa
+    let gwei = wei / U256::exp10(9);
+    if gwei > U256::from(u64::MAX) {
+        return None;
+    }
+    Some(Decimal::from(gwei.as_u64()) / Decimal::from(1_000_000_000u64))
+}
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
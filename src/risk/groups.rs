@@ -0,0 +1,53 @@
+use crate::markets::TokenMeta;
+
+// ==================================================
+// CORRELATED MARKET GROUPS
+// ==================================================
+
+/// Named sets of markets on the same underlying (an election, a game, a
+/// coin), matched by keyword against the question, slug and tags, so
+/// equivalent bets spread over many markets share one limit.
+#[derive(Debug, Clone, Default)]
+pub struct CorrelationGroups {
+    /// (name, lowercase keywords)
+    groups: Vec<(String, Vec<String>)>,
+}
+
+impl CorrelationGroups {
+    /// Parse `name=kw1,kw2;name2=kw3`
+    pub fn parse(spec: &str) -> Self {
+        let groups = spec
+            .split(';')
+            .filter_map(|group| {
+                let (name, words) = group.split_once('=')?;
+                let words: Vec<String> = words
+                    .split(',')
+                    .map(|w| w.trim().to_lowercase())
+                    .filter(|w| !w.is_empty())
+                    .collect();
+                let name = name.trim();
+                (!name.is_empty() && !words.is_empty()).then(|| (name.to_string(), words))
+            })
+            .collect();
+        Self { groups }
+    }
+
+    /// `RISK_GROUPS`, e.g. `us-election=trump,harris;btc=bitcoin,btc`
+    pub fn from_env() -> Self {
+        Self::parse(&std::env::var("RISK_GROUPS").unwrap_or_default())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Names of every group `meta` belongs to
+    pub fn groups_of(&self, meta: &TokenMeta) -> Vec<String> {
+        let text = format!("{} {} {}", meta.market, meta.slug, meta.labels.join(" ")).to_lowercase();
+        self.groups
+            .iter()
+            .filter(|(_, words)| words.iter().any(|w| text.contains(w.as_str())))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use log::{info, warn};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::execution::clob_client::ClobClient;
+
+// ==================================================
+// KILL SWITCH
+// ==================================================
+
+/// Stops all trading until explicitly reset.
+///
+/// The state is a file holding the trip reason, so it survives restarts
+/// and can be flipped from another process (`kill` / `resume` CLI
+/// commands). While tripped the risk manager refuses every order, and
+/// `run` cancels everything resting as soon as it sees the trip.
+pub struct KillSwitch {
+    path: PathBuf,
+}
+
+impl KillSwitch {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `KILL_SWITCH_PATH` (default `kill_switch`)
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("KILL_SWITCH_PATH").unwrap_or_else(|_| "kill_switch".to_string()))
+    }
+
+    /// Why trading is halted, if it is
+    pub fn tripped(&self) -> Option<String> {
+        if !self.path.exists() {
+            return None;
+        }
+        let reason = std::fs::read_to_string(&self.path).unwrap_or_default();
+        Some(match reason.trim() {
+            "" => "kill switch tripped".to_string(),
+            r => r.to_string(),
+        })
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Halt trading; a trip that's already in place keeps its reason
+    pub fn trip(&self, reason: &str) -> Result<()> {
+        if self.is_tripped() {
+            return Ok(());
+        }
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        std::fs::write(&self.path, format!("{} (at unix {})", reason, secs))?;
+        warn!("🛑 KILL SWITCH TRIPPED: {}", reason);
+        Ok(())
+    }
+
+    pub fn reset(&self) -> Result<()> {
+        if self.is_tripped() {
+            std::fs::remove_file(&self.path)?;
+            info!("✅ Kill switch reset, trading may resume");
+        }
+        Ok(())
+    }
+
+    /// Watch for trips every `every` and cancel all open orders on each
+    /// one, including trips made by another process
+    pub async fn run(self: Arc<Self>, clob: Arc<ClobClient>, every: Duration) {
+        let mut tick = tokio::time::interval(every);
+        let mut handled = false;
+        loop {
+            tick.tick().await;
+            let Some(reason) = self.tripped() else {
+                handled = false;
+                continue;
+            };
+            if handled {
+                continue;
+            }
+            warn!("🛑 Kill switch: cancelling all orders ({})", reason);
+            match clob.cancel_all().await {
+                Ok(_) => handled = true,
+                Err(e) => warn!("⚠️  Kill switch cancel-all failed, retrying: {}", e),
+            }
+        }
+    }
+}
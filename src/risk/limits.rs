@@ -0,0 +1,117 @@
+use rust_decimal::Decimal;
+
+// ==================================================
+// EXPOSURE LIMITS
+// ==================================================
+
+/// What to do with an order that would breach a limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreachAction {
+    Reject,
+    /// Cut the order to whatever still fits
+    Downsize,
+}
+
+/// Caps on long exposure; `None` = unlimited. Position limits are in
+/// outcome tokens, notional limits in USDC at entry price.
+#[derive(Debug, Clone)]
+pub struct RiskLimits {
+    pub max_token_position: Option<Decimal>,
+    pub max_token_notional: Option<Decimal>,
+    /// Across every outcome token of one condition
+    pub max_market_position: Option<Decimal>,
+    pub max_market_notional: Option<Decimal>,
+    /// Across every market of one Gamma event, USDC
+    pub max_event_notional: Option<Decimal>,
+    /// Across every market of one correlation group, USDC
+    pub max_group_notional: Option<Decimal>,
+    /// Whole book, USDC
+    pub max_gross_notional: Option<Decimal>,
+    /// Whole book net of complete YES+NO pairs (which pay $1 whatever
+    /// happens), USDC
+    pub max_net_notional: Option<Decimal>,
+    pub on_breach: BreachAction,
+    /// Trip the kill switch when an order would breach a portfolio limit
+    pub kill_on_breach: bool,
+}
+
+impl Default for RiskLimits {
+    fn default() -> Self {
+        Self {
+            max_token_position: None,
+            max_token_notional: None,
+            max_market_position: None,
+            max_market_notional: None,
+            max_event_notional: None,
+            max_group_notional: None,
+            max_gross_notional: None,
+            max_net_notional: None,
+            on_breach: BreachAction::Reject,
+            kill_on_breach: false,
+        }
+    }
+}
+
+impl RiskLimits {
+    /// `RISK_MAX_TOKEN_POSITION`, `RISK_MAX_TOKEN_NOTIONAL`,
+    /// `RISK_MAX_MARKET_POSITION`, `RISK_MAX_MARKET_NOTIONAL`,
+    /// `RISK_MAX_EVENT_NOTIONAL`, `RISK_MAX_GROUP_NOTIONAL`,
+    /// `RISK_MAX_GROSS_NOTIONAL`, `RISK_MAX_NET_NOTIONAL`,
+    /// `RISK_ON_BREACH` = reject (default) | downsize and
+    /// `RISK_KILL_ON_BREACH`
+    pub fn from_env() -> Self {
+        let var = |key: &str| std::env::var(key).ok().and_then(|v| v.parse::<Decimal>().ok());
+        let on_breach = match std::env::var("RISK_ON_BREACH")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "downsize" => BreachAction::Downsize,
+            _ => BreachAction::Reject,
+        };
+        Self {
+            max_token_position: var("RISK_MAX_TOKEN_POSITION"),
+            max_token_notional: var("RISK_MAX_TOKEN_NOTIONAL"),
+            max_market_position: var("RISK_MAX_MARKET_POSITION"),
+            max_market_notional: var("RISK_MAX_MARKET_NOTIONAL"),
+            max_event_notional: var("RISK_MAX_EVENT_NOTIONAL"),
+            max_group_notional: var("RISK_MAX_GROUP_NOTIONAL"),
+            max_gross_notional: var("RISK_MAX_GROSS_NOTIONAL"),
+            max_net_notional: var("RISK_MAX_NET_NOTIONAL"),
+            on_breach,
+            kill_on_breach: std::env::var("RISK_KILL_ON_BREACH").as_deref() == Ok("true"),
+        }
+    }
+}
+
+/// Long holdings plus resting buys on a token or market
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Exposure {
+    pub size: Decimal,
+    pub notional: Decimal,
+}
+
+impl Exposure {
+    pub fn add(&mut self, size: Decimal, price: Decimal) {
+        self.size += size;
+        self.notional += size * price;
+    }
+}
+
+/// Largest additional size at `price` that keeps `current` within both
+/// caps; `None` when neither is set
+pub fn headroom(
+    current: &Exposure,
+    price: Decimal,
+    max_position: Option<Decimal>,
+    max_notional: Option<Decimal>,
+) -> Option<Decimal> {
+    let by_position = max_position.map(|cap| cap - current.size);
+    let by_notional = max_notional
+        .filter(|_| price > Decimal::ZERO)
+        .map(|cap| (cap - current.notional) / price);
+    match (by_position, by_notional) {
+        (Some(a), Some(b)) => Some(a.min(b).max(Decimal::ZERO)),
+        (a, b) => a.or(b).map(|h| h.max(Decimal::ZERO)),
+    }
+}
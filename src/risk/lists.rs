@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+
+use crate::markets::{TagFilter, TokenMeta};
+
+// ==================================================
+// MARKET ALLOW / DENY LISTS
+// ==================================================
+
+/// Markets the operator allows or excludes, by condition id, tag or
+/// keyword (case-insensitive substring of the question or slug).
+///
+/// Any deny match rejects. When any allowlist is set, a market must also
+/// match at least one of them.
+#[derive(Debug, Clone, Default)]
+pub struct MarketLists {
+    pub allow_conditions: HashSet<String>,
+    pub deny_conditions: HashSet<String>,
+    /// `include` is an allowlist, `exclude` a denylist
+    pub tags: TagFilter,
+    pub allow_keywords: Vec<String>,
+    pub deny_keywords: Vec<String>,
+}
+
+impl MarketLists {
+    /// Comma-separated `RISK_ALLOW_CONDITIONS`, `RISK_DENY_CONDITIONS`,
+    /// `RISK_INCLUDE_TAGS`, `RISK_EXCLUDE_TAGS`, `RISK_ALLOW_KEYWORDS`
+    /// and `RISK_DENY_KEYWORDS`
+    pub fn from_env() -> Self {
+        let list = |key: &str| -> Vec<String> {
+            std::env::var(key)
+                .unwrap_or_default()
+                .split(',')
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+        Self {
+            allow_conditions: list("RISK_ALLOW_CONDITIONS").into_iter().collect(),
+            deny_conditions: list("RISK_DENY_CONDITIONS").into_iter().collect(),
+            tags: TagFilter::from_env("RISK"),
+            allow_keywords: list("RISK_ALLOW_KEYWORDS"),
+            deny_keywords: list("RISK_DENY_KEYWORDS"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allow_conditions.is_empty()
+            && self.deny_conditions.is_empty()
+            && self.tags.is_empty()
+            && self.allow_keywords.is_empty()
+            && self.deny_keywords.is_empty()
+    }
+
+    fn has_allowlist(&self) -> bool {
+        !self.allow_conditions.is_empty() || !self.tags.include.is_empty() || !self.allow_keywords.is_empty()
+    }
+
+    /// `Err(reason)` if the market of `meta` may not be traded
+    pub fn check(&self, meta: &TokenMeta) -> Result<(), String> {
+        let condition = meta.condition_id.to_lowercase();
+        let text = format!("{} {}", meta.market, meta.slug).to_lowercase();
+        let tagged = |wanted: &[String]| {
+            meta.labels
+                .iter()
+                .find(|l| wanted.iter().any(|w| l.eq_ignore_ascii_case(w)))
+                .cloned()
+        };
+        let keyword = |words: &[String]| words.iter().find(|w| text.contains(w.as_str())).cloned();
+
+        if self.deny_conditions.contains(&condition) {
+            return Err(format!("market {} is denylisted", meta.condition_id));
+        }
+        if let Some(tag) = tagged(&self.tags.exclude) {
+            return Err(format!("tag '{}' is excluded", tag));
+        }
+        if let Some(word) = keyword(&self.deny_keywords) {
+            return Err(format!("keyword '{}' is denylisted", word));
+        }
+
+        let allowed = !self.has_allowlist()
+            || self.allow_conditions.contains(&condition)
+            || tagged(&self.tags.include).is_some()
+            || keyword(&self.allow_keywords).is_some();
+        if !allowed {
+            return Err(format!("{} is not on any allowlist", meta.market));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::Result;
+use chrono::{NaiveDate, Utc};
+use log::{info, warn};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::execution::clob_client::ClobClient;
+use crate::portfolio::{Marker, PnlEngine, Positions};
+
+// ==================================================
+// DAILY LOSS CIRCUIT BREAKER
+// ==================================================
+
+/// Halts trading for the rest of the UTC day once intraday PnL falls to
+/// `-max_daily_loss`.
+///
+/// Intraday PnL is today's realized PnL plus the change in unrealized
+/// PnL since the first check of the day. While halted the risk manager
+/// refuses buys (sells still pass, so positions can be reduced); with
+/// `flatten` every open position is also sold at market on the trip.
+///
+/// The halt is kept in a state file so a restart doesn't clear it. The
+/// `resume` command overrides it for the rest of the day.
+pub struct LossBreaker {
+    max_daily_loss: Option<Decimal>,
+    flatten: bool,
+    flatten_slippage: f64,
+    path: PathBuf,
+    // (day, unrealized PnL at its first check)
+    open: Mutex<Option<(NaiveDate, Decimal)>>,
+}
+
+impl LossBreaker {
+    pub fn new(max_daily_loss: Option<Decimal>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            max_daily_loss,
+            flatten: false,
+            flatten_slippage: 0.05,
+            path: path.into(),
+            open: Mutex::new(None),
+        }
+    }
+
+    /// Sell everything at market when tripped, accepting up to
+    /// `slippage` below the best bid
+    pub fn flatten(mut self, slippage: f64) -> Self {
+        self.flatten = true;
+        self.flatten_slippage = slippage;
+        self
+    }
+
+    /// `RISK_MAX_DAILY_LOSS` (USDC, unset = off), `LOSS_BREAKER_PATH`
+    /// (default `loss_breaker`), `LOSS_BREAKER_FLATTEN` and
+    /// `LOSS_BREAKER_FLATTEN_SLIPPAGE` (default 0.05)
+    pub fn from_env() -> Self {
+        let breaker = Self::new(
+            std::env::var("RISK_MAX_DAILY_LOSS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            std::env::var("LOSS_BREAKER_PATH").unwrap_or_else(|_| "loss_breaker".to_string()),
+        );
+        if std::env::var("LOSS_BREAKER_FLATTEN").as_deref() != Ok("true") {
+            return breaker;
+        }
+        let slippage = std::env::var("LOSS_BREAKER_FLATTEN_SLIPPAGE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.05);
+        breaker.flatten(slippage)
+    }
+
+    pub fn max_daily_loss(&self) -> Option<Decimal> {
+        self.max_daily_loss
+    }
+
+    /// Whether trading is halted for today
+    pub fn is_halted(&self) -> bool {
+        self.state() == Some(("halted".to_string(), today()))
+    }
+
+    fn is_overridden(&self) -> bool {
+        self.state() == Some(("override".to_string(), today()))
+    }
+
+    /// Resume trading for the rest of today despite the loss
+    pub fn override_today(&self) -> Result<()> {
+        if self.is_halted() {
+            std::fs::write(&self.path, format!("override {}", today()))?;
+            info!("✅ Daily loss breaker overridden for {}", today());
+        }
+        Ok(())
+    }
+
+    /// ("halted" | "override", day) from the state file
+    fn state(&self) -> Option<(String, NaiveDate)> {
+        let text = std::fs::read_to_string(&self.path).ok()?;
+        let (kind, date) = text.trim().split_once(' ')?;
+        Some((kind.to_string(), date.parse().ok()?))
+    }
+
+    /// Intraday PnL: today's realized plus unrealized since the day's
+    /// first check
+    pub async fn intraday(&self, pnl: &Mutex<PnlEngine>, marks: &HashMap<String, f64>) -> Decimal {
+        let day = today();
+        let (realized, unrealized) = {
+            let pnl = pnl.lock().await;
+            (pnl.realized_on(day), pnl.total(marks).unrealized)
+        };
+        let mut open = self.open.lock().await;
+        let start = match *open {
+            Some((d, start)) if d == day => start,
+            _ => {
+                *open = Some((day, unrealized));
+                unrealized
+            }
+        };
+        realized + unrealized - start
+    }
+
+    /// Trip if intraday PnL has reached the limit; returns it when this
+    /// call tripped the breaker. Cancelling and flattening is the
+    /// caller's job.
+    pub async fn check(&self, pnl: &Mutex<PnlEngine>, marks: &HashMap<String, f64>) -> Option<Decimal> {
+        let limit = self.max_daily_loss?;
+        let intraday = self.intraday(pnl, marks).await;
+        if intraday > -limit || self.is_halted() || self.is_overridden() {
+            return None;
+        }
+        warn!(
+            "🛑 Daily loss limit hit: ${:.2} today (limit ${}), halting until tomorrow (UTC)",
+            intraday, limit
+        );
+        if let Err(e) = std::fs::write(&self.path, format!("halted {}", today())) {
+            warn!("⚠️  Failed to persist loss breaker: {}", e);
+        }
+        Some(intraday)
+    }
+
+    /// Check intraday PnL every `every` and trip on the limit
+    pub async fn run(
+        self: Arc<Self>,
+        clob: Arc<ClobClient>,
+        pnl: Arc<Mutex<PnlEngine>>,
+        marker: Arc<Marker>,
+        positions: Arc<Positions>,
+        every: Duration,
+    ) {
+        if self.max_daily_loss.is_none() {
+            return;
+        }
+        let mut tick = tokio::time::interval(every);
+        loop {
+            tick.tick().await;
+            let open = positions.open().await;
+            let marks = marker.marks(&open).await;
+            if self.check(&pnl, &marks).await.is_none() {
+                continue;
+            }
+            if let Err(e) = clob.cancel_all().await {
+                warn!("⚠️  Loss breaker cancel-all failed: {}", e);
+            }
+            if !self.flatten {
+                continue;
+            }
+            for p in open.iter().filter(|p| p.size > Decimal::ZERO) {
+                info!("🧹 Flattening {} of {}", p.size, p.token_id);
+                if let Err(e) = clob.market_sell(&p.token_id, p.size, self.flatten_slippage).await {
+                    warn!("⚠️  Failed to flatten {}: {}", p.token_id, e);
+                }
+            }
+        }
+    }
+}
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
@@ -0,0 +1,347 @@
+pub mod deadman;
+pub mod gas;
+pub mod groups;
+pub mod kill;
+pub mod limits;
+pub mod lists;
+pub mod loss;
+
+pub use deadman::DeadMansSwitch;
+pub use gas::{GasBudget, TxPriority};
+pub use groups::CorrelationGroups;
+pub use kill::KillSwitch;
+pub use limits::{headroom, BreachAction, Exposure, RiskLimits};
+pub use lists::MarketLists;
+pub use loss::LossBreaker;
+
+use anyhow::Result;
+use log::warn;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::order::Side;
+use crate::execution::intents::OrderIntent;
+use crate::markets::{MetadataCache, TokenMeta};
+use crate::portfolio::Positions;
+
+// ==================================================
+// RISK MANAGER
+// ==================================================
+
+/// An order as the risk manager sees it, before or after signing
+#[derive(Debug, Clone)]
+pub struct OrderRequest {
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskDecision {
+    Allow,
+    /// Allowed at this smaller size
+    Resize(Decimal),
+    Reject(String),
+    /// Rejected, and the kill switch was tripped: cancel everything
+    Halt(String),
+}
+
+/// One long holding or resting buy, attributed to its market, event
+/// and correlation groups
+struct Holding {
+    token_id: String,
+    market: String,
+    event: Option<String>,
+    groups: Vec<String>,
+    size: Decimal,
+    price: Decimal,
+}
+
+/// Pre-trade exposure limits per token, per market (condition), per
+/// event and correlation group and for the whole book, market
+/// allow/deny lists, the kill switch and the daily loss breaker.
+///
+/// Exposure is long holdings at cost plus resting buys at their limit
+/// price. Sells only reduce exposure and pass unless the kill switch is
+/// tripped. Tokens are mapped to their condition through the metadata
+/// cache, falling back to the position's market; a token that can't be
+/// mapped is its own market, and is refused outright when allow/deny
+/// lists are configured (they can't be checked).
+pub struct RiskManager {
+    positions: Arc<Positions>,
+    metadata: Arc<MetadataCache>,
+    limits: RiskLimits,
+    lists: MarketLists,
+    groups: CorrelationGroups,
+    kill: Arc<KillSwitch>,
+    loss: Option<Arc<LossBreaker>>,
+}
+
+impl RiskManager {
+    pub fn new(
+        positions: Arc<Positions>,
+        metadata: Arc<MetadataCache>,
+        limits: RiskLimits,
+        kill: Arc<KillSwitch>,
+    ) -> Self {
+        Self {
+            positions,
+            metadata,
+            limits,
+            lists: MarketLists::default(),
+            groups: CorrelationGroups::default(),
+            kill,
+            loss: None,
+        }
+    }
+
+    /// Only buy markets these lists allow
+    pub fn lists(mut self, lists: MarketLists) -> Self {
+        self.lists = lists;
+        self
+    }
+
+    /// Refuse buys while `breaker` has halted trading for the day
+    pub fn loss_breaker(mut self, breaker: Arc<LossBreaker>) -> Self {
+        self.loss = Some(breaker);
+        self
+    }
+
+    /// Share the group limit across markets on the same underlying
+    pub fn groups(mut self, groups: CorrelationGroups) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Limits, market lists, groups, kill switch and, when `RISK_MAX_DAILY_LOSS`
+    /// is set, the daily loss breaker
+    pub fn from_env(positions: Arc<Positions>) -> Result<Self> {
+        let manager = Self::new(
+            positions,
+            Arc::new(MetadataCache::from_env()?),
+            RiskLimits::from_env(),
+            Arc::new(KillSwitch::from_env()),
+        )
+        .lists(MarketLists::from_env())
+        .groups(CorrelationGroups::from_env());
+        let breaker = LossBreaker::from_env();
+        Ok(match breaker.max_daily_loss() {
+            Some(_) => manager.loss_breaker(Arc::new(breaker)),
+            None => manager,
+        })
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Holdings exposure is measured on
+    pub fn positions(&self) -> Arc<Positions> {
+        self.positions.clone()
+    }
+
+    pub fn kill_switch(&self) -> Arc<KillSwitch> {
+        self.kill.clone()
+    }
+
+    pub fn breaker(&self) -> Option<Arc<LossBreaker>> {
+        self.loss.clone()
+    }
+
+    /// Decide on `order` given the orders already resting
+    pub async fn evaluate(&self, order: &OrderRequest, resting: &[OrderIntent]) -> RiskDecision {
+        if let Some(reason) = self.kill.tripped() {
+            return RiskDecision::Reject(format!("kill switch: {}", reason));
+        }
+        if order.side == Side::Sell || order.size <= Decimal::ZERO {
+            return RiskDecision::Allow;
+        }
+        if self.loss.as_ref().is_some_and(|l| l.is_halted()) {
+            return RiskDecision::Reject("daily loss limit hit, buys halted until tomorrow (UTC)".to_string());
+        }
+        let meta = match self.metadata.get_or_fetch(&order.token_id).await {
+            Ok(meta) => {
+                if let Err(reason) = self.lists.check(&meta) {
+                    return RiskDecision::Reject(reason);
+                }
+                Some(meta)
+            }
+            Err(e) if !self.lists.is_empty() => {
+                return RiskDecision::Reject(format!("can't check market lists for {}: {}", order.token_id, e));
+            }
+            Err(_) => None,
+        };
+        let target = self.attribute(&order.token_id, meta.as_ref(), None, order.size, order.price);
+        let holdings = self.holdings(resting).await;
+        let room = self.binding_room(&target, order.price, &holdings);
+
+        let Some((room, scope)) = room.filter(|(h, _)| *h < order.size) else {
+            return RiskDecision::Allow;
+        };
+        let room = room.round_dp_with_strategy(2, RoundingStrategy::ToZero);
+        let reason = format!(
+            "BUY {} of {} exceeds the {} limit ({} left)",
+            order.size, order.token_id, scope, room
+        );
+
+        let limits = &self.limits;
+        let portfolio = matches!(scope.as_str(), "gross" | "net");
+        if portfolio && limits.kill_on_breach {
+            if let Err(e) = self.kill.trip(&reason) {
+                warn!("⚠️  Failed to persist kill switch: {}", e);
+            }
+            return RiskDecision::Halt(reason);
+        }
+        match limits.on_breach {
+            BreachAction::Downsize if room > Decimal::ZERO => {
+                warn!("✂️  {}, downsizing", reason);
+                RiskDecision::Resize(room)
+            }
+            _ => RiskDecision::Reject(reason),
+        }
+    }
+
+    /// Tokens of `token_id` that can still be bought at `price` within
+    /// every exposure limit; `None` when no limit applies. Zero while the
+    /// kill switch, loss breaker or market lists refuse buys. Unlike
+    /// `evaluate` this never trips anything, so it's safe for sizing.
+    pub async fn buy_room(&self, token_id: &str, price: Decimal, resting: &[OrderIntent]) -> Option<Decimal> {
+        if self.kill.tripped().is_some() || self.loss.as_ref().is_some_and(|l| l.is_halted()) {
+            return Some(Decimal::ZERO);
+        }
+        let meta = self.metadata.get_or_fetch(token_id).await.ok();
+        match &meta {
+            Some(m) if self.lists.check(m).is_err() => return Some(Decimal::ZERO),
+            None if !self.lists.is_empty() => return Some(Decimal::ZERO),
+            _ => {}
+        }
+        let target = self.attribute(token_id, meta.as_ref(), None, Decimal::ZERO, price);
+        let holdings = self.holdings(resting).await;
+        self.binding_room(&target, price, &holdings)
+            .map(|(room, _)| room.max(Decimal::ZERO).round_dp_with_strategy(2, RoundingStrategy::ToZero))
+    }
+
+    /// Smallest headroom at `price` across the scopes `target` falls in,
+    /// and that scope's name
+    fn binding_room(&self, target: &Holding, price: Decimal, holdings: &[Holding]) -> Option<(Decimal, String)> {
+        let exposure = |keep: &dyn Fn(&Holding) -> bool| {
+            let mut e = Exposure::default();
+            for h in holdings.iter().filter(|h| keep(h)) {
+                e.add(h.size, h.price);
+            }
+            e
+        };
+        let token = exposure(&|h| h.token_id == target.token_id);
+        let in_market = exposure(&|h| h.market == target.market);
+        let gross = exposure(&|_| true);
+        let net = Exposure {
+            size: gross.size,
+            notional: gross.notional - hedged_notional(holdings),
+        };
+
+        let limits = &self.limits;
+        let mut rooms: Vec<(Option<Decimal>, String)> = vec![
+            (
+                headroom(&token, price, limits.max_token_position, limits.max_token_notional),
+                "token".to_string(),
+            ),
+            (
+                headroom(&in_market, price, limits.max_market_position, limits.max_market_notional),
+                "market".to_string(),
+            ),
+            (headroom(&gross, price, None, limits.max_gross_notional), "gross".to_string()),
+            (headroom(&net, price, None, limits.max_net_notional), "net".to_string()),
+        ];
+        if let Some(event) = &target.event {
+            let in_event = exposure(&|h| h.event.as_ref() == Some(event));
+            rooms.push((
+                headroom(&in_event, price, None, limits.max_event_notional),
+                format!("event {}", event),
+            ));
+        }
+        for group in &target.groups {
+            let in_group = exposure(&|h| h.groups.contains(group));
+            rooms.push((
+                headroom(&in_group, price, None, limits.max_group_notional),
+                format!("group {}", group),
+            ));
+        }
+        rooms
+            .into_iter()
+            .filter_map(|(h, scope)| Some((h?, scope)))
+            .min_by(|a, b| a.0.cmp(&b.0))
+    }
+
+    /// Long positions plus resting buys
+    async fn holdings(&self, resting: &[OrderIntent]) -> Vec<Holding> {
+        let mut holdings = Vec::new();
+        for p in self.positions.open().await.into_iter().filter(|p| p.size > Decimal::ZERO) {
+            let meta = self.metadata.get(&p.token_id).await;
+            holdings.push(self.attribute(&p.token_id, meta.as_ref(), Some(&p.market), p.size, p.avg_price));
+        }
+        for r in resting.iter().filter(|r| r.side == "BUY") {
+            let (Some(size), Some(price)) = (
+                Decimal::from_f64_retain(r.size),
+                Decimal::from_f64_retain(r.price),
+            ) else {
+                continue;
+            };
+            let meta = self.metadata.get(&r.token_id).await;
+            holdings.push(self.attribute(&r.token_id, meta.as_ref(), None, size, price));
+        }
+        holdings
+    }
+
+    /// Place a holding: its condition comes from `meta`, else
+    /// `fallback_market`, else the token itself; event and groups need
+    /// `meta`
+    fn attribute(
+        &self,
+        token_id: &str,
+        meta: Option<&TokenMeta>,
+        fallback_market: Option<&str>,
+        size: Decimal,
+        price: Decimal,
+    ) -> Holding {
+        Holding {
+            token_id: token_id.to_string(),
+            market: match meta {
+                Some(m) => m.condition_id.clone(),
+                None => fallback_market.unwrap_or(token_id).to_string(),
+            },
+            event: meta.and_then(|m| m.event_id.clone()),
+            groups: meta.map(|m| self.groups.groups_of(m)).unwrap_or_default(),
+            size,
+            price,
+        }
+    }
+}
+
+/// Cost of complete YES+NO pairs in binary markets, which carry no
+/// directional risk
+fn hedged_notional(holdings: &[Holding]) -> Decimal {
+    // market → token → (size, notional)
+    let mut by_market: HashMap<&str, HashMap<&str, Exposure>> = HashMap::new();
+    for h in holdings {
+        by_market
+            .entry(&h.market)
+            .or_default()
+            .entry(&h.token_id)
+            .or_default()
+            .add(h.size, h.price);
+    }
+    by_market
+        .values()
+        .filter(|tokens| tokens.len() == 2)
+        .map(|tokens| {
+            let pairs = tokens.values().map(|e| e.size).min().unwrap_or_default();
+            tokens
+                .values()
+                .filter(|e| e.size > Decimal::ZERO)
+                .map(|e| pairs * e.notional / e.size)
+                .sum::<Decimal>()
+        })
+        .sum()
+}
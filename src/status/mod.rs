@@ -0,0 +1,178 @@
+use anyhow::Result;
+use log::{debug, info, warn};
+use rust_decimal::prelude::ToPrimitive;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+use crate::portfolio::{Marker, PerformanceStats, PnlEngine, PnlLine, Positions};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+
+// ==================================================
+// STATUS API
+// ==================================================
+
+/// Read-only JSON status endpoint for dashboards and monitoring.
+///
+/// - `GET /status`: uptime, open positions, total PnL and stats
+/// - `GET /stats`: performance statistics only
+/// - `GET /pnl`: PnL per strategy and per market
+///
+/// Unrealized PnL and position values use the attached `Marker`, so
+/// they agree with the rest of the bot; without one they are zero.
+/// Binds to localhost by default; there is no authentication.
+pub struct StatusServer {
+    addr: String,
+    positions: Arc<Positions>,
+    pnl: Arc<Mutex<PnlEngine>>,
+    marker: Option<Arc<Marker>>,
+    capital: Option<f64>,
+    started: Instant,
+}
+
+impl StatusServer {
+    pub fn new(addr: impl Into<String>, positions: Arc<Positions>, pnl: Arc<Mutex<PnlEngine>>) -> Self {
+        Self {
+            addr: addr.into(),
+            positions,
+            pnl,
+            marker: None,
+            capital: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Value positions with this marker
+    pub fn marker(mut self, marker: Arc<Marker>) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Bankroll turnover is measured against
+    pub fn capital(mut self, usdc: Option<f64>) -> Self {
+        self.capital = usdc;
+        self
+    }
+
+    /// `STATUS_API_ADDR` (default 127.0.0.1:8787), `STATS_CAPITAL_USDC`
+    pub fn from_env(positions: Arc<Positions>, pnl: Arc<Mutex<PnlEngine>>) -> Self {
+        let addr = std::env::var("STATUS_API_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+        Self::new(addr, positions, pnl).capital(PerformanceStats::capital_from_env())
+    }
+
+    /// Serve until the listener fails
+    pub async fn run(self: Arc<Self>) -> Result<()> {
+        let listener = TcpListener::bind(&self.addr).await?;
+        info!("🩺 Status API listening on http://{}", self.addr);
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.handle(stream).await {
+                    debug!("Status request from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, mut stream: TcpStream) -> Result<()> {
+        let mut buf = [0u8; 2048];
+        let n = stream.read(&mut buf).await?;
+        let request = String::from_utf8_lossy(&buf[..n]);
+        let mut parts = request.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        let path = path.split('?').next().unwrap_or("");
+
+        let (code, body) = match (method, path) {
+            ("GET", "/status") => ("200 OK", self.status().await),
+            ("GET", "/stats") => ("200 OK", json!(self.stats().await)),
+            ("GET", "/pnl") => ("200 OK", self.pnl().await),
+            ("GET", _) => ("404 Not Found", json!({ "error": "not found" })),
+            _ => ("405 Method Not Allowed", json!({ "error": "method not allowed" })),
+        };
+
+        let body = body.to_string();
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            code,
+            body.len(),
+            body
+        );
+        if let Err(e) = stream.write_all(response.as_bytes()).await {
+            warn!("⚠️  Status API write failed: {}", e);
+        }
+        Ok(())
+    }
+
+    async fn stats(&self) -> PerformanceStats {
+        self.pnl.lock().await.stats(self.capital)
+    }
+
+    /// Marks of every open position
+    async fn marks(&self) -> HashMap<String, f64> {
+        match &self.marker {
+            Some(marker) => marker.marks(&self.positions.open().await).await,
+            None => HashMap::new(),
+        }
+    }
+
+    async fn status(&self) -> Value {
+        let marks = self.marks().await;
+        let positions: Vec<Value> = self
+            .positions
+            .open()
+            .await
+            .into_iter()
+            .map(|p| {
+                let mark = marks.get(&p.token_id).copied();
+                json!({
+                    "token_id": p.token_id,
+                    "market": p.market,
+                    "size": p.size.to_string(),
+                    "avg_price": p.avg_price.to_string(),
+                    "mark": mark,
+                    "value": mark.and_then(|m| Some(p.size.to_f64()? * m)),
+                })
+            })
+            .collect();
+        let (total, stats) = {
+            let pnl = self.pnl.lock().await;
+            (pnl.total(&marks), pnl.stats(self.capital))
+        };
+        json!({
+            "uptime_secs": self.started.elapsed().as_secs(),
+            "mark_method": self.marker.as_ref().map(|m| m.source().as_str()),
+            "positions": positions,
+            "pnl": line_json(&total),
+            "stats": stats,
+        })
+    }
+
+    async fn pnl(&self) -> Value {
+        let marks = self.marks().await;
+        let pnl = self.pnl.lock().await;
+        json!({
+            "method": format!("{:?}", pnl.method()),
+            "mark_method": self.marker.as_ref().map(|m| m.source().as_str()),
+            "total": line_json(&pnl.total(&marks)),
+            "by_strategy": pnl.by_strategy(&marks).iter().map(line_json).collect::<Vec<_>>(),
+            "by_market": pnl.by_market(&marks).iter().map(line_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn line_json(line: &PnlLine) -> Value {
+    json!({
+        "key": line.key,
+        "realized": line.realized.to_string(),
+        "unrealized": line.unrealized.to_string(),
+        "total": line.total().to_string(),
+        "position": line.position.to_string(),
+        "cost_basis": line.cost_basis.to_string(),
+    })
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use log::info;
+use rust_decimal::Decimal;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::Journal;
+use crate::portfolio::{CostMethod, PnlEngine};
+
+const CSV_HEADER: &str = "datetime_utc,timestamp_ms,trade_id,order_id,market,token_id,side,role,price,size,notional_usdc,fee_usdc,realized_pnl_usdc";
+
+// ==================================================
+// TRADE HISTORY EXPORT
+// ==================================================
+
+/// Totals of an export, for the summary line
+#[derive(Debug, Clone, Default)]
+pub struct ExportSummary {
+    pub fills: usize,
+    pub notional: Decimal,
+    pub fees: Decimal,
+    pub realized: Decimal,
+}
+
+/// Write every journalled fill from `since_ms` on to `path` as CSV, one
+/// row per fill, oldest first.
+///
+/// Realized PnL is gross of fees and recomputed from the full fill
+/// history under `method`, so sales are matched against entries made
+/// before `since_ms` too; subtract `fee_usdc` for the net figure.
+pub fn export_fills_csv(
+    journal: &Journal,
+    path: impl AsRef<Path>,
+    since_ms: u64,
+    method: CostMethod,
+) -> Result<ExportSummary> {
+    let mut pnl = PnlEngine::new(method);
+    let mut out = BufWriter::new(File::create(path.as_ref())?);
+    writeln!(out, "{}", CSV_HEADER)?;
+
+    let mut summary = ExportSummary::default();
+    for fill in journal.fills_since(0)? {
+        let realized = pnl.on_fill(&fill);
+        if fill.at_ms < since_ms {
+            continue;
+        }
+        let notional = fill.price * fill.size;
+        let datetime = Utc
+            .timestamp_millis_opt(fill.at_ms as i64)
+            .single()
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            datetime,
+            fill.at_ms,
+            fill.trade_id,
+            fill.order_id,
+            fill.market,
+            fill.token_id,
+            fill.side.as_str(),
+            if fill.maker { "maker" } else { "taker" },
+            fill.price,
+            fill.size,
+            notional.round_dp(6),
+            fill.fee.round_dp(6),
+            realized.round_dp(6),
+        )?;
+
+        summary.fills += 1;
+        summary.notional += notional;
+        summary.fees += fill.fee;
+        summary.realized += realized;
+    }
+    out.flush()?;
+
+    info!(
+        "🧾 Exported {} fill(s) to {}: notional ${:.2}, fees ${:.2}, realized ${:.2}",
+        summary.fills,
+        path.as_ref().display(),
+        summary.notional,
+        summary.fees,
+        summary.realized
+    );
+    Ok(summary)
+}
@@ -0,0 +1,340 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rusqlite::{params, Connection};
+use rust_decimal::Decimal;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::domain::order::Side;
+use crate::orders::TrackedOrder;
+use crate::portfolio::{Fill, Position, Positions};
+
+pub mod export;
+pub mod report;
+
+pub use export::{export_fills_csv, ExportSummary};
+pub use report::{EquityPoint, FeeImpact, MarketAttribution, PnlReport};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS orders (
+    key           TEXT PRIMARY KEY,
+    order_id      TEXT,
+    state         TEXT NOT NULL,
+    size          TEXT NOT NULL,
+    filled        TEXT NOT NULL,
+    reject_reason TEXT,
+    updated_ms    INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS order_events (
+    id       INTEGER PRIMARY KEY AUTOINCREMENT,
+    key      TEXT NOT NULL,
+    order_id TEXT,
+    state    TEXT NOT NULL,
+    filled   TEXT NOT NULL,
+    at_ms    INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS fills (
+    trade_id TEXT NOT NULL,
+    order_id TEXT NOT NULL,
+    token_id TEXT NOT NULL,
+    market   TEXT NOT NULL,
+    side     TEXT NOT NULL,
+    price    TEXT NOT NULL,
+    size     TEXT NOT NULL,
+    fee      TEXT NOT NULL,
+    maker    INTEGER NOT NULL,
+    at_ms    INTEGER NOT NULL,
+    PRIMARY KEY (trade_id, order_id, token_id)
+);
+CREATE TABLE IF NOT EXISTS positions (
+    token_id   TEXT PRIMARY KEY,
+    market     TEXT NOT NULL,
+    size       TEXT NOT NULL,
+    avg_price  TEXT NOT NULL,
+    updated_ms INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS position_events (
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    token_id  TEXT NOT NULL,
+    size      TEXT NOT NULL,
+    avg_price TEXT NOT NULL,
+    at_ms     INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS fills_at ON fills (at_ms);
+CREATE INDEX IF NOT EXISTS order_events_key ON order_events (key);
+";
+
+// ==================================================
+// SQLITE JOURNAL
+// ==================================================
+
+/// An order's last journalled state
+#[derive(Debug, Clone)]
+pub struct JournalledOrder {
+    pub key: String,
+    pub order_id: Option<String>,
+    /// `OrderState` name, e.g. `PartiallyFilled`
+    pub state: String,
+    pub size: Decimal,
+    pub filled: Decimal,
+    pub reject_reason: Option<String>,
+    pub updated_ms: u64,
+}
+
+/// Append-only journal of order transitions, fills and position changes
+/// in SQLite, plus the latest state of each order and position.
+///
+/// Cancels, expiries and rejections are order transitions like any
+/// other. Decimals are stored as text so nothing is rounded.
+pub struct Journal {
+    conn: Mutex<Connection>,
+}
+
+impl Journal {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Throwaway journal, e.g. for backtests
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    /// `JOURNAL_PATH` (default `journal.sqlite`)
+    pub fn from_env() -> Result<Self> {
+        let path = std::env::var("JOURNAL_PATH").unwrap_or_else(|_| "journal.sqlite".to_string());
+        Self::open(path)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> Result<std::sync::MutexGuard<'_, Connection>> {
+        self.conn.lock().map_err(|_| anyhow!("Journal lock poisoned"))
+    }
+
+    // ==================================================
+    // WRITES
+    // ==================================================
+
+    pub fn record_order(&self, order: &TrackedOrder) -> Result<()> {
+        let at_ms = order.history.last().map(|(_, t)| *t).unwrap_or_else(now_ms);
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO orders (key, order_id, state, size, filled, reject_reason, updated_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(key) DO UPDATE SET
+                order_id = excluded.order_id, state = excluded.state,
+                filled = excluded.filled, reject_reason = excluded.reject_reason,
+                updated_ms = excluded.updated_ms",
+            params![
+                order.key,
+                order.order_id,
+                order.state.to_string(),
+                order.size.to_string(),
+                order.filled.to_string(),
+                order.reject_reason,
+                at_ms as i64,
+            ],
+        )?;
+        conn.execute(
+            "INSERT INTO order_events (key, order_id, state, filled, at_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                order.key,
+                order.order_id,
+                order.state.to_string(),
+                order.filled.to_string(),
+                at_ms as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Idempotent: a fill replayed after a reconnect is stored once
+    pub fn record_fill(&self, fill: &Fill) -> Result<()> {
+        self.conn()?.execute(
+            "INSERT OR IGNORE INTO fills
+             (trade_id, order_id, token_id, market, side, price, size, fee, maker, at_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                fill.trade_id,
+                fill.order_id,
+                fill.token_id,
+                fill.market,
+                fill.side.as_str(),
+                fill.price.to_string(),
+                fill.size.to_string(),
+                fill.fee.to_string(),
+                fill.maker,
+                fill.at_ms as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_position(&self, position: &Position) -> Result<()> {
+        let at_ms = now_ms() as i64;
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO positions (token_id, market, size, avg_price, updated_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(token_id) DO UPDATE SET
+                size = excluded.size, avg_price = excluded.avg_price,
+                updated_ms = excluded.updated_ms",
+            params![
+                position.token_id,
+                position.market,
+                position.size.to_string(),
+                position.avg_price.to_string(),
+                at_ms,
+            ],
+        )?;
+        conn.execute(
+            "INSERT INTO position_events (token_id, size, avg_price, at_ms)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                position.token_id,
+                position.size.to_string(),
+                position.avg_price.to_string(),
+                at_ms,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ==================================================
+    // QUERIES
+    // ==================================================
+
+    /// Latest state of every journalled order, most recently updated
+    /// first
+    pub fn orders(&self) -> Result<Vec<JournalledOrder>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT key, order_id, state, size, filled, reject_reason, updated_ms
+             FROM orders ORDER BY updated_ms DESC",
+        )?;
+        let rows = stmt.query_map([], |r| {
+            Ok(JournalledOrder {
+                key: r.get(0)?,
+                order_id: r.get(1)?,
+                state: r.get(2)?,
+                size: dec(&r.get::<_, String>(3)?),
+                filled: dec(&r.get::<_, String>(4)?),
+                reject_reason: r.get(5)?,
+                updated_ms: r.get::<_, i64>(6)? as u64,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Fills at or after `since_ms`, oldest first
+    pub fn fills_since(&self, since_ms: u64) -> Result<Vec<Fill>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT trade_id, order_id, token_id, market, side, price, size, fee, maker, at_ms
+             FROM fills WHERE at_ms >= ?1 ORDER BY at_ms",
+        )?;
+        let rows = stmt.query_map(params![since_ms as i64], |r| {
+            Ok(Fill {
+                trade_id: r.get(0)?,
+                order_id: r.get(1)?,
+                token_id: r.get(2)?,
+                market: r.get(3)?,
+                side: if r.get::<_, String>(4)? == "buy" { Side::Buy } else { Side::Sell },
+                price: dec(&r.get::<_, String>(5)?),
+                size: dec(&r.get::<_, String>(6)?),
+                fee: dec(&r.get::<_, String>(7)?),
+                maker: r.get(8)?,
+                at_ms: r.get::<_, i64>(9)? as u64,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Last journalled size of every token
+    pub fn positions(&self) -> Result<Vec<Position>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT token_id, market, size, avg_price FROM positions")?;
+        let rows = stmt.query_map([], |r| {
+            Ok(Position {
+                token_id: r.get(0)?,
+                market: r.get(1)?,
+                size: dec(&r.get::<_, String>(2)?),
+                avg_price: dec(&r.get::<_, String>(3)?),
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Load the journalled positions into a tracker after a restart
+    pub async fn restore_positions(&self, positions: &Positions) -> Result<usize> {
+        let saved = self.positions()?;
+        let n = saved.len();
+        for position in saved {
+            positions.set(position).await;
+        }
+        info!("💾 Restored {} position(s) from journal", n);
+        Ok(n)
+    }
+}
+
+/// Journal every order transition, fill and position change from the
+/// given streams until one closes. Without a fill stream (no user
+/// channel) only orders and positions are journalled.
+pub async fn run_journal(
+    journal: Arc<Journal>,
+    mut orders: broadcast::Receiver<TrackedOrder>,
+    mut fills: Option<broadcast::Receiver<Fill>>,
+    mut positions: broadcast::Receiver<Position>,
+) {
+    loop {
+        let next_fill = async {
+            match fills.as_mut() {
+                Some(rx) => rx.recv().await,
+                None => std::future::pending().await,
+            }
+        };
+        let written = tokio::select! {
+            o = orders.recv() => match o {
+                Ok(order) => journal.record_order(&order),
+                Err(RecvError::Lagged(n)) => Err(anyhow!("missed {} order update(s)", n)),
+                Err(RecvError::Closed) => return,
+            },
+            f = next_fill => match f {
+                Ok(fill) => journal.record_fill(&fill),
+                Err(RecvError::Lagged(n)) => Err(anyhow!("missed {} fill(s)", n)),
+                Err(RecvError::Closed) => return,
+            },
+            p = positions.recv() => match p {
+                Ok(position) => journal.record_position(&position),
+                Err(RecvError::Lagged(n)) => Err(anyhow!("missed {} position update(s)", n)),
+                Err(RecvError::Closed) => return,
+            },
+        };
+        if let Err(e) = written {
+            warn!("⚠️  Journal write failed: {}", e);
+        }
+    }
+}
+
+fn dec(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap_or_default()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
@@ -0,0 +1,280 @@
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use log::info;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::Journal;
+use crate::portfolio::{CostMethod, Fill, PerformanceStats, PnlEngine};
+
+// ==================================================
+// PNL REPORTS
+// ==================================================
+// One report shape for live sessions and backtests, both read from a
+// journal (a backtest journals its simulated fills to an in-memory one):
+//
+//   report.json  everything below, machine readable
+//   markets.csv  PnL, volume and fees per market
+//   equity.csv   cumulative realized PnL after every fill
+//
+// Realized figures are net of fees, as `PnlEngine` books them. The fee
+// impact section adds the fees back to show what trading cost.
+
+/// One market's share of the result
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MarketAttribution {
+    /// Condition id
+    pub market: String,
+    pub fills: usize,
+    pub volume: Decimal,
+    pub fees: Decimal,
+    pub realized: Decimal,
+    pub unrealized: Decimal,
+    pub total: Decimal,
+    /// Tokens still held at the end
+    pub position: Decimal,
+}
+
+/// What fees took out of the result
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FeeImpact {
+    pub fees: Decimal,
+    /// PnL had trading been free
+    pub gross: Decimal,
+    pub net: Decimal,
+    /// Fees / gross PnL, when gross is positive
+    pub share_of_gross: Option<f64>,
+    /// Fees per notional traded, in basis points
+    pub bps_of_volume: Option<f64>,
+}
+
+/// Running totals after one fill
+#[derive(Debug, Clone, Serialize)]
+pub struct EquityPoint {
+    pub at_ms: u64,
+    pub realized: Decimal,
+    pub fees: Decimal,
+    pub volume: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PnlReport {
+    /// `live` or `backtest <strategy>`
+    pub source: String,
+    pub generated_ms: u64,
+    /// First and last fill covered
+    pub from_ms: Option<u64>,
+    pub to_ms: Option<u64>,
+    pub fills: usize,
+    pub volume: Decimal,
+    pub realized: Decimal,
+    /// Open holdings at the supplied marks; zero where none was given
+    pub unrealized: Decimal,
+    pub total: Decimal,
+    pub fee_impact: FeeImpact,
+    pub stats: PerformanceStats,
+    /// Largest total PnL first
+    pub markets: Vec<MarketAttribution>,
+    pub equity: Vec<EquityPoint>,
+}
+
+impl PnlReport {
+    /// Report on the journalled fills from `since_ms` on. Every fill is
+    /// replayed so earlier entries still set the cost basis; open
+    /// holdings are valued at `marks` (token → price).
+    pub fn from_journal(
+        source: &str,
+        journal: &Journal,
+        since_ms: u64,
+        method: CostMethod,
+        marks: &HashMap<String, f64>,
+    ) -> Result<Self> {
+        Ok(Self::from_fills(source, &journal.fills_since(0)?, since_ms, method, marks))
+    }
+
+    /// Same, over fills already loaded (oldest first)
+    pub fn from_fills(
+        source: &str,
+        fills: &[Fill],
+        since_ms: u64,
+        method: CostMethod,
+        marks: &HashMap<String, f64>,
+    ) -> Self {
+        let mut pnl = PnlEngine::new(method);
+        let mut markets: BTreeMap<String, MarketAttribution> = BTreeMap::new();
+        let mut equity = Vec::new();
+        let (mut realized, mut fees, mut volume) = (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO);
+        let (mut from_ms, mut to_ms) = (None, None);
+
+        for fill in fills {
+            let booked = pnl.on_fill(fill);
+            if fill.at_ms < since_ms {
+                continue;
+            }
+            let notional = fill.price * fill.size;
+            realized += booked;
+            fees += fill.fee;
+            volume += notional;
+            from_ms.get_or_insert(fill.at_ms);
+            to_ms = Some(fill.at_ms);
+            equity.push(EquityPoint {
+                at_ms: fill.at_ms,
+                realized,
+                fees,
+                volume,
+            });
+
+            let market = markets.entry(fill.market.clone()).or_insert_with(|| MarketAttribution {
+                market: fill.market.clone(),
+                ..Default::default()
+            });
+            market.fills += 1;
+            market.volume += notional;
+            market.fees += fill.fee;
+            market.realized += booked;
+        }
+
+        // Holdings are as of the end, whenever they were opened
+        let mut unrealized = Decimal::ZERO;
+        for line in pnl.by_market(marks) {
+            unrealized += line.unrealized;
+            let market = markets.entry(line.key.clone()).or_insert_with(|| MarketAttribution {
+                market: line.key.clone(),
+                ..Default::default()
+            });
+            market.unrealized = line.unrealized;
+            market.position = line.position;
+        }
+        let mut markets: Vec<MarketAttribution> = markets
+            .into_values()
+            .map(|mut m| {
+                m.total = m.realized + m.unrealized;
+                m
+            })
+            .filter(|m| m.fills > 0 || !m.position.is_zero())
+            .collect();
+        markets.sort_by_key(|m| std::cmp::Reverse(m.total));
+
+        let since_date = Utc
+            .timestamp_millis_opt(since_ms as i64)
+            .single()
+            .map(|t| t.date_naive());
+        let daily: Vec<_> = pnl
+            .daily()
+            .into_iter()
+            .filter(|d| since_date.is_none_or(|s| d.date >= s))
+            .collect();
+
+        let net = realized + unrealized;
+        let gross = net + fees;
+        let fee_impact = FeeImpact {
+            fees,
+            gross,
+            net,
+            share_of_gross: (gross > Decimal::ZERO).then(|| (fees / gross).to_f64().unwrap_or_default()),
+            bps_of_volume: (volume > Decimal::ZERO)
+                .then(|| (fees / volume * Decimal::from(10_000)).to_f64().unwrap_or_default()),
+        };
+
+        Self {
+            source: source.to_string(),
+            generated_ms: Utc::now().timestamp_millis() as u64,
+            from_ms,
+            to_ms,
+            fills: equity.len(),
+            volume,
+            realized,
+            unrealized,
+            total: net,
+            fee_impact,
+            stats: PerformanceStats::from_daily(&daily, PerformanceStats::capital_from_env()),
+            markets,
+            equity,
+        }
+    }
+
+    /// Write `report.json`, `markets.csv` and `equity.csv` into `dir`
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("report.json"), serde_json::to_string_pretty(self)?)?;
+
+        let mut out = BufWriter::new(File::create(dir.join("markets.csv"))?);
+        writeln!(out, "market,fills,volume_usdc,fees_usdc,realized_usdc,unrealized_usdc,total_usdc,position")?;
+        for m in &self.markets {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{}",
+                m.market,
+                m.fills,
+                m.volume.round_dp(6),
+                m.fees.round_dp(6),
+                m.realized.round_dp(6),
+                m.unrealized.round_dp(6),
+                m.total.round_dp(6),
+                m.position
+            )?;
+        }
+        out.flush()?;
+
+        let mut out = BufWriter::new(File::create(dir.join("equity.csv"))?);
+        writeln!(out, "datetime_utc,timestamp_ms,realized_usdc,fees_usdc,volume_usdc")?;
+        for p in &self.equity {
+            let datetime = Utc
+                .timestamp_millis_opt(p.at_ms as i64)
+                .single()
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                datetime,
+                p.at_ms,
+                p.realized.round_dp(6),
+                p.fees.round_dp(6),
+                p.volume.round_dp(6)
+            )?;
+        }
+        out.flush()?;
+
+        info!("🧾 Wrote {} report to {}", self.source, dir.display());
+        Ok(())
+    }
+
+    pub fn log(&self) {
+        info!(
+            "🧾 {} report: {} fill(s), {} market(s), volume ${:.2}",
+            self.source,
+            self.fills,
+            self.markets.len(),
+            self.volume
+        );
+        info!(
+            "   PnL ${:.2} (realized ${:.2}, unrealized ${:.2}) | fees ${:.2} = {} of gross ${:.2}, {} of volume",
+            self.total,
+            self.realized,
+            self.unrealized,
+            self.fee_impact.fees,
+            self.fee_impact
+                .share_of_gross
+                .map(|s| format!("{:.1}%", s * 100.0))
+                .unwrap_or_else(|| "n/a".to_string()),
+            self.fee_impact.gross,
+            self.fee_impact
+                .bps_of_volume
+                .map(|b| format!("{:.1}bps", b))
+                .unwrap_or_else(|| "n/a".to_string()),
+        );
+        for m in self.markets.iter().take(5) {
+            info!(
+                "   {} ${:.2} ({} fill(s), fees ${:.2}, holding {})",
+                m.market, m.total, m.fills, m.fees, m.position
+            );
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use anyhow::Result;
+use log::{debug, info, warn};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::TryRecvError;
+
+use super::clock::{ClockTimer, SimClock};
+use super::costs::CostModel;
+use super::latency::LatencyModel;
+use super::runtime::{deliver_sim_fills, Strategy, StrategyBudget, StrategyContext};
+use super::sim::{SimAction, SimStats, SimVenue};
+use crate::execution::ClobClient;
+use crate::market_ws::book::timestamp;
+use crate::market_ws::replay::Replay;
+use crate::market_ws::{MarketBooks, MarketTrades};
+use crate::domain::order::Side;
+use crate::portfolio::PnlLine;
+use crate::risk::RiskManager;
+use crate::storage::Journal;
+use crate::wallet::order_builder::MarketRules;
+
+// ==================================================
+// BACKTESTING
+// ==================================================
+// Runs one strategy over a recording instead of the live feeds:
+//
+//   Replay event  →  MarketBooks / MarketTrades  →  on_book_update
+//   recorded time passes its timer               →  on_timer
+//
+// The strategy, its timer and the venue's fills all read a `SimClock`
+// set to each event's receive time, so runs are deterministic and take
+// no longer than the handlers do.
+//   orders  →  SimVenue (matched against the replayed books)  →  on_fill
+//
+// Fills pay the run's `CostModel` (fees, taker slippage), which
+// `BacktestReport::pnl` is net of.
+//
+// The run's `LatencyModel` splits the books in two: the venue matches
+// against each recorded book as it arrives, while the strategy's books
+// and trade tape get it a drawn feed delay later (never out of order).
+// Orders and cancels are in flight on the venue as `SimVenue` describes,
+// so a strategy quoting off a stale book can be picked off, and a
+// cancel can lose the race to a fill.
+//
+// The strategy's context is in shadow mode, so nothing reaches the
+// exchange. Give it an offline client (`ClobClient::offline`) with its
+// markets' rules seeded; anything else a strategy fetches over the
+// network fails and is counted as a handler error. Resolutions are not
+// replayed.
+//
+// With a `Journal` attached every simulated fill is journalled, so the
+// same reports as for live trading (`storage::PnlReport`) can be built
+// from a backtest. With a `RiskManager` attached orders pass its limits
+// and kill switch, and its loss breaker is checked after every event;
+// a trip cancels the strategy's orders. Flattening is not simulated.
+
+/// What a backtest did
+#[derive(Debug, Clone, Default)]
+pub struct BacktestReport {
+    pub strategy: String,
+    /// Recorded messages replayed
+    pub events: usize,
+    /// Book updates delivered to the strategy
+    pub book_updates: usize,
+    pub timers: usize,
+    /// Handlers that returned an error
+    pub errors: usize,
+    pub orders: SimStats,
+    /// Open holdings marked at the last replayed mid
+    pub pnl: PnlLine,
+    /// Last replayed mid of each of the strategy's markets
+    pub marks: HashMap<String, f64>,
+    /// Only filled in with a risk manager attached
+    pub risk: RiskOutcome,
+    /// Every order and cancel the strategy sent, in order
+    pub actions: Vec<SimAction>,
+}
+
+/// How the risk stack behaved over a backtest
+#[derive(Debug, Clone, Default)]
+pub struct RiskOutcome {
+    /// Recorded time the loss breaker tripped
+    pub breaker_tripped_ms: Option<u64>,
+    /// Kill switch reason, if it was tripped
+    pub kill_switch: Option<String>,
+    /// Lowest PnL seen after any event
+    pub worst_pnl: Decimal,
+    /// Largest long notional held or resting
+    pub peak_exposure: Decimal,
+    /// Buy fills after trading was halted; should stay zero
+    pub buys_after_halt: usize,
+}
+
+impl RiskOutcome {
+    pub fn is_halted(&self) -> bool {
+        self.breaker_tripped_ms.is_some() || self.kill_switch.is_some()
+    }
+}
+
+impl BacktestReport {
+    fn record(&mut self, handled: Result<()>) {
+        if let Err(e) = handled {
+            debug!("Backtest {}: {}", self.strategy, e);
+            self.errors += 1;
+        }
+    }
+
+    pub fn log(&self) {
+        info!(
+            "🧪 Backtest {}: {} event(s), {} book update(s), {} timer(s), {} error(s)",
+            self.strategy, self.events, self.book_updates, self.timers, self.errors
+        );
+        info!(
+            "   orders {} | rejected {} | cancelled {} ({} missed) | fills {} ({} maker) | volume ${:.2} | fees ${:.2}",
+            self.orders.orders,
+            self.orders.rejected,
+            self.orders.cancelled,
+            self.orders.cancel_misses,
+            self.orders.fills,
+            self.orders.maker_fills,
+            self.orders.volume,
+            self.orders.fees
+        );
+        info!(
+            "   PnL ${:.2} (realized ${:.2}, unrealized ${:.2}) | holding {}",
+            self.pnl.total(),
+            self.pnl.realized,
+            self.pnl.unrealized,
+            self.pnl.position
+        );
+    }
+}
+
+/// One strategy over one recording
+pub struct Backtest {
+    clob: Arc<ClobClient>,
+    replay: Replay,
+    budget: Option<StrategyBudget>,
+    rules: Option<MarketRules>,
+    costs: Option<CostModel>,
+    journal: Option<Arc<Journal>>,
+    risk: Option<Arc<RiskManager>>,
+    latency: Option<LatencyModel>,
+}
+
+impl Backtest {
+    pub fn new(clob: Arc<ClobClient>, replay: Replay) -> Self {
+        Self {
+            clob,
+            replay,
+            budget: None,
+            rules: None,
+            costs: None,
+            journal: None,
+            risk: None,
+            latency: None,
+        }
+    }
+
+    /// Budget instead of the strategy's `STRATEGY_<NAME>_*` one
+    pub fn budget(mut self, budget: StrategyBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Seed `rules` for every market of the strategy
+    pub fn rules(mut self, rules: MarketRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Fees and slippage for this run instead of `CostModel::from_env`
+    pub fn costs(mut self, costs: CostModel) -> Self {
+        self.costs = Some(costs);
+        self
+    }
+
+    /// Order, cancel and feed delays instead of `LatencyModel::from_env`
+    pub fn latency(mut self, latency: LatencyModel) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Journal every simulated fill
+    pub fn journal(mut self, journal: Arc<Journal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Check orders against `risk` and run its loss breaker; give it
+    /// fresh positions and kill switch / breaker state files
+    pub fn risk(mut self, risk: Arc<RiskManager>) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    /// Replay the whole recording through `strategy`
+    pub async fn run(mut self, strategy: Arc<dyn Strategy>) -> Result<BacktestReport> {
+        let name = strategy.name().to_string();
+        let markets = strategy.markets();
+        if let Some(rules) = self.rules {
+            for token_id in &markets {
+                self.clob.set_market_rules(token_id, rules).await;
+            }
+        }
+
+        // The venue's view of the market, current as of each event
+        let venue_books = MarketBooks::new();
+        let venue_trades = MarketTrades::from_env();
+        // The strategy's, behind it by the feed delay
+        let books = Arc::new(MarketBooks::new());
+        let trades = Arc::new(MarketTrades::from_env());
+        let clock = Arc::new(SimClock::new(self.replay.next_ms().unwrap_or_default()));
+        let costs = self.costs.unwrap_or_else(CostModel::from_env);
+        let latency = self.latency.unwrap_or_else(LatencyModel::from_env);
+        let sim = Arc::new(
+            SimVenue::new(&format!("backtest-{}", name))
+                .with_clock(clock.clone())
+                .with_costs(costs)
+                .with_latency(latency)
+                .record_actions(),
+        );
+        let mut feed_rng = StdRng::seed_from_u64(latency.seed);
+        let mut feed: VecDeque<(u64, Value)> = VecDeque::new();
+        let mut feed_last = 0u64;
+        let budget = self.budget.unwrap_or_else(|| StrategyBudget::from_env(&name));
+        let mut ctx = StrategyContext::new(&name, self.clob.clone(), books.clone(), budget)
+            .with_trades(trades.clone())
+            .with_sim(sim.clone())
+            .with_clock(clock.clone());
+        if let Some(risk) = &self.risk {
+            ctx = ctx.with_risk(risk.clone());
+        }
+        let mut report = BacktestReport {
+            strategy: name.clone(),
+            ..Default::default()
+        };
+
+        info!("🧪 Backtesting {} over {} event(s)", name, self.replay.remaining());
+        let mut venue_updates = venue_books.subscribe();
+        let mut updates = books.subscribe();
+        report.record(strategy.on_start(&ctx).await);
+        self.deliver(strategy.as_ref(), &ctx, &mut report).await;
+
+        let mut timer = strategy.timer().filter(|t| !t.is_zero()).map(ClockTimer::new);
+        while let Some(event) = self.replay.step(&venue_books, &venue_trades).await {
+            let (recv_ms, msg) = (event.recv_ms, event.msg.clone());
+            clock.set(recv_ms);
+            report.events += 1;
+
+            // Resting orders meet the book as it is now
+            for token_id in drain(&mut venue_updates) {
+                if !markets.contains(&token_id) || venue_books.is_degraded(&token_id).await {
+                    continue;
+                }
+                if let Some(book) = venue_books.order_book(&token_id).await {
+                    sim.on_book(&token_id, &book).await;
+                }
+            }
+            sim.advance().await;
+            self.deliver(strategy.as_ref(), &ctx, &mut report).await;
+
+            // The strategy hears of it once the feed delay has passed
+            feed_last = (recv_ms + latency.book.sample(&mut feed_rng)).max(feed_last);
+            feed.push_back((feed_last, msg));
+            while let Some((_, msg)) = feed.pop_front_if(|(at, _)| *at <= recv_ms) {
+                trades.set_clock(timestamp(&msg).unwrap_or(recv_ms));
+                trades.on_market_message(&msg).await;
+                books.apply(&msg).await;
+            }
+
+            for token_id in drain(&mut updates) {
+                if !markets.contains(&token_id) || books.is_degraded(&token_id).await {
+                    continue;
+                }
+                let Some(book) = books.order_book(&token_id).await else {
+                    continue;
+                };
+                report.book_updates += 1;
+                report.record(strategy.on_book_update(&ctx, &token_id, &book).await);
+                self.deliver(strategy.as_ref(), &ctx, &mut report).await;
+            }
+
+            if timer.as_mut().is_some_and(|t| t.poll(clock.as_ref())) {
+                report.timers += 1;
+                report.record(strategy.on_timer(&ctx).await);
+                self.deliver(strategy.as_ref(), &ctx, &mut report).await;
+            }
+            self.check_risk(&ctx, &mut report).await;
+        }
+
+        report.record(strategy.on_stop(&ctx).await);
+        self.deliver(strategy.as_ref(), &ctx, &mut report).await;
+        report.orders = sim.stats().await;
+        report.actions = sim.actions().await;
+        report.pnl = ctx.pnl().await;
+        for token_id in &markets {
+            if let Some(mid) = venue_books.get(token_id).await.and_then(|b| b.mid()) {
+                report.marks.insert(token_id.clone(), mid.to_f64().unwrap_or_default());
+            }
+        }
+        if let Some(risk) = &self.risk {
+            report.risk.kill_switch = risk.kill_switch().tripped();
+        }
+        Ok(report)
+    }
+
+    async fn deliver(&self, strategy: &dyn Strategy, ctx: &StrategyContext, report: &mut BacktestReport) {
+        let fills = deliver_sim_fills(strategy, ctx).await;
+        for fill in &fills {
+            if let Some(journal) = &self.journal {
+                if let Err(e) = journal.record_fill(fill) {
+                    warn!("⚠️  Backtest journal: {}", e);
+                }
+            }
+            if let Some(risk) = &self.risk {
+                if fill.side == Side::Buy && (report.risk.is_halted() || risk.kill_switch().is_tripped()) {
+                    report.risk.buys_after_halt += 1;
+                }
+                risk.positions().apply_fill(fill).await;
+            }
+        }
+    }
+
+    /// Track PnL and exposure and give the loss breaker its check
+    async fn check_risk(&self, ctx: &StrategyContext, report: &mut BacktestReport) {
+        let Some(risk) = &self.risk else {
+            return;
+        };
+        let pnl = ctx.pnl().await.total();
+        report.risk.worst_pnl = report.risk.worst_pnl.min(pnl);
+        report.risk.peak_exposure = report.risk.peak_exposure.max(ctx.exposure().await.1.notional);
+
+        let Some(breaker) = risk.breaker() else {
+            return;
+        };
+        if breaker.check(ctx.pnl_engine(), &ctx.marks().await).await.is_some() {
+            report.risk.breaker_tripped_ms = Some(ctx.now_ms());
+            report.record(ctx.cancel_all().await);
+        }
+    }
+}
+
+/// Tokens whose book changed since the last call
+fn drain(updates: &mut tokio::sync::broadcast::Receiver<String>) -> Vec<String> {
+    let mut tokens = Vec::new();
+    loop {
+        match updates.try_recv() {
+            Ok(token_id) => tokens.push(token_id),
+            Err(TryRecvError::Lagged(_)) => continue,
+            Err(_) => return tokens,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// ==================================================
+// CLOCK
+// ==================================================
+// Strategies, their timers and the simulated venue read the time from a
+// `Clock` instead of the OS:
+//
+//   live / shadow  →  SystemClock
+//   backtest       →  SimClock, moved to each recorded event's time
+//
+// On a `SimClock` nothing waits for the wall clock, so a backtest runs
+// as fast as its events can be handled and gives the same result every
+// time.
+
+/// Source of "now" for strategy code
+pub trait Clock: Debug + Send + Sync {
+    /// Unix milliseconds
+    fn now_ms(&self) -> u64;
+
+    /// Time since `since_ms` (a `now_ms` reading), zero if in the future
+    fn elapsed(&self, since_ms: u64) -> Duration {
+        Duration::from_millis(self.now_ms().saturating_sub(since_ms))
+    }
+}
+
+/// The OS clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
+    }
+}
+
+/// A clock that only moves when told to; never goes backwards
+#[derive(Debug, Default)]
+pub struct SimClock {
+    now_ms: AtomicU64,
+}
+
+impl SimClock {
+    pub fn new(start_ms: u64) -> Self {
+        Self {
+            now_ms: AtomicU64::new(start_ms),
+        }
+    }
+
+    /// Move to `at_ms`; earlier times are ignored
+    pub fn set(&self, at_ms: u64) {
+        self.now_ms.fetch_max(at_ms, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now_ms.fetch_add(by.as_millis() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Clock for SimClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::Relaxed)
+    }
+}
+
+/// A strategy timer driven by a `Clock` rather than a tokio interval:
+/// due one period after the first poll, then every period
+#[derive(Debug, Clone)]
+pub struct ClockTimer {
+    period: Duration,
+    next_ms: Option<u64>,
+}
+
+impl ClockTimer {
+    pub fn new(period: Duration) -> Self {
+        Self { period, next_ms: None }
+    }
+
+    /// Whether the timer fired by `clock`'s now; consumes the tick. A
+    /// jump over several periods fires once.
+    pub fn poll(&mut self, clock: &dyn Clock) -> bool {
+        let now = clock.now_ms();
+        let period = self.period.as_millis() as u64;
+        let due = *self.next_ms.get_or_insert(now + period);
+        if now < due {
+            return false;
+        }
+        self.next_ms = Some(now + period);
+        true
+    }
+}
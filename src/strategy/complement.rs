@@ -0,0 +1,346 @@
+use anyhow::{anyhow, Result};
+use ethers::types::H256;
+use log::{info, warn};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::runtime::{Strategy, StrategyContext, StrategyFuture};
+use crate::domain::order::Side;
+use crate::execution::orderbook::{fetch_books, Level, OrderBook};
+use crate::execution::ClobClient;
+use crate::markets::{GammaClient, GammaMarket, MarketQuery};
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// YES+NO MISPRICING ARBITRAGE
+// ==================================================
+// A full set (1 YES + 1 NO) is always worth exactly 1 USDC through the
+// CTF, so:
+//
+//   ask(YES) + ask(NO) < 1  →  buy both, merge the pairs into USDC
+//   bid(YES) + bid(NO) > 1  →  split USDC into pairs, sell both
+//
+// Both legs are FOK; a leg that fails after the other filled is unwound
+// (sold back or merged) rather than left as naked exposure.
+
+/// Limits for the complement arbitrage
+#[derive(Debug, Clone)]
+pub struct ComplementArbConfig {
+    /// Profit per full set (USDC) required to trade; must cover taker
+    /// fees and the gas of the merge/split
+    pub min_edge: f64,
+    /// Full sets below this aren't worth the transactions
+    pub min_size: f64,
+    /// Cap on full sets per trade
+    pub max_size: f64,
+    /// Only scan markets with at least this Gamma liquidity
+    pub min_liquidity: f64,
+    /// Slippage allowed when unwinding a half-filled arbitrage
+    pub unwind_slippage: f64,
+}
+
+impl Default for ComplementArbConfig {
+    fn default() -> Self {
+        Self {
+            min_edge: 0.01,
+            min_size: 5.0,
+            max_size: 100.0,
+            min_liquidity: 1_000.0,
+            unwind_slippage: 0.05,
+        }
+    }
+}
+
+impl ComplementArbConfig {
+    /// `COMPLEMENT_ARB_MIN_EDGE`, `COMPLEMENT_ARB_MIN_SIZE`,
+    /// `COMPLEMENT_ARB_MAX_SIZE`, `COMPLEMENT_ARB_MIN_LIQUIDITY`,
+    /// `COMPLEMENT_ARB_UNWIND_SLIPPAGE`
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            min_edge: var("COMPLEMENT_ARB_MIN_EDGE", d.min_edge),
+            min_size: var("COMPLEMENT_ARB_MIN_SIZE", d.min_size),
+            max_size: var("COMPLEMENT_ARB_MAX_SIZE", d.max_size),
+            min_liquidity: var("COMPLEMENT_ARB_MIN_LIQUIDITY", d.min_liquidity),
+            unwind_slippage: var("COMPLEMENT_ARB_UNWIND_SLIPPAGE", d.unwind_slippage),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArbDirection {
+    /// Asks sum below 1: buy YES and NO, merge
+    BuyMerge,
+    /// Bids sum above 1: split, sell YES and NO
+    SplitSell,
+}
+
+/// A mispricing the books can fill, sized level by level so every
+/// full set taken clears `min_edge`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Opportunity {
+    pub direction: ArbDirection,
+    /// Full sets
+    pub size: f64,
+    /// Worst YES/NO levels touched: the FOK limit prices
+    pub yes_limit: f64,
+    pub no_limit: f64,
+    /// USDC paid (BuyMerge) or received (SplitSell) on each leg
+    pub yes_notional: f64,
+    pub no_notional: f64,
+}
+
+impl Opportunity {
+    /// Expected profit before fees and gas
+    pub fn profit(&self) -> f64 {
+        let legs = self.yes_notional + self.no_notional;
+        match self.direction {
+            ArbDirection::BuyMerge => self.size - legs,
+            ArbDirection::SplitSell => legs - self.size,
+        }
+    }
+
+    /// Average profit per full set
+    pub fn edge(&self) -> f64 {
+        if self.size <= 0.0 {
+            return 0.0;
+        }
+        self.profit() / self.size
+    }
+}
+
+/// Best arbitrage between a market's YES and NO books, if any clears
+/// the configured edge and minimum size
+pub fn detect(yes: &OrderBook, no: &OrderBook, config: &ComplementArbConfig) -> Option<Opportunity> {
+    let buy = walk(&yes.asks, &no.asks, config, |sum| 1.0 - sum)
+        .map(|w| w.into_opportunity(ArbDirection::BuyMerge));
+    let sell = walk(&yes.bids, &no.bids, config, |sum| sum - 1.0)
+        .map(|w| w.into_opportunity(ArbDirection::SplitSell));
+
+    // Both can't hold on a sane book, but a crossed one might offer both
+    [buy, sell]
+        .into_iter()
+        .flatten()
+        .filter(|o| o.size >= config.min_size)
+        .max_by(|a, b| a.profit().total_cmp(&b.profit()))
+}
+
+#[derive(Default)]
+struct Walk {
+    size: f64,
+    yes_limit: f64,
+    no_limit: f64,
+    yes_notional: f64,
+    no_notional: f64,
+}
+
+impl Walk {
+    fn into_opportunity(self, direction: ArbDirection) -> Opportunity {
+        Opportunity {
+            direction,
+            size: self.size,
+            yes_limit: self.yes_limit,
+            no_limit: self.no_limit,
+            yes_notional: self.yes_notional,
+            no_notional: self.no_notional,
+        }
+    }
+}
+
+/// Take both sides level by level while a pair still clears the edge
+fn walk(
+    yes: &[Level],
+    no: &[Level],
+    config: &ComplementArbConfig,
+    edge: impl Fn(f64) -> f64,
+) -> Option<Walk> {
+    let (mut i, mut j) = (0, 0);
+    let (mut yes_left, mut no_left) = (yes.first()?.size, no.first()?.size);
+    let mut w = Walk::default();
+
+    while i < yes.len() && j < no.len() && config.max_size - w.size > 1e-9 {
+        let (y, n) = (yes[i], no[j]);
+        if edge(y.price + n.price) < config.min_edge {
+            break;
+        }
+        let take = yes_left.min(no_left).min(config.max_size - w.size);
+        w.size += take;
+        w.yes_notional += take * y.price;
+        w.no_notional += take * n.price;
+        w.yes_limit = y.price;
+        w.no_limit = n.price;
+
+        yes_left -= take;
+        no_left -= take;
+        if yes_left <= 1e-9 {
+            i += 1;
+            yes_left = yes.get(i).map(|l| l.size).unwrap_or_default();
+        }
+        if no_left <= 1e-9 {
+            j += 1;
+            no_left = no.get(j).map(|l| l.size).unwrap_or_default();
+        }
+    }
+
+    (w.size > 0.0).then_some(w)
+}
+
+/// Scans open binary markets for complement mispricings and trades them
+pub struct ComplementArb {
+    clob: Arc<ClobClient>,
+    gamma: GammaClient,
+    config: ComplementArbConfig,
+}
+
+impl ComplementArb {
+    pub fn new(clob: Arc<ClobClient>, gamma: GammaClient, config: ComplementArbConfig) -> Self {
+        Self { clob, gamma, config }
+    }
+
+    pub fn from_env(clob: Arc<ClobClient>) -> Self {
+        Self::new(clob, GammaClient::from_env(), ComplementArbConfig::from_env())
+    }
+
+    /// Poll interval from `COMPLEMENT_ARB_POLL_SECS` (default 10)
+    pub fn poll_interval() -> Duration {
+        let secs = std::env::var("COMPLEMENT_ARB_POLL_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .unwrap_or(10);
+        Duration::from_secs(secs)
+    }
+
+    pub fn config(&self) -> &ComplementArbConfig {
+        &self.config
+    }
+
+    /// Current opportunities across tradeable binary markets, most
+    /// profitable first
+    pub async fn scan(&self) -> Result<Vec<(GammaMarket, Opportunity)>> {
+        let query = MarketQuery::open().liquidity_min(self.config.min_liquidity);
+        let markets: Vec<GammaMarket> = self
+            .gamma
+            .all_markets(&query)
+            .await?
+            .into_iter()
+            .filter(|m| m.is_tradeable() && m.token_ids().len() == 2)
+            .collect();
+
+        let tokens: Vec<String> = markets.iter().flat_map(|m| m.token_ids()).collect();
+        let books = fetch_books(self.clob.clob_url(), &tokens).await?;
+
+        let mut found: Vec<(GammaMarket, Opportunity)> = markets
+            .into_iter()
+            .filter_map(|m| {
+                let ids = m.token_ids();
+                let opp = detect(books.get(&ids[0])?, books.get(&ids[1])?, &self.config)?;
+                Some((m, opp))
+            })
+            .collect();
+        found.sort_by(|a, b| b.1.profit().total_cmp(&a.1.profit()));
+        Ok(found)
+    }
+
+    /// Trade both legs (through `ctx`, so they count against the
+    /// strategy's budget) and complete through the CTF
+    pub async fn execute(&self, ctx: &StrategyContext, market: &GammaMarket, opp: &Opportunity) -> Result<()> {
+        let ids = market.token_ids();
+        let [yes, no] = [&ids[0], &ids[1]];
+        let condition: H256 = market
+            .condition_id
+            .parse()
+            .map_err(|e| anyhow!("Invalid condition id {}: {}", market.condition_id, e))?;
+        let size = decimal(opp.size)?.round_dp_with_strategy(2, RoundingStrategy::ToZero);
+        let (yes_limit, no_limit) = (decimal(opp.yes_limit)?.round_dp(4), decimal(opp.no_limit)?.round_dp(4));
+
+        info!(
+            "⚖️  {:?} {} full sets on {}: YES {} + NO {}, expected ${:.2} ({:.4}/set)",
+            opp.direction,
+            size,
+            market.question,
+            yes_limit,
+            no_limit,
+            opp.profit(),
+            opp.edge()
+        );
+
+        match opp.direction {
+            ArbDirection::BuyMerge => {
+                ctx.place_order(yes, Side::Buy, yes_limit, size, OrderType::Fok)
+                    .await?;
+                if let Err(e) = ctx
+                    .place_order(no, Side::Buy, no_limit, size, OrderType::Fok)
+                    .await
+                {
+                    warn!("⚠️  NO leg failed ({}), selling the YES leg back", e);
+                    self.clob.market_sell(yes, size, self.config.unwind_slippage).await?;
+                    return Err(e);
+                }
+                self.clob
+                    .merge_full_sets(condition, [yes, no], size, market.neg_risk)
+                    .await?;
+            }
+            ArbDirection::SplitSell => {
+                self.clob
+                    .mint_full_set(condition, size, market.neg_risk)
+                    .await?;
+                if let Err(e) = ctx
+                    .place_order(yes, Side::Sell, yes_limit, size, OrderType::Fok)
+                    .await
+                {
+                    warn!("⚠️  YES leg failed ({}), merging the sets back", e);
+                    self.clob
+                        .merge_full_sets(condition, [yes, no], size, market.neg_risk)
+                        .await?;
+                    return Err(e);
+                }
+                if let Err(e) = ctx
+                    .place_order(no, Side::Sell, no_limit, size, OrderType::Fok)
+                    .await
+                {
+                    warn!("⚠️  NO leg failed ({}), selling it at market", e);
+                    self.clob.market_sell(no, size, self.config.unwind_slippage).await?;
+                }
+            }
+        }
+
+        info!("✅ Complement arbitrage done on {}", market.question);
+        Ok(())
+    }
+}
+
+/// Scans every poll interval and trades the best opportunity found;
+/// books move after each trade, so one is taken per scan
+impl Strategy for ComplementArb {
+    fn name(&self) -> &str {
+        "complement-arb"
+    }
+
+    fn timer(&self) -> Option<Duration> {
+        Some(Self::poll_interval())
+    }
+
+    fn on_timer<'a>(&'a self, ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            let found = self.scan().await?;
+            if let Some((market, opp)) = found.first() {
+                self.execute(ctx, market, opp)
+                    .await
+                    .map_err(|e| anyhow!("{} failed: {}", market.question, e))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+fn decimal(v: f64) -> Result<Decimal> {
+    Decimal::from_f64(v).ok_or_else(|| anyhow!("Invalid number {}", v))
+}
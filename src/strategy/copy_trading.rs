@@ -0,0 +1,225 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use super::runtime::{Strategy, StrategyContext, StrategyFuture};
+use crate::domain::order::Side;
+use crate::portfolio::{DataApiClient, DataApiTrade};
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// COPY TRADING
+// ==================================================
+// Follows a leader wallet's trades from the data API and mirrors them:
+//
+//   leader BUY  n @ p  →  BUY  n × COPY_SCALE, limit p + COPY_MAX_SLIPPAGE
+//   leader SELL n @ p  →  SELL n × COPY_SCALE (at most what we hold),
+//                         limit p - COPY_MAX_SLIPPAGE
+//
+// each after COPY_DELAY_SECS, as FAK. Trades seen at startup or older
+// than COPY_MAX_AGE_SECS aren't copied. Orders go through the strategy
+// context, so the strategy budget, risk manager and pre-trade checks
+// all apply.
+
+/// Trades fetched per poll; more than a leader makes between polls
+const TRADES_PER_POLL: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct CopyConfig {
+    /// Proxy wallet to follow
+    pub leader: String,
+    /// Our size per leader token
+    pub scale: Decimal,
+    /// Wait this long before mirroring
+    pub delay: Duration,
+    /// Cap on tokens per mirrored order
+    pub max_order: Decimal,
+    /// How far past the leader's price we'll trade
+    pub max_slippage: f64,
+    /// Skip leader trades older than this when first seen
+    pub max_age: Duration,
+    pub poll: Duration,
+}
+
+impl CopyConfig {
+    /// `COPY_LEADER` (required), `COPY_SCALE` (default 0.1),
+    /// `COPY_DELAY_SECS` (0), `COPY_MAX_ORDER` (100), `COPY_MAX_SLIPPAGE`
+    /// (0.02), `COPY_MAX_AGE_SECS` (300), `COPY_POLL_SECS` (15)
+    pub fn from_env() -> Result<Self> {
+        let leader = std::env::var("COPY_LEADER")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow!("COPY_LEADER is not set"))?;
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+        Ok(Self {
+            leader: leader.to_lowercase(),
+            scale: var("COPY_SCALE").unwrap_or(dec!(0.1)),
+            delay: Duration::from_secs(var("COPY_DELAY_SECS").unwrap_or(0)),
+            max_order: var("COPY_MAX_ORDER").unwrap_or(dec!(100)),
+            max_slippage: var("COPY_MAX_SLIPPAGE").unwrap_or(0.02),
+            max_age: Duration::from_secs(var("COPY_MAX_AGE_SECS").unwrap_or(300)),
+            poll: Duration::from_secs(var("COPY_POLL_SECS").unwrap_or(15)),
+        })
+    }
+}
+
+/// Mirrors one leader wallet
+pub struct CopyTrader {
+    api: DataApiClient,
+    config: CopyConfig,
+    seen: Mutex<HashSet<String>>,
+    // Leader trades waiting out the delay (due ms, context clock),
+    // oldest first
+    pending: Mutex<VecDeque<(u64, DataApiTrade)>>,
+}
+
+impl CopyTrader {
+    pub fn new(api: DataApiClient, config: CopyConfig) -> Self {
+        Self {
+            api,
+            config,
+            seen: Mutex::new(HashSet::new()),
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(DataApiClient::from_env(), CopyConfig::from_env()?))
+    }
+
+    pub fn config(&self) -> &CopyConfig {
+        &self.config
+    }
+
+    /// Queue leader trades we haven't seen yet
+    async fn poll(&self, ctx: &StrategyContext) -> Result<()> {
+        let mut trades = self.api.trades(&self.config.leader, TRADES_PER_POLL).await?;
+        trades.sort_by_key(|t| t.timestamp);
+        let now_ms = ctx.now_ms();
+        let now = (now_ms / 1000) as i64;
+
+        let mut seen = self.seen.lock().await;
+        let mut pending = self.pending.lock().await;
+        for trade in trades {
+            if !seen.insert(trade.key()) {
+                continue;
+            }
+            let age = now.saturating_sub(trade.timestamp).max(0) as u64;
+            if age > self.config.max_age.as_secs() {
+                warn!("⏭️  Not copying {} {} of {}: {}s old", trade.side, trade.size, trade.asset, age);
+                continue;
+            }
+            info!(
+                "👀 Leader {} {} @ {} on {} {}",
+                trade.side, trade.size, trade.price, trade.title, trade.outcome
+            );
+            pending.push_back((now_ms + self.config.delay.as_millis() as u64, trade));
+        }
+        Ok(())
+    }
+
+    /// Mirror the queued trades whose delay has passed
+    async fn mirror_due(&self, ctx: &StrategyContext) {
+        loop {
+            let trade = {
+                let mut pending = self.pending.lock().await;
+                match pending.front() {
+                    Some((due, _)) if *due <= ctx.now_ms() => pending.pop_front().map(|(_, t)| t),
+                    _ => None,
+                }
+            };
+            let Some(trade) = trade else {
+                return;
+            };
+            if let Err(e) = self.mirror(ctx, &trade).await {
+                warn!("⚠️  Copy of {} {} on {} failed: {}", trade.side, trade.size, trade.asset, e);
+            }
+        }
+    }
+
+    async fn mirror(&self, ctx: &StrategyContext, trade: &DataApiTrade) -> Result<()> {
+        let side = match trade.side.as_str() {
+            "BUY" => Side::Buy,
+            "SELL" => Side::Sell,
+            other => return Err(anyhow!("unknown side {}", other)),
+        };
+        let leader_size = Decimal::from_f64(trade.size).ok_or_else(|| anyhow!("bad size {}", trade.size))?;
+        let mut size = (leader_size * self.config.scale).min(self.config.max_order);
+        if side == Side::Sell {
+            size = size.min(ctx.held(&trade.asset).await);
+        }
+
+        let rules = ctx.clob().market_rules(&trade.asset).await?;
+        let size = size.round_dp_with_strategy(2, RoundingStrategy::ToZero);
+        if size < rules.min_size || size.is_zero() {
+            info!("⏭️  Copy of {} {} too small ({})", trade.side, trade.asset, size);
+            return Ok(());
+        }
+        let tick = rules.tick_size;
+        let limit = match side {
+            Side::Buy => trade.price + self.config.max_slippage,
+            Side::Sell => trade.price - self.config.max_slippage,
+        };
+        let limit = Decimal::from_f64(limit).ok_or_else(|| anyhow!("bad price {}", limit))?;
+        let limit = match side {
+            Side::Buy => (limit / tick).floor() * tick,
+            Side::Sell => (limit / tick).ceil() * tick,
+        }
+        .clamp(tick, Decimal::ONE - tick);
+
+        let order_id = ctx
+            .place_order(&trade.asset, side.clone(), limit, size, OrderType::Fak)
+            .await?;
+        info!(
+            "🪞 Copied {} {} of {} (leader {}) limit {}: {}",
+            side.as_str(),
+            size,
+            trade.asset,
+            trade.size,
+            limit,
+            order_id
+        );
+        Ok(())
+    }
+}
+
+/// Polls the leader every `COPY_POLL_SECS`
+impl Strategy for CopyTrader {
+    fn name(&self) -> &str {
+        "copy-trading"
+    }
+
+    fn timer(&self) -> Option<Duration> {
+        Some(self.config.poll)
+    }
+
+    /// Everything the leader did before we started is history
+    fn on_start<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            let trades = self.api.trades(&self.config.leader, TRADES_PER_POLL).await?;
+            self.seen.lock().await.extend(trades.iter().map(|t| t.key()));
+            info!(
+                "🪞 Copy trading {} at {}x (delay {}s)",
+                self.config.leader,
+                self.config.scale,
+                self.config.delay.as_secs()
+            );
+            Ok(())
+        })
+    }
+
+    fn on_timer<'a>(&'a self, ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            let polled = self.poll(ctx).await;
+            self.mirror_due(ctx).await;
+            polled
+        })
+    }
+}
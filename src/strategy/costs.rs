@@ -0,0 +1,119 @@
+use log::warn;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use crate::domain::order::Side;
+use crate::wallet::fees;
+
+// ==================================================
+// SIMULATED TRADING COSTS
+// ==================================================
+// What a `SimVenue` charges on top of the prices in the book:
+//
+//   fee       Polymarket's rate * min(p, 1 - p) per token
+//             (`wallet::fees`), with separate taker and maker rates
+//   slippage  taker fills priced worse than the levels they took:
+//             none | fixed:<price> | bps:<n>
+//
+// Maker fills never slip; they trade at the order's own price. The
+// defaults charge nothing, matching the venue before costs existed.
+
+/// How much worse than the book a taker fill is priced
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Slippage {
+    #[default]
+    None,
+    /// Fixed price offset (0.005 = half a cent per token)
+    Fixed(Decimal),
+    /// Basis points of the fill price
+    Bps(Decimal),
+}
+
+impl Slippage {
+    /// `price` moved against a taker on `side`, kept inside [0, 1]
+    pub fn apply(&self, side: &Side, price: Decimal) -> Decimal {
+        let offset = match self {
+            Slippage::None => return price,
+            Slippage::Fixed(offset) => *offset,
+            Slippage::Bps(bps) => price * bps / Decimal::from(10_000),
+        };
+        match side {
+            Side::Buy => (price + offset).min(Decimal::ONE),
+            Side::Sell => (price - offset).max(Decimal::ZERO),
+        }
+    }
+}
+
+impl FromStr for Slippage {
+    type Err = String;
+
+    /// `none` | `fixed:<price>` | `bps:<n>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if s.is_empty() || s == "none" {
+            return Ok(Slippage::None);
+        }
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected none, fixed:<price> or bps:<n>, got {}", s))?;
+        let value = Decimal::from_str(value).map_err(|e| format!("bad slippage {}: {}", value, e))?;
+        if value < Decimal::ZERO {
+            return Err(format!("negative slippage {}", value));
+        }
+        match kind {
+            "fixed" => Ok(Slippage::Fixed(value)),
+            "bps" => Ok(Slippage::Bps(value)),
+            other => Err(format!("unknown slippage model {}", other)),
+        }
+    }
+}
+
+/// Fees and slippage applied to simulated fills
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostModel {
+    /// Fee rate on fills that took liquidity
+    pub taker_fee_bps: u32,
+    /// Fee rate on fills of resting orders
+    pub maker_fee_bps: u32,
+    pub slippage: Slippage,
+}
+
+impl CostModel {
+    /// `SIM_TAKER_FEE_BPS`, `SIM_MAKER_FEE_BPS` (default 0) and
+    /// `SIM_SLIPPAGE` (default none)
+    pub fn from_env() -> Self {
+        let bps = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0)
+        };
+        let slippage = match std::env::var("SIM_SLIPPAGE") {
+            Ok(v) => v.parse().unwrap_or_else(|e| {
+                warn!("⚠️  SIM_SLIPPAGE ignored: {}", e);
+                Slippage::None
+            }),
+            Err(_) => Slippage::None,
+        };
+        Self {
+            taker_fee_bps: bps("SIM_TAKER_FEE_BPS"),
+            maker_fee_bps: bps("SIM_MAKER_FEE_BPS"),
+            slippage,
+        }
+    }
+
+    /// Price a fill at `price` from the book actually trades at
+    pub fn fill_price(&self, side: &Side, price: Decimal, maker: bool) -> Decimal {
+        if maker {
+            price
+        } else {
+            self.slippage.apply(side, price)
+        }
+    }
+
+    /// Fee on a fill, in USDC
+    pub fn fee(&self, side: &Side, price: Decimal, size: Decimal, maker: bool) -> Decimal {
+        let bps = if maker { self.maker_fee_bps } else { self.taker_fee_bps };
+        fees::fee(side, price, size, bps).usdc
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::{anyhow, Result};
+use futures_util::future::BoxFuture;
+use log::{debug, info, warn};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, RwLock};
+
+use crate::execution::sanity::PriceReference;
+use crate::execution::ClobClient;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8790";
+/// Largest push request accepted
+const MAX_REQUEST: usize = 1 << 20;
+/// Buffered fair-value notifications per subscriber
+const UPDATE_BUFFER: usize = 1024;
+
+// ==================================================
+// EXTERNAL FAIR VALUES
+// ==================================================
+// Lets an outside model (a Python/ML service, a pricing engine) set the
+// center quoting strategies price around:
+//
+//   model  --POST /fair-values-->  FairValuePush  -->  FairValueStore
+//                                                        │
+//                  MarketMaker center, price sanity  <───┘
+//
+// Values older than FAIR_VALUE_MAX_AGE_SECS are treated as missing, so
+// a dead model stops quotes rather than leaving them on stale prices.
+
+/// Where a strategy gets its view of a token's probability
+pub trait FairValueSource: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Current probability of `token_id` resolving YES, `None` when the
+    /// source has no fresh view
+    fn fair_value<'a>(&'a self, token_id: &'a str) -> BoxFuture<'a, Option<f64>>;
+}
+
+/// Latest pushed probability per token
+pub struct FairValueStore {
+    max_age: Duration,
+    values: RwLock<HashMap<String, (f64, Instant)>>,
+    updates: broadcast::Sender<String>,
+}
+
+impl FairValueStore {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            values: RwLock::new(HashMap::new()),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+        }
+    }
+
+    /// `FAIR_VALUE_MAX_AGE_SECS` (default 30)
+    pub fn from_env() -> Self {
+        let secs = std::env::var("FAIR_VALUE_MAX_AGE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+        Self::new(Duration::from_secs(secs))
+    }
+
+    /// Token ids whose value changed
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.updates.subscribe()
+    }
+
+    pub async fn set(&self, token_id: &str, probability: f64) -> Result<()> {
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(anyhow!("probability {} for {} outside [0, 1]", probability, token_id));
+        }
+        self.values
+            .write()
+            .await
+            .insert(token_id.to_string(), (probability, Instant::now()));
+        let _ = self.updates.send(token_id.to_string());
+        Ok(())
+    }
+
+    pub async fn get(&self, token_id: &str) -> Option<f64> {
+        let (p, at) = *self.values.read().await.get(token_id)?;
+        (at.elapsed() <= self.max_age).then_some(p)
+    }
+
+    /// Fresh values only
+    pub async fn snapshot(&self) -> HashMap<String, f64> {
+        self.values
+            .read()
+            .await
+            .iter()
+            .filter(|(_, (_, at))| at.elapsed() <= self.max_age)
+            .map(|(t, (p, _))| (t.clone(), *p))
+            .collect()
+    }
+}
+
+impl FairValueSource for FairValueStore {
+    fn name(&self) -> &str {
+        "model"
+    }
+
+    fn fair_value<'a>(&'a self, token_id: &'a str) -> BoxFuture<'a, Option<f64>> {
+        Box::pin(self.get(token_id))
+    }
+}
+
+/// The model's value as the price sanity reference
+impl PriceReference for FairValueStore {
+    fn name(&self) -> &str {
+        "model"
+    }
+
+    fn price<'a>(&'a self, _clob: &'a ClobClient, token_id: &'a str) -> BoxFuture<'a, Result<Option<f64>>> {
+        Box::pin(async move { Ok(self.get(token_id).await) })
+    }
+}
+
+/// One pushed value
+#[derive(Debug, Deserialize)]
+struct Push {
+    token_id: String,
+    probability: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PushBody {
+    One(Push),
+    Many(Vec<Push>),
+}
+
+/// HTTP endpoint models push into.
+///
+/// - `POST /fair-values`: `{"token_id": "…", "probability": 0.42}` or an
+///   array of them
+/// - `GET /fair-values`: the fresh values held
+///
+/// With `FAIR_VALUE_PUSH_TOKEN` set, pushes need `Authorization: Bearer
+/// <token>`. Binds to localhost by default.
+pub struct FairValuePush {
+    addr: String,
+    token: Option<String>,
+    store: Arc<FairValueStore>,
+}
+
+impl FairValuePush {
+    pub fn new(addr: impl Into<String>, store: Arc<FairValueStore>) -> Self {
+        Self {
+            addr: addr.into(),
+            token: None,
+            store,
+        }
+    }
+
+    /// Require this bearer token on pushes
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// `FAIR_VALUE_LISTEN_ADDR` (default 127.0.0.1:8790),
+    /// `FAIR_VALUE_PUSH_TOKEN`
+    pub fn from_env(store: Arc<FairValueStore>) -> Self {
+        let addr = std::env::var("FAIR_VALUE_LISTEN_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+        let push = Self::new(addr, store);
+        match std::env::var("FAIR_VALUE_PUSH_TOKEN") {
+            Ok(token) if !token.is_empty() => push.token(token),
+            _ => push,
+        }
+    }
+
+    /// Serve until the listener fails
+    pub async fn run(self: Arc<Self>) -> Result<()> {
+        let listener = TcpListener::bind(&self.addr).await?;
+        info!("🔮 Fair-value push endpoint on http://{}/fair-values", self.addr);
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = server.handle(stream).await {
+                    debug!("Fair-value request from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, mut stream: TcpStream) -> Result<()> {
+        let (head, body) = read_request(&mut stream).await?;
+        let mut lines = head.lines();
+        let mut parts = lines.next().unwrap_or("").split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        let path = path.split('?').next().unwrap_or("");
+        let authorized = match &self.token {
+            Some(token) => lines.any(|l| {
+                l.split_once(':').is_some_and(|(k, v)| {
+                    k.eq_ignore_ascii_case("authorization") && v.trim() == format!("Bearer {}", token)
+                })
+            }),
+            None => true,
+        };
+
+        let (code, reply) = match (method, path) {
+            ("GET", "/fair-values") => ("200 OK", json!(self.store.snapshot().await)),
+            ("POST", "/fair-values") if !authorized => ("401 Unauthorized", json!({ "error": "unauthorized" })),
+            ("POST", "/fair-values") => match self.apply(&body).await {
+                Ok(n) => ("200 OK", json!({ "accepted": n })),
+                Err(e) => ("400 Bad Request", json!({ "error": e.to_string() })),
+            },
+            (_, "/fair-values") => ("405 Method Not Allowed", json!({ "error": "method not allowed" })),
+            _ => ("404 Not Found", json!({ "error": "not found" })),
+        };
+
+        let reply = reply.to_string();
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            code,
+            reply.len(),
+            reply
+        );
+        if let Err(e) = stream.write_all(response.as_bytes()).await {
+            warn!("⚠️  Fair-value reply failed: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Store every value in `body`; all or nothing
+    async fn apply(&self, body: &[u8]) -> Result<usize> {
+        let pushes = match serde_json::from_slice::<PushBody>(body)? {
+            PushBody::One(p) => vec![p],
+            PushBody::Many(ps) => ps,
+        };
+        if let Some(bad) = pushes.iter().find(|p| !(0.0..=1.0).contains(&p.probability)) {
+            return Err(anyhow!("probability {} for {} outside [0, 1]", bad.probability, bad.token_id));
+        }
+        for p in &pushes {
+            self.store.set(&p.token_id, p.probability).await?;
+        }
+        Ok(pushes.len())
+    }
+}
+
+/// Request head and the body its Content-Length announces
+async fn read_request(stream: &mut TcpStream) -> Result<(String, Vec<u8>)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(anyhow!("connection closed mid-request"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.len() > MAX_REQUEST {
+            return Err(anyhow!("request too large"));
+        }
+        let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+            continue;
+        };
+        let head = String::from_utf8_lossy(&buf[..end]).to_string();
+        let length: usize = head
+            .lines()
+            .filter_map(|l| l.split_once(':'))
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.trim().parse().ok())
+            .unwrap_or(0);
+        if length > MAX_REQUEST {
+            return Err(anyhow!("request too large"));
+        }
+        while buf.len() < end + 4 + length {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let body = buf[end + 4..].iter().take(length).copied().collect();
+        return Ok((head, body));
+    }
+}
+
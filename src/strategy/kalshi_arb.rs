@@ -0,0 +1,363 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::runtime::{Strategy, StrategyContext, StrategyFuture};
+use crate::domain::order::Side;
+use crate::execution::orderbook::{fetch_books, Level, OrderBook};
+use crate::execution::ClobClient;
+use crate::kalshi::{taker_fee, KalshiAction, KalshiBook, KalshiClient, KalshiOrder, KalshiSide};
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// CROSS-VENUE ARBITRAGE (POLYMARKET × KALSHI)
+// ==================================================
+// A YES on one venue plus a NO on the other pays exactly 1 whichever
+// way an equivalent question resolves, so:
+//
+//   ask(PM YES) + ask(Kalshi NO) + fees < 1  →  buy both
+//   ask(PM NO)  + ask(Kalshi YES) + fees < 1 →  buy both
+//
+// Pairs are configured by hand (KALSHI_ARB_PAIRS): only list contracts
+// whose resolution criteria really match. The Polymarket leg goes first
+// (FOK); if the Kalshi FOK then fails, the Polymarket leg is sold back.
+
+/// One question listed on both venues; Polymarket YES ≡ Kalshi YES
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KalshiPair {
+    pub yes_token: String,
+    pub no_token: String,
+    pub ticker: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct KalshiArbConfig {
+    pub pairs: Vec<KalshiPair>,
+    /// Profit per contract (USDC) required after fees
+    pub min_edge: f64,
+    /// Contracts below this aren't worth the round trip
+    pub min_size: u64,
+    /// Cap on contracts per trade
+    pub max_size: u64,
+    /// Polymarket taker fee rate: fee = rate × min(p, 1 - p) per token
+    pub poly_fee_rate: f64,
+    /// Slippage allowed when selling back a stranded Polymarket leg
+    pub unwind_slippage: f64,
+}
+
+impl Default for KalshiArbConfig {
+    fn default() -> Self {
+        Self {
+            pairs: Vec::new(),
+            min_edge: 0.02,
+            min_size: 5,
+            max_size: 100,
+            poly_fee_rate: 0.0,
+            unwind_slippage: 0.05,
+        }
+    }
+}
+
+impl KalshiArbConfig {
+    /// `KALSHI_ARB_PAIRS` (`yes_token:no_token:TICKER,…`),
+    /// `KALSHI_ARB_MIN_EDGE`, `KALSHI_ARB_MIN_SIZE`, `KALSHI_ARB_MAX_SIZE`,
+    /// `KALSHI_ARB_POLY_FEE_RATE`, `KALSHI_ARB_UNWIND_SLIPPAGE`
+    pub fn from_env() -> Result<Self> {
+        let d = Self::default();
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+        let mut pairs = Vec::new();
+        for entry in std::env::var("KALSHI_ARB_PAIRS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let parts: Vec<&str> = entry.split(':').collect();
+            let [yes, no, ticker] = parts[..] else {
+                return Err(anyhow!("Bad KALSHI_ARB_PAIRS entry {:?}, want yes_token:no_token:TICKER", entry));
+            };
+            pairs.push(KalshiPair {
+                yes_token: yes.to_string(),
+                no_token: no.to_string(),
+                ticker: ticker.to_string(),
+            });
+        }
+        Ok(Self {
+            pairs,
+            min_edge: var("KALSHI_ARB_MIN_EDGE").unwrap_or(d.min_edge),
+            min_size: var("KALSHI_ARB_MIN_SIZE").unwrap_or(d.min_size),
+            max_size: var("KALSHI_ARB_MAX_SIZE").unwrap_or(d.max_size),
+            poly_fee_rate: var("KALSHI_ARB_POLY_FEE_RATE").unwrap_or(d.poly_fee_rate),
+            unwind_slippage: var("KALSHI_ARB_UNWIND_SLIPPAGE").unwrap_or(d.unwind_slippage),
+        })
+    }
+
+    fn poly_fee(&self, price: f64) -> f64 {
+        self.poly_fee_rate * price.min(1.0 - price)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossDirection {
+    /// Buy YES on Polymarket, NO on Kalshi
+    PolyYesKalshiNo,
+    /// Buy NO on Polymarket, YES on Kalshi
+    PolyNoKalshiYes,
+}
+
+/// A crossed pair the books can fill, sized level by level so every
+/// contract taken clears `min_edge` after fees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossOpportunity {
+    pub direction: CrossDirection,
+    /// Contracts on each venue
+    pub size: u64,
+    /// Worst levels touched: the FOK limit prices
+    pub poly_limit: f64,
+    pub kalshi_limit: f64,
+    pub poly_notional: f64,
+    pub kalshi_notional: f64,
+    /// Both venues' taker fees
+    pub fees: f64,
+}
+
+impl CrossOpportunity {
+    /// Guaranteed payout less both legs and fees
+    pub fn profit(&self) -> f64 {
+        self.size as f64 - self.poly_notional - self.kalshi_notional - self.fees
+    }
+
+    pub fn edge(&self) -> f64 {
+        match self.size {
+            0 => 0.0,
+            n => self.profit() / n as f64,
+        }
+    }
+}
+
+/// Best cross-venue arbitrage on one pair, if any clears the configured
+/// edge and minimum size
+pub fn detect(
+    poly_yes: &OrderBook,
+    poly_no: &OrderBook,
+    kalshi: &KalshiBook,
+    config: &KalshiArbConfig,
+) -> Option<CrossOpportunity> {
+    [
+        walk(&poly_yes.asks, &kalshi.no.asks, config, CrossDirection::PolyYesKalshiNo),
+        walk(&poly_no.asks, &kalshi.yes.asks, config, CrossDirection::PolyNoKalshiYes),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|o| o.size >= config.min_size && o.edge() >= config.min_edge)
+    .max_by(|a, b| a.profit().total_cmp(&b.profit()))
+}
+
+/// Take both ask ladders level by level while a contract still clears
+/// the edge, then round down to whole contracts and price the fees
+fn walk(poly: &[Level], kalshi: &[Level], config: &KalshiArbConfig, direction: CrossDirection) -> Option<CrossOpportunity> {
+    let (mut i, mut j) = (0, 0);
+    let (mut poly_left, mut kalshi_left) = (poly.first()?.size, kalshi.first()?.size);
+    let max = config.max_size as f64;
+    let (mut size, mut poly_limit, mut kalshi_limit) = (0.0, 0.0, 0.0);
+    let mut taken: Vec<(f64, f64, f64)> = Vec::new();
+
+    while i < poly.len() && j < kalshi.len() && max - size > 1e-9 {
+        let (p, k) = (poly[i].price, kalshi[j].price);
+        let unit_edge = 1.0 - p - k - config.poly_fee(p) - 0.07 * k * (1.0 - k);
+        if unit_edge < config.min_edge {
+            break;
+        }
+        let take = poly_left.min(kalshi_left).min(max - size);
+        taken.push((take, p, k));
+        size += take;
+        poly_limit = p;
+        kalshi_limit = k;
+
+        poly_left -= take;
+        kalshi_left -= take;
+        if poly_left <= 1e-9 {
+            i += 1;
+            poly_left = poly.get(i).map(|l| l.size).unwrap_or_default();
+        }
+        if kalshi_left <= 1e-9 {
+            j += 1;
+            kalshi_left = kalshi.get(j).map(|l| l.size).unwrap_or_default();
+        }
+    }
+
+    // Kalshi trades whole contracts; drop the fraction from the worst levels
+    let contracts = size.floor() as u64;
+    if contracts == 0 {
+        return None;
+    }
+    let mut left = contracts as f64;
+    let (mut poly_notional, mut kalshi_notional, mut fees) = (0.0, 0.0, 0.0);
+    for (take, p, k) in taken {
+        let take = take.min(left);
+        if take <= 0.0 {
+            break;
+        }
+        poly_notional += take * p;
+        kalshi_notional += take * k;
+        fees += take * config.poly_fee(p);
+        left -= take;
+    }
+    // Charged once on the whole FOK, at its (worst-level) limit
+    fees += taker_fee(contracts, kalshi_limit);
+
+    Some(CrossOpportunity {
+        direction,
+        size: contracts,
+        poly_limit,
+        kalshi_limit,
+        poly_notional,
+        kalshi_notional,
+        fees,
+    })
+}
+
+/// Watches configured Polymarket/Kalshi pairs and trades crossed prices
+pub struct KalshiArb {
+    clob: Arc<ClobClient>,
+    kalshi: KalshiClient,
+    config: KalshiArbConfig,
+}
+
+impl KalshiArb {
+    pub fn new(clob: Arc<ClobClient>, kalshi: KalshiClient, config: KalshiArbConfig) -> Self {
+        Self { clob, kalshi, config }
+    }
+
+    pub fn from_env(clob: Arc<ClobClient>) -> Result<Self> {
+        Ok(Self::new(clob, KalshiClient::from_env()?, KalshiArbConfig::from_env()?))
+    }
+
+    /// Poll interval from `KALSHI_ARB_POLL_SECS` (default 10)
+    pub fn poll_interval() -> Duration {
+        let secs = std::env::var("KALSHI_ARB_POLL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10);
+        Duration::from_secs(secs)
+    }
+
+    pub fn config(&self) -> &KalshiArbConfig {
+        &self.config
+    }
+
+    /// Current opportunities across the configured pairs, most
+    /// profitable first
+    pub async fn scan(&self) -> Result<Vec<(KalshiPair, CrossOpportunity)>> {
+        let tokens: Vec<String> = self
+            .config
+            .pairs
+            .iter()
+            .flat_map(|p| [p.yes_token.clone(), p.no_token.clone()])
+            .collect();
+        let books = fetch_books(self.clob.clob_url(), &tokens).await?;
+
+        let mut found = Vec::new();
+        for pair in &self.config.pairs {
+            let (Some(yes), Some(no)) = (books.get(&pair.yes_token), books.get(&pair.no_token)) else {
+                continue;
+            };
+            let kalshi = match self.kalshi.orderbook(&pair.ticker).await {
+                Ok(book) => book,
+                Err(e) => {
+                    warn!("⚠️  Kalshi book for {} unavailable: {}", pair.ticker, e);
+                    continue;
+                }
+            };
+            if let Some(opp) = detect(yes, no, &kalshi, &self.config) {
+                found.push((pair.clone(), opp));
+            }
+        }
+        found.sort_by(|a, b| b.1.profit().total_cmp(&a.1.profit()));
+        Ok(found)
+    }
+
+    /// Buy the Polymarket leg (through `ctx`, against the strategy's
+    /// budget), then the Kalshi leg; unwind the first if the second fails
+    pub async fn execute(&self, ctx: &StrategyContext, pair: &KalshiPair, opp: &CrossOpportunity) -> Result<()> {
+        let (token, kalshi_side) = match opp.direction {
+            CrossDirection::PolyYesKalshiNo => (&pair.yes_token, KalshiSide::No),
+            CrossDirection::PolyNoKalshiYes => (&pair.no_token, KalshiSide::Yes),
+        };
+        let size = Decimal::from(opp.size);
+        let poly_limit = Decimal::from_f64(opp.poly_limit)
+            .ok_or_else(|| anyhow!("Invalid price {}", opp.poly_limit))?
+            .round_dp(4);
+        let kalshi_cents = (opp.kalshi_limit * 100.0).round() as i64;
+
+        info!(
+            "🌉 {:?} {} contracts on {}: PM {} + Kalshi {}¢, expected ${:.2} ({:.4}/contract)",
+            opp.direction,
+            opp.size,
+            pair.ticker,
+            poly_limit,
+            kalshi_cents,
+            opp.profit(),
+            opp.edge()
+        );
+
+        ctx.place_order(token, Side::Buy, poly_limit, size, OrderType::Fok)
+            .await?;
+        let order = KalshiOrder::fill_or_kill(&pair.ticker, KalshiAction::Buy, kalshi_side, opp.size, kalshi_cents);
+        let kalshi_leg = match self.kalshi.place_order(&order).await {
+            Ok(ack) if ack.is_filled() => Ok(()),
+            Ok(ack) => Err(anyhow!("Kalshi order {} not filled ({})", ack.order_id, ack.status)),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = kalshi_leg {
+            warn!("⚠️  Kalshi leg failed ({}), selling the Polymarket leg back", e);
+            self.clob.market_sell(token, size, self.config.unwind_slippage).await?;
+            return Err(e);
+        }
+
+        info!("✅ Cross-venue arbitrage done on {}", pair.ticker);
+        Ok(())
+    }
+}
+
+/// Scans every poll interval and trades the best opportunity found
+impl Strategy for KalshiArb {
+    fn name(&self) -> &str {
+        "kalshi-arb"
+    }
+
+    fn timer(&self) -> Option<Duration> {
+        Some(Self::poll_interval())
+    }
+
+    fn on_start<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            if !self.kalshi.can_trade() {
+                return Err(anyhow!("Kalshi credentials not configured"));
+            }
+            if self.config.pairs.is_empty() {
+                return Err(anyhow!("no KALSHI_ARB_PAIRS configured"));
+            }
+            let cash = self.kalshi.balance().await?;
+            info!("🌉 Watching {} Kalshi pair(s), ${:.2} on Kalshi", self.config.pairs.len(), cash);
+            Ok(())
+        })
+    }
+
+    fn on_timer<'a>(&'a self, ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            let found = self.scan().await?;
+            if let Some((pair, opp)) = found.first() {
+                self.execute(ctx, pair, opp)
+                    .await
+                    .map_err(|e| anyhow!("{} failed: {}", pair.ticker, e))?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,144 @@
+use log::warn;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::str::FromStr;
+
+// ==================================================
+// SIMULATED LATENCY
+// ==================================================
+// How long things take on a simulated venue, each drawn from its own
+// distribution:
+//
+//   submit  order sent  →  order on the book (or rejected)
+//   cancel  cancel sent →  order off the book; it can still fill meanwhile
+//   book    book change →  the strategy sees it (backtests only; live
+//                          shadow books are as stale as the feed is)
+//
+// Distributions: 0 | fixed:<ms> | uniform:<min>-<max> |
+// normal:<mean>,<sd> | exp:<mean>. Draws come from a seeded RNG, so a
+// backtest with latency still repeats exactly. The defaults are zero,
+// matching the venue before latency existed.
+
+/// A delay distribution, in milliseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Delay {
+    #[default]
+    Zero,
+    Fixed(u64),
+    Uniform(u64, u64),
+    /// Mean and standard deviation; draws below zero count as zero
+    Normal(f64, f64),
+    /// Exponential with this mean
+    Exponential(f64),
+}
+
+impl Delay {
+    pub fn is_zero(&self) -> bool {
+        matches!(self, Delay::Zero | Delay::Fixed(0))
+    }
+
+    /// One draw, in ms
+    pub fn sample(&self, rng: &mut StdRng) -> u64 {
+        match *self {
+            Delay::Zero => 0,
+            Delay::Fixed(ms) => ms,
+            Delay::Uniform(min, max) => rng.random_range(min..=max),
+            Delay::Normal(mean, sd) => {
+                // Box-Muller
+                let u1: f64 = rng.random_range(f64::EPSILON..1.0);
+                let u2: f64 = rng.random();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                (mean + sd * z).max(0.0).round() as u64
+            }
+            Delay::Exponential(mean) => {
+                let u: f64 = rng.random();
+                (-mean * (1.0 - u).ln()).round() as u64
+            }
+        }
+    }
+}
+
+impl FromStr for Delay {
+    type Err = String;
+
+    /// `0` | `fixed:<ms>` | `uniform:<min>-<max>` | `normal:<mean>,<sd>` |
+    /// `exp:<mean>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if s.is_empty() || s == "0" || s == "none" {
+            return Ok(Delay::Zero);
+        }
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected 0, fixed:, uniform:, normal: or exp:, got {}", s))?;
+        let num = |v: &str| v.trim().parse::<f64>().map_err(|e| format!("bad delay {}: {}", v, e));
+        let pair = |sep: char| {
+            value
+                .split_once(sep)
+                .ok_or_else(|| format!("expected two values in {}", value))
+        };
+        let delay = match kind {
+            "fixed" => Delay::Fixed(num(value)? as u64),
+            "uniform" => {
+                let (min, max) = pair('-')?;
+                let (min, max) = (num(min)? as u64, num(max)? as u64);
+                if min > max {
+                    return Err(format!("uniform range {} is backwards", value));
+                }
+                Delay::Uniform(min, max)
+            }
+            "normal" => {
+                let (mean, sd) = pair(',')?;
+                Delay::Normal(num(mean)?, num(sd)?)
+            }
+            "exp" => Delay::Exponential(num(value)?),
+            other => return Err(format!("unknown delay distribution {}", other)),
+        };
+        let negative = match delay {
+            Delay::Normal(mean, sd) => mean < 0.0 || sd < 0.0,
+            Delay::Exponential(mean) => mean < 0.0,
+            _ => false,
+        };
+        if negative {
+            return Err(format!("negative delay {}", value));
+        }
+        Ok(delay)
+    }
+}
+
+/// Latencies applied by a `SimVenue` and the backtest feeding it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LatencyModel {
+    pub submit: Delay,
+    pub cancel: Delay,
+    pub book: Delay,
+    /// Seed of the draws
+    pub seed: u64,
+}
+
+impl LatencyModel {
+    /// `SIM_SUBMIT_LATENCY`, `SIM_CANCEL_LATENCY`, `SIM_BOOK_LATENCY`
+    /// (default 0) and `SIM_LATENCY_SEED` (default 0)
+    pub fn from_env() -> Self {
+        let delay = |name: &str| match std::env::var(name) {
+            Ok(v) => v.parse().unwrap_or_else(|e| {
+                warn!("⚠️  {} ignored: {}", name, e);
+                Delay::Zero
+            }),
+            Err(_) => Delay::Zero,
+        };
+        Self {
+            submit: delay("SIM_SUBMIT_LATENCY"),
+            cancel: delay("SIM_CANCEL_LATENCY"),
+            book: delay("SIM_BOOK_LATENCY"),
+            seed: std::env::var("SIM_LATENCY_SEED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.submit.is_zero() && self.cancel.is_zero() && self.book.is_zero()
+    }
+}
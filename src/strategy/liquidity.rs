@@ -0,0 +1,273 @@
+use anyhow::{anyhow, Result};
+use ethers::types::H256;
+use log::{info, warn};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use super::runtime::{Strategy, StrategyContext, StrategyFuture};
+use crate::domain::order::Side;
+use crate::execution::orderbook::fetch_book;
+use crate::execution::ClobClient;
+use crate::markets::GammaMarket;
+use crate::portfolio::Fill;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// TWO-SIDED LIQUIDITY PROVISION
+// ==================================================
+// Inventory comes from the CTF rather than the book: split USDC into
+// YES+NO and rest a sell on each outcome. Selling YES at a and NO at b
+// with a + b > 1 earns a + b - 1 per pair once both sides trade.
+//
+//   fill on a leg   →  requote it from what's left
+//   a leg runs dry  →  split more sets (up to max_inventory)
+//   stop            →  cancel both quotes, merge leftover pairs to USDC
+
+/// Sizing and pricing of the two-sided quotes
+#[derive(Debug, Clone)]
+pub struct LiquidityConfig {
+    /// Full sets split at start and on each replenishment
+    pub sets: Decimal,
+    /// Size of each resting sell
+    pub quote_size: Decimal,
+    /// Distance of each ask above its book's mid
+    pub half_spread: f64,
+    /// Never hold more than this of either outcome; caps replenishment
+    /// when one side keeps filling and the other doesn't
+    pub max_inventory: Decimal,
+}
+
+impl Default for LiquidityConfig {
+    fn default() -> Self {
+        Self {
+            sets: dec!(100),
+            quote_size: dec!(50),
+            half_spread: 0.01,
+            max_inventory: dec!(300),
+        }
+    }
+}
+
+impl LiquidityConfig {
+    /// `LP_SETS`, `LP_QUOTE_SIZE`, `LP_HALF_SPREAD`, `LP_MAX_INVENTORY`
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<Decimal>().ok());
+        Self {
+            sets: var("LP_SETS").unwrap_or(d.sets),
+            quote_size: var("LP_QUOTE_SIZE").unwrap_or(d.quote_size),
+            half_spread: var("LP_HALF_SPREAD")
+                .and_then(|v| v.to_f64())
+                .unwrap_or(d.half_spread),
+            max_inventory: var("LP_MAX_INVENTORY").unwrap_or(d.max_inventory),
+        }
+    }
+}
+
+/// One outcome's inventory and resting sell
+#[derive(Debug, Clone, Default)]
+pub struct LpLeg {
+    pub token_id: String,
+    /// Tokens held, resting or not
+    pub inventory: Decimal,
+    pub order_id: Option<String>,
+    /// Unfilled size of the resting sell
+    pub resting: Decimal,
+}
+
+/// Quotes both outcomes of one binary market from split inventory
+pub struct TwoSidedLp {
+    clob: Arc<ClobClient>,
+    condition_id: H256,
+    neg_risk: bool,
+    question: String,
+    config: LiquidityConfig,
+    /// YES, NO token ids
+    tokens: [String; 2],
+    /// YES, NO
+    legs: Mutex<[LpLeg; 2]>,
+}
+
+impl TwoSidedLp {
+    pub fn new(clob: Arc<ClobClient>, market: &GammaMarket, config: LiquidityConfig) -> Result<Self> {
+        let ids = market.token_ids();
+        if ids.len() != 2 {
+            return Err(anyhow!("{} is not a binary market", market.question));
+        }
+        let condition_id = market
+            .condition_id
+            .parse()
+            .map_err(|e| anyhow!("Invalid condition id {}: {}", market.condition_id, e))?;
+        let leg = |token_id: &String| LpLeg {
+            token_id: token_id.clone(),
+            ..Default::default()
+        };
+        Ok(Self {
+            clob,
+            condition_id,
+            neg_risk: market.neg_risk,
+            question: market.question.clone(),
+            config,
+            tokens: [ids[0].clone(), ids[1].clone()],
+            legs: Mutex::new([leg(&ids[0]), leg(&ids[1])]),
+        })
+    }
+
+    /// Snapshot of both legs (YES, NO)
+    pub async fn legs(&self) -> [LpLeg; 2] {
+        self.legs.lock().await.clone()
+    }
+
+    /// Split the initial sets and quote both sides
+    pub async fn start(&self, ctx: &StrategyContext) -> Result<()> {
+        info!("🏦 Providing liquidity on {}", self.question);
+        self.split(self.config.sets).await?;
+        self.requote(ctx).await
+    }
+
+    /// Apply one of our fills; fills of other orders are ignored
+    pub async fn apply_fill(&self, ctx: &StrategyContext, fill: &Fill) -> Result<()> {
+        if fill.side != Side::Sell {
+            return Ok(());
+        }
+        {
+            let mut legs = self.legs.lock().await;
+            let Some(leg) = legs
+                .iter_mut()
+                .find(|l| l.order_id.as_deref() == Some(fill.order_id.as_str()))
+            else {
+                return Ok(());
+            };
+            leg.inventory = (leg.inventory - fill.size).max(Decimal::ZERO);
+            leg.resting = (leg.resting - fill.size).max(Decimal::ZERO);
+            if leg.resting.is_zero() {
+                leg.order_id = None;
+            }
+            info!(
+                "🏦 Sold {} @ {} of {} ({} left)",
+                fill.size, fill.price, leg.token_id, leg.inventory
+            );
+        }
+        self.replenish().await?;
+        self.requote(ctx).await
+    }
+
+    /// Split more sets when a leg can no longer fill a full quote,
+    /// unless that would push the other leg past `max_inventory`
+    async fn replenish(&self) -> Result<()> {
+        let (low, heaviest) = {
+            let legs = self.legs.lock().await;
+            (
+                legs.iter().any(|l| l.inventory < self.config.quote_size),
+                legs.iter().map(|l| l.inventory).max().unwrap_or_default(),
+            )
+        };
+        if !low {
+            return Ok(());
+        }
+        if heaviest + self.config.sets > self.config.max_inventory {
+            warn!(
+                "⚠️  Not replenishing {}: inventory would exceed {}",
+                self.question, self.config.max_inventory
+            );
+            return Ok(());
+        }
+        self.split(self.config.sets).await
+    }
+
+    async fn split(&self, sets: Decimal) -> Result<()> {
+        self.clob
+            .mint_full_set(self.condition_id, sets, self.neg_risk)
+            .await?;
+        for leg in self.legs.lock().await.iter_mut() {
+            leg.inventory += sets;
+        }
+        Ok(())
+    }
+
+    /// Rest a sell on every leg that has inventory but no live quote
+    async fn requote(&self, ctx: &StrategyContext) -> Result<()> {
+        let mut legs = self.legs.lock().await;
+        for leg in legs.iter_mut().filter(|l| l.order_id.is_none()) {
+            let size = leg.inventory.min(self.config.quote_size);
+            let rules = self.clob.market_rules(&leg.token_id).await?;
+            if size.is_zero() || size < rules.min_size {
+                continue;
+            }
+            let price = self.ask_price(&leg.token_id, rules.tick_size).await?;
+            let id = ctx
+                .place_post_only(&leg.token_id, Side::Sell, price, size, OrderType::Gtc)
+                .await?;
+            info!("🏦 Quoting {} of {} @ {}", size, leg.token_id, price);
+            leg.order_id = Some(id);
+            leg.resting = size;
+        }
+        Ok(())
+    }
+
+    /// Mid plus the half spread, rounded up to the tick and kept inside
+    /// (0, 1)
+    async fn ask_price(&self, token_id: &str, tick: Decimal) -> Result<Decimal> {
+        let book = fetch_book(self.clob.clob_url(), token_id).await?;
+        let reference = book
+            .mid()
+            .or_else(|| book.best_ask().map(|l| l.price))
+            .ok_or_else(|| anyhow!("Empty book for {}", token_id))?;
+        let target = Decimal::from_f64(reference + self.config.half_spread)
+            .ok_or_else(|| anyhow!("Invalid price for {}", token_id))?;
+        let price = (target / tick).ceil() * tick;
+        Ok(price.min(Decimal::ONE - tick).max(tick))
+    }
+
+    /// Cancel both quotes and merge every remaining YES+NO pair back
+    /// into USDC. Unpaired inventory stays as a position.
+    pub async fn stop(&self) -> Result<()> {
+        let mut legs = self.legs.lock().await;
+        let ids: Vec<String> = legs.iter_mut().filter_map(|l| l.order_id.take()).collect();
+        self.clob.cancel_orders(&ids).await?;
+        for leg in legs.iter_mut() {
+            leg.resting = Decimal::ZERO;
+        }
+
+        let pairs = legs[0].inventory.min(legs[1].inventory);
+        if pairs > Decimal::ZERO {
+            self.clob
+                .merge_full_sets(self.condition_id, [&legs[0].token_id, &legs[1].token_id], pairs, self.neg_risk)
+                .await?;
+            for leg in legs.iter_mut() {
+                leg.inventory -= pairs;
+            }
+        }
+        info!(
+            "🏦 Stopped liquidity on {}: merged {} pairs, left YES {} / NO {}",
+            self.question, pairs, legs[0].inventory, legs[1].inventory
+        );
+        Ok(())
+    }
+}
+
+/// Quotes from `on_start`, requotes on each fill and unwinds on stop
+impl Strategy for TwoSidedLp {
+    fn name(&self) -> &str {
+        "liquidity"
+    }
+
+    fn markets(&self) -> Vec<String> {
+        self.tokens.to_vec()
+    }
+
+    fn on_start<'a>(&'a self, ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(self.start(ctx))
+    }
+
+    fn on_fill<'a>(&'a self, ctx: &'a StrategyContext, fill: &'a Fill) -> StrategyFuture<'a> {
+        Box::pin(self.apply_fill(ctx, fill))
+    }
+
+    fn on_stop<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(self.stop())
+    }
+}
@@ -0,0 +1,383 @@
+use anyhow::Result;
+use log::{info, warn};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use super::fair_value::FairValueSource;
+use super::runtime::{Strategy, StrategyContext, StrategyFuture};
+use crate::domain::order::Side;
+use crate::execution::orderbook::OrderBook;
+use crate::portfolio::Fill;
+use crate::wallet::order_builder::MarketRules;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// REFERENCE MARKET MAKER
+// ==================================================
+// Rests a ladder of post-only bids and asks on one outcome token around
+// a fair value, skewed by inventory:
+//
+//   center  = fair - skew × inventory / max_inventory
+//   bid[i]  = center - half_spread - i × level_step   (rounded down)
+//   ask[i]  = center + half_spread + i × level_step   (rounded up)
+//   size[i] = level_size × size_growth^i
+//
+// Long inventory lowers both sides, so asks get hit and bids don't.
+// Bids stop at max_inventory; asks never offer more than is held.
+// Quotes that moved are amended with cancel/replace. With a shared
+// inventory controller its bias is added to the center too.
+//
+// Fair value is the book mid unless a `FairValueSource` is attached;
+// then quotes are pulled whenever the source has no fresh value.
+
+#[derive(Debug, Clone)]
+pub struct MarketMakerConfig {
+    pub half_spread: f64,
+    /// Quotes per side
+    pub levels: usize,
+    /// Size of the innermost quote
+    pub level_size: Decimal,
+    /// Price distance between levels
+    pub level_step: f64,
+    /// Size multiplier from one level to the next
+    pub size_growth: Decimal,
+    pub max_inventory: Decimal,
+    /// Center shift at max_inventory
+    pub skew: f64,
+    /// Leave a quote alone unless its target moved at least this much
+    pub requote_threshold: f64,
+    /// Minimum time between requotes
+    pub min_requote: Duration,
+}
+
+impl Default for MarketMakerConfig {
+    fn default() -> Self {
+        Self {
+            half_spread: 0.02,
+            levels: 3,
+            level_size: dec!(20),
+            level_step: 0.01,
+            size_growth: Decimal::ONE,
+            max_inventory: dec!(200),
+            skew: 0.02,
+            requote_threshold: 0.005,
+            min_requote: Duration::from_secs(1),
+        }
+    }
+}
+
+impl MarketMakerConfig {
+    /// `MM_HALF_SPREAD`, `MM_LEVELS`, `MM_LEVEL_SIZE`, `MM_LEVEL_STEP`,
+    /// `MM_SIZE_GROWTH`, `MM_MAX_INVENTORY`, `MM_SKEW`,
+    /// `MM_REQUOTE_THRESHOLD`, `MM_MIN_REQUOTE_MS`
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Same variables as `from_env`, looked up through `lookup` (e.g. a
+    /// walk-forward parameter set over the environment)
+    pub fn from_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let d = Self::default();
+        fn var<T: std::str::FromStr>(lookup: &dyn Fn(&str) -> Option<String>, name: &str) -> Option<T> {
+            lookup(name).and_then(|v| v.parse().ok())
+        }
+        Self {
+            half_spread: var(&lookup, "MM_HALF_SPREAD").unwrap_or(d.half_spread),
+            levels: var(&lookup, "MM_LEVELS").unwrap_or(d.levels),
+            level_size: var(&lookup, "MM_LEVEL_SIZE").unwrap_or(d.level_size),
+            level_step: var(&lookup, "MM_LEVEL_STEP").unwrap_or(d.level_step),
+            size_growth: var(&lookup, "MM_SIZE_GROWTH").unwrap_or(d.size_growth),
+            max_inventory: var(&lookup, "MM_MAX_INVENTORY").unwrap_or(d.max_inventory),
+            skew: var(&lookup, "MM_SKEW").unwrap_or(d.skew),
+            requote_threshold: var(&lookup, "MM_REQUOTE_THRESHOLD").unwrap_or(d.requote_threshold),
+            min_requote: var(&lookup, "MM_MIN_REQUOTE_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(d.min_requote),
+        }
+    }
+}
+
+/// Price and size one ladder slot should rest at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteTarget {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// Target bids and asks, innermost first; `None` where a level
+/// shouldn't be quoted
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ladder {
+    pub bids: Vec<Option<QuoteTarget>>,
+    pub asks: Vec<Option<QuoteTarget>>,
+}
+
+/// The ladder for `fair` given what we hold
+pub fn ladder(fair: f64, inventory: Decimal, config: &MarketMakerConfig, rules: &MarketRules) -> Ladder {
+    let tick = rules.tick_size;
+    let ratio = match config.max_inventory > Decimal::ZERO {
+        true => (inventory / config.max_inventory).clamp(Decimal::ZERO, Decimal::ONE),
+        false => Decimal::ONE,
+    };
+    let center = fair - config.skew * ratio.to_f64().unwrap_or_default();
+
+    let mut bid_room = (config.max_inventory - inventory).max(Decimal::ZERO);
+    let mut ask_room = inventory.max(Decimal::ZERO);
+    let mut size = config.level_size;
+    let mut out = Ladder::default();
+
+    for i in 0..config.levels {
+        let offset = config.half_spread + i as f64 * config.level_step;
+        let slot = |target: f64, room: &mut Decimal, round_up: bool| {
+            let price = Decimal::from_f64(target)?;
+            let price = match round_up {
+                true => (price / tick).ceil() * tick,
+                false => (price / tick).floor() * tick,
+            };
+            let take = size.min(*room).round_dp_with_strategy(2, rust_decimal::RoundingStrategy::ToZero);
+            if price < tick || price > Decimal::ONE - tick || take.is_zero() || take < rules.min_size {
+                return None;
+            }
+            *room -= take;
+            Some(QuoteTarget { price, size: take })
+        };
+        out.bids.push(slot(center - offset, &mut bid_room, false));
+        out.asks.push(slot(center + offset, &mut ask_room, true));
+        size *= config.size_growth;
+    }
+    out
+}
+
+#[derive(Debug, Clone)]
+struct Quote {
+    order_id: String,
+    price: Decimal,
+    size: Decimal,
+}
+
+#[derive(Default)]
+struct Quotes {
+    bids: Vec<Option<Quote>>,
+    asks: Vec<Option<Quote>>,
+}
+
+/// Quotes one outcome token from the live book
+pub struct MarketMaker {
+    token_id: String,
+    config: MarketMakerConfig,
+    fair_values: Option<Arc<dyn FairValueSource>>,
+    inventory: Mutex<Decimal>,
+    quotes: Mutex<Quotes>,
+    // ms, context clock
+    last_requote: Mutex<Option<u64>>,
+}
+
+impl MarketMaker {
+    pub fn new(token_id: impl Into<String>, config: MarketMakerConfig) -> Self {
+        Self {
+            token_id: token_id.into(),
+            config,
+            fair_values: None,
+            inventory: Mutex::new(Decimal::ZERO),
+            quotes: Mutex::new(Quotes::default()),
+            last_requote: Mutex::new(None),
+        }
+    }
+
+    /// Center quotes on `source` instead of the book mid
+    pub fn fair_values(mut self, source: Arc<dyn FairValueSource>) -> Self {
+        self.fair_values = Some(source);
+        self
+    }
+
+    pub fn config(&self) -> &MarketMakerConfig {
+        &self.config
+    }
+
+    pub async fn inventory(&self) -> Decimal {
+        *self.inventory.lock().await
+    }
+
+    /// Center price before skew: the attached source's value, else the
+    /// book mid
+    pub async fn fair_value(&self, book: &OrderBook) -> Option<f64> {
+        match &self.fair_values {
+            Some(source) => source.fair_value(&self.token_id).await,
+            None => book.mid(),
+        }
+    }
+
+    async fn requote(&self, ctx: &StrategyContext, book: &OrderBook) -> Result<()> {
+        {
+            let mut last = self.last_requote.lock().await;
+            if last.is_some_and(|t| ctx.clock().elapsed(t) < self.config.min_requote) {
+                return Ok(());
+            }
+            *last = Some(ctx.now_ms());
+        }
+        let target = match self.fair_value(book).await {
+            Some(fair) => {
+                let fair = fair + ctx.inventory_bias(&self.token_id).await;
+                let rules = ctx.clob().market_rules(&self.token_id).await?;
+                ladder(fair, self.inventory().await, &self.config, &rules)
+            }
+            // No fresh model value: pull every quote
+            None if self.fair_values.is_some() => Ladder::default(),
+            None => return Ok(()),
+        };
+
+        let mut quotes = self.quotes.lock().await;
+        let Quotes { bids, asks } = &mut *quotes;
+        self.sync_side(ctx, Side::Buy, bids, &target.bids).await;
+        self.sync_side(ctx, Side::Sell, asks, &target.asks).await;
+        Ok(())
+    }
+
+    /// Bring one side's resting quotes to `targets`, slot by slot
+    async fn sync_side(
+        &self,
+        ctx: &StrategyContext,
+        side: Side,
+        current: &mut Vec<Option<Quote>>,
+        targets: &[Option<QuoteTarget>],
+    ) {
+        current.resize(targets.len().max(current.len()), None);
+        for (i, slot) in current.iter_mut().enumerate() {
+            let target = targets.get(i).copied().flatten();
+            *slot = match (slot.take(), target) {
+                (Some(q), Some(t)) if self.is_close(&q, &t) => Some(q),
+                (Some(q), Some(t)) => match ctx.replace_post_only(&q.order_id, t.price, t.size).await {
+                    Ok(report) => report.new_order_id.map(|order_id| Quote {
+                        order_id,
+                        price: t.price,
+                        size: report.new_size,
+                    }),
+                    Err(e) => {
+                        warn!("⚠️  MM: requote of {} failed: {}", q.order_id, e);
+                        self.if_resting(ctx, q).await
+                    }
+                },
+                (Some(q), None) => match ctx.cancel_order(&q.order_id).await {
+                    Ok(()) => None,
+                    Err(e) => {
+                        warn!("⚠️  MM: cancel of {} failed: {}", q.order_id, e);
+                        self.if_resting(ctx, q).await
+                    }
+                },
+                (None, Some(t)) => match ctx
+                    .place_post_only(&self.token_id, side.clone(), t.price, t.size, OrderType::Gtc)
+                    .await
+                {
+                    Ok(order_id) => Some(Quote {
+                        order_id,
+                        price: t.price,
+                        size: t.size,
+                    }),
+                    Err(e) => {
+                        warn!("⚠️  MM: {} {} @ {} not placed: {}", side.as_str(), t.size, t.price, e);
+                        None
+                    }
+                },
+                (None, None) => None,
+            };
+        }
+    }
+
+    fn is_close(&self, quote: &Quote, target: &QuoteTarget) -> bool {
+        let moved = (quote.price - target.price).abs().to_f64().unwrap_or(f64::MAX);
+        moved < self.config.requote_threshold && quote.size == target.size
+    }
+
+    /// Keep tracking a quote whose amend failed while it's still resting
+    async fn if_resting(&self, ctx: &StrategyContext, quote: Quote) -> Option<Quote> {
+        let resting = ctx
+            .clob()
+            .order_intents()
+            .await
+            .iter()
+            .any(|i| i.order_id == quote.order_id);
+        resting.then_some(quote)
+    }
+
+    async fn apply_fill(&self, ctx: &StrategyContext, fill: &Fill) -> Result<()> {
+        if fill.token_id != self.token_id || !ctx.owns(&fill.order_id).await {
+            return Ok(());
+        }
+        {
+            let mut inventory = self.inventory.lock().await;
+            match fill.side {
+                Side::Buy => *inventory += fill.size,
+                Side::Sell => *inventory = (*inventory - fill.size).max(Decimal::ZERO),
+            }
+            info!(
+                "🏪 MM {} {} @ {} (inventory {})",
+                fill.side.as_str(),
+                fill.size,
+                fill.price,
+                inventory
+            );
+        }
+        let mut quotes = self.quotes.lock().await;
+        let Quotes { bids, asks } = &mut *quotes;
+        for slot in bids.iter_mut().chain(asks.iter_mut()) {
+            let Some(q) = slot.as_mut().filter(|q| q.order_id == fill.order_id) else {
+                continue;
+            };
+            q.size -= fill.size;
+            if q.size <= Decimal::ZERO {
+                *slot = None;
+            }
+        }
+        // Requote on the next book update with the new skew
+        *self.last_requote.lock().await = None;
+        Ok(())
+    }
+}
+
+impl Strategy for MarketMaker {
+    fn name(&self) -> &str {
+        "market-maker"
+    }
+
+    fn markets(&self) -> Vec<String> {
+        vec![self.token_id.clone()]
+    }
+
+    /// Start from what the funder already holds (nothing in shadow mode)
+    fn on_start<'a>(&'a self, ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            let held = if ctx.is_shadow() {
+                Decimal::ZERO
+            } else {
+                ctx.clob().token_balance(&self.token_id).await?
+            };
+            *self.inventory.lock().await = held;
+            info!("🏪 Market making {} (inventory {})", self.token_id, held);
+            Ok(())
+        })
+    }
+
+    fn on_book_update<'a>(
+        &'a self,
+        ctx: &'a StrategyContext,
+        _token_id: &'a str,
+        book: &'a OrderBook,
+    ) -> StrategyFuture<'a> {
+        Box::pin(self.requote(ctx, book))
+    }
+
+    fn on_fill<'a>(&'a self, ctx: &'a StrategyContext, fill: &'a Fill) -> StrategyFuture<'a> {
+        Box::pin(self.apply_fill(ctx, fill))
+    }
+
+    fn on_stop<'a>(&'a self, ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            *self.quotes.lock().await = Quotes::default();
+            ctx.cancel_all().await
+        })
+    }
+}
@@ -0,0 +1,245 @@
+use anyhow::{anyhow, Result};
+use log::{debug, info};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use super::runtime::{Strategy, StrategyContext, StrategyFuture};
+use crate::domain::order::Side;
+use crate::execution::orderbook::OrderBook;
+use crate::market_ws::signals::microprice;
+use crate::portfolio::Fill;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// MEAN REVERSION AROUND MICROPRICE
+// ==================================================
+// A trade printing well below the microprice is usually an impatient
+// seller, not new information; the book tends to pull the price back:
+//
+//   microprice - last trade ≥ MEANREV_THRESHOLD  →  buy MEANREV_SIZE, FAK
+//                                                   at microprice - MEANREV_MIN_EDGE
+//   last trade ≥ microprice, or held MEANREV_MAX_HOLD_SECS  →  sell at the bid
+//
+// Long only, one position per token, MEANREV_COOLDOWN_SECS between
+// orders. Signals are logged at debug level for checking the plumbing.
+
+#[derive(Debug, Clone)]
+pub struct MeanReversionConfig {
+    pub tokens: Vec<String>,
+    /// Dislocation (microprice - last trade) that triggers an entry
+    pub threshold: f64,
+    /// Entry limit sits this far below the microprice
+    pub min_edge: f64,
+    pub size: Decimal,
+    pub max_hold: Duration,
+    pub cooldown: Duration,
+}
+
+impl Default for MeanReversionConfig {
+    fn default() -> Self {
+        Self {
+            tokens: Vec::new(),
+            threshold: 0.02,
+            min_edge: 0.005,
+            size: dec!(20),
+            max_hold: Duration::from_secs(300),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl MeanReversionConfig {
+    /// `MEANREV_TOKENS` (comma-separated), `MEANREV_THRESHOLD`,
+    /// `MEANREV_MIN_EDGE`, `MEANREV_SIZE`, `MEANREV_MAX_HOLD_SECS`,
+    /// `MEANREV_COOLDOWN_SECS`
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Same variables as `from_env`, looked up through `lookup`
+    pub fn from_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let d = Self::default();
+        fn var<T: std::str::FromStr>(lookup: &dyn Fn(&str) -> Option<String>, name: &str) -> Option<T> {
+            lookup(name).and_then(|v| v.parse().ok())
+        }
+        Self {
+            tokens: lookup("MEANREV_TOKENS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect(),
+            threshold: var(&lookup, "MEANREV_THRESHOLD").unwrap_or(d.threshold),
+            min_edge: var(&lookup, "MEANREV_MIN_EDGE").unwrap_or(d.min_edge),
+            size: var(&lookup, "MEANREV_SIZE").unwrap_or(d.size),
+            max_hold: var(&lookup, "MEANREV_MAX_HOLD_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(d.max_hold),
+            cooldown: var(&lookup, "MEANREV_COOLDOWN_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(d.cooldown),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct TokenState {
+    // First fill of the open position (ms, context clock)
+    entered: Option<u64>,
+    // Our fills arrive after the order; don't act twice meanwhile
+    last_order: Option<u64>,
+}
+
+/// Fades last-trade dislocations from the microprice
+pub struct MeanReversion {
+    config: MeanReversionConfig,
+    state: Mutex<HashMap<String, TokenState>>,
+}
+
+impl MeanReversion {
+    pub fn new(config: MeanReversionConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(MeanReversionConfig::from_env())
+    }
+
+    pub fn config(&self) -> &MeanReversionConfig {
+        &self.config
+    }
+
+    async fn evaluate(&self, ctx: &StrategyContext, token_id: &str, book: &OrderBook) -> Result<()> {
+        let (Some(local), Some(last)) = (
+            ctx.books().get(token_id).await,
+            ctx.trades().last_price(token_id).await,
+        ) else {
+            return Ok(());
+        };
+        let Some(micro) = microprice(&local) else {
+            return Ok(());
+        };
+        let dislocation = micro - last;
+        debug!(
+            "📉 {} micro {:.4} last {:.4} dislocation {:+.4}",
+            token_id, micro, last, dislocation
+        );
+
+        let held = ctx.held(token_id).await;
+        let mut state = self.state.lock().await;
+        let state = state.entry(token_id.to_string()).or_default();
+        if state.last_order.is_some_and(|t| ctx.clock().elapsed(t) < self.config.cooldown) {
+            return Ok(());
+        }
+
+        if held > Decimal::ZERO {
+            let reverted = last >= micro;
+            let expired = state.entered.is_some_and(|t| ctx.clock().elapsed(t) >= self.config.max_hold);
+            if !(reverted || expired) {
+                return Ok(());
+            }
+            let Some(bid) = book.best_bid() else {
+                return Ok(());
+            };
+            let limit = decimal(bid.price)?;
+            state.last_order = Some(ctx.now_ms());
+            let order_id = ctx
+                .place_order(token_id, Side::Sell, limit, held, OrderType::Fak)
+                .await?;
+            info!(
+                "📉 Mean reversion exit {} of {} at {} ({}): {}",
+                held,
+                token_id,
+                limit,
+                if reverted { "reverted" } else { "max hold" },
+                order_id
+            );
+            return Ok(());
+        }
+
+        if dislocation < self.config.threshold {
+            return Ok(());
+        }
+        let rules = ctx.clob().market_rules(token_id).await?;
+        let limit = decimal(micro - self.config.min_edge)?;
+        let limit = (limit / rules.tick_size).floor() * rules.tick_size;
+        if limit < rules.tick_size {
+            return Ok(());
+        }
+        state.last_order = Some(ctx.now_ms());
+        let order_id = ctx
+            .place_order(token_id, Side::Buy, limit, self.config.size, OrderType::Fak)
+            .await?;
+        info!(
+            "📉 Mean reversion entry {} of {} at {} (micro {:.4}, last {:.4}): {}",
+            self.config.size, token_id, limit, micro, last, order_id
+        );
+        Ok(())
+    }
+
+    async fn apply_fill(&self, ctx: &StrategyContext, fill: &Fill) -> Result<()> {
+        if !ctx.owns(&fill.order_id).await {
+            return Ok(());
+        }
+        let held = ctx.held(&fill.token_id).await;
+        let mut state = self.state.lock().await;
+        let state = state.entry(fill.token_id.clone()).or_default();
+        match (held > Decimal::ZERO, state.entered) {
+            (true, None) => state.entered = Some(ctx.now_ms()),
+            (false, _) => state.entered = None,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Strategy for MeanReversion {
+    fn name(&self) -> &str {
+        "mean-reversion"
+    }
+
+    fn markets(&self) -> Vec<String> {
+        self.config.tokens.clone()
+    }
+
+    fn on_start<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            if self.config.tokens.is_empty() {
+                return Err(anyhow!("no MEANREV_TOKENS configured"));
+            }
+            info!(
+                "📉 Mean reversion on {} token(s), threshold {}",
+                self.config.tokens.len(),
+                self.config.threshold
+            );
+            Ok(())
+        })
+    }
+
+    fn on_book_update<'a>(
+        &'a self,
+        ctx: &'a StrategyContext,
+        token_id: &'a str,
+        book: &'a OrderBook,
+    ) -> StrategyFuture<'a> {
+        Box::pin(self.evaluate(ctx, token_id, book))
+    }
+
+    fn on_fill<'a>(&'a self, ctx: &'a StrategyContext, fill: &'a Fill) -> StrategyFuture<'a> {
+        Box::pin(self.apply_fill(ctx, fill))
+    }
+}
+
+fn decimal(v: f64) -> Result<Decimal> {
+    Decimal::from_f64(v)
+        .map(|d| d.round_dp(4))
+        .ok_or_else(|| anyhow!("Invalid number {}", v))
+}
@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::time::Duration;
+
+use super::runtime::{Strategy, StrategyContext, StrategyFuture};
+use crate::domain::order::Side;
+use crate::execution::ClobClient;
+use crate::history::Candle;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// MOMENTUM (MOVING-AVERAGE CROSS)
+// ==================================================
+// Directional reference strategy on closed candles of outcome prices:
+//
+//   fast SMA crosses above slow SMA by MOMENTUM_MIN_GAP  →  buy MOMENTUM_SIZE
+//   fast SMA crosses back below slow SMA                 →  sell what we hold
+//
+// Long only: to bet against an outcome, list its complement token.
+// Entries and exits are FAK up to MOMENTUM_MAX_SLIPPAGE past the touch.
+
+#[derive(Debug, Clone)]
+pub struct MomentumConfig {
+    pub tokens: Vec<String>,
+    /// Candle length
+    pub interval: Duration,
+    /// Candles in the fast and slow averages
+    pub fast: usize,
+    pub slow: usize,
+    /// Required separation of the averages on entry
+    pub min_gap: f64,
+    /// Tokens bought per entry
+    pub size: Decimal,
+    pub max_slippage: f64,
+}
+
+impl Default for MomentumConfig {
+    fn default() -> Self {
+        Self {
+            tokens: Vec::new(),
+            interval: Duration::from_secs(300),
+            fast: 5,
+            slow: 20,
+            min_gap: 0.005,
+            size: dec!(20),
+            max_slippage: 0.02,
+        }
+    }
+}
+
+impl MomentumConfig {
+    /// `MOMENTUM_TOKENS` (comma-separated), `MOMENTUM_INTERVAL_SECS`,
+    /// `MOMENTUM_FAST`, `MOMENTUM_SLOW`, `MOMENTUM_MIN_GAP`,
+    /// `MOMENTUM_SIZE`, `MOMENTUM_MAX_SLIPPAGE`
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+        Self {
+            tokens: std::env::var("MOMENTUM_TOKENS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect(),
+            interval: var("MOMENTUM_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(d.interval),
+            fast: var("MOMENTUM_FAST").unwrap_or(d.fast),
+            slow: var("MOMENTUM_SLOW").unwrap_or(d.slow),
+            min_gap: var("MOMENTUM_MIN_GAP").unwrap_or(d.min_gap),
+            size: var("MOMENTUM_SIZE").unwrap_or(d.size),
+            max_slippage: var("MOMENTUM_MAX_SLIPPAGE").unwrap_or(d.max_slippage),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// Fast average just crossed above the slow one
+    Enter,
+    /// Fast average just crossed below the slow one
+    Exit,
+    Hold,
+}
+
+/// Mean of the last `n` values, `None` if there are fewer
+fn sma(values: &[f64], n: usize) -> Option<f64> {
+    (n > 0 && values.len() >= n).then(|| values[values.len() - n..].iter().sum::<f64>() / n as f64)
+}
+
+/// Moving-average cross on `closes` (oldest first), comparing the last
+/// bar with the one before
+pub fn signal(closes: &[f64], fast: usize, slow: usize, min_gap: f64) -> Signal {
+    let Some(prev) = closes.len().checked_sub(1).map(|n| &closes[..n]) else {
+        return Signal::Hold;
+    };
+    let (Some(fast_now), Some(slow_now), Some(fast_prev), Some(slow_prev)) =
+        (sma(closes, fast), sma(closes, slow), sma(prev, fast), sma(prev, slow))
+    else {
+        return Signal::Hold;
+    };
+    if fast_prev <= slow_prev && fast_now > slow_now + min_gap {
+        Signal::Enter
+    } else if fast_prev >= slow_prev && fast_now < slow_now {
+        Signal::Exit
+    } else {
+        Signal::Hold
+    }
+}
+
+/// Trades moving-average crosses on a fixed set of tokens
+pub struct Momentum {
+    config: MomentumConfig,
+}
+
+impl Momentum {
+    pub fn new(config: MomentumConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(MomentumConfig::from_env())
+    }
+
+    pub fn config(&self) -> &MomentumConfig {
+        &self.config
+    }
+
+    /// Closed candles covering the slow average and one bar before it
+    async fn closed_candles(&self, ctx: &StrategyContext, token_id: &str) -> Result<Vec<Candle>> {
+        let interval = self.config.interval;
+        let lookback = interval * (self.config.slow as u32 + 3);
+        let mut candles = ctx.clob().candles(token_id, lookback, interval).await?;
+        let now = ctx.now_ms() / 1000;
+        // The current bucket is still forming
+        candles.retain(|c| c.start + interval.as_secs() <= now);
+        Ok(candles)
+    }
+
+    async fn step(&self, ctx: &StrategyContext, token_id: &str) -> Result<()> {
+        let candles = self.closed_candles(ctx, token_id).await?;
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let held = ctx.held(token_id).await;
+
+        match signal(&closes, self.config.fast, self.config.slow, self.config.min_gap) {
+            Signal::Enter if held.is_zero() => self.trade(ctx, token_id, Side::Buy, self.config.size).await,
+            Signal::Exit if held > Decimal::ZERO => self.trade(ctx, token_id, Side::Sell, held).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// FAK at most `max_slippage` past the touch
+    async fn trade(&self, ctx: &StrategyContext, token_id: &str, side: Side, size: Decimal) -> Result<()> {
+        let book = ctx.clob().get_orderbook(token_id).await?;
+        let touch = ClobClient::best_price(&book, &side)?;
+        let limit = match side {
+            Side::Buy => (touch + self.config.max_slippage).min(0.99),
+            Side::Sell => (touch - self.config.max_slippage).max(0.01),
+        };
+        let limit = Decimal::from_f64(limit)
+            .ok_or_else(|| anyhow!("bad price {}", limit))?
+            .round_dp(2);
+        let order_id = ctx
+            .place_order(token_id, side.clone(), limit, size, OrderType::Fak)
+            .await?;
+        info!(
+            "📈 Momentum {} {} of {} (limit {}): {}",
+            side.as_str(),
+            size,
+            token_id,
+            limit,
+            order_id
+        );
+        Ok(())
+    }
+}
+
+/// Checks every token once per candle
+impl Strategy for Momentum {
+    fn name(&self) -> &str {
+        "momentum"
+    }
+
+    fn timer(&self) -> Option<Duration> {
+        Some(self.config.interval)
+    }
+
+    fn on_start<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            if self.config.tokens.is_empty() {
+                return Err(anyhow!("no MOMENTUM_TOKENS configured"));
+            }
+            if self.config.fast >= self.config.slow {
+                return Err(anyhow!(
+                    "MOMENTUM_FAST ({}) must be shorter than MOMENTUM_SLOW ({})",
+                    self.config.fast,
+                    self.config.slow
+                ));
+            }
+            info!(
+                "📈 Momentum on {} token(s): SMA {}/{} of {}s candles",
+                self.config.tokens.len(),
+                self.config.fast,
+                self.config.slow,
+                self.config.interval.as_secs()
+            );
+            Ok(())
+        })
+    }
+
+    fn on_timer<'a>(&'a self, ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async move {
+            for token_id in &self.config.tokens {
+                if let Err(e) = self.step(ctx, token_id).await {
+                    warn!("⚠️  Momentum on {} failed: {}", token_id, e);
+                }
+            }
+            Ok(())
+        })
+    }
+}
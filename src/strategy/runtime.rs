@@ -0,0 +1,838 @@
+use anyhow::{anyhow, Result};
+use futures_util::future::BoxFuture;
+use log::{info, warn};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::Mutex;
+
+use crate::domain::order::Side;
+use crate::execution::clob_client::ReplaceReport;
+use crate::execution::orderbook::OrderBook;
+use crate::execution::ClobClient;
+use crate::market_ws::{MarketBooks, MarketTrades, MarketWs};
+use crate::markets::ResolutionEvent;
+use crate::net::{ConnectionEvent, Connections};
+use super::clock::{Clock, SystemClock};
+use super::costs::CostModel;
+use super::latency::LatencyModel;
+use super::sim::{SimOrder, SimVenue};
+use crate::portfolio::{Fill, InventoryController, PnlEngine, PnlLine};
+use crate::execution::intents::OrderIntent;
+use crate::risk::{headroom, Exposure, OrderRequest, RiskDecision, RiskManager};
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// STRATEGY RUNTIME
+// ==================================================
+// Strategies react to events; the runner owns the feeds and hosts each
+// strategy in its own task:
+//
+//   book update on one of its markets  →  on_book_update
+//   fill on one of its markets         →  on_fill
+//   its timer                          →  on_timer
+//   resolution of one of its markets   →  on_resolution
+//
+// Handlers of one strategy never overlap; different strategies run
+// concurrently. A failing handler is logged and the strategy carries on.
+//
+// A strategy hosted in shadow mode runs the same way, but its orders go
+// to a `SimVenue` matching them against the live books: it sees only
+// simulated fills, and its PnL is logged next to the live instance of
+// the same strategy every SHADOW_REPORT_SECS. Only orders placed
+// through the context are simulated; strategies holding their own
+// client (complement-arb, kalshi-arb, two-sided LP) must not be
+// shadowed.
+
+pub type StrategyFuture<'a> = BoxFuture<'a, Result<()>>;
+
+/// An event-driven trading strategy. Every handler defaults to doing
+/// nothing; state that handlers change lives behind the strategy's own
+/// locks.
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Outcome tokens whose books, fills and resolutions reach this
+    /// strategy. Fixed for the life of the runner.
+    fn markets(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// How often `on_timer` fires (`None`: never)
+    fn timer(&self) -> Option<Duration> {
+        None
+    }
+
+    fn on_start<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn on_book_update<'a>(
+        &'a self,
+        _ctx: &'a StrategyContext,
+        _token_id: &'a str,
+        _book: &'a OrderBook,
+    ) -> StrategyFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn on_fill<'a>(&'a self, _ctx: &'a StrategyContext, _fill: &'a Fill) -> StrategyFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn on_timer<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn on_resolution<'a>(&'a self, _ctx: &'a StrategyContext, _event: &'a ResolutionEvent) -> StrategyFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// A feed connected, dropped or came back (`event.resumed`: books
+    /// are degraded until resynced, and fills may have been missed)
+    fn on_connection<'a>(&'a self, _ctx: &'a StrategyContext, _event: &'a ConnectionEvent) -> StrategyFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// The feeds closed; pull quotes and unwind what should be unwound
+    fn on_stop<'a>(&'a self, _ctx: &'a StrategyContext) -> StrategyFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Caps on what one strategy may hold through its context, on top of
+/// the client-wide risk manager
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrategyBudget {
+    /// Tokens of any one outcome
+    pub max_position: Option<Decimal>,
+    /// Long holdings at cost plus resting buys, across its markets
+    pub max_notional: Option<Decimal>,
+}
+
+impl StrategyBudget {
+    /// `STRATEGY_<NAME>_MAX_POSITION`, `STRATEGY_<NAME>_MAX_NOTIONAL`,
+    /// with the name upper-cased and `-` as `_` (e.g.
+    /// `STRATEGY_COMPLEMENT_ARB_MAX_NOTIONAL`)
+    pub fn from_env(name: &str) -> Self {
+        let prefix = format!("STRATEGY_{}", name.to_uppercase().replace(['-', ' '], "_"));
+        let var = |suffix: &str| {
+            std::env::var(format!("{}_{}", prefix, suffix))
+                .ok()
+                .and_then(|v| v.parse().ok())
+        };
+        Self {
+            max_position: var("MAX_POSITION"),
+            max_notional: var("MAX_NOTIONAL"),
+        }
+    }
+}
+
+/// A strategy's handle on the exchange: orders placed through it count
+/// against its budget and their fills against its holdings
+pub struct StrategyContext {
+    name: String,
+    clob: Arc<ClobClient>,
+    books: Arc<MarketBooks>,
+    trades: Arc<MarketTrades>,
+    budget: StrategyBudget,
+    inventory: Option<Arc<InventoryController>>,
+    // Order ids placed through this context
+    orders: Mutex<HashSet<String>>,
+    // Ours but cancelled or replaced: a fill that beat the cancel still counts
+    retired: Mutex<HashSet<String>>,
+    // Long holdings per token from our fills, at cost
+    held: Mutex<HashMap<String, Exposure>>,
+    // PnL of our fills
+    pnl: Mutex<PnlEngine>,
+    // Set in shadow mode: orders are simulated, never sent
+    sim: Option<Arc<SimVenue>>,
+    // Wall clock live, recorded time in a backtest
+    clock: Arc<dyn Clock>,
+    // Checks simulated orders; live ones go through the client's own
+    risk: Option<Arc<RiskManager>>,
+}
+
+impl StrategyContext {
+    pub fn new(name: &str, clob: Arc<ClobClient>, books: Arc<MarketBooks>, budget: StrategyBudget) -> Self {
+        Self {
+            name: name.to_string(),
+            clob,
+            books,
+            trades: Arc::new(MarketTrades::from_env()),
+            budget,
+            inventory: None,
+            orders: Mutex::new(HashSet::new()),
+            retired: Mutex::new(HashSet::new()),
+            held: Mutex::new(HashMap::new()),
+            pnl: Mutex::new(PnlEngine::from_env()),
+            sim: None,
+            clock: Arc::new(SystemClock),
+            risk: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn clob(&self) -> &Arc<ClobClient> {
+        &self.clob
+    }
+
+    /// Live books of the runner's markets
+    pub fn books(&self) -> &Arc<MarketBooks> {
+        &self.books
+    }
+
+    /// Trade tape of the runner's markets
+    pub fn trades(&self) -> &Arc<MarketTrades> {
+        &self.trades
+    }
+
+    /// Read trades from a shared tape
+    pub fn with_trades(mut self, trades: Arc<MarketTrades>) -> Self {
+        self.trades = trades;
+        self
+    }
+
+    /// Shadow mode: match orders on `sim` against the live books
+    /// instead of placing them
+    pub fn with_sim(mut self, sim: Arc<SimVenue>) -> Self {
+        self.sim = Some(sim);
+        self
+    }
+
+    /// Read the time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Pass simulated orders through `risk` as the client does live
+    /// ones. Its positions are the caller's to keep in step with the
+    /// simulated fills.
+    pub fn with_risk(mut self, risk: Arc<RiskManager>) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Unix milliseconds on the context's clock; strategies use this
+    /// rather than the OS so backtests replay deterministically
+    pub fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
+    /// Whether orders placed through this context are only simulated
+    pub fn is_shadow(&self) -> bool {
+        self.sim.is_some()
+    }
+
+    pub fn budget(&self) -> StrategyBudget {
+        self.budget
+    }
+
+    /// Share target bands and quote bias with the other strategies
+    pub fn with_inventory(mut self, inventory: Arc<InventoryController>) -> Self {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    /// The runner's shared inventory controller, if any
+    pub fn inventory(&self) -> Option<&Arc<InventoryController>> {
+        self.inventory.as_ref()
+    }
+
+    /// Quote shift the inventory controller wants on `token_id` (zero
+    /// without one)
+    pub async fn inventory_bias(&self, token_id: &str) -> f64 {
+        match &self.inventory {
+            Some(inventory) => inventory.bias(token_id).await,
+            None => 0.0,
+        }
+    }
+
+    /// Whether `order_id` was placed through this context
+    pub async fn owns(&self, order_id: &str) -> bool {
+        self.orders.lock().await.contains(order_id) || self.retired.lock().await.contains(order_id)
+    }
+
+    /// Holdings from our fills plus our resting buys: (per token, total)
+    pub async fn exposure(&self) -> (HashMap<String, Exposure>, Exposure) {
+        let mut per_token = self.held.lock().await.clone();
+        for order in self.resting().await {
+            if order.side == Side::Buy {
+                per_token.entry(order.token_id).or_default().add(order.size, order.price);
+            }
+        }
+        let mut total = Exposure::default();
+        for e in per_token.values() {
+            total.size += e.size;
+            total.notional += e.notional;
+        }
+        (per_token, total)
+    }
+
+    /// Our resting orders, from the order intents or the simulation
+    async fn resting(&self) -> Vec<SimOrder> {
+        if let Some(sim) = &self.sim {
+            return sim.resting().await;
+        }
+        let ours = self.orders.lock().await.clone();
+        self.clob
+            .order_intents()
+            .await
+            .into_iter()
+            .filter(|i| ours.contains(&i.order_id))
+            .filter_map(|i| {
+                Some(SimOrder {
+                    side: if i.side == "BUY" { Side::Buy } else { Side::Sell },
+                    price: Decimal::from_f64_retain(i.price)?,
+                    size: Decimal::from_f64_retain(i.size)?,
+                    order_id: i.order_id,
+                    token_id: i.token_id,
+                })
+            })
+            .collect()
+    }
+
+    /// Tokens of `token_id` this strategy holds from its own fills
+    pub async fn held(&self, token_id: &str) -> Decimal {
+        self.held
+            .lock()
+            .await
+            .get(token_id)
+            .map(|e| e.size)
+            .unwrap_or_default()
+    }
+
+    /// PnL of this strategy's fills, open holdings marked at the mid
+    pub async fn pnl(&self) -> PnlLine {
+        let marks = self.marks().await;
+        self.pnl.lock().await.total(&marks)
+    }
+
+    /// Mid of every token this strategy holds, where there's a book
+    pub async fn marks(&self) -> HashMap<String, f64> {
+        let mut marks = HashMap::new();
+        for token_id in self.held.lock().await.keys() {
+            if let Some(mid) = self.books.get(token_id).await.and_then(|b| b.mid()) {
+                marks.insert(token_id.clone(), mid.to_f64().unwrap_or_default());
+            }
+        }
+        marks
+    }
+
+    /// The engine behind `pnl`, e.g. for a loss breaker
+    pub fn pnl_engine(&self) -> &Mutex<PnlEngine> {
+        &self.pnl
+    }
+
+    /// Fills made on our simulated orders since the last call
+    pub(crate) async fn take_sim_fills(&self) -> Vec<Fill> {
+        match &self.sim {
+            Some(sim) => sim.take_fills().await,
+            None => Vec::new(),
+        }
+    }
+
+    /// Live book a simulated order is matched against
+    async fn sim_book(&self, token_id: &str) -> Result<OrderBook> {
+        self.books
+            .order_book(token_id)
+            .await
+            .ok_or_else(|| anyhow!("No live book for {} to simulate against", token_id))
+    }
+
+    /// Size the risk manager lets a simulated order through at: `size`,
+    /// less when it downsizes, or an error. Live orders are checked by
+    /// the client.
+    async fn sim_risk_size(&self, token_id: &str, side: &Side, price: Decimal, size: Decimal) -> Result<Decimal> {
+        let (Some(risk), Some(sim)) = (&self.risk, &self.sim) else {
+            return Ok(size);
+        };
+        let resting: Vec<OrderIntent> = self
+            .resting()
+            .await
+            .into_iter()
+            .map(|o| OrderIntent {
+                order_id: o.order_id,
+                token_id: o.token_id,
+                side: if o.side == Side::Buy { "BUY" } else { "SELL" }.to_string(),
+                price: o.price.to_f64().unwrap_or_default(),
+                size: o.size.to_f64().unwrap_or_default(),
+                order_type: OrderType::Gtc,
+                created_at: 0,
+            })
+            .collect();
+        let request = OrderRequest {
+            token_id: token_id.to_string(),
+            side: side.clone(),
+            price,
+            size,
+        };
+        match risk.evaluate(&request, &resting).await {
+            RiskDecision::Allow => Ok(size),
+            RiskDecision::Resize(room) => {
+                sim.note_risk(true).await;
+                Ok(room)
+            }
+            RiskDecision::Reject(reason) => {
+                sim.note_risk(false).await;
+                Err(anyhow!("🛑 Risk: {}", reason))
+            }
+            RiskDecision::Halt(reason) => {
+                sim.note_risk(false).await;
+                self.cancel_all().await?;
+                Err(anyhow!("🛑 Risk: {} (kill switch tripped)", reason))
+            }
+        }
+    }
+
+    /// Refuse a buy that would take this strategy past its budget
+    async fn check_budget(&self, token_id: &str, side: &Side, price: Decimal, size: Decimal) -> Result<()> {
+        if *side == Side::Sell {
+            return Ok(());
+        }
+        let (per_token, total) = self.exposure().await;
+        let token = per_token.get(token_id).cloned().unwrap_or_default();
+        let room = [
+            headroom(&token, price, self.budget.max_position, None),
+            headroom(&total, price, None, self.budget.max_notional),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        match room {
+            Some(room) if room < size => Err(anyhow!(
+                "🧮 Strategy {}: BUY {} of {} exceeds its budget ({} left)",
+                self.name,
+                size,
+                token_id,
+                room.round_dp(2)
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn place_order(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+    ) -> Result<String> {
+        self.check_budget(token_id, &side, price, size).await?;
+        let id = match &self.sim {
+            Some(sim) => {
+                let size = self.sim_risk_size(token_id, &side, price, size).await?;
+                let book = self.sim_book(token_id).await?;
+                sim.place(&book, token_id, side, price, size, order_type).await?
+            }
+            None => self.clob.place_order(token_id, side, price, size, order_type).await?,
+        };
+        self.orders.lock().await.insert(id.clone());
+        Ok(id)
+    }
+
+    pub async fn place_post_only(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+    ) -> Result<String> {
+        self.check_budget(token_id, &side, price, size).await?;
+        let id = match &self.sim {
+            Some(sim) => {
+                let size = self.sim_risk_size(token_id, &side, price, size).await?;
+                let book = self.sim_book(token_id).await?;
+                sim.place_post_only(&book, token_id, side, price, size, order_type)
+                    .await?
+            }
+            None => {
+                self.clob
+                    .place_post_only(token_id, side, price, size, order_type)
+                    .await?
+            }
+        };
+        self.orders.lock().await.insert(id.clone());
+        Ok(id)
+    }
+
+    /// Move one of our resting quotes (see `ClobClient::replace_post_only`).
+    /// Only growth of a buy is checked against the budget.
+    pub async fn replace_post_only(&self, order_id: &str, price: Decimal, size: Decimal) -> Result<ReplaceReport> {
+        let old = self.resting().await.into_iter().find(|o| o.order_id == order_id);
+        if let Some(old) = old.as_ref().filter(|o| o.side == Side::Buy) {
+            let grows = price * size - old.price * old.size;
+            if grows > Decimal::ZERO && price > Decimal::ZERO {
+                self.check_budget(&old.token_id, &Side::Buy, price, grows / price)
+                    .await?;
+                let allowed = self.sim_risk_size(&old.token_id, &Side::Buy, price, grows / price).await?;
+                if allowed < grows / price {
+                    return Err(anyhow!("🛑 Risk: no room to grow {} to {}", order_id, size));
+                }
+            }
+        }
+        let report = match (&self.sim, &old) {
+            (Some(sim), Some(old)) => {
+                let book = self.sim_book(&old.token_id).await?;
+                sim.replace_post_only(&book, order_id, price, size).await?
+            }
+            (Some(_), None) => return Err(anyhow!("No resting simulated order {}", order_id)),
+            (None, _) => self.clob.replace_post_only(order_id, price, size).await?,
+        };
+        let mut orders = self.orders.lock().await;
+        if orders.remove(order_id) {
+            self.retired.lock().await.insert(order_id.to_string());
+        }
+        if let Some(id) = &report.new_order_id {
+            orders.insert(id.clone());
+        }
+        Ok(report)
+    }
+
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        match &self.sim {
+            Some(sim) => sim.cancel(order_id).await,
+            None => {
+                self.clob.cancel_order(order_id).await?;
+            }
+        }
+        if self.orders.lock().await.remove(order_id) {
+            self.retired.lock().await.insert(order_id.to_string());
+        }
+        Ok(())
+    }
+
+    /// Cancel every order this strategy still has resting
+    pub async fn cancel_all(&self) -> Result<()> {
+        let mut ids: Vec<String> = self.orders.lock().await.drain().collect();
+        // Same order every run
+        ids.sort();
+        self.retired.lock().await.extend(ids.iter().cloned());
+        match &self.sim {
+            Some(sim) => {
+                for id in &ids {
+                    sim.cancel(id).await;
+                }
+            }
+            None => {
+                self.clob.cancel_orders(&ids).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Book a fill of one of our orders against the budget
+    async fn apply_fill(&self, fill: &Fill) {
+        if !self.owns(&fill.order_id).await {
+            return;
+        }
+        self.pnl.lock().await.on_fill(fill);
+        let mut held = self.held.lock().await;
+        let e = held.entry(fill.token_id.clone()).or_default();
+        match fill.side {
+            Side::Buy => e.add(fill.size, fill.price),
+            Side::Sell if e.size > Decimal::ZERO => {
+                let left = (e.size - fill.size).max(Decimal::ZERO);
+                e.notional = e.notional * left / e.size;
+                e.size = left;
+            }
+            Side::Sell => {}
+        }
+    }
+}
+
+struct Hosted {
+    strategy: Arc<dyn Strategy>,
+    ctx: Arc<StrategyContext>,
+    markets: HashSet<String>,
+}
+
+/// Hosts several strategies over shared book, fill and resolution feeds
+pub struct StrategyRunner {
+    clob: Arc<ClobClient>,
+    books: Arc<MarketBooks>,
+    trades: Arc<MarketTrades>,
+    hosted: Vec<Hosted>,
+    resolutions: Option<broadcast::Receiver<ResolutionEvent>>,
+    inventory: Option<Arc<InventoryController>>,
+    connections: Arc<Connections>,
+}
+
+impl StrategyRunner {
+    pub fn new(clob: Arc<ClobClient>) -> Self {
+        Self {
+            clob,
+            books: Arc::new(MarketBooks::new()),
+            trades: Arc::new(MarketTrades::from_env()),
+            hosted: Vec::new(),
+            resolutions: None,
+            inventory: None,
+            connections: Arc::new(Connections::new()),
+        }
+    }
+
+    /// Host `strategy` under `budget`
+    pub fn add(self, strategy: Arc<dyn Strategy>, budget: StrategyBudget) -> Self {
+        self.host(strategy, budget, None)
+    }
+
+    /// Host `strategy` in shadow mode: full signal and order flow, with
+    /// every order simulated against the live books (costs from
+    /// `CostModel::from_env`, latency from `LatencyModel::from_env`)
+    pub fn add_shadow(self, strategy: Arc<dyn Strategy>, budget: StrategyBudget) -> Self {
+        let sim = Arc::new(
+            SimVenue::new(&format!("shadow-{}", strategy.name()))
+                .with_costs(CostModel::from_env())
+                .with_latency(LatencyModel::from_env()),
+        );
+        self.host(strategy, budget, Some(sim))
+    }
+
+    fn host(mut self, strategy: Arc<dyn Strategy>, budget: StrategyBudget, sim: Option<Arc<SimVenue>>) -> Self {
+        let mut ctx = StrategyContext::new(strategy.name(), self.clob.clone(), self.books.clone(), budget)
+            .with_trades(self.trades.clone());
+        if let Some(inventory) = &self.inventory {
+            ctx = ctx.with_inventory(inventory.clone());
+        }
+        if let Some(sim) = sim {
+            ctx = ctx.with_sim(sim);
+        }
+        self.hosted.push(Hosted {
+            markets: strategy.markets().into_iter().collect(),
+            strategy,
+            ctx: Arc::new(ctx),
+        });
+        self
+    }
+
+    /// Host `strategy` under its `STRATEGY_<NAME>_*` budget, in shadow
+    /// mode if `STRATEGY_<NAME>_SHADOW=true`
+    pub fn add_from_env(self, strategy: Arc<dyn Strategy>) -> Self {
+        let budget = StrategyBudget::from_env(strategy.name());
+        let shadow = format!("STRATEGY_{}_SHADOW", strategy.name().to_uppercase().replace(['-', ' '], "_"));
+        if std::env::var(shadow).as_deref() == Ok("true") {
+            self.add_shadow(strategy, budget)
+        } else {
+            self.add(strategy, budget)
+        }
+    }
+
+    /// Share `inventory` with every strategy added after this
+    pub fn inventory(mut self, inventory: Arc<InventoryController>) -> Self {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    /// Deliver resolution status changes (e.g. `ResolutionWatcher::subscribe`)
+    pub fn resolutions(mut self, updates: broadcast::Receiver<ResolutionEvent>) -> Self {
+        self.resolutions = Some(updates);
+        self
+    }
+
+    /// Share connection state with the other feeds: the runner's own
+    /// market feed publishes here, and strategies hear every feed's
+    /// changes
+    pub fn connections(mut self, connections: Arc<Connections>) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hosted.is_empty()
+    }
+
+    /// Every market some strategy trades
+    pub fn markets(&self) -> Vec<String> {
+        let all: HashSet<&String> = self.hosted.iter().flat_map(|h| &h.markets).collect();
+        all.into_iter().cloned().collect()
+    }
+
+    /// Stream the books of every hosted market and run all strategies
+    /// until the fill feed closes (forever without one), then stop them
+    pub async fn run(self, fills: Option<broadcast::Receiver<Fill>>) {
+        let markets = self.markets();
+        if !markets.is_empty() {
+            let ws = MarketWs::from_env(markets, self.books.clone())
+                .with_trades(self.trades.clone())
+                .with_connections(self.connections.clone());
+            tokio::spawn(async move { ws.run().await });
+        }
+        info!("🧠 Running {} strateg(ies)", self.hosted.len());
+        if self.hosted.iter().any(|h| h.ctx.is_shadow()) {
+            let contexts = self.hosted.iter().map(|h| h.ctx.clone()).collect();
+            tokio::spawn(report_shadows(contexts, shadow_report_interval()));
+        }
+
+        let tasks = self.hosted.into_iter().map(|hosted| {
+            let fills = fills.as_ref().map(|f| f.resubscribe());
+            let resolutions = self.resolutions.as_ref().map(|r| r.resubscribe());
+            let connections = self.connections.subscribe();
+            tokio::spawn(run_one(hosted, self.books.subscribe(), fills, resolutions, connections))
+        });
+        futures_util::future::join_all(tasks).await;
+    }
+}
+
+/// `SHADOW_REPORT_SECS` (default 300)
+fn shadow_report_interval() -> Duration {
+    Duration::from_secs(
+        std::env::var("SHADOW_REPORT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(300),
+    )
+}
+
+/// Log each shadow strategy's PnL next to its live namesake
+async fn report_shadows(contexts: Vec<Arc<StrategyContext>>, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        for shadow in contexts.iter().filter(|c| c.is_shadow()) {
+            let pnl = shadow.pnl().await;
+            let live = contexts
+                .iter()
+                .find(|c| !c.is_shadow() && c.name() == shadow.name());
+            let versus = match live {
+                Some(live) => {
+                    let live = live.pnl().await;
+                    format!("live {:+.2} (diff {:+.2})", live.total(), pnl.total() - live.total())
+                }
+                None => "not running live".to_string(),
+            };
+            info!(
+                "🌘 Shadow {}: PnL {:+.2} (realized {:+.2}, holding {}) vs {}",
+                shadow.name(),
+                pnl.total(),
+                pnl.realized,
+                pnl.position,
+                versus
+            );
+        }
+    }
+}
+
+async fn run_one(
+    hosted: Hosted,
+    mut updates: broadcast::Receiver<String>,
+    mut fills: Option<broadcast::Receiver<Fill>>,
+    mut resolutions: Option<broadcast::Receiver<ResolutionEvent>>,
+    mut connections: broadcast::Receiver<ConnectionEvent>,
+) {
+    let Hosted { strategy, ctx, markets } = hosted;
+    let name = strategy.name().to_string();
+    if let Err(e) = strategy.on_start(&ctx).await {
+        warn!("⚠️  Strategy {} failed to start: {}", name, e);
+        return;
+    }
+    let mut timer = strategy.timer().map(tokio::time::interval);
+
+    loop {
+        let next_fill = async {
+            match fills.as_mut() {
+                Some(rx) => rx.recv().await,
+                None => std::future::pending().await,
+            }
+        };
+        let next_resolution = async {
+            match resolutions.as_mut() {
+                Some(rx) => rx.recv().await,
+                None => std::future::pending().await,
+            }
+        };
+        let next_tick = async {
+            match timer.as_mut() {
+                Some(t) => t.tick().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        let handled = tokio::select! {
+            u = updates.recv() => match u {
+                Ok(token) if markets.contains(&token) && !ctx.books.is_degraded(&token).await => {
+                    match ctx.books.order_book(&token).await {
+                        Some(book) => {
+                            if let Some(sim) = &ctx.sim {
+                                sim.on_book(&token, &book).await;
+                            }
+                            strategy.on_book_update(&ctx, &token, &book).await
+                        }
+                        None => Ok(()),
+                    }
+                }
+                Ok(_) => Ok(()),
+                Err(RecvError::Lagged(n)) => Err(anyhow!("missed {} book update(s)", n)),
+                Err(RecvError::Closed) => Ok(()),
+            },
+            f = next_fill => match f {
+                // Real fills aren't ours in shadow mode
+                Ok(_) if ctx.is_shadow() => Ok(()),
+                Ok(fill) if markets.contains(&fill.token_id) || ctx.owns(&fill.order_id).await => {
+                    ctx.apply_fill(&fill).await;
+                    strategy.on_fill(&ctx, &fill).await
+                }
+                Ok(_) => Ok(()),
+                Err(RecvError::Lagged(n)) => Err(anyhow!("missed {} fill(s)", n)),
+                Err(RecvError::Closed) => break,
+            },
+            r = next_resolution => match r {
+                Ok(event) if event.token_ids.iter().any(|t| markets.contains(t)) => {
+                    strategy.on_resolution(&ctx, &event).await
+                }
+                Ok(_) => Ok(()),
+                Err(RecvError::Lagged(n)) => Err(anyhow!("missed {} resolution update(s)", n)),
+                Err(RecvError::Closed) => {
+                    resolutions = None;
+                    Ok(())
+                }
+            },
+            c = connections.recv() => match c {
+                Ok(event) => strategy.on_connection(&ctx, &event).await,
+                Err(RecvError::Lagged(n)) => Err(anyhow!("missed {} connection update(s)", n)),
+                Err(RecvError::Closed) => Ok(()),
+            },
+            _ = next_tick => strategy.on_timer(&ctx).await,
+        };
+        if let Err(e) = handled {
+            warn!("⚠️  Strategy {}: {}", name, e);
+        }
+        deliver_sim_fills(strategy.as_ref(), &ctx).await;
+    }
+
+    if let Err(e) = strategy.on_stop(&ctx).await {
+        warn!("⚠️  Strategy {} failed to stop cleanly: {}", name, e);
+    }
+}
+
+/// Hand simulated fills to the strategy until its handlers stop
+/// producing more; returns what was handed over
+pub(super) async fn deliver_sim_fills(strategy: &dyn Strategy, ctx: &StrategyContext) -> Vec<Fill> {
+    let mut delivered = Vec::new();
+    loop {
+        let fills = ctx.take_sim_fills().await;
+        if fills.is_empty() {
+            return delivered;
+        }
+        for fill in fills {
+            ctx.apply_fill(&fill).await;
+            if let Err(e) = strategy.on_fill(ctx, &fill).await {
+                warn!("⚠️  Strategy {}: {}", strategy.name(), e);
+            }
+            delivered.push(fill);
+        }
+    }
+}
@@ -0,0 +1,494 @@
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::backtest::{Backtest, RiskOutcome};
+use super::costs::CostModel;
+use super::runtime::{Strategy, StrategyBudget};
+use crate::execution::ClobClient;
+use crate::market_ws::book::LocalBook;
+use crate::market_ws::replay::{Replay, ReplayEvent};
+use crate::market_ws::MarketBooks;
+use crate::markets::MetadataCache;
+use crate::portfolio::Positions;
+use crate::risk::{CorrelationGroups, KillSwitch, LossBreaker, MarketLists, RiskLimits, RiskManager};
+use crate::wallet::order_builder::MarketRules;
+
+// ==================================================
+// MONTE CARLO SCENARIOS
+// ==================================================
+// Stresses the strategy and the risk stack with perturbed copies of a
+// recording. Each path draws its own perturbations:
+//
+//   resolution jump        every token resolves somewhere in the last
+//                          stretch of its recording: its book jumps to
+//                          0.99 (won) or 0.01 (lost) and stays there
+//   liquidity evaporation  for a while every book keeps only its best
+//                          levels, at a fraction of their size
+//
+// and is backtested with a fresh risk manager (limits, kill switch,
+// daily loss breaker) whose state files live in the temp dir. Every
+// path is then checked against the limits it ran under:
+//
+//   no buy fills once trading was halted
+//   exposure never above the gross / strategy notional limits
+//   a loss past the daily limit always tripped the breaker
+//
+// How far PnL fell past the loss limit after the trip (positions are
+// held through jumps) is reported but isn't a violation. Paths are
+// seeded, so a run can be repeated exactly.
+
+/// One way of perturbing a recorded path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Perturbation {
+    /// Each token resolves at a random point in the last `window` of its
+    /// recording, won with probability `p_win`
+    ResolutionJump { window: Duration, p_win: f64 },
+    /// With `probability` per path, from a random point for `duration`,
+    /// books keep their best `levels` levels at `keep` of their size
+    LiquidityEvaporation {
+        probability: f64,
+        duration: Duration,
+        keep: f64,
+        levels: usize,
+    },
+}
+
+/// Paths to run and how to perturb them
+#[derive(Debug, Clone)]
+pub struct ScenarioConfig {
+    pub paths: usize,
+    pub seed: u64,
+    pub perturbations: Vec<Perturbation>,
+}
+
+/// One path's result and the checks it failed
+#[derive(Debug, Clone)]
+pub struct PathOutcome {
+    pub path: usize,
+    pub pnl: Decimal,
+    /// Tokens that resolved mid-recording
+    pub jumps: usize,
+    /// Whether liquidity evaporated on this path
+    pub evaporated: bool,
+    pub risk: RiskOutcome,
+    pub violations: Vec<String>,
+}
+
+/// Results of a Monte Carlo run
+#[derive(Debug, Clone, Default)]
+pub struct ScenarioReport {
+    pub strategy: String,
+    pub max_daily_loss: Option<Decimal>,
+    pub paths: Vec<PathOutcome>,
+}
+
+impl ScenarioReport {
+    /// Paths that broke at least one check
+    pub fn failed(&self) -> Vec<&PathOutcome> {
+        self.paths.iter().filter(|p| !p.violations.is_empty()).collect()
+    }
+
+    /// PnL at `q` (0-1) across paths
+    pub fn pnl_quantile(&self, q: f64) -> Decimal {
+        let mut pnl: Vec<Decimal> = self.paths.iter().map(|p| p.pnl).collect();
+        pnl.sort();
+        let Some(last) = pnl.len().checked_sub(1) else {
+            return Decimal::ZERO;
+        };
+        pnl[((last as f64) * q.clamp(0.0, 1.0)).round() as usize]
+    }
+
+    pub fn log(&self) {
+        let n = self.paths.len().max(1) as f64;
+        let share = |keep: &dyn Fn(&PathOutcome) -> bool| {
+            self.paths.iter().filter(|p| keep(p)).count() as f64 / n * 100.0
+        };
+        info!("🎲 Scenarios {}: {} path(s)", self.strategy, self.paths.len());
+        info!(
+            "   PnL worst ${:.2} | p5 ${:.2} | median ${:.2} | best ${:.2}",
+            self.pnl_quantile(0.0),
+            self.pnl_quantile(0.05),
+            self.pnl_quantile(0.5),
+            self.pnl_quantile(1.0)
+        );
+        info!(
+            "   loss breaker tripped {:.0}% | kill switch {:.0}% | liquidity evaporated {:.0}%",
+            share(&|p| p.risk.breaker_tripped_ms.is_some()),
+            share(&|p| p.risk.kill_switch.is_some()),
+            share(&|p| p.evaporated)
+        );
+        if let Some(limit) = self.max_daily_loss {
+            let overshoot = self
+                .paths
+                .iter()
+                .filter(|p| p.risk.breaker_tripped_ms.is_some())
+                .map(|p| -(p.risk.worst_pnl + limit))
+                .max()
+                .unwrap_or_default();
+            info!("   worst loss past the ${} limit after tripping: ${:.2}", limit, overshoot.max(Decimal::ZERO));
+        }
+
+        let failed = self.failed();
+        if failed.is_empty() {
+            info!("   ✅ Every path stayed within its limits");
+            return;
+        }
+        warn!("   🚨 {} path(s) broke a limit", failed.len());
+        for path in failed.iter().take(10) {
+            warn!("      path {}: {}", path.path, path.violations.join("; "));
+        }
+    }
+}
+
+/// Monte Carlo runs of one strategy over perturbed copies of a recording
+pub struct ScenarioEngine {
+    clob: Arc<ClobClient>,
+    replay: Replay,
+    config: ScenarioConfig,
+    limits: RiskLimits,
+    max_daily_loss: Option<Decimal>,
+    budget: Option<StrategyBudget>,
+    rules: Option<MarketRules>,
+    costs: Option<CostModel>,
+}
+
+impl ScenarioEngine {
+    /// Risk limits and the daily loss limit default to the live ones
+    /// (`RiskLimits::from_env`, `RISK_MAX_DAILY_LOSS`)
+    pub fn new(clob: Arc<ClobClient>, replay: Replay, config: ScenarioConfig) -> Self {
+        Self {
+            clob,
+            replay,
+            config,
+            limits: RiskLimits::from_env(),
+            max_daily_loss: LossBreaker::from_env().max_daily_loss(),
+            budget: None,
+            rules: None,
+            costs: None,
+        }
+    }
+
+    pub fn limits(mut self, limits: RiskLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Daily loss limit for the breaker; `None` runs without one
+    pub fn max_daily_loss(mut self, limit: Option<Decimal>) -> Self {
+        self.max_daily_loss = limit;
+        self
+    }
+
+    /// Budget for every path; see `Backtest::budget`
+    pub fn budget(mut self, budget: StrategyBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Rules seeded for every market; see `Backtest::rules`
+    pub fn rules(mut self, rules: MarketRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Fees and slippage for every path; see `Backtest::costs`
+    pub fn costs(mut self, costs: CostModel) -> Self {
+        self.costs = Some(costs);
+        self
+    }
+
+    /// Run every path, building a fresh strategy for each with `build`
+    pub async fn run<F>(&self, build: F) -> Result<ScenarioReport>
+    where
+        F: Fn() -> Result<Arc<dyn Strategy>>,
+    {
+        if self.replay.is_empty() {
+            return Err(anyhow!("Recording is empty"));
+        }
+        let metadata = Arc::new(MetadataCache::from_env()?);
+        let mut report = ScenarioReport {
+            max_daily_loss: self.max_daily_loss,
+            ..Default::default()
+        };
+
+        for path in 0..self.config.paths {
+            let strategy = build()?;
+            report.strategy = strategy.name().to_string();
+            let budget = self
+                .budget
+                .unwrap_or_else(|| StrategyBudget::from_env(strategy.name()));
+
+            let mut rng = StdRng::seed_from_u64(self.config.seed.wrapping_add(path as u64));
+            let perturbed = perturb(self.replay.events(), &self.config.perturbations, &mut rng).await;
+
+            let kill_path = state_path(path, "kill");
+            let loss_path = state_path(path, "loss");
+            let mut risk = RiskManager::new(
+                Arc::new(Positions::new()),
+                metadata.clone(),
+                self.limits.clone(),
+                Arc::new(KillSwitch::new(&kill_path)),
+            )
+            .lists(MarketLists::from_env())
+            .groups(CorrelationGroups::from_env());
+            if self.max_daily_loss.is_some() {
+                risk = risk.loss_breaker(Arc::new(LossBreaker::new(self.max_daily_loss, &loss_path)));
+            }
+
+            let mut backtest = Backtest::new(self.clob.clone(), Replay::new(perturbed.events))
+                .budget(budget)
+                .risk(Arc::new(risk));
+            if let Some(rules) = self.rules {
+                backtest = backtest.rules(rules);
+            }
+            if let Some(costs) = self.costs {
+                backtest = backtest.costs(costs);
+            }
+            let run = backtest.run(strategy).await;
+            let _ = std::fs::remove_file(&kill_path);
+            let _ = std::fs::remove_file(&loss_path);
+            let run = run?;
+
+            let violations = self.check(&run.risk, budget);
+            report.paths.push(PathOutcome {
+                path,
+                pnl: run.pnl.total(),
+                jumps: perturbed.jumps,
+                evaporated: perturbed.evaporated,
+                risk: run.risk,
+                violations,
+            });
+        }
+        Ok(report)
+    }
+
+    /// Limits the path's risk outcome broke
+    fn check(&self, risk: &RiskOutcome, budget: StrategyBudget) -> Vec<String> {
+        let mut violations = Vec::new();
+        if risk.buys_after_halt > 0 {
+            violations.push(format!("{} buy fill(s) after trading was halted", risk.buys_after_halt));
+        }
+        for (limit, name) in [
+            (self.limits.max_gross_notional, "gross notional"),
+            (budget.max_notional, "strategy notional"),
+        ] {
+            if let Some(limit) = limit.filter(|l| risk.peak_exposure > *l) {
+                violations.push(format!("exposure ${:.2} above the {} limit ${}", risk.peak_exposure, name, limit));
+            }
+        }
+        if let Some(limit) = self.max_daily_loss {
+            if risk.worst_pnl <= -limit && risk.breaker_tripped_ms.is_none() {
+                violations.push(format!("lost ${:.2} without tripping the loss breaker", -risk.worst_pnl));
+            }
+        }
+        violations
+    }
+}
+
+fn state_path(path: usize, what: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("scenario-{}-{}-{}", std::process::id(), path, what))
+}
+
+// ==================================================
+// PATH PERTURBATION
+// ==================================================
+
+/// A perturbed copy of a recording
+struct Perturbed {
+    events: Vec<ReplayEvent>,
+    jumps: usize,
+    evaporated: bool,
+}
+
+/// A drawn evaporation window
+struct Drought {
+    from_ms: u64,
+    to_ms: u64,
+    keep: f64,
+    levels: usize,
+}
+
+/// Draw this path's perturbations and rewrite `events` with them. Book
+/// messages touching a perturbed token are replaced by snapshots of the
+/// perturbed book; trades of a resolved token are dropped.
+async fn perturb(events: &[ReplayEvent], perturbations: &[Perturbation], rng: &mut StdRng) -> Perturbed {
+    // First and last receive time of each token
+    let mut spans: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+    for event in events {
+        for token in tokens_of(&event.msg) {
+            let span = spans.entry(token).or_insert((event.recv_ms, event.recv_ms));
+            span.1 = event.recv_ms;
+        }
+    }
+    let (first_ms, last_ms) = (
+        events.iter().map(|e| e.recv_ms).min().unwrap_or_default(),
+        events.iter().map(|e| e.recv_ms).max().unwrap_or_default(),
+    );
+
+    // token → (resolution time, won)
+    let mut jumps: HashMap<String, (u64, bool)> = HashMap::new();
+    let mut droughts = Vec::new();
+    for perturbation in perturbations {
+        match *perturbation {
+            Perturbation::ResolutionJump { window, p_win } => {
+                for (token, (start, end)) in &spans {
+                    let from = end.saturating_sub(window.as_millis() as u64).max(*start);
+                    let at = rng.random_range(from..=*end);
+                    jumps.insert(token.clone(), (at, rng.random_bool(p_win.clamp(0.0, 1.0))));
+                }
+            }
+            Perturbation::LiquidityEvaporation {
+                probability,
+                duration,
+                keep,
+                levels,
+            } => {
+                if !rng.random_bool(probability.clamp(0.0, 1.0)) {
+                    continue;
+                }
+                let from_ms = rng.random_range(first_ms..=last_ms);
+                droughts.push(Drought {
+                    from_ms,
+                    to_ms: from_ms + duration.as_millis() as u64,
+                    keep: keep.clamp(0.0, 1.0),
+                    levels: levels.max(1),
+                });
+            }
+        }
+    }
+
+    let truth = MarketBooks::new();
+    let mut known: BTreeSet<String> = BTreeSet::new();
+    let mut resolved: BTreeSet<String> = BTreeSet::new();
+    // Whether the previous event fell in a drought, to resnapshot every
+    // book as one starts or ends
+    let mut was_dry = false;
+    let mut out = Vec::with_capacity(events.len());
+
+    for event in events {
+        let tokens = tokens_of(&event.msg);
+        truth.apply(&event.msg).await;
+        known.extend(tokens.iter().cloned());
+
+        let drought = droughts
+            .iter()
+            .find(|d| event.recv_ms >= d.from_ms && event.recv_ms < d.to_ms);
+        let is_book = matches!(
+            event.msg.get("event_type").and_then(|t| t.as_str()),
+            Some("book") | Some("price_change")
+        );
+        let jumped = |token: &str| jumps.get(token).is_some_and(|(at, _)| event.recv_ms >= *at);
+
+        // Books to snapshot after this event: all of them when a drought
+        // starts or ends, else those it touched if any is perturbed
+        let dry = drought.is_some();
+        let touched: Vec<String> = if dry != was_dry {
+            if !is_book && !tokens.iter().any(|t| jumped(t)) {
+                out.push(event.clone());
+            }
+            known.iter().cloned().collect()
+        } else if is_book && (dry || tokens.iter().any(|t| jumped(t))) {
+            tokens.clone()
+        } else if !is_book && tokens.iter().any(|t| jumped(t)) {
+            // Nothing trades on a resolved market
+            continue;
+        } else {
+            out.push(event.clone());
+            continue;
+        };
+        was_dry = dry;
+
+        for token in touched {
+            let snapshot = if jumped(&token) {
+                if !resolved.insert(token.clone()) {
+                    continue;
+                }
+                resolved_snapshot(&token, jumps[&token].1, event)
+            } else {
+                let Some(book) = truth.get(&token).await else {
+                    continue;
+                };
+                snapshot(&book, drought, event)
+            };
+            out.push(ReplayEvent {
+                seq: event.seq,
+                recv_ms: event.recv_ms,
+                msg: snapshot,
+            });
+        }
+    }
+
+    Perturbed {
+        events: out,
+        jumps: resolved.len(),
+        evaporated: !droughts.is_empty(),
+    }
+}
+
+/// Tokens a market-channel message is about
+fn tokens_of(msg: &Value) -> Vec<String> {
+    let mut tokens: Vec<String> = msg
+        .get("price_changes")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.get("asset_id")?.as_str())
+        .chain(msg.get("asset_id").and_then(|a| a.as_str()))
+        .map(String::from)
+        .collect();
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+/// `book` message for `book`, thinned by `drought` if one applies
+fn snapshot(book: &LocalBook, drought: Option<&Drought>, event: &ReplayEvent) -> Value {
+    let (levels, keep) = drought.map_or((usize::MAX, Decimal::ONE), |d| {
+        (d.levels, Decimal::from_f64(d.keep).unwrap_or(Decimal::ONE))
+    });
+    let side = |levels_in: &mut dyn Iterator<Item = (&Decimal, &Decimal)>| -> Vec<Value> {
+        levels_in
+            .take(levels)
+            .map(|(p, s)| (p, (s * keep).round_dp(2)))
+            .filter(|(_, s)| *s > Decimal::ZERO)
+            .map(|(p, s)| json!({ "price": p.to_string(), "size": s.to_string() }))
+            .collect()
+    };
+    json!({
+        "event_type": "book",
+        "asset_id": book.token_id,
+        "market": book.market,
+        "timestamp": stamp(event),
+        "bids": side(&mut book.bids.iter().rev()),
+        "asks": side(&mut book.asks.iter()),
+    })
+}
+
+/// Book of a token that just resolved: pinned at 0.99 or 0.01
+fn resolved_snapshot(token: &str, won: bool, event: &ReplayEvent) -> Value {
+    let (bid, ask) = if won { ("0.98", "0.99") } else { ("0.01", "0.02") };
+    json!({
+        "event_type": "book",
+        "asset_id": token,
+        "market": event.msg.get("market").cloned().unwrap_or(Value::Null),
+        "timestamp": stamp(event),
+        "bids": [{ "price": bid, "size": "10000" }],
+        "asks": [{ "price": ask, "size": "10000" }],
+    })
+}
+
+fn stamp(event: &ReplayEvent) -> String {
+    crate::market_ws::book::timestamp(&event.msg)
+        .unwrap_or(event.recv_ms)
+        .to_string()
+}
+
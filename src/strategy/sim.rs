@@ -0,0 +1,579 @@
+use anyhow::{anyhow, Result};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use super::clock::{Clock, SystemClock};
+use super::costs::CostModel;
+use super::latency::{Delay, LatencyModel};
+use crate::domain::order::Side;
+use crate::execution::clob_client::ReplaceReport;
+use crate::execution::orderbook::OrderBook;
+use crate::portfolio::Fill;
+use crate::wallet::signer::OrderType;
+
+// ==================================================
+// SIMULATED VENUE
+// ==================================================
+// Matches a strategy's orders against books it is shown instead of
+// sending them to the exchange:
+//
+//   placed, crossing the book  →  takes the levels up to its limit
+//   FOK short of its size      →  rejected, nothing fills
+//   FAK remainder              →  dropped
+//   GTC/GTD remainder          →  rests; fills at its own price once a
+//                                 later book trades through it
+//
+// Fills are optimistic: resting orders have no queue position and
+// liquidity we took is still there on the next book. Fees and taker
+// slippage come from the venue's `CostModel` (none by default).
+//
+// With a `LatencyModel`, orders and cancels are in flight for a drawn
+// delay before they act, the way a round trip to the exchange is:
+//
+//   order in flight   →  matched against the venue's book on arrival,
+//                        not the one the strategy priced it off; a FOK
+//                        or post-only refusal then only shows in stats
+//   cancel in flight  →  the order keeps resting and filling until it
+//                        lands; a cancel landing before its order (or
+//                        after a full fill) misses
+//
+// In-flight work lands on the next `on_book` or `advance` at or past its
+// arrival time, in arrival order. With zero latency everything acts at
+// once, as before.
+
+/// One of our simulated orders still resting
+#[derive(Debug, Clone)]
+pub struct SimOrder {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    /// Unfilled size
+    pub size: Decimal,
+}
+
+/// An order or cancel a strategy sent a `SimVenue`, whatever became
+/// of it
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimAction {
+    Place {
+        at_ms: u64,
+        order_id: String,
+        token_id: String,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+        post_only: bool,
+    },
+    Cancel {
+        at_ms: u64,
+        order_id: String,
+    },
+}
+
+/// One line per action, stable enough to diff between runs
+impl std::fmt::Display for SimAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimAction::Place {
+                at_ms,
+                order_id,
+                token_id,
+                side,
+                price,
+                size,
+                order_type,
+                post_only,
+            } => write!(
+                f,
+                "{} place {} {} {} {} @ {} {}{}",
+                at_ms,
+                order_id,
+                token_id,
+                side.as_str(),
+                size.normalize(),
+                price.normalize(),
+                order_type.as_str(),
+                if *post_only { " post-only" } else { "" }
+            ),
+            SimAction::Cancel { at_ms, order_id } => write!(f, "{} cancel {}", at_ms, order_id),
+        }
+    }
+}
+
+/// Order flow through a `SimVenue`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SimStats {
+    /// Orders accepted
+    pub orders: u64,
+    /// Refused: FOK short of size, post-only crossing
+    pub rejected: u64,
+    /// Cancelled (replacements included) while still resting
+    pub cancelled: u64,
+    pub fills: u64,
+    pub maker_fills: u64,
+    /// Notional filled (USDC)
+    pub volume: Decimal,
+    /// Fees charged (USDC)
+    pub fees: Decimal,
+    /// Refused by the context's risk manager before reaching the venue
+    pub risk_rejected: u64,
+    /// Cut down by the risk manager
+    pub risk_resized: u64,
+    /// Cancels that landed with nothing resting: filled, or their order
+    /// still in flight
+    pub cancel_misses: u64,
+}
+
+#[derive(Debug, Default)]
+struct SimState {
+    next_id: u64,
+    next_trade: u64,
+    resting: HashMap<String, SimOrder>,
+    // Not yet collected by `take_fills`
+    fills: Vec<Fill>,
+    stats: SimStats,
+    // Fill timestamp for the current match
+    now_ms: u64,
+    costs: CostModel,
+    latency: LatencyModel,
+    // Seeded from the latency model on the first draw
+    rng: Option<StdRng>,
+    // Orders and cancels sent but not landed: (arrival ms, action)
+    in_flight: Vec<(u64, InFlight)>,
+    // Last book seen per token; in-flight orders land against it
+    books: HashMap<String, OrderBook>,
+    // Everything sent, when recording
+    actions: Option<Vec<SimAction>>,
+}
+
+/// An order as sent to the venue
+#[derive(Debug, Clone)]
+struct SimRequest {
+    token_id: String,
+    side: Side,
+    price: Decimal,
+    size: Decimal,
+    order_type: OrderType,
+    post_only: bool,
+}
+
+#[derive(Debug, Clone)]
+enum InFlight {
+    // The book it was priced off, for a token the venue has no book of
+    Place(String, SimRequest, OrderBook),
+    Cancel(String),
+}
+
+/// Order matching without the exchange, for one strategy
+#[derive(Debug)]
+pub struct SimVenue {
+    // Order ids are `<prefix>-<n>`
+    prefix: String,
+    state: Mutex<SimState>,
+    // Stamps fills
+    clock: Arc<dyn Clock>,
+}
+
+impl SimVenue {
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            state: Mutex::new(SimState::default()),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Charge fees and taker slippage per `costs`
+    pub fn with_costs(mut self, costs: CostModel) -> Self {
+        self.state.get_mut().costs = costs;
+        self
+    }
+
+    /// Stamp fills with `clock`'s time instead of the system clock's
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Delay orders, cancels and (in a backtest) books per `latency`
+    pub fn with_latency(mut self, latency: LatencyModel) -> Self {
+        self.state.get_mut().latency = latency;
+        self
+    }
+
+    /// Keep every order and cancel sent, for `actions`
+    pub fn record_actions(mut self) -> Self {
+        self.state.get_mut().actions = Some(Vec::new());
+        self
+    }
+
+    /// Orders and cancels sent so far, oldest first (empty unless
+    /// `record_actions`)
+    pub async fn actions(&self) -> Vec<SimAction> {
+        self.state.lock().await.actions.clone().unwrap_or_default()
+    }
+
+    pub async fn latency(&self) -> LatencyModel {
+        self.state.lock().await.latency
+    }
+
+    /// Match an order against `book`; the order id, even when nothing
+    /// rests
+    pub async fn place(
+        &self,
+        book: &OrderBook,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+    ) -> Result<String> {
+        self.submit(
+            book,
+            SimRequest {
+                token_id: token_id.to_string(),
+                side,
+                price,
+                size,
+                order_type,
+                post_only: false,
+            },
+        )
+        .await
+    }
+
+    /// Rest a GTC/GTD order without taking; refused if it would cross
+    pub async fn place_post_only(
+        &self,
+        book: &OrderBook,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+    ) -> Result<String> {
+        self.submit(
+            book,
+            SimRequest {
+                token_id: token_id.to_string(),
+                side,
+                price,
+                size,
+                order_type,
+                post_only: true,
+            },
+        )
+        .await
+    }
+
+    /// Cancel `order_id` and rest a post-only replacement at the new
+    /// price and size, less what the old one had filled
+    pub async fn replace_post_only(
+        &self,
+        book: &OrderBook,
+        order_id: &str,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<ReplaceReport> {
+        let (old, in_flight) = {
+            let state = self.state.lock().await;
+            (state.find(order_id), !state.latency.submit.is_zero())
+        };
+        let old = old.ok_or_else(|| anyhow!("No resting simulated order {}", order_id))?;
+        // With the order landing later, a crossing replacement is refused
+        // on arrival, after the old one is already on its way out
+        if !in_flight && book.crosses(&old.side, price.to_f64().unwrap_or_default()) {
+            self.state.lock().await.stats.rejected += 1;
+            return Err(anyhow!("Post-only {} at {} would cross the book", old.side.as_str(), price));
+        }
+        self.cancel(order_id).await;
+        let new_order_id = self
+            .place_post_only(book, &old.token_id, old.side, price, size, OrderType::Gtc)
+            .await?;
+        Ok(ReplaceReport {
+            old_order_id: order_id.to_string(),
+            new_order_id: Some(new_order_id),
+            new_size: size,
+            ..Default::default()
+        })
+    }
+
+    pub async fn cancel(&self, order_id: &str) {
+        let mut state = self.state.lock().await;
+        let now = self.clock.now_ms();
+        state.land(now);
+        state.record(|| SimAction::Cancel {
+            at_ms: now,
+            order_id: order_id.to_string(),
+        });
+        let delay = state.draw(|l| l.cancel);
+        if delay == 0 {
+            state.cancel(order_id);
+        } else {
+            state.in_flight.push((now + delay, InFlight::Cancel(order_id.to_string())));
+        }
+    }
+
+    /// Land orders and cancels whose arrival time has passed
+    pub async fn advance(&self) {
+        let now = self.clock.now_ms();
+        self.state.lock().await.land(now);
+    }
+
+    /// Orders and cancels not landed yet
+    pub async fn in_flight(&self) -> usize {
+        self.state.lock().await.in_flight.len()
+    }
+
+    async fn submit(&self, book: &OrderBook, request: SimRequest) -> Result<String> {
+        let mut state = self.state.lock().await;
+        let now = self.clock.now_ms();
+        state.land(now);
+        state.next_id += 1;
+        let order_id = format!("{}-{}", self.prefix, state.next_id);
+        state.record(|| SimAction::Place {
+            at_ms: now,
+            order_id: order_id.clone(),
+            token_id: request.token_id.clone(),
+            side: request.side.clone(),
+            price: request.price,
+            size: request.size,
+            order_type: request.order_type,
+            post_only: request.post_only,
+        });
+        let delay = state.draw(|l| l.submit);
+        if delay == 0 {
+            state.execute(&order_id, &request, book)?;
+        } else {
+            state
+                .in_flight
+                .push((now + delay, InFlight::Place(order_id.clone(), request, book.clone())));
+        }
+        Ok(order_id)
+    }
+
+    /// Fill resting orders on `token_id` that `book` trades through
+    pub async fn on_book(&self, token_id: &str, book: &OrderBook) {
+        let mut state = self.state.lock().await;
+        // What arrived before this book met the one before it
+        state.land(self.clock.now_ms());
+        state.books.insert(token_id.to_string(), book.clone());
+        let crossed: Vec<(String, Side, Decimal, Decimal)> = state
+            .resting
+            .values()
+            .filter(|o| o.token_id == token_id)
+            .filter_map(|o| {
+                let available: Decimal = crossed(book, &o.side, o.price).iter().map(|(_, s)| *s).sum();
+                let take = o.size.min(available);
+                (!take.is_zero()).then(|| (o.order_id.clone(), o.side.clone(), o.price, take))
+            })
+            .collect();
+        for (order_id, side, price, take) in crossed {
+            state.fill(&order_id, token_id, &side, price, take, true);
+            let done = match state.resting.get_mut(&order_id) {
+                Some(o) => {
+                    o.size -= take;
+                    o.size.is_zero()
+                }
+                None => false,
+            };
+            if done {
+                state.resting.remove(&order_id);
+            }
+        }
+    }
+
+    /// Fills since the last call, oldest first
+    pub async fn take_fills(&self) -> Vec<Fill> {
+        std::mem::take(&mut self.state.lock().await.fills)
+    }
+
+    /// Resting orders, plus orders still in flight at their full size
+    pub async fn resting(&self) -> Vec<SimOrder> {
+        let state = self.state.lock().await;
+        let mut orders: Vec<SimOrder> = state.resting.values().cloned().collect();
+        orders.extend(state.in_flight.iter().filter_map(|(_, a)| match a {
+            InFlight::Place(order_id, r, _) => Some(r.order(order_id, r.size)),
+            InFlight::Cancel(_) => None,
+        }));
+        orders
+    }
+
+    pub async fn stats(&self) -> SimStats {
+        self.state.lock().await.stats
+    }
+
+    /// Count an order the risk manager refused (or cut, `resized`)
+    pub async fn note_risk(&self, resized: bool) {
+        let stats = &mut self.state.lock().await.stats;
+        if resized {
+            stats.risk_resized += 1;
+        } else {
+            stats.risk_rejected += 1;
+        }
+    }
+}
+
+impl SimRequest {
+    fn order(&self, order_id: &str, size: Decimal) -> SimOrder {
+        SimOrder {
+            order_id: order_id.to_string(),
+            token_id: self.token_id.clone(),
+            side: self.side.clone(),
+            price: self.price,
+            size,
+        }
+    }
+}
+
+impl SimState {
+    /// Draw one delay from the latency model
+    fn draw(&mut self, delay: impl Fn(&LatencyModel) -> Delay) -> u64 {
+        let delay = delay(&self.latency);
+        if delay.is_zero() {
+            return 0;
+        }
+        let seed = self.latency.seed;
+        delay.sample(self.rng.get_or_insert_with(|| StdRng::seed_from_u64(seed)))
+    }
+
+    fn record(&mut self, action: impl FnOnce() -> SimAction) {
+        if let Some(actions) = &mut self.actions {
+            actions.push(action());
+        }
+    }
+
+    /// An order resting or in flight
+    fn find(&self, order_id: &str) -> Option<SimOrder> {
+        self.resting.get(order_id).cloned().or_else(|| {
+            self.in_flight.iter().find_map(|(_, a)| match a {
+                InFlight::Place(id, r, _) if id == order_id => Some(r.order(id, r.size)),
+                _ => None,
+            })
+        })
+    }
+
+    /// Act on everything in flight that has arrived by `now`, in arrival
+    /// order
+    fn land(&mut self, now: u64) {
+        let mut due = Vec::new();
+        self.in_flight.retain(|(at, action)| {
+            if *at <= now {
+                due.push((*at, action.clone()));
+            }
+            *at > now
+        });
+        // Stable, so same-instant arrivals keep the order they were sent
+        due.sort_by_key(|(at, _)| *at);
+        for (at, action) in due {
+            match action {
+                InFlight::Place(order_id, request, sent_with) => {
+                    self.now_ms = at;
+                    let book = self.books.get(&request.token_id).cloned().unwrap_or(sent_with);
+                    // Refusals are counted; nobody is waiting for the error
+                    let _ = self.execute(&order_id, &request, &book);
+                }
+                InFlight::Cancel(order_id) => self.cancel(&order_id),
+            }
+        }
+        self.now_ms = now;
+    }
+
+    /// Match `request` against `book` now
+    fn execute(&mut self, order_id: &str, request: &SimRequest, book: &OrderBook) -> Result<()> {
+        let SimRequest {
+            token_id,
+            side,
+            price,
+            size,
+            order_type,
+            post_only,
+        } = request;
+        if *post_only && book.crosses(side, price.to_f64().unwrap_or_default()) {
+            self.stats.rejected += 1;
+            return Err(anyhow!("Post-only {} at {} would cross the book", side.as_str(), price));
+        }
+        let levels = crossed(book, side, *price);
+        let available: Decimal = levels.iter().map(|(_, s)| *s).sum();
+        if *order_type == OrderType::Fok && available < *size {
+            self.stats.rejected += 1;
+            return Err(anyhow!(
+                "FOK {} {} of {} at {} not filled ({} available)",
+                side.as_str(),
+                size,
+                token_id,
+                price,
+                available
+            ));
+        }
+
+        self.stats.orders += 1;
+        let mut left = *size;
+        for (level_price, level_size) in levels {
+            let take = left.min(level_size);
+            if take.is_zero() {
+                break;
+            }
+            self.fill(order_id, token_id, side, level_price, take, false);
+            left -= take;
+        }
+        if left > Decimal::ZERO && matches!(order_type, OrderType::Gtc | OrderType::Gtd) {
+            self.resting.insert(order_id.to_string(), request.order(order_id, left));
+        }
+        Ok(())
+    }
+
+    fn cancel(&mut self, order_id: &str) {
+        if self.resting.remove(order_id).is_some() {
+            self.stats.cancelled += 1;
+        } else {
+            self.stats.cancel_misses += 1;
+        }
+    }
+
+    fn fill(&mut self, order_id: &str, token_id: &str, side: &Side, price: Decimal, size: Decimal, maker: bool) {
+        let price = self.costs.fill_price(side, price, maker);
+        let fee = self.costs.fee(side, price, size, maker);
+        self.next_trade += 1;
+        self.stats.fills += 1;
+        self.stats.maker_fills += maker as u64;
+        self.stats.volume += price * size;
+        self.stats.fees += fee;
+        self.fills.push(Fill {
+            trade_id: format!("{}-t{}", order_id, self.next_trade),
+            order_id: order_id.to_string(),
+            token_id: token_id.to_string(),
+            market: String::new(),
+            side: side.clone(),
+            price,
+            size,
+            fee,
+            maker,
+            at_ms: self.now_ms,
+        });
+    }
+}
+
+/// Opposite-side levels an order at `price` trades with, best first
+fn crossed(book: &OrderBook, side: &Side, price: Decimal) -> Vec<(Decimal, Decimal)> {
+    let limit = price.to_f64().unwrap_or_default();
+    let levels = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    };
+    levels
+        .iter()
+        .take_while(|l| match side {
+            Side::Buy => l.price <= limit,
+            Side::Sell => l.price >= limit,
+        })
+        .filter_map(|l| Some((Decimal::from_f64(l.price)?.round_dp(4), Decimal::from_f64(l.size)?)))
+        .collect()
+}
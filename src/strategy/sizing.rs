@@ -0,0 +1,151 @@
+use anyhow::{anyhow, Result};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::domain::order::Side;
+use crate::execution::ClobClient;
+
+// ==================================================
+// KELLY SIZING
+// ==================================================
+// Turns an edge estimate into an order size. An outcome token pays 1 if
+// it resolves YES; bought at price p with probability q of YES:
+//
+//   BUY   f* = (q - p) / (1 - p)   staked at p per token
+//   SELL  f* = (p - q) / p         risking 1 - p per token
+//
+//   tokens = KELLY_FRACTION × f* × bankroll / risk per token
+//
+// capped at KELLY_MAX_BET of the bankroll, then (buys) at the risk
+// manager's headroom, and rounded down to the market's lot rules.
+// Full Kelly is far too aggressive for noisy edge estimates; the
+// default fraction is a quarter.
+
+/// A strategy's view on one token
+#[derive(Debug, Clone)]
+pub struct Edge {
+    /// Probability the token resolves YES
+    pub probability: f64,
+    /// Price we'd trade at
+    pub price: f64,
+    pub side: Side,
+}
+
+impl Edge {
+    pub fn new(probability: f64, price: f64, side: Side) -> Self {
+        Self {
+            probability,
+            price,
+            side,
+        }
+    }
+
+    /// Expected profit per token traded (negative = no edge)
+    pub fn per_token(&self) -> f64 {
+        match self.side {
+            Side::Buy => self.probability - self.price,
+            Side::Sell => self.price - self.probability,
+        }
+    }
+
+    /// Capital at risk per token: the price paid for a buy, what a sell
+    /// gives up if the token resolves YES
+    pub fn risk_per_token(&self) -> f64 {
+        match self.side {
+            Side::Buy => self.price,
+            Side::Sell => 1.0 - self.price,
+        }
+    }
+}
+
+/// Full-Kelly fraction of the bankroll to stake on `edge`; zero without
+/// an edge or for prices outside (0, 1)
+pub fn kelly_fraction(edge: &Edge) -> f64 {
+    let (q, p) = (edge.probability.clamp(0.0, 1.0), edge.price);
+    if p <= 0.0 || p >= 1.0 {
+        return 0.0;
+    }
+    let f = match edge.side {
+        Side::Buy => (q - p) / (1.0 - p),
+        Side::Sell => (p - q) / p,
+    };
+    f.max(0.0)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct KellySizer {
+    /// Multiple of full Kelly to bet
+    pub fraction: f64,
+    /// Most of the bankroll staked on one order
+    pub max_bet: f64,
+}
+
+impl Default for KellySizer {
+    fn default() -> Self {
+        Self {
+            fraction: 0.25,
+            max_bet: 0.10,
+        }
+    }
+}
+
+impl KellySizer {
+    pub fn new(fraction: f64, max_bet: f64) -> Self {
+        Self { fraction, max_bet }
+    }
+
+    /// `KELLY_FRACTION` (default 0.25) and `KELLY_MAX_BET` (default 0.10)
+    pub fn from_env() -> Self {
+        let d = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<f64>().ok());
+        Self {
+            fraction: var("KELLY_FRACTION").unwrap_or(d.fraction),
+            max_bet: var("KELLY_MAX_BET").unwrap_or(d.max_bet),
+        }
+    }
+
+    /// Share of `bankroll` to stake on `edge`
+    pub fn stake_fraction(&self, edge: &Edge) -> f64 {
+        (self.fraction * kelly_fraction(edge)).min(self.max_bet).max(0.0)
+    }
+
+    /// Tokens to trade on `edge` out of `bankroll` USDC, before any
+    /// exchange or risk caps
+    pub fn tokens(&self, edge: &Edge, bankroll: Decimal) -> Decimal {
+        let risk = edge.risk_per_token();
+        if risk <= 0.0 {
+            return Decimal::ZERO;
+        }
+        let stake = bankroll.to_f64().unwrap_or_default() * self.stake_fraction(edge);
+        Decimal::from_f64(stake / risk)
+            .unwrap_or_default()
+            .round_dp_with_strategy(2, RoundingStrategy::ToZero)
+    }
+
+    /// Order size for `edge` on `token_id`: Kelly tokens, capped by the
+    /// risk manager's headroom on buys and by `token_id`'s tick and
+    /// minimum size. Zero means don't trade.
+    pub async fn order_size(
+        &self,
+        clob: &ClobClient,
+        token_id: &str,
+        edge: &Edge,
+        bankroll: Decimal,
+    ) -> Result<Decimal> {
+        let mut size = self.tokens(edge, bankroll);
+        if size.is_zero() {
+            return Ok(size);
+        }
+        if let (Side::Buy, Some(risk)) = (&edge.side, clob.risk_manager()) {
+            let price = Decimal::from_f64(edge.price).ok_or_else(|| anyhow!("Bad price {}", edge.price))?;
+            if let Some(room) = risk.buy_room(token_id, price, &clob.order_intents().await).await {
+                size = size.min(room);
+            }
+        }
+        let rules = clob.market_rules(token_id).await?;
+        Ok(match size < rules.min_size {
+            true => Decimal::ZERO,
+            false => size,
+        })
+    }
+}
@@ -0,0 +1,369 @@
+use anyhow::{anyhow, Result};
+use chrono::DateTime;
+use log::info;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::backtest::{Backtest, BacktestReport};
+use super::costs::CostModel;
+use super::runtime::{Strategy, StrategyBudget};
+use crate::execution::ClobClient;
+use crate::market_ws::replay::Replay;
+use crate::wallet::order_builder::MarketRules;
+
+// ==================================================
+// WALK-FORWARD OPTIMIZATION
+// ==================================================
+// Sweeps a grid of strategy parameters over rolling windows of a
+// recording:
+//
+//   |── in-sample ──|── out-of-sample ──|
+//          step →   |── in-sample ──|── out-of-sample ──|
+//
+// Every parameter set is backtested on the in-sample window; the one
+// with the best PnL (net of fees) is then run once, unchanged, on the
+// out-of-sample window that follows. Out-of-sample PnL is what the
+// method would have earned live.
+//
+// Parameters are the strategies' own .env variables (`MM_HALF_SPREAD`,
+// `MEANREV_THRESHOLD`, ...); variables not swept keep their .env value.
+// The stability report counts how often each value won. A value that
+// wins most windows is the one exported for live use; one that changes
+// every window is overfitting noise.
+
+/// Variable name → value of one point in the grid
+pub type ParamSet = BTreeMap<String, String>;
+
+/// One variable to sweep and its candidate values
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamRange {
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+impl FromStr for ParamRange {
+    type Err = String;
+
+    /// `NAME=v1,v2,...`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, values) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected NAME=v1,v2,..., got {}", s))?;
+        let name = name.trim().to_string();
+        let values: Vec<String> = values
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+            .collect();
+        if name.is_empty() || values.is_empty() {
+            return Err(format!("expected NAME=v1,v2,..., got {}", s));
+        }
+        Ok(Self { name, values })
+    }
+}
+
+/// Every combination of `ranges`, in the order given
+pub fn grid(ranges: &[ParamRange]) -> Vec<ParamSet> {
+    let mut sets = vec![ParamSet::new()];
+    for range in ranges {
+        sets = sets
+            .into_iter()
+            .flat_map(|set| {
+                range.values.iter().map(move |value| {
+                    let mut set = set.clone();
+                    set.insert(range.name.clone(), value.clone());
+                    set
+                })
+            })
+            .collect();
+    }
+    sets
+}
+
+/// `params` over the environment, for `*Config::from_vars`
+pub fn lookup(params: &ParamSet, name: &str) -> Option<String> {
+    params.get(name).cloned().or_else(|| std::env::var(name).ok())
+}
+
+/// How the recording is cut into windows
+#[derive(Debug, Clone, Copy)]
+pub struct WalkForwardConfig {
+    pub in_sample: Duration,
+    pub out_of_sample: Duration,
+    /// How far each window moves on; the out-of-sample length if unset
+    pub step: Option<Duration>,
+}
+
+/// One in-sample fit and its out-of-sample run
+#[derive(Debug, Clone)]
+pub struct WindowResult {
+    /// In-sample start (unix ms)
+    pub start_ms: u64,
+    /// In-sample end, out-of-sample start
+    pub split_ms: u64,
+    /// Out-of-sample end
+    pub end_ms: u64,
+    /// Winning parameter set
+    pub chosen: ParamSet,
+    /// In-sample PnL of every set, grid order
+    pub scores: Vec<(ParamSet, Decimal)>,
+    /// In-sample PnL of the winner
+    pub in_sample: Decimal,
+    pub out_of_sample: BacktestReport,
+}
+
+/// How consistently one variable's winning value held
+#[derive(Debug, Clone)]
+pub struct ParamStability {
+    pub name: String,
+    /// Value → windows it won
+    pub wins: BTreeMap<String, usize>,
+    /// Value exported for live use
+    pub chosen: String,
+    /// Share of windows `chosen` won (0-1)
+    pub share: f64,
+}
+
+/// Results of a walk-forward run
+#[derive(Debug, Clone, Default)]
+pub struct WalkForwardReport {
+    pub strategy: String,
+    pub windows: Vec<WindowResult>,
+}
+
+impl WalkForwardReport {
+    pub fn in_sample_pnl(&self) -> Decimal {
+        self.windows.iter().map(|w| w.in_sample).sum()
+    }
+
+    pub fn out_of_sample_pnl(&self) -> Decimal {
+        self.windows.iter().map(|w| w.out_of_sample.pnl.total()).sum()
+    }
+
+    /// Per variable, the value that won most windows; ties go to the
+    /// later winner
+    pub fn stability(&self) -> Vec<ParamStability> {
+        let Some(first) = self.windows.first() else {
+            return Vec::new();
+        };
+        first
+            .chosen
+            .keys()
+            .map(|name| {
+                let mut wins = BTreeMap::new();
+                let mut chosen = String::new();
+                let mut best = 0;
+                for window in &self.windows {
+                    let value = window.chosen.get(name).cloned().unwrap_or_default();
+                    let count = wins.entry(value.clone()).or_insert(0);
+                    *count += 1;
+                    if *count >= best {
+                        best = *count;
+                        chosen = value;
+                    }
+                }
+                ParamStability {
+                    name: name.clone(),
+                    wins,
+                    chosen,
+                    share: best as f64 / self.windows.len() as f64,
+                }
+            })
+            .collect()
+    }
+
+    /// The set to run live: each variable's most frequent winner
+    pub fn chosen(&self) -> ParamSet {
+        self.stability().into_iter().map(|p| (p.name, p.chosen)).collect()
+    }
+
+    /// Write the chosen set as .env lines
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let mut out = format!(
+            "# Walk-forward choice for {} over {} window(s), out-of-sample PnL ${:.2}\n",
+            self.strategy,
+            self.windows.len(),
+            self.out_of_sample_pnl()
+        );
+        for (name, value) in self.chosen() {
+            out.push_str(&format!("{}={}\n", name, value));
+        }
+        std::fs::write(path, out)?;
+        info!("💾 Exported walk-forward parameters to {}", path.display());
+        Ok(())
+    }
+
+    pub fn log(&self) {
+        info!("🧪 Walk-forward {}: {} window(s)", self.strategy, self.windows.len());
+        for (i, window) in self.windows.iter().enumerate() {
+            info!(
+                "   #{} {} → {} → {} | in ${:.2} | out ${:.2} ({} fill(s), fees ${:.2}) | {}",
+                i + 1,
+                format_ms(window.start_ms),
+                format_ms(window.split_ms),
+                format_ms(window.end_ms),
+                window.in_sample,
+                window.out_of_sample.pnl.total(),
+                window.out_of_sample.orders.fills,
+                window.out_of_sample.orders.fees,
+                format_params(&window.chosen)
+            );
+        }
+        info!(
+            "   in-sample ${:.2} | out-of-sample ${:.2}",
+            self.in_sample_pnl(),
+            self.out_of_sample_pnl()
+        );
+        for param in self.stability() {
+            let wins: Vec<String> = param.wins.iter().map(|(v, n)| format!("{}×{}", v, n)).collect();
+            let flag = if param.share > 0.5 { "✅" } else { "⚠️ " };
+            info!(
+                "   {} {} = {} ({:.0}% of windows) | {}",
+                flag,
+                param.name,
+                param.chosen,
+                param.share * 100.0,
+                wins.join(" ")
+            );
+        }
+    }
+}
+
+fn format_ms(ms: u64) -> String {
+    DateTime::from_timestamp_millis(ms as i64)
+        .map(|t| t.format("%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| ms.to_string())
+}
+
+fn format_params(params: &ParamSet) -> String {
+    params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Walk-forward sweep of one strategy over one recording
+pub struct WalkForward {
+    clob: Arc<ClobClient>,
+    replay: Replay,
+    config: WalkForwardConfig,
+    budget: Option<StrategyBudget>,
+    rules: Option<MarketRules>,
+    costs: Option<CostModel>,
+}
+
+impl WalkForward {
+    pub fn new(clob: Arc<ClobClient>, replay: Replay, config: WalkForwardConfig) -> Self {
+        Self {
+            clob,
+            replay,
+            config,
+            budget: None,
+            rules: None,
+            costs: None,
+        }
+    }
+
+    /// Budget for every run; see `Backtest::budget`
+    pub fn budget(mut self, budget: StrategyBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Rules seeded for every market; see `Backtest::rules`
+    pub fn rules(mut self, rules: MarketRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Fees and slippage for every run; see `Backtest::costs`
+    pub fn costs(mut self, costs: CostModel) -> Self {
+        self.costs = Some(costs);
+        self
+    }
+
+    /// Sweep `ranges`, building each run's strategy with `build`
+    pub async fn run<F>(&self, ranges: &[ParamRange], build: F) -> Result<WalkForwardReport>
+    where
+        F: Fn(&ParamSet) -> Result<Arc<dyn Strategy>>,
+    {
+        let sets = grid(ranges);
+        let strategy = build(&sets[0])?.name().to_string();
+        let (first_ms, last_ms) = self
+            .replay
+            .span()
+            .ok_or_else(|| anyhow!("Recording is empty"))?;
+        let in_sample = self.config.in_sample.as_millis() as u64;
+        let out_of_sample = self.config.out_of_sample.as_millis() as u64;
+        let step = self.config.step.unwrap_or(self.config.out_of_sample).as_millis() as u64;
+        if in_sample == 0 || out_of_sample == 0 || step == 0 {
+            return Err(anyhow!("Walk-forward windows must be longer than zero"));
+        }
+
+        info!(
+            "🧪 Walk-forward {}: {} parameter set(s), {}m in / {}m out",
+            strategy,
+            sets.len(),
+            in_sample / 60_000,
+            out_of_sample / 60_000
+        );
+        let mut report = WalkForwardReport {
+            strategy,
+            windows: Vec::new(),
+        };
+        let mut start_ms = first_ms;
+        // Stop once no out-of-sample data would follow the fit
+        while start_ms + in_sample <= last_ms {
+            let split_ms = start_ms + in_sample;
+            let end_ms = split_ms + out_of_sample;
+
+            let mut scores = Vec::with_capacity(sets.len());
+            for params in &sets {
+                let run = self.backtest(start_ms, split_ms).run(build(params)?).await?;
+                scores.push((params.clone(), run.pnl.total()));
+            }
+            // First of equals wins, so ties favour the earlier grid point
+            let (chosen, best) = scores
+                .iter()
+                .fold(None, |best: Option<&(ParamSet, Decimal)>, s| match best {
+                    Some(b) if b.1 >= s.1 => Some(b),
+                    _ => Some(s),
+                })
+                .cloned()
+                .expect("grid has at least one set");
+            let out = self.backtest(split_ms, end_ms).run(build(&chosen)?).await?;
+
+            report.windows.push(WindowResult {
+                start_ms,
+                split_ms,
+                end_ms,
+                chosen,
+                scores,
+                in_sample: best,
+                out_of_sample: out,
+            });
+            start_ms += step;
+        }
+        Ok(report)
+    }
+
+    fn backtest(&self, from_ms: u64, to_ms: u64) -> Backtest {
+        let mut backtest = Backtest::new(self.clob.clone(), self.replay.window(from_ms, to_ms));
+        if let Some(budget) = self.budget {
+            backtest = backtest.budget(budget);
+        }
+        if let Some(rules) = self.rules {
+            backtest = backtest.rules(rules);
+        }
+        if let Some(costs) = self.costs {
+            backtest = backtest.costs(costs);
+        }
+        backtest
+    }
+}
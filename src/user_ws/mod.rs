@@ -0,0 +1,376 @@
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, info, warn};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, Mutex};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::clob::ApiCredentials;
+use crate::domain::order::Side;
+use crate::execution::clob_client::ClobClient;
+use crate::net::{feeds, Connections, Reconnector};
+use crate::orders::OrderEvent;
+use crate::portfolio::{self, DataApiClient, Fill, Positions};
+use crate::wallet::fees;
+
+/// Authenticated user channel
+pub const USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
+
+/// Fills kept for slow subscribers
+const UPDATE_BUFFER: usize = 1024;
+
+// ==================================================
+// USER CHANNEL CONNECTION
+// ==================================================
+
+/// Streams our own order and trade updates from the user channel.
+///
+/// `order` messages drive the client's order state machine; `trade`
+/// messages become fills, which update `positions` and are broadcast.
+/// A trade is applied when first MATCHED and reversed if it later
+/// FAILS on-chain.
+///
+/// Updates sent while the channel was down are lost, so after a
+/// reconnect every order still open locally is looked up over REST.
+/// If any of them matched meanwhile, positions are resynced from the
+/// data API.
+pub struct UserWs {
+    url: String,
+    creds: ApiCredentials,
+    // Condition ids to subscribe to; empty = every market
+    markets: Vec<String>,
+    clob: Arc<ClobClient>,
+    positions: Arc<Positions>,
+    fills: broadcast::Sender<Fill>,
+    connections: Arc<Connections>,
+    // order id → size matched so far, to turn updates into fill deltas
+    matched: Mutex<HashMap<String, Decimal>>,
+    // trade id → fills applied for it
+    trades: Mutex<HashMap<String, Vec<Fill>>>,
+}
+
+impl UserWs {
+    pub fn new(
+        url: impl Into<String>,
+        creds: ApiCredentials,
+        clob: Arc<ClobClient>,
+        positions: Arc<Positions>,
+    ) -> Self {
+        Self {
+            url: url.into(),
+            creds,
+            markets: vec![],
+            clob,
+            positions,
+            fills: broadcast::channel(UPDATE_BUFFER).0,
+            connections: Arc::new(Connections::new()),
+            matched: Mutex::new(HashMap::new()),
+            trades: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Only follow these condition ids
+    pub fn markets(mut self, condition_ids: Vec<String>) -> Self {
+        self.markets = condition_ids;
+        self
+    }
+
+    /// Publish connection changes on a shared hub
+    pub fn with_connections(mut self, connections: Arc<Connections>) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    /// `USER_WS_URL` (default the public user channel) with the
+    /// client's API credentials
+    pub fn from_env(clob: Arc<ClobClient>, positions: Arc<Positions>) -> Result<Self> {
+        let creds = clob
+            .api_credentials()
+            .ok_or_else(|| anyhow!("User channel needs CLOB API credentials"))?;
+        let url = std::env::var("USER_WS_URL").unwrap_or_else(|_| USER_WS_URL.to_string());
+        Ok(Self::new(url, creds, clob, positions))
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Fill> {
+        self.fills.subscribe()
+    }
+
+    pub fn positions(&self) -> Arc<Positions> {
+        self.positions.clone()
+    }
+
+    /// Run forever
+    pub async fn run(&self) {
+        let mut link = Reconnector::new(feeds::USER, self.connections.clone());
+        loop {
+            let result = self.stream(&mut link).await;
+            link.disconnected(result).await;
+        }
+    }
+
+    async fn stream(&self, link: &mut Reconnector) -> Result<()> {
+        link.connecting();
+        let (ws, _) = connect_async(self.url.as_str()).await?;
+        let (mut write, mut read) = ws.split();
+
+        let sub = json!({
+            "type": "user",
+            "auth": {
+                "apiKey": self.creds.api_key,
+                "secret": self.creds.secret,
+                "passphrase": self.creds.passphrase,
+            },
+            "markets": self.markets,
+        });
+        write.send(Message::Text(sub.to_string())).await?;
+        info!("📡 User WS subscribed");
+        if link.connected() {
+            // Updates from here on are buffered on the socket meanwhile
+            self.refetch_orders().await;
+        }
+
+        let mut hb = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            tokio::select! {
+                _ = hb.tick() => {
+                    write.send(Message::Text("PING".to_string())).await?;
+                }
+                msg = read.next() => {
+                    let msg = msg.ok_or_else(|| anyhow!("WS closed"))??;
+                    if let Message::Text(txt) = msg {
+                        if let Ok(v) = serde_json::from_str::<Value>(&txt) {
+                            self.on_message(&v).await;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Apply one channel message (a single event or an array of them)
+    pub async fn on_message(&self, v: &Value) {
+        let events: Vec<&Value> = match v.as_array() {
+            Some(arr) => arr.iter().collect(),
+            None => vec![v],
+        };
+        for event in events {
+            match event.get("event_type").and_then(|e| e.as_str()) {
+                Some("order") => {
+                    self.on_order(event).await;
+                }
+                Some("trade") => self.on_trade(event).await,
+                _ => {}
+            }
+        }
+    }
+
+    /// Catch up on orders that changed while the channel was down
+    async fn refetch_orders(&self) {
+        let ids = self.clob.open_order_ids().await;
+        if ids.is_empty() {
+            return;
+        }
+        info!("🔄 Refetching {} open order(s) missed while the user channel was down", ids.len());
+        let mut matched = Decimal::ZERO;
+        for id in ids {
+            let order = match self.clob.get_order(&id).await {
+                Ok(order) => order,
+                Err(e) => {
+                    warn!("⚠️  Lookup of order {} failed: {}", id, e);
+                    continue;
+                }
+            };
+            // Shaped like the channel's own order message
+            let kind = match order.status.to_uppercase().as_str() {
+                "CANCELED" | "CANCELLED" => "CANCELLATION",
+                _ => "UPDATE",
+            };
+            let update = json!({ "id": id, "type": kind, "size_matched": order.size_matched });
+            matched += self.on_order(&update).await;
+        }
+        if matched.is_zero() {
+            return;
+        }
+        // The trades behind those matches went by unseen
+        warn!("⚠️  {} matched while the user channel was down, resyncing positions", matched);
+        let funder = format!("{:?}", self.clob.funder());
+        if let Err(e) = portfolio::sync_positions(&DataApiClient::from_env(), &self.positions, &funder).await {
+            warn!("⚠️  Position resync failed: {}", e);
+        }
+    }
+
+    /// Apply an order update; returns the size newly matched
+    async fn on_order(&self, v: &Value) -> Decimal {
+        let (Some(order_id), Some(kind)) = (str_field(v, "id"), str_field(v, "type")) else {
+            return Decimal::ZERO;
+        };
+        let size_matched = decimal(v, "size_matched").unwrap_or_default();
+
+        let delta = {
+            let mut matched = self.matched.lock().await;
+            let prev = matched.insert(order_id.to_string(), size_matched);
+            size_matched - prev.unwrap_or_default()
+        };
+
+        let Some(event) = OrderEvent::from_user_order(kind, order_id, delta) else {
+            return Decimal::ZERO;
+        };
+        match self.clob.on_order_event(order_id, event).await {
+            Some(state) => {
+                debug!("📬 Order {} → {}", order_id, state);
+                if state.is_terminal() {
+                    self.matched.lock().await.remove(order_id);
+                }
+            }
+            None => debug!("📬 Update for untracked order {}", order_id),
+        }
+        delta.max(Decimal::ZERO)
+    }
+
+    async fn on_trade(&self, v: &Value) {
+        let (Some(trade_id), Some(status)) = (str_field(v, "id"), str_field(v, "status")) else {
+            return;
+        };
+
+        match status.to_uppercase().as_str() {
+            "MATCHED" => {
+                let mut trades = self.trades.lock().await;
+                if trades.contains_key(trade_id) {
+                    return;
+                }
+                let fills = self.our_fills(trade_id, v);
+                trades.insert(trade_id.to_string(), fills.clone());
+                drop(trades);
+
+                for fill in fills {
+                    let position = self.positions.apply_fill(&fill).await;
+                    info!(
+                        "✅ Fill {} {} @ {} on {} (now {})",
+                        fill.side.as_str(),
+                        fill.size,
+                        fill.price,
+                        fill.token_id,
+                        position.size
+                    );
+                    let _ = self.fills.send(fill);
+                }
+            }
+            "FAILED" => {
+                let Some(fills) = self.trades.lock().await.remove(trade_id) else {
+                    return;
+                };
+                for fill in fills {
+                    warn!("⚠️  Trade {} failed on-chain, reversing fill", trade_id);
+                    self.positions.reverse_fill(&fill).await;
+                }
+            }
+            // MINED / CONFIRMED / RETRYING: already counted
+            _ => {}
+        }
+    }
+
+    /// Our side(s) of a trade: the taker order if it's ours, plus any of
+    /// our maker orders it matched
+    fn our_fills(&self, trade_id: &str, v: &Value) -> Vec<Fill> {
+        let ours = |owner: Option<&str>| owner == Some(self.creds.api_key.as_str());
+        let at_ms = v
+            .get("match_time")
+            .or_else(|| v.get("timestamp"))
+            .and_then(|t| t.as_str())
+            .and_then(|t| t.parse::<u64>().ok())
+            .map(|t| if t < 10_000_000_000 { t * 1000 } else { t })
+            .unwrap_or_else(now_ms);
+        let market = str_field(v, "market").unwrap_or_default().to_string();
+        let taker_token = str_field(v, "asset_id").unwrap_or_default();
+        let Some(taker_side) = side(v) else {
+            return vec![];
+        };
+
+        let mut fills = Vec::new();
+        if ours(str_field(v, "trade_owner")) {
+            if let (Some(price), Some(size)) = (decimal(v, "price"), decimal(v, "size")) {
+                fills.push(Fill {
+                    trade_id: trade_id.to_string(),
+                    order_id: str_field(v, "taker_order_id").unwrap_or_default().to_string(),
+                    token_id: taker_token.to_string(),
+                    market: market.clone(),
+                    side: taker_side.clone(),
+                    price,
+                    size,
+                    fee: fee_usdc(v, &taker_side, price, size),
+                    maker: false,
+                    at_ms,
+                });
+            }
+        }
+
+        let makers = v.get("maker_orders").and_then(|m| m.as_array());
+        for maker in makers.into_iter().flatten() {
+            if !ours(str_field(maker, "owner")) {
+                continue;
+            }
+            let (Some(price), Some(size)) = (decimal(maker, "price"), decimal(maker, "matched_amount"))
+            else {
+                continue;
+            };
+            let token = str_field(maker, "asset_id").unwrap_or(taker_token);
+            // Same token: the maker took the other side. Complementary
+            // token: both sides bought (mint) or both sold (merge).
+            let side = match (token == taker_token, &taker_side) {
+                (true, Side::Buy) => Side::Sell,
+                (true, Side::Sell) => Side::Buy,
+                (false, s) => s.clone(),
+            };
+            let fee = fee_usdc(maker, &side, price, size);
+            fills.push(Fill {
+                trade_id: trade_id.to_string(),
+                order_id: str_field(maker, "order_id").unwrap_or_default().to_string(),
+                token_id: token.to_string(),
+                market: market.clone(),
+                side,
+                price,
+                size,
+                fee,
+                maker: true,
+                at_ms,
+            });
+        }
+        fills
+    }
+}
+
+fn str_field<'a>(v: &'a Value, key: &str) -> Option<&'a str> {
+    v.get(key).and_then(|x| x.as_str())
+}
+
+fn decimal(v: &Value, key: &str) -> Option<Decimal> {
+    str_field(v, key).and_then(|s| Decimal::from_str(s).ok())
+}
+
+/// USDC value of the fee at the message's `fee_rate_bps`
+fn fee_usdc(v: &Value, side: &Side, price: Decimal, size: Decimal) -> Decimal {
+    let bps = str_field(v, "fee_rate_bps")
+        .and_then(|b| b.parse().ok())
+        .unwrap_or(0);
+    fees::fee(side, price, size, bps).usdc
+}
+
+fn side(v: &Value) -> Option<Side> {
+    match str_field(v, "side")?.to_uppercase().as_str() {
+        "BUY" => Some(Side::Buy),
+        "SELL" => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
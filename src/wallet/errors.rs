@@ -0,0 +1,66 @@
+use ethers::types::{Address, H256};
+use rust_decimal::Decimal;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum SigningError {
+    InvalidSignature(String),
+    SignerMismatch { order_signer: Address, wallet: Address },
+    SignatureMismatch {
+        order_hash: H256,
+        expected: Address,
+        recovered: Address,
+    },
+}
+
+impl fmt::Display for SigningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigningError::InvalidSignature(msg) => write!(f, "Invalid signature: {}", msg),
+            SigningError::SignerMismatch { order_signer, wallet } => {
+                write!(f, "Order signer {:?} is not the wallet {:?}", order_signer, wallet)
+            }
+            SigningError::SignatureMismatch {
+                order_hash,
+                expected,
+                recovered,
+            } => write!(
+                f,
+                "Signature for order {:?} recovers to {:?}, expected {:?}",
+                order_hash, recovered, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SigningError {}
+
+/// Order parameters the exchange would reject for the market's rules
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderValidationError {
+    PriceOutOfRange { price: Decimal, min: Decimal, max: Decimal },
+    PriceOffTick { price: Decimal, tick_size: Decimal },
+    BelowMinSize { size: Decimal, min_size: Decimal },
+    SizeRoundsToZero { size: Decimal },
+}
+
+impl fmt::Display for OrderValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderValidationError::PriceOutOfRange { price, min, max } => {
+                write!(f, "Price {} outside [{}, {}]", price, min, max)
+            }
+            OrderValidationError::PriceOffTick { price, tick_size } => {
+                write!(f, "Price {} is not a multiple of tick size {}", price, tick_size)
+            }
+            OrderValidationError::BelowMinSize { size, min_size } => {
+                write!(f, "Size {} below market minimum {}", size, min_size)
+            }
+            OrderValidationError::SizeRoundsToZero { size } => {
+                write!(f, "Size {} rounds to zero", size)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrderValidationError {}
@@ -0,0 +1,102 @@
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::domain::order::Side;
+use crate::wallet::signer::ClobOrder;
+
+// ==================================================
+// EXCHANGE FEES
+// ==================================================
+// The CTF exchange charges `rate * min(p, 1 - p)` per outcome token,
+// so fees are symmetric around 0.5 and vanish near 0 and 1:
+//
+//   SELL: fee in USDC   = rate * min(p, 1 - p) * size
+//   BUY:  fee in tokens = rate * min(p, 1 - p) * size / p
+//
+// `rate` is the order's `fee_rate_bps` / 10_000.
+
+/// Bisection steps when solving for a fee-adjusted price
+const SOLVE_ITERATIONS: usize = 40;
+
+/// Expected fee on one order, in the asset it is charged in plus its
+/// USDC value
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeeEstimate {
+    /// Outcome tokens withheld (BUY)
+    pub tokens: Decimal,
+    /// USDC withheld (SELL) or the USDC value of `tokens` (BUY)
+    pub usdc: Decimal,
+}
+
+fn rate(fee_rate_bps: u32) -> Decimal {
+    Decimal::from(fee_rate_bps) / dec!(10_000)
+}
+
+/// Fee for trading `size` tokens at `price`
+pub fn fee(side: &Side, price: Decimal, size: Decimal, fee_rate_bps: u32) -> FeeEstimate {
+    if price <= Decimal::ZERO || price >= Decimal::ONE {
+        return FeeEstimate::default();
+    }
+    let usdc = rate(fee_rate_bps) * price.min(Decimal::ONE - price) * size;
+    match side {
+        Side::Buy => FeeEstimate {
+            tokens: usdc / price,
+            usdc,
+        },
+        Side::Sell => FeeEstimate {
+            tokens: Decimal::ZERO,
+            usdc,
+        },
+    }
+}
+
+/// Expected fee if `order` fills completely
+pub fn order_fee(order: &ClobOrder) -> FeeEstimate {
+    let (price, size) = order.price_and_size();
+    let side = if order.side == 0 { Side::Buy } else { Side::Sell };
+    fee(
+        &side,
+        Decimal::from_f64_retain(price).unwrap_or_default(),
+        Decimal::from_f64_retain(size).unwrap_or_default(),
+        order.fee_rate_bps.as_u32(),
+    )
+}
+
+/// Per-token economics after fees at limit `price`: USDC paid per token
+/// actually received (BUY) or USDC received per token sold (SELL)
+pub fn net_price(side: &Side, price: Decimal, fee_rate_bps: u32) -> Decimal {
+    let f = fee(side, price, Decimal::ONE, fee_rate_bps);
+    match side {
+        Side::Buy => price / (Decimal::ONE - f.tokens),
+        Side::Sell => price - f.usdc,
+    }
+}
+
+/// Limit price whose post-fee economics match `target`: the highest BUY
+/// price costing at most `target` per token received, or the lowest SELL
+/// price netting at least `target` per token. `None` if no price in (0, 1)
+/// gets there. Net price is increasing in the limit on both sides.
+pub fn fee_adjusted_price(side: &Side, target: Decimal, fee_rate_bps: u32) -> Option<Decimal> {
+    if fee_rate_bps == 0 {
+        return Some(target);
+    }
+
+    let (mut lo, mut hi) = (Decimal::ZERO, Decimal::ONE);
+    for _ in 0..SOLVE_ITERATIONS {
+        let mid = (lo + hi) / dec!(2);
+        let ok = match side {
+            Side::Buy => net_price(side, mid, fee_rate_bps) <= target,
+            Side::Sell => net_price(side, mid, fee_rate_bps) >= target,
+        };
+        match (side, ok) {
+            (Side::Buy, true) | (Side::Sell, false) => lo = mid,
+            (Side::Buy, false) | (Side::Sell, true) => hi = mid,
+        }
+    }
+
+    let price = match side {
+        Side::Buy => lo,
+        Side::Sell => hi,
+    };
+    (price > Decimal::ZERO && price < Decimal::ONE).then_some(price)
+}
@@ -0,0 +1,8 @@
+pub mod signer;
+pub mod errors;
+pub mod fees;
+pub mod order_builder;
+pub mod balance;
+pub mod proxy;
+pub mod safe;
+pub mod allowance;
@@ -0,0 +1,379 @@
+use anyhow::{anyhow, Result};
+use ethers::types::{Address, U256};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal_macros::dec;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::domain::order::Side;
+use crate::wallet::errors::OrderValidationError;
+use crate::wallet::fees;
+use crate::wallet::signer::{ClobOrder, OrderType, SignatureType};
+
+/// Size precision accepted by the CLOB (2 dp). Amounts carry two more
+/// decimals than the tick size (4 dp on the default 0.01 tick).
+const SIZE_DECIMALS: u32 = 2;
+const AMOUNT_DECIMALS: u32 = 4;
+
+/// Shortest GTD lifetime the exchange accepts: it applies a 60s
+/// security threshold to expirations.
+pub const MIN_GTD_SECS: u64 = 61;
+
+/// Low bits of the salt reserved for the per-millisecond counter.
+const SALT_COUNTER_BITS: u32 = 12;
+
+// ==================================================
+// MARKET RULES
+// ==================================================
+
+/// Per-market price grid, minimum size and exchange, as served by
+/// `GET /book`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketRules {
+    pub tick_size: Decimal,
+    pub min_size: Decimal,
+    /// Orders go to (and are signed for) the neg-risk exchange
+    pub neg_risk: bool,
+}
+
+impl Default for MarketRules {
+    fn default() -> Self {
+        Self {
+            tick_size: dec!(0.01),
+            min_size: Decimal::ZERO,
+            neg_risk: false,
+        }
+    }
+}
+
+impl MarketRules {
+    fn amount_decimals(&self) -> u32 {
+        self.tick_size.normalize().scale() + SIZE_DECIMALS
+    }
+
+    /// Check (or with `round`, snap) `price` onto the tick grid.
+    /// Rounding is passive: BUY rounds down, SELL rounds up.
+    pub fn validate_price(
+        &self,
+        side: &Side,
+        price: Decimal,
+        round: bool,
+    ) -> std::result::Result<Decimal, OrderValidationError> {
+        let tick = self.tick_size;
+        let mut price = price;
+
+        if !(price / tick).fract().is_zero() {
+            if !round {
+                return Err(OrderValidationError::PriceOffTick { price, tick_size: tick });
+            }
+            let strategy = match side {
+                Side::Buy => RoundingStrategy::ToNegativeInfinity,
+                Side::Sell => RoundingStrategy::ToPositiveInfinity,
+            };
+            price = (price / tick).round_dp_with_strategy(0, strategy) * tick;
+        }
+
+        let (min, max) = (tick, Decimal::ONE - tick);
+        if price < min || price > max {
+            return Err(OrderValidationError::PriceOutOfRange { price, min, max });
+        }
+        Ok(price.normalize())
+    }
+
+    /// Round `size` down to the CLOB's precision and enforce the minimum
+    pub fn validate_size(&self, size: Decimal) -> std::result::Result<Decimal, OrderValidationError> {
+        let rounded = size.round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+        if rounded <= Decimal::ZERO {
+            return Err(OrderValidationError::SizeRoundsToZero { size });
+        }
+        if rounded < self.min_size {
+            return Err(OrderValidationError::BelowMinSize {
+                size: rounded,
+                min_size: self.min_size,
+            });
+        }
+        Ok(rounded)
+    }
+}
+
+// ==================================================
+// SALT GENERATOR
+// ==================================================
+
+/// Strictly increasing order salts, shared across builder clones.
+///
+/// Salts are `(unix_ms << 12) | counter`, which keeps them unique
+/// across restarts and below 2^53 so they survive JSON number parsing.
+#[derive(Debug, Clone, Default)]
+pub struct SaltGenerator {
+    last: Arc<AtomicU64>,
+}
+
+impl SaltGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next(&self) -> u64 {
+        let candidate = now_ms() << SALT_COUNTER_BITS;
+        let prev = self
+            .last
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+                Some(candidate.max(last + 1))
+            })
+            .unwrap_or_default();
+        candidate.max(prev + 1)
+    }
+}
+
+/// =================================================
+/// Order Builder
+/// =================================================
+/// Turns (token, side, price, size) into the maker/taker
+/// amounts of a signable `ClobOrder`.
+///
+/// `funder` is the wallet holding the collateral; it only becomes
+/// the order maker for proxy signature types.
+#[derive(Debug, Clone)]
+pub struct OrderBuilder {
+    funder: Address,
+    signer: Address,
+    signature_type: SignatureType,
+    fee_rate_bps: u32,
+    order_type: OrderType,
+    post_only: bool,
+    expiration_secs: Option<u64>,
+    rules: Option<MarketRules>,
+    round_to_tick: bool,
+    fee_adjusted: bool,
+    nonce: U256,
+    salts: SaltGenerator,
+}
+
+impl OrderBuilder {
+    pub fn new(funder: Address, signer: Address) -> Self {
+        Self {
+            funder,
+            signer,
+            signature_type: SignatureType::Eoa,
+            fee_rate_bps: 0,
+            order_type: OrderType::default(),
+            post_only: false,
+            expiration_secs: None,
+            rules: None,
+            round_to_tick: false,
+            fee_adjusted: false,
+            nonce: U256::zero(),
+            salts: SaltGenerator::new(),
+        }
+    }
+
+    /// Share a salt generator between builders (e.g. one per strategy)
+    pub fn salts(mut self, salts: SaltGenerator) -> Self {
+        self.salts = salts;
+        self
+    }
+
+    /// Exchange nonce; bumping it on-chain invalidates all older orders
+    pub fn nonce(mut self, nonce: U256) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn signature_type(mut self, signature_type: SignatureType) -> Self {
+        self.signature_type = signature_type;
+        self
+    }
+
+    /// EOA orders are made by the signer itself, proxy orders by the funder
+    fn maker(&self) -> Address {
+        if self.signature_type.uses_proxy() {
+            self.funder
+        } else {
+            self.signer
+        }
+    }
+
+    pub fn fee_rate_bps(mut self, bps: u32) -> Self {
+        self.fee_rate_bps = bps;
+        self
+    }
+
+    /// Time-in-force for built orders (FOK by default)
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
+
+    /// GTD order expiring `secs` seconds after it is built.
+    ///
+    /// The exchange applies a 60s security threshold, so anything
+    /// below `MIN_GTD_SECS` is rejected as already expired.
+    pub fn expires_in(mut self, secs: u64) -> Self {
+        self.order_type = OrderType::Gtd;
+        self.expiration_secs = Some(secs);
+        self
+    }
+
+    /// Mark built orders post-only: they must rest on the book and are
+    /// refused (or repriced) if they would cross it. GTC/GTD only.
+    pub fn post_only(mut self) -> Self {
+        self.post_only = true;
+        self
+    }
+
+    /// Validate built orders against the market's tick size and
+    /// minimum size instead of waiting for the exchange to reject them
+    pub fn market_rules(mut self, rules: MarketRules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Snap off-grid prices to the tick (passively) instead of failing
+    pub fn round_to_tick(mut self) -> Self {
+        self.round_to_tick = true;
+        self
+    }
+
+    /// Treat the price passed to `build` as the target price after fees
+    /// and post whatever limit achieves it at `fee_rate_bps`, snapped
+    /// passively to the tick (see `fees::fee_adjusted_price`)
+    pub fn fee_adjusted(mut self) -> Self {
+        self.fee_adjusted = true;
+        self
+    }
+
+    /// Build an unsigned limit order.
+    ///
+    /// `token_id` is the decimal ERC-1155 id as returned by Gamma
+    /// (`clobTokenIds`); `size` is in outcome tokens.
+    pub fn build(
+        &self,
+        token_id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<ClobOrder> {
+        let price = if self.fee_adjusted {
+            let adjusted = fees::fee_adjusted_price(&side, price, self.fee_rate_bps)
+                .ok_or_else(|| {
+                    anyhow!("No price nets {} after a {} bps fee", price, self.fee_rate_bps)
+                })?;
+            // Off-grid by construction; without market rules use the default tick
+            match &self.rules {
+                Some(_) => adjusted,
+                None => MarketRules::default().validate_price(&side, adjusted, true)?,
+            }
+        } else {
+            price
+        };
+
+        let (price, size, amount_decimals) = match &self.rules {
+            Some(rules) => (
+                rules.validate_price(&side, price, self.round_to_tick || self.fee_adjusted)?,
+                rules.validate_size(size)?,
+                rules.amount_decimals(),
+            ),
+            None => {
+                if price <= Decimal::ZERO || price >= Decimal::ONE {
+                    return Err(anyhow!("Price {} outside (0, 1)", price));
+                }
+                (price, size, AMOUNT_DECIMALS)
+            }
+        };
+
+        if self.post_only && !self.order_type.is_resting() {
+            return Err(anyhow!(
+                "Post-only order must rest, not {}",
+                self.order_type.as_str()
+            ));
+        }
+
+        let token_id = U256::from_dec_str(token_id)
+            .map_err(|e| anyhow!("Invalid token ID {}: {}", token_id, e))?;
+
+        let (maker_amount, taker_amount) = order_amounts(&side, price, size, amount_decimals)?;
+
+        // Only GTD orders carry an expiration; the CLOB rejects it on others
+        let expiration = match (self.order_type, self.expiration_secs) {
+            (OrderType::Gtd, Some(secs)) => U256::from(now_ts() + secs),
+            (OrderType::Gtd, None) => {
+                return Err(anyhow!("GTD order needs an expiration (expires_in)"));
+            }
+            _ => U256::zero(),
+        };
+
+        Ok(ClobOrder {
+            salt: U256::from(self.salts.next()),
+            maker: self.maker(),
+            signer: self.signer,
+            taker: Address::zero(),
+            token_id,
+            maker_amount,
+            taker_amount,
+            expiration,
+            nonce: self.nonce,
+            fee_rate_bps: U256::from(self.fee_rate_bps),
+            side: match side {
+                Side::Buy => 0,
+                Side::Sell => 1,
+            },
+            signature_type: self.signature_type.into(),
+            order_type: self.order_type,
+            post_only: self.post_only,
+            neg_risk: self.rules.is_some_and(|r| r.neg_risk),
+        })
+    }
+}
+
+// ==================================================
+// Helpers
+// ==================================================
+
+/// BUY:  maker = USDC paid,   taker = tokens received
+/// SELL: maker = tokens sold, taker = USDC received
+fn order_amounts(
+    side: &Side,
+    price: Decimal,
+    size: Decimal,
+    amount_decimals: u32,
+) -> Result<(U256, U256)> {
+    let size = size.round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+    if size <= Decimal::ZERO {
+        return Err(anyhow!("Order size rounds to zero"));
+    }
+
+    let notional = (size * price)
+        .round_dp_with_strategy(amount_decimals, RoundingStrategy::ToZero);
+
+    let (maker, taker) = match side {
+        Side::Buy => (notional, size),
+        Side::Sell => (size, notional),
+    };
+
+    Ok((to_base_units(maker)?, to_base_units(taker)?))
+}
+
+fn to_base_units(v: Decimal) -> Result<U256> {
+    let units = (v * dec!(1_000_000))
+        .trunc()
+        .to_u128()
+        .ok_or_else(|| anyhow!("Amount {} out of range", v))?;
+    Ok(U256::from(units))
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
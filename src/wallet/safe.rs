@@ -0,0 +1,103 @@
+use anyhow::{anyhow, Result};
+use ethers::prelude::*;
+use ethers::types::{Address, Bytes, TransactionReceipt, U256};
+use log::info;
+use std::sync::Arc;
+
+use crate::net::RpcProvider;
+
+type SignerClient = SignerMiddleware<RpcProvider, LocalWallet>;
+
+// ================================
+// GNOSIS SAFE ABI
+// ================================
+abigen!(
+    GnosisSafe,
+    r#"[
+        function nonce() view returns (uint256)
+        function getThreshold() view returns (uint256)
+        function isOwner(address owner) view returns (bool)
+        function getTransactionHash(address to, uint256 value, bytes data, uint8 operation, uint256 safeTxGas, uint256 baseGas, uint256 gasPrice, address gasToken, address refundReceiver, uint256 _nonce) view returns (bytes32)
+        function execTransaction(address to, uint256 value, bytes data, uint8 operation, uint256 safeTxGas, uint256 baseGas, uint256 gasPrice, address gasToken, address refundReceiver, bytes signatures) payable returns (bool)
+    ]"#
+);
+
+/// Safe operation type for a plain CALL
+const OP_CALL: u8 = 0;
+
+// ================================
+// EXEC THROUGH SAFE (1-of-N OWNER)
+// ================================
+
+/// Execute `to.call(data)` from the Safe, signed by the connected EOA.
+///
+/// Only works when the EOA is an owner and the Safe threshold is 1,
+/// which is how Polymarket provisions browser-wallet proxies.
+pub async fn exec_transaction(
+    client: Arc<SignerClient>,
+    safe: Address,
+    to: Address,
+    data: Bytes,
+) -> Result<TransactionReceipt> {
+    let contract = GnosisSafe::new(safe, client.clone());
+    let owner = client.address();
+
+    if !contract.is_owner(owner).call().await? {
+        return Err(anyhow!("❌ {:?} is not an owner of Safe {:?}", owner, safe));
+    }
+
+    let threshold = contract.get_threshold().call().await?;
+    if threshold != U256::one() {
+        return Err(anyhow!(
+            "❌ Safe {:?} needs {} signatures — execute approvals in Polymarket UI",
+            safe,
+            threshold
+        ));
+    }
+
+    let nonce = contract.nonce().call().await?;
+    let tx_hash = contract
+        .get_transaction_hash(
+            to,
+            U256::zero(),
+            data.clone(),
+            OP_CALL,
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            Address::zero(),
+            Address::zero(),
+            nonce,
+        )
+        .call()
+        .await?;
+
+    // Plain ECDSA over the Safe tx hash (v = 27/28)
+    let sig = client.signer().sign_hash(H256::from(tx_hash))?;
+
+    info!("🔐 Executing Safe tx (nonce {}) → {:?}", nonce, to);
+
+    let receipt = contract
+        .exec_transaction(
+            to,
+            U256::zero(),
+            data,
+            OP_CALL,
+            U256::zero(),
+            U256::zero(),
+            U256::zero(),
+            Address::zero(),
+            Address::zero(),
+            Bytes::from(sig.to_vec()),
+        )
+        .send()
+        .await?
+        .await?
+        .ok_or_else(|| anyhow!("Safe tx dropped from mempool"))?;
+
+    if receipt.status != Some(1.into()) {
+        return Err(anyhow!("❌ Safe tx reverted: {:?}", receipt.transaction_hash));
+    }
+
+    Ok(receipt)
+}
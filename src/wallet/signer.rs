@@ -0,0 +1,353 @@
+use anyhow::Result;
+use ethers::abi::{encode, Token};
+use ethers::prelude::*;
+use ethers::types::{Address, H256, U256};
+use ethers::utils::keccak256;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::wallet::errors::SigningError;
+
+// ==================================================
+// CONSTANTS (Polygon / Polymarket)
+// ==================================================
+
+const POLYMARKET_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+const POLYMARKET_NEG_RISK_EXCHANGE: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+
+const DOMAIN_NAME: &str = "Polymarket CTF Exchange";
+const DOMAIN_VERSION: &str = "1";
+
+const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+const ORDER_TYPE: &str = "Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)";
+
+// ==================================================
+// SIGNER
+// ==================================================
+
+#[derive(Debug, Clone)]
+pub struct WalletSigner {
+    wallet: LocalWallet,
+    chain_id: u64,
+    exchange: Address,
+    neg_risk_exchange: Address,
+}
+
+impl WalletSigner {
+    pub fn new(private_key: &str, chain_id: u64) -> Result<Self> {
+        let wallet: LocalWallet = private_key.parse()?;
+        Ok(Self {
+            wallet: wallet.with_chain_id(chain_id),
+            chain_id,
+            exchange: Address::from_str(POLYMARKET_EXCHANGE)?,
+            neg_risk_exchange: Address::from_str(POLYMARKET_NEG_RISK_EXCHANGE)?,
+        })
+    }
+
+    /// Use a different verifying contract for the EIP-712 domain.
+    pub fn with_exchange(mut self, exchange: Address) -> Self {
+        self.exchange = exchange;
+        self
+    }
+
+    /// Use a different verifying contract for neg-risk orders.
+    pub fn with_neg_risk_exchange(mut self, exchange: Address) -> Self {
+        self.neg_risk_exchange = exchange;
+        self
+    }
+
+    pub fn address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Verifying contract of `order`: the neg-risk exchange for orders
+    /// on neg-risk markets, the CTF exchange otherwise
+    pub fn exchange(&self, order: &ClobOrder) -> Address {
+        if order.neg_risk {
+            self.neg_risk_exchange
+        } else {
+            self.exchange
+        }
+    }
+
+    /// keccak256(EIP712Domain) for the exchange `order` goes to.
+    pub fn domain_separator(&self, order: &ClobOrder) -> H256 {
+        domain_separator(self.chain_id, self.exchange(order))
+    }
+
+    /// Canonical order hash (the EIP-712 digest) under its exchange's domain.
+    pub fn order_hash(&self, order: &ClobOrder) -> H256 {
+        order.digest(self.domain_separator(order))
+    }
+
+    /// Sign the EIP-712 digest of `order` with the EOA key.
+    ///
+    /// The signature is checked against the order hash before it is
+    /// returned, so encoding bugs surface here instead of at the exchange.
+    pub async fn sign_order(&self, order: &ClobOrder) -> Result<Signature> {
+        if order.signer != self.address() {
+            return Err(SigningError::SignerMismatch {
+                order_signer: order.signer,
+                wallet: self.address(),
+            }
+            .into());
+        }
+
+        let sig = self.wallet.sign_hash(self.order_hash(order))?;
+        verify_order_signature(order, &sig, self.domain_separator(order))?;
+        Ok(sig)
+    }
+}
+
+// ==================================================
+// EIP-712 HASHING
+// ==================================================
+
+pub fn domain_separator(chain_id: u64, exchange: Address) -> H256 {
+    H256::from(keccak256(encode(&[
+        Token::FixedBytes(keccak256(EIP712_DOMAIN_TYPE).to_vec()),
+        Token::FixedBytes(keccak256(DOMAIN_NAME).to_vec()),
+        Token::FixedBytes(keccak256(DOMAIN_VERSION).to_vec()),
+        Token::Uint(U256::from(chain_id)),
+        Token::Address(exchange),
+    ])))
+}
+
+// ==================================================
+// SIGNATURE TYPES
+// ==================================================
+
+/// How the exchange validates `signer` against `maker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SignatureType {
+    /// EOA signs and funds the order (maker == signer)
+    Eoa = 0,
+    /// Polymarket proxy wallet (email / Magic login) funds, EOA owner signs
+    PolyProxy = 1,
+    /// Gnosis Safe proxy (browser wallet login) funds, Safe owner signs
+    PolyGnosisSafe = 2,
+}
+
+impl SignatureType {
+    /// Read `SIGNATURE_TYPE` (EOA | POLY_PROXY | POLY_GNOSIS_SAFE or 0/1/2).
+    /// Defaults to POLY_GNOSIS_SAFE, the usual setup behind `PROXY_WALLET`.
+    pub fn from_env() -> Self {
+        match std::env::var("SIGNATURE_TYPE")
+            .unwrap_or_default()
+            .to_uppercase()
+            .as_str()
+        {
+            "EOA" | "0" => SignatureType::Eoa,
+            "POLY_PROXY" | "1" => SignatureType::PolyProxy,
+            _ => SignatureType::PolyGnosisSafe,
+        }
+    }
+
+    /// Funds come from a proxy contract rather than the signing EOA
+    pub fn uses_proxy(&self) -> bool {
+        !matches!(self, SignatureType::Eoa)
+    }
+}
+
+impl From<SignatureType> for u8 {
+    fn from(t: SignatureType) -> u8 {
+        t as u8
+    }
+}
+
+// ==================================================
+// ORDER TYPES
+// ==================================================
+
+/// Time-in-force sent alongside the signed order. Not part of the
+/// EIP-712 struct, so changing it does not change the order hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OrderType {
+    /// Good-til-cancelled: rests on the book
+    Gtc,
+    /// Good-til-date: rests until `expiration`
+    Gtd,
+    /// Fill-or-kill: fills completely right away or is cancelled
+    #[default]
+    Fok,
+    /// Fill-and-kill: fills what it can right away, cancels the rest
+    Fak,
+}
+
+impl OrderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderType::Gtc => "GTC",
+            OrderType::Gtd => "GTD",
+            OrderType::Fok => "FOK",
+            OrderType::Fak => "FAK",
+        }
+    }
+
+    /// Whether unfilled size stays on the book
+    pub fn is_resting(&self) -> bool {
+        matches!(self, OrderType::Gtc | OrderType::Gtd)
+    }
+}
+
+/// Recover the signer of `sig` over the order hash and check it is
+/// `order.signer`. Returns the order hash on success.
+pub fn verify_order_signature(
+    order: &ClobOrder,
+    sig: &Signature,
+    domain_separator: H256,
+) -> std::result::Result<H256, SigningError> {
+    let order_hash = order.digest(domain_separator);
+
+    let recovered = sig
+        .recover(RecoveryMessage::Hash(order_hash))
+        .map_err(|e| SigningError::InvalidSignature(e.to_string()))?;
+
+    if recovered != order.signer {
+        return Err(SigningError::SignatureMismatch {
+            order_hash,
+            expected: order.signer,
+            recovered,
+        });
+    }
+
+    Ok(order_hash)
+}
+
+/// =================================================
+/// Polymarket CTF Exchange Order (EIP-712)
+/// =================================================
+/// Field order and types mirror the on-chain `Order` struct.
+/// Amounts are in 6-decimal base units (USDC / outcome tokens).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClobOrder {
+    pub salt: U256,
+    pub maker: Address,         // funder (proxy wallet or EOA)
+    pub signer: Address,        // EOA that signs
+    pub taker: Address,         // zero address = public order
+    pub token_id: U256,         // ERC-1155 position id
+    pub maker_amount: U256,     // what we give
+    pub taker_amount: U256,     // what we receive
+    pub expiration: U256,       // unix seconds, 0 = none
+    pub nonce: U256,            // exchange nonce (for onchain cancels)
+    pub fee_rate_bps: U256,
+    pub side: u8,               // 0 = BUY, 1 = SELL
+    pub signature_type: u8,     // see SignatureType
+    #[serde(default)]
+    pub order_type: OrderType,  // not signed
+    #[serde(default)]
+    pub post_only: bool,        // not signed
+    #[serde(default)]
+    pub neg_risk: bool,         // not signed; picks the verifying contract
+}
+
+impl ClobOrder {
+    /// keccak256(abi.encode(ORDER_TYPEHASH, ...fields))
+    pub fn struct_hash(&self) -> H256 {
+        H256::from(keccak256(encode(&[
+            Token::FixedBytes(keccak256(ORDER_TYPE).to_vec()),
+            Token::Uint(self.salt),
+            Token::Address(self.maker),
+            Token::Address(self.signer),
+            Token::Address(self.taker),
+            Token::Uint(self.token_id),
+            Token::Uint(self.maker_amount),
+            Token::Uint(self.taker_amount),
+            Token::Uint(self.expiration),
+            Token::Uint(self.nonce),
+            Token::Uint(self.fee_rate_bps),
+            Token::Uint(U256::from(self.side)),
+            Token::Uint(U256::from(self.signature_type)),
+        ])))
+    }
+
+    /// keccak256("\x19\x01" ‖ domainSeparator ‖ structHash)
+    pub fn digest(&self, domain_separator: H256) -> H256 {
+        let mut buf = Vec::with_capacity(66);
+        buf.extend_from_slice(&[0x19, 0x01]);
+        buf.extend_from_slice(domain_separator.as_bytes());
+        buf.extend_from_slice(self.struct_hash().as_bytes());
+        H256::from(keccak256(buf))
+    }
+
+    /// Stable key for a submission attempt: retries of the same signed
+    /// order share it, while any rebuilt order gets a fresh salt and key.
+    pub fn idempotency_key(&self) -> String {
+        format!("{:?}", self.struct_hash())
+    }
+
+    /// (price, size in outcome tokens) implied by the maker/taker amounts
+    pub fn price_and_size(&self) -> (f64, f64) {
+        let maker = self.maker_amount.as_u128() as f64 / 1_000_000.0;
+        let taker = self.taker_amount.as_u128() as f64 / 1_000_000.0;
+        if self.side == 0 {
+            // BUY: USDC in, tokens out
+            (maker / taker, taker)
+        } else {
+            // SELL: tokens in, USDC out
+            (taker / maker, maker)
+        }
+    }
+
+    /// `price_and_size` as exact decimals, for comparing against limits
+    pub fn exact_price_and_size(&self) -> (Decimal, Decimal) {
+        let amount = |units: U256| Decimal::from_i128_with_scale(units.low_u128() as i128, 6);
+        let (maker, taker) = (amount(self.maker_amount), amount(self.taker_amount));
+        let (usdc, tokens) = if self.side == 0 { (maker, taker) } else { (taker, maker) };
+        if tokens.is_zero() {
+            return (Decimal::ZERO, Decimal::ZERO);
+        }
+        ((usdc / tokens).normalize(), tokens.normalize())
+    }
+
+    pub fn side_str(&self) -> &'static str {
+        if self.side == 0 { "BUY" } else { "SELL" }
+    }
+
+    /// JSON body expected by the CLOB `POST /order` endpoint.
+    pub fn to_payload(&self, sig: &Signature) -> SignedOrderPayload {
+        SignedOrderPayload {
+            salt: self.salt.as_u64(),
+            maker: format!("{:?}", self.maker),
+            signer: format!("{:?}", self.signer),
+            taker: format!("{:?}", self.taker),
+            token_id: self.token_id.to_string(),
+            maker_amount: self.maker_amount.to_string(),
+            taker_amount: self.taker_amount.to_string(),
+            expiration: self.expiration.to_string(),
+            nonce: self.nonce.to_string(),
+            fee_rate_bps: self.fee_rate_bps.to_string(),
+            side: self.side_str().to_string(),
+            signature_type: self.signature_type,
+            signature: format!("0x{}", sig),
+        }
+    }
+}
+
+/// ---------- Signed Payload ----------
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedOrderPayload {
+    pub salt: u64,
+    pub maker: String,
+    pub signer: String,
+    pub taker: String,
+    pub token_id: String,
+    pub maker_amount: String,
+    pub taker_amount: String,
+    pub expiration: String,
+    pub nonce: String,
+    pub fee_rate_bps: String,
+    pub side: String,
+    pub signature_type: u8,
+    pub signature: String,
+}
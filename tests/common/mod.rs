@@ -0,0 +1,300 @@
+#![allow(dead_code)]
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Once};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+use polymarket_15m_arbitrage_bot::clob::ApiCredentials;
+use polymarket_15m_arbitrage_bot::execution::ClobClient;
+
+// ==================================================
+// REGRESSION HARNESS
+// ==================================================
+// Canned exchange sessions for the integration tests:
+//
+//   tests/fixtures/*.csv   market-channel recordings (`Recorder` format),
+//                          replayed through backtests or straight into a
+//                          strategy's books
+//   tests/fixtures/*.json  recorded CLOB HTTP responses, served by a
+//                          `FixtureServer` that `ClobClient` trades against
+//   tests/golden/*.txt     what the code sent last time it was right
+//
+// A test renders what it emitted (orders, cancels) as text and compares
+// it with its golden file. When a change in trading behaviour is
+// intended, rerun with `UPDATE_GOLDEN=1` and review the diff.
+
+/// Signing key of every test client; the address is deterministic, so
+/// payloads are too (salts and signatures aside)
+pub const TEST_KEY: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+pub fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+/// Compare `actual` with `tests/golden/<name>.txt`; `UPDATE_GOLDEN=1`
+/// rewrites the file instead
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.txt", name));
+    if std::env::var("UPDATE_GOLDEN").as_deref() == Ok("1") {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {} (run with UPDATE_GOLDEN=1 to create it)", path.display(), e));
+    if expected != actual {
+        panic!(
+            "{} changed; rerun with UPDATE_GOLDEN=1 if intended\n--- expected\n{}\n--- actual\n{}",
+            name, expected, actual
+        );
+    }
+}
+
+/// Keep the client's persisted state out of the working tree
+fn isolate_state() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let dir = std::env::temp_dir().join(format!("oe-regression-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::env::set_var("ORDER_INTENTS_PATH", dir.join("order_intents.json"));
+    });
+}
+
+/// Offline client with no network access at all
+pub fn offline_client() -> ClobClient {
+    isolate_state();
+    ClobClient::offline(TEST_KEY).unwrap()
+}
+
+/// Client trading through the native API at `server`
+pub fn fixture_client(server: &FixtureServer) -> ClobClient {
+    let creds = ApiCredentials::new(
+        "test-key".to_string(),
+        // base64url of "fixture-secret"
+        "Zml4dHVyZS1zZWNyZXQ=".to_string(),
+        "test-passphrase".to_string(),
+    );
+    offline_client().api(&server.url, creds).read_only(false)
+}
+
+// ==================================================
+// HTTP FIXTURES
+// ==================================================
+
+/// `{ "routes": [ { "method", "path", "responses": [ { "status", "body", "delay_ms" } ] } ] }`
+///
+/// Each route answers with its responses in order and then keeps
+/// repeating the last one. Paths match without the query string.
+/// `delay_ms` holds the answer back, to make the client time out.
+#[derive(Debug, Deserialize)]
+struct Fixture {
+    routes: Vec<Route>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Route {
+    method: String,
+    path: String,
+    responses: Vec<Response>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Response {
+    #[serde(default = "ok")]
+    status: u16,
+    body: Value,
+    #[serde(default)]
+    delay_ms: u64,
+}
+
+fn ok() -> u16 {
+    200
+}
+
+/// One request the server received
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub body: Value,
+}
+
+#[derive(Default)]
+struct Routes {
+    // (method, path) → responses left, last one sticky
+    responses: HashMap<(String, String), Vec<Response>>,
+    requests: Vec<Recorded>,
+}
+
+/// Local HTTP server answering from a recorded fixture
+pub struct FixtureServer {
+    pub url: String,
+    routes: Arc<Mutex<Routes>>,
+}
+
+impl FixtureServer {
+    pub async fn start(name: &str) -> Self {
+        let text = std::fs::read_to_string(fixture(name)).unwrap();
+        let fixture: Fixture = serde_json::from_str(&text).unwrap();
+        let mut routes = Routes::default();
+        for route in fixture.routes {
+            routes
+                .responses
+                .insert((route.method.to_uppercase(), route.path), route.responses);
+        }
+        let routes = Arc::new(Mutex::new(routes));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let shared = routes.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let routes = shared.clone();
+                tokio::spawn(async move {
+                    let (read, mut write) = stream.into_split();
+                    let mut read = BufReader::new(read);
+                    // Keep-alive: several requests per connection
+                    while let Some(request) = read_request(&mut read).await {
+                        let response = answer(&routes, request);
+                        tokio::time::sleep(std::time::Duration::from_millis(response.delay_ms)).await;
+                        let body = response.body.to_string();
+                        let head = format!(
+                            "HTTP/1.1 {} FIXTURE\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                            response.status,
+                            body.len()
+                        );
+                        if write.write_all(head.as_bytes()).await.is_err()
+                            || write.write_all(body.as_bytes()).await.is_err()
+                        {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        Self { url, routes }
+    }
+
+    /// Answer `method path` with `body` from now on, for paths only known
+    /// once the test runs (order hashes)
+    pub fn route(&self, method: &str, path: &str, body: Value) {
+        let response = Response { status: 200, body, delay_ms: 0 };
+        self.routes
+            .lock()
+            .unwrap()
+            .responses
+            .insert((method.to_uppercase(), path.to_string()), vec![response]);
+    }
+
+    /// Every request received, in arrival order
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.routes.lock().unwrap().requests.clone()
+    }
+
+    /// The requests that change state (anything but GET), one line each:
+    /// orders with what decides their economics, cancels with their ids
+    /// sorted. Salts, signatures and timestamps are left out.
+    pub fn transcript(&self) -> String {
+        let mut out = String::new();
+        for r in self.requests().iter().filter(|r| r.method != "GET") {
+            let line = match (r.method.as_str(), r.path.as_str()) {
+                ("POST", "/order") => order_line(&r.body),
+                ("POST", "/orders") => {
+                    let orders: Vec<String> = r.body.as_array().into_iter().flatten().map(order_line).collect();
+                    format!("batch [{}]", orders.join("; "))
+                }
+                (_, "/order") => format!("cancel {}", r.body["orderID"].as_str().unwrap_or_default()),
+                (_, "/orders") => {
+                    let mut ids: Vec<&str> =
+                        r.body.as_array().into_iter().flatten().filter_map(Value::as_str).collect();
+                    ids.sort();
+                    format!("cancel [{}]", ids.join(", "))
+                }
+                (method, path) => format!("{} {} {}", method, path, r.body),
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn order_line(body: &Value) -> String {
+    let order = &body["order"];
+    let field = |name: &str| match &order[name] {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    format!(
+        "order {} {} token={} maker_amount={} taker_amount={} expiration={} fee_bps={} maker={} sig_type={}",
+        body["orderType"].as_str().unwrap_or_default(),
+        field("side"),
+        field("tokenId"),
+        field("makerAmount"),
+        field("takerAmount"),
+        field("expiration"),
+        field("feeRateBps"),
+        field("maker"),
+        field("signatureType"),
+    )
+}
+
+fn answer(routes: &Mutex<Routes>, request: Recorded) -> Response {
+    let mut routes = routes.lock().unwrap();
+    let key = (request.method.clone(), request.path.clone());
+    routes.requests.push(request);
+    match routes.responses.get_mut(&key) {
+        Some(responses) if responses.len() > 1 => responses.remove(0),
+        Some(responses) if !responses.is_empty() => responses[0].clone(),
+        _ => Response {
+            status: 404,
+            body: serde_json::json!({ "error": format!("no fixture for {} {}", key.0, key.1) }),
+            delay_ms: 0,
+        },
+    }
+}
+
+async fn read_request<R: AsyncBufReadExt + Unpin>(read: &mut R) -> Option<Recorded> {
+    let mut line = String::new();
+    if read.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_uppercase();
+    let target = parts.next()?.to_string();
+
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        read.read_line(&mut header).await.ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok()?;
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    read.read_exact(&mut body).await.ok()?;
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), query.to_string()),
+        None => (target, String::new()),
+    };
+    Some(Recorded {
+        method,
+        path,
+        query,
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    })
+}
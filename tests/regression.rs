@@ -0,0 +1,455 @@
+mod common;
+
+use ethers::providers::{Middleware, Provider};
+use ethers::types::H256;
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal_macros::dec;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+use common::{assert_golden, fixture, fixture_client, offline_client, FixtureServer, TEST_KEY};
+use polymarket_15m_arbitrage_bot::domain::order::Side;
+use polymarket_15m_arbitrage_bot::clob::api::OpenOrder;
+use polymarket_15m_arbitrage_bot::error::ClobError;
+use polymarket_15m_arbitrage_bot::execution::orderbook::{fetch_book, fetch_midpoint};
+use polymarket_15m_arbitrage_bot::execution::paper::PaperExchange;
+use polymarket_15m_arbitrage_bot::execution::ClobClient;
+use polymarket_15m_arbitrage_bot::market_ws::{MarketBooks, MarketTrades, MarketWs, Replay};
+use polymarket_15m_arbitrage_bot::net::{feeds, ConnectionState, Connections, RpcPool, RpcPoolConfig};
+use polymarket_15m_arbitrage_bot::strategy::{
+    Backtest, BacktestReport, CostModel, Delay, LatencyModel, MarketMaker, MarketMakerConfig, SimClock, Strategy,
+    StrategyBudget, StrategyContext,
+};
+use polymarket_15m_arbitrage_bot::markets::gamma::GammaClient;
+use polymarket_15m_arbitrage_bot::markets::metadata::MetadataCache;
+use polymarket_15m_arbitrage_bot::orders::{OrderEvent, OrderState, OrderTracker};
+use polymarket_15m_arbitrage_bot::portfolio::Positions;
+use polymarket_15m_arbitrage_bot::risk::{KillSwitch, RiskLimits, RiskManager};
+use polymarket_15m_arbitrage_bot::wallet::order_builder::{MarketRules, OrderBuilder};
+use polymarket_15m_arbitrage_bot::wallet::signer::{domain_separator, verify_order_signature, OrderType, WalletSigner};
+
+const TOKEN: &str = "1001";
+
+const RULES: MarketRules = MarketRules {
+    tick_size: dec!(0.01),
+    min_size: dec!(5),
+    neg_risk: false,
+};
+
+/// Two bid levels of 10, requoting on every book
+fn market_maker() -> Arc<dyn Strategy> {
+    let vars: HashMap<&str, &str> = HashMap::from([
+        ("MM_HALF_SPREAD", "0.02"),
+        ("MM_LEVELS", "2"),
+        ("MM_LEVEL_SIZE", "10"),
+        ("MM_LEVEL_STEP", "0.01"),
+        ("MM_MAX_INVENTORY", "200"),
+        ("MM_SKEW", "0.02"),
+        ("MM_REQUOTE_THRESHOLD", "0.005"),
+        ("MM_MIN_REQUOTE_MS", "0"),
+    ]);
+    let config = MarketMakerConfig::from_vars(|name| vars.get(name).map(|v| v.to_string()));
+    Arc::new(MarketMaker::new(TOKEN, config))
+}
+
+/// What the strategy sent and what came of it
+fn render(report: &BacktestReport) -> String {
+    let mut out = String::new();
+    for action in &report.actions {
+        out.push_str(&format!("{}\n", action));
+    }
+    let o = &report.orders;
+    out.push_str(&format!(
+        "orders {} rejected {} cancelled {} missed {} fills {} maker {} volume {} fees {}\n",
+        o.orders,
+        o.rejected,
+        o.cancelled,
+        o.cancel_misses,
+        o.fills,
+        o.maker_fills,
+        o.volume.normalize(),
+        o.fees.normalize()
+    ));
+    out.push_str(&format!(
+        "pnl realized {} unrealized {} position {}\n",
+        report.pnl.realized.round_dp(6).normalize(),
+        report.pnl.unrealized.round_dp(6).normalize(),
+        report.pnl.position.normalize()
+    ));
+    out
+}
+
+async fn backtest(latency: LatencyModel) -> BacktestReport {
+    let replay = Replay::from_files(&[fixture("market_session.csv")]).unwrap();
+    Backtest::new(Arc::new(offline_client()), replay)
+        .rules(RULES)
+        .budget(StrategyBudget::default())
+        .costs(CostModel::default())
+        .latency(latency)
+        .run(market_maker())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn market_maker_backtest_orders_unchanged() {
+    let report = backtest(LatencyModel::default()).await;
+    assert_eq!(report.errors, 0);
+    assert!(report.orders.fills > 0, "the session should trade through the quotes");
+    assert_golden("market_maker_backtest", &render(&report));
+}
+
+#[tokio::test]
+async fn market_maker_backtest_with_latency_unchanged() {
+    let latency = LatencyModel {
+        submit: Delay::Fixed(1500),
+        cancel: Delay::Uniform(500, 2500),
+        book: Delay::Fixed(500),
+        seed: 7,
+    };
+    let report = backtest(latency).await;
+    assert_eq!(report.errors, 0);
+    assert_golden("market_maker_backtest_latency", &render(&report));
+
+    // Same seed, same run
+    assert_eq!(render(&backtest(latency).await), render(&report));
+}
+
+#[tokio::test]
+async fn clob_client_requests_unchanged() {
+    let server = FixtureServer::start("clob_orders.json").await;
+    let clob = fixture_client(&server);
+    clob.set_market_rules(TOKEN, RULES).await;
+
+    let bid = clob.place_order(TOKEN, Side::Buy, dec!(0.45), dec!(10), OrderType::Gtc).await;
+    assert_eq!(bid.unwrap(), "0xa1");
+    let ask = clob.place_post_only(TOKEN, Side::Sell, dec!(0.55), dec!(5), OrderType::Gtc).await;
+    assert_eq!(ask.unwrap(), "0xa2");
+    // Through the 0.56 ask: refused before anything is sent
+    let crossing = clob.place_post_only(TOKEN, Side::Buy, dec!(0.57), dec!(5), OrderType::Gtc).await;
+    assert!(matches!(crossing, Err(ClobError::Refused { check, .. }) if check == "post-only"));
+    // Sent, and rejected by the exchange for "not enough balance / allowance"
+    let rejected = clob.place_order(TOKEN, Side::Buy, dec!(0.40), dec!(20), OrderType::Fok).await;
+    match rejected.unwrap_err() {
+        e @ ClobError::InsufficientBalance { .. } => assert!(!e.is_retryable()),
+        e => panic!("expected a balance error, got {:?}", e),
+    }
+
+    let cancelled = clob.cancel_order("0xa1").await.unwrap();
+    assert_eq!(cancelled.canceled, vec!["0xa1".to_string()]);
+    let cancelled = clob.cancel_orders(&["0xa1".to_string(), "0xa2".to_string()]).await.unwrap();
+    assert!(cancelled.not_canceled.contains_key("0xa1"));
+
+    assert_golden("clob_client_requests", &server.transcript());
+}
+
+#[tokio::test]
+async fn identical_orders_get_distinct_keys() {
+    let clob = offline_client();
+    clob.set_market_rules(TOKEN, RULES).await;
+
+    // Same millisecond, same order: only the salt tells them apart
+    let mut keys = HashSet::new();
+    for _ in 0..200 {
+        let key = clob.place_order(TOKEN, Side::Buy, dec!(0.45), dec!(10), OrderType::Gtc).await.unwrap();
+        assert!(keys.insert(key), "two orders share an idempotency key");
+    }
+}
+
+#[tokio::test]
+async fn timed_out_order_is_not_resent_and_duplicate_is_recovered() {
+    std::env::set_var("CLOB_TIMEOUT_MS", "300");
+    let server = FixtureServer::start("order_timeout.json").await;
+    let clob = fixture_client(&server);
+
+    let order = OrderBuilder::new(clob.funder(), clob.signer_address())
+        .salts(clob.salts())
+        .market_rules(RULES)
+        .build(TOKEN, Side::Buy, dec!(0.45), dec!(10))
+        .unwrap();
+    let signer = WalletSigner::new(TEST_KEY, clob.chain().chain_id).unwrap().with_exchange(clob.chain().exchange);
+    let sig = signer.sign_order(&order).await.unwrap();
+    let hash = format!("{:?}", signer.order_hash(&order));
+    server.route(
+        "GET",
+        &format!("/data/order/{}", hash),
+        serde_json::json!({
+            "id": hash, "status": "LIVE", "asset_id": TOKEN, "side": "BUY", "original_size": "10", "price": "0.45"
+        }),
+    );
+
+    // The post may have reached the exchange: timed out, and not resent
+    let timed_out = clob.submit_order(order.clone(), sig, "").await.unwrap_err();
+    assert!(timed_out.is_retryable(), "{:?}", timed_out);
+    let posts = |server: &FixtureServer| server.requests().iter().filter(|r| r.method == "POST").count();
+    assert_eq!(posts(&server), 1);
+
+    // It had: the retry is refused as a duplicate and found by its hash
+    let order_id = clob.submit_order(order, sig, "").await.unwrap();
+    assert_eq!(order_id, hash);
+    assert_eq!(posts(&server), 2);
+}
+
+#[tokio::test]
+async fn gtd_replace_near_expiry_is_refused_before_cancelling() {
+    let server = FixtureServer::start("clob_orders.json").await;
+    let clob = fixture_client(&server);
+    clob.set_market_rules(TOKEN, RULES).await;
+
+    // 30s left: inside the exchange's 60s threshold
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+    server.route(
+        "GET",
+        "/data/order/0xa1",
+        serde_json::json!({
+            "id": "0xa1", "status": "LIVE", "asset_id": TOKEN, "side": "BUY", "original_size": "10",
+            "price": "0.45", "order_type": "GTD", "expiration": (now + 30).to_string()
+        }),
+    );
+
+    let refused = clob.replace_order("0xa1", dec!(0.46), dec!(10)).await.unwrap_err();
+    assert!(matches!(refused, ClobError::Refused { check, .. } if check == "gtd expiry"));
+    // The old order is left alone
+    assert!(server.requests().iter().all(|r| r.method == "GET"));
+}
+
+/// Offline client whose risk manager allows `notional` USDC per token.
+/// Gamma answers nothing: no metadata, only the token limits apply.
+async fn risk_limited_client(name: &str, notional: rust_decimal::Decimal) -> (ClobClient, FixtureServer) {
+    let gamma = FixtureServer::start("flaky_clob.json").await;
+    let dir = std::env::temp_dir().join(format!("oe-{}-{}", name, std::process::id()));
+    let metadata = MetadataCache::load(GammaClient::new(&gamma.url), dir.join("metadata.json"), Duration::from_secs(60)).unwrap();
+    let limits = RiskLimits {
+        max_token_notional: Some(notional),
+        ..RiskLimits::default()
+    };
+    let risk = RiskManager::new(
+        Arc::new(Positions::new()),
+        Arc::new(metadata),
+        limits,
+        Arc::new(KillSwitch::new(dir.join("kill_switch.json"))),
+    );
+    let clob = offline_client().risk(Arc::new(risk));
+    clob.set_market_rules(TOKEN, RULES).await;
+    (clob, gamma)
+}
+
+#[tokio::test]
+async fn order_sized_to_the_risk_limit_passes_the_check() {
+    let (clob, _gamma) = risk_limited_client("risk-limit", dec!(53)).await;
+
+    // 100 @ 0.53 is exactly the 53 USDC allowed
+    let placed = clob.place_order(TOKEN, Side::Buy, dec!(0.53), dec!(100), OrderType::Gtc).await;
+    assert!(placed.is_ok(), "{:?}", placed);
+}
+
+#[tokio::test]
+async fn batch_orders_count_against_the_limits_without_native_api() {
+    let (clob, _gamma) = risk_limited_client("risk-batch", dec!(53)).await;
+    let builder = OrderBuilder::new(clob.funder(), clob.signer_address()).market_rules(RULES);
+    let orders = vec![
+        builder.build(TOKEN, Side::Buy, dec!(0.53), dec!(60)).unwrap(),
+        builder.build(TOKEN, Side::Buy, dec!(0.53), dec!(60)).unwrap(),
+    ];
+
+    // Each fits the limit alone, not both together
+    let results = clob.submit_orders(orders).await;
+    assert!(results[0].is_ok(), "{:?}", results[0]);
+    assert!(matches!(results[1], Err(ClobError::Risk { .. })), "{:?}", results[1]);
+}
+
+#[tokio::test]
+async fn neg_risk_orders_are_signed_for_the_neg_risk_exchange() {
+    let clob = offline_client();
+    let chain = clob.chain();
+    let signer = WalletSigner::new(TEST_KEY, chain.chain_id)
+        .unwrap()
+        .with_exchange(chain.exchange)
+        .with_neg_risk_exchange(chain.neg_risk_exchange);
+
+    let rules = MarketRules { neg_risk: true, ..RULES };
+    let order = OrderBuilder::new(clob.funder(), clob.signer_address())
+        .market_rules(rules)
+        .build(TOKEN, Side::Buy, dec!(0.45), dec!(10))
+        .unwrap();
+    let sig = signer.sign_order(&order).await.unwrap();
+
+    let neg_risk = domain_separator(chain.chain_id, chain.neg_risk_exchange);
+    assert_eq!(verify_order_signature(&order, &sig, neg_risk).unwrap(), signer.order_hash(&order));
+    assert!(verify_order_signature(&order, &sig, domain_separator(chain.chain_id, chain.exchange)).is_err());
+}
+
+#[tokio::test]
+async fn adopted_order_is_tracked_until_filled() {
+    let clob = offline_client();
+    let order: OpenOrder = serde_json::from_value(serde_json::json!({
+        "id": "0xd1", "status": "LIVE", "asset_id": TOKEN, "side": "BUY", "original_size": "10",
+        "size_matched": "4", "price": "0.45", "order_type": "GTC"
+    }))
+    .unwrap();
+    clob.adopt_order(&order).await.unwrap();
+    assert_eq!(clob.open_order_ids().await, ["0xd1"]);
+
+    // The user channel reports the rest filling
+    let state = clob.on_order_event("0xd1", OrderEvent::Fill { size: dec!(6) }).await;
+    assert_eq!(state, Some(OrderState::Filled));
+    assert!(clob.open_order_ids().await.is_empty());
+    assert!(clob.order_intents().await.iter().all(|i| i.order_id != "0xd1"));
+}
+
+#[tokio::test]
+async fn merge_without_both_outcomes_is_refused_before_sending() {
+    let paper = PaperExchange::new("ws://127.0.0.1:9", "http://127.0.0.1:9", dec!(100));
+    let clob = offline_client().paper(Arc::new(paper));
+
+    let merged = clob.merge_full_sets(H256::zero(), [TOKEN, "1002"], dec!(5), false).await;
+    assert!(
+        matches!(&merged, Err(ClobError::InsufficientBalance { asset, .. }) if asset == TOKEN),
+        "{:?}",
+        merged.map(|r| r.transaction_hash)
+    );
+}
+
+#[test]
+fn partially_matched_fak_keeps_its_fill_and_drops_the_rest() {
+    let mut orders = OrderTracker::new();
+    orders.create("k", dec!(10));
+    let mut events = vec![OrderEvent::Signed, OrderEvent::Submitted];
+    events.extend(OrderEvent::from_post_status("matched", "0xfa", dec!(10), Some(dec!(4))));
+    assert_eq!(orders.apply_all("k", events), Ok(OrderState::Cancelled));
+    assert_eq!(orders.get("k").unwrap().filled, dec!(4));
+}
+
+#[tokio::test]
+async fn market_maker_through_client_unchanged() {
+    let server = FixtureServer::start("client_session.json").await;
+    let clob = Arc::new(fixture_client(&server));
+    clob.set_market_rules(TOKEN, RULES).await;
+
+    let books = Arc::new(MarketBooks::new());
+    let trades = MarketTrades::from_env();
+    let mut replay = Replay::from_files(&[fixture("client_session.csv")]).unwrap();
+    let clock = Arc::new(SimClock::new(replay.next_ms().unwrap()));
+    let ctx = StrategyContext::new("market-maker", clob.clone(), books.clone(), StrategyBudget::default())
+        .with_clock(clock.clone());
+
+    // As the runner does: every book update goes to the strategy. Its
+    // `on_start` reads the on-chain balance, so it starts flat instead.
+    let strategy = market_maker();
+    while let Some(event) = replay.step(&books, &trades).await {
+        clock.set(event.recv_ms);
+        let book = books.order_book(TOKEN).await.unwrap();
+        strategy.on_book_update(&ctx, TOKEN, &book).await.unwrap();
+    }
+    strategy.on_stop(&ctx).await.unwrap();
+
+    assert_golden("market_maker_through_client", &server.transcript());
+}
+
+#[tokio::test]
+async fn http_retries_transient_failures_only() {
+    let server = FixtureServer::start("flaky_clob.json").await;
+
+    // 503, then 429, then an answer: two retries
+    let mid = fetch_midpoint(&server.url, TOKEN).await.unwrap();
+    assert_eq!(mid, 0.42);
+    // A bad request is final
+    assert!(fetch_book(&server.url, TOKEN).await.is_err());
+
+    let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(paths, ["/midpoint", "/midpoint", "/midpoint", "/book"]);
+}
+
+#[tokio::test]
+async fn rpc_fails_over_and_benches_the_failing_url() {
+    let down = FixtureServer::start("rpc_failing.json").await;
+    let up = FixtureServer::start("rpc_polygon.json").await;
+    let config = RpcPoolConfig {
+        timeout: Duration::from_secs(5),
+        max_failures: 1,
+        ..RpcPoolConfig::default()
+    };
+    let provider = Provider::new(RpcPool::new(&format!("{}, {}", down.url, up.url), config).unwrap());
+
+    assert_eq!(provider.get_chainid().await.unwrap().as_u64(), 137);
+    // The node's own answer: no failover, and the benched URL isn't retried
+    let reverted = provider.get_block_number().await.unwrap_err();
+    assert!(reverted.to_string().contains("execution reverted"));
+    assert_eq!(down.requests().len(), 1);
+    assert_eq!(up.requests().len(), 2);
+
+    let status = provider.as_ref().status();
+    assert!(status[0].benched.is_some() && status[0].failures == 1);
+    assert!(status[1].benched.is_none() && status[1].failures == 0);
+}
+
+#[tokio::test]
+async fn market_feed_resubscribes_and_reports_reconnects() {
+    std::env::set_var("WS_RECONNECT_BASE_MS", "10");
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    // Snapshot, then drop the first connection; keep the second
+    let server = tokio::spawn(async move {
+        let mut subscriptions = Vec::new();
+        let mut kept = None;
+        for bid in ["0.45", "0.46"] {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let sub = ws.next().await.unwrap().unwrap().into_text().unwrap();
+            subscriptions.push(serde_json::from_str::<serde_json::Value>(&sub).unwrap());
+            let book = serde_json::json!({
+                "event_type": "book",
+                "asset_id": TOKEN,
+                "bids": [{ "price": bid, "size": "10" }],
+                "asks": [{ "price": "0.55", "size": "10" }],
+            });
+            ws.send(Message::Text(book.to_string())).await.unwrap();
+            if bid == "0.46" {
+                kept = Some(ws);
+            }
+        }
+        (subscriptions, kept)
+    });
+
+    let books = Arc::new(MarketBooks::new());
+    let connections = Arc::new(Connections::new());
+    let mut events = connections.subscribe();
+    let ws = MarketWs::new(&url, "http://127.0.0.1:9", vec![TOKEN.to_string()], books.clone())
+        .with_connections(connections.clone());
+    let feed = tokio::spawn(async move { ws.run().await });
+
+    let mut seen = Vec::new();
+    while seen.last() != Some(&(ConnectionState::Connected, true)) {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv()).await.unwrap().unwrap();
+        assert_eq!(event.feed, feeds::MARKET);
+        seen.push((event.state, event.resumed));
+    }
+    assert_eq!(
+        seen,
+        [
+            (ConnectionState::Connecting, false),
+            (ConnectionState::Connected, false),
+            (ConnectionState::Disconnected, false),
+            (ConnectionState::Connecting, false),
+            (ConnectionState::Connected, true),
+        ]
+    );
+    let (subscriptions, _kept) = server.await.unwrap();
+    assert_eq!(subscriptions[0], subscriptions[1]);
+    assert_eq!(subscriptions[1]["assets_ids"][0], TOKEN);
+
+    // The second connection's snapshot restores the degraded book
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while books.is_degraded(TOKEN).await || books.best_bid_ask(TOKEN).await.0 != Some(dec!(0.46)) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(connections.state(feeds::MARKET), Some(ConnectionState::Connected));
+    assert!(connections.down_for(feeds::MARKET).is_none());
+    feed.abort();
+}